@@ -2,7 +2,8 @@ use std::sync::Arc;
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use lsp_types::{
-    CompletionParams, Position, TextDocumentIdentifier, TextDocumentPositionParams, Url,
+    CompletionParams, Position, RenameParams, TextDocumentIdentifier, TextDocumentPositionParams,
+    Url,
 };
 use texlab::{features::FeatureRequest, syntax::latex, DocumentLanguage, Workspace};
 
@@ -35,6 +36,30 @@ fn criterion_benchmark(c: &mut Criterion) {
             })
         });
     });
+
+    c.bench_function("LaTeX/Rename/Command", |b| {
+        let uri = Arc::new(Url::parse("http://example.com/texlab.tex").unwrap());
+        let text = Arc::new(include_str!("../texlab.tex").to_string());
+        let mut workspace = Workspace::default();
+        workspace
+            .open(Arc::clone(&uri), text, DocumentLanguage::Latex)
+            .unwrap();
+
+        b.iter(|| {
+            texlab::features::rename_all(FeatureRequest {
+                params: RenameParams {
+                    new_name: "foo".to_string(),
+                    work_done_progress_params: Default::default(),
+                    text_document_position: TextDocumentPositionParams::new(
+                        TextDocumentIdentifier::new(uri.as_ref().clone()),
+                        Position::new(12, 2),
+                    ),
+                },
+                workspace: workspace.clone(),
+                uri: Arc::clone(&uri),
+            })
+        });
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
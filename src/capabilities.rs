@@ -1,4 +1,4 @@
-use lsp_types::{ClientCapabilities, MarkupKind};
+use lsp_types::{ClientCapabilities, MarkupKind, ResourceOperationKind};
 
 pub trait ClientCapabilitiesExt {
     fn has_definition_link_support(&self) -> bool;
@@ -14,6 +14,12 @@ pub trait ClientCapabilitiesExt {
     fn has_push_configuration_support(&self) -> bool;
 
     fn has_file_watching_support(&self) -> bool;
+
+    fn has_completion_snippet_support(&self) -> bool;
+
+    fn has_completion_markdown_support(&self) -> bool;
+
+    fn has_rename_file_support(&self) -> bool;
 }
 
 impl ClientCapabilitiesExt for ClientCapabilities {
@@ -65,14 +71,44 @@ impl ClientCapabilitiesExt for ClientCapabilities {
             .and_then(|cap| cap.dynamic_registration)
             == Some(true)
     }
+
+    fn has_completion_snippet_support(&self) -> bool {
+        self.text_document
+            .as_ref()
+            .and_then(|cap| cap.completion.as_ref())
+            .and_then(|cap| cap.completion_item.as_ref())
+            .and_then(|cap| cap.snippet_support)
+            == Some(true)
+    }
+
+    fn has_completion_markdown_support(&self) -> bool {
+        self.text_document
+            .as_ref()
+            .and_then(|cap| cap.completion.as_ref())
+            .and_then(|cap| cap.completion_item.as_ref())
+            .and_then(|cap| cap.documentation_format.as_ref())
+            .filter(|formats| formats.contains(&MarkupKind::Markdown))
+            .is_some()
+    }
+
+    fn has_rename_file_support(&self) -> bool {
+        self.workspace
+            .as_ref()
+            .and_then(|cap| cap.workspace_edit.as_ref())
+            .filter(|cap| cap.document_changes == Some(true))
+            .and_then(|cap| cap.resource_operations.as_ref())
+            .filter(|kinds| kinds.contains(&ResourceOperationKind::Rename))
+            .is_some()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use lsp_types::{
-        DocumentSymbolClientCapabilities, GotoCapability, HoverClientCapabilities,
-        TextDocumentClientCapabilities, WindowClientCapabilities,
+        CompletionClientCapabilities, CompletionItemCapability, DocumentSymbolClientCapabilities,
+        GotoCapability, HoverClientCapabilities, TextDocumentClientCapabilities,
+        WindowClientCapabilities, WorkspaceClientCapabilities, WorkspaceEditClientCapabilities,
     };
 
     #[test]
@@ -155,4 +191,74 @@ mod tests {
         let capabilities = ClientCapabilities::default();
         assert!(!capabilities.has_hover_markdown_support());
     }
+
+    #[test]
+    fn test_has_completion_snippet_support_true() {
+        let capabilities = ClientCapabilities {
+            text_document: Some(TextDocumentClientCapabilities {
+                completion: Some(CompletionClientCapabilities {
+                    completion_item: Some(CompletionItemCapability {
+                        snippet_support: Some(true),
+                        ..CompletionItemCapability::default()
+                    }),
+                    ..CompletionClientCapabilities::default()
+                }),
+                ..TextDocumentClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        };
+        assert!(capabilities.has_completion_snippet_support());
+    }
+
+    #[test]
+    fn test_has_completion_snippet_support_false() {
+        let capabilities = ClientCapabilities::default();
+        assert!(!capabilities.has_completion_snippet_support());
+    }
+
+    #[test]
+    fn test_has_completion_markdown_support_true() {
+        let capabilities = ClientCapabilities {
+            text_document: Some(TextDocumentClientCapabilities {
+                completion: Some(CompletionClientCapabilities {
+                    completion_item: Some(CompletionItemCapability {
+                        documentation_format: Some(vec![MarkupKind::Markdown]),
+                        ..CompletionItemCapability::default()
+                    }),
+                    ..CompletionClientCapabilities::default()
+                }),
+                ..TextDocumentClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        };
+        assert!(capabilities.has_completion_markdown_support());
+    }
+
+    #[test]
+    fn test_has_completion_markdown_support_false() {
+        let capabilities = ClientCapabilities::default();
+        assert!(!capabilities.has_completion_markdown_support());
+    }
+
+    #[test]
+    fn test_has_rename_file_support_true() {
+        let capabilities = ClientCapabilities {
+            workspace: Some(WorkspaceClientCapabilities {
+                workspace_edit: Some(WorkspaceEditClientCapabilities {
+                    document_changes: Some(true),
+                    resource_operations: Some(vec![ResourceOperationKind::Rename]),
+                    ..WorkspaceEditClientCapabilities::default()
+                }),
+                ..WorkspaceClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        };
+        assert!(capabilities.has_rename_file_support());
+    }
+
+    #[test]
+    fn test_has_rename_file_support_false() {
+        let capabilities = ClientCapabilities::default();
+        assert!(!capabilities.has_rename_file_support());
+    }
 }
@@ -1,19 +1,101 @@
 mod driver;
 mod entry;
 pub(crate) mod field;
+mod label;
 mod output;
 
+use rowan::ast::AstNode;
 use unicode_normalization::UnicodeNormalization;
 
-use crate::syntax::bibtex;
+use crate::{
+    syntax::{bibtex, latex::ResolvedCitation},
+    CitationStyle, Document, Workspace,
+};
 
-use self::{driver::Driver, output::Inline};
+use self::{driver::Driver, entry::EntryData, label::render_label, output::Inline};
 
+pub use self::label::render_author_year;
+
+/// Returns the citation style of the project.
+///
+/// A style given by `\bibliographystyle` takes precedence over the `citation.style` option.
 #[must_use]
-pub fn render(entry: &bibtex::Entry) -> Option<String> {
+pub fn find_style(workspace: &Workspace) -> CitationStyle {
+    workspace
+        .documents_by_uri
+        .values()
+        .filter_map(|document| document.data.as_latex())
+        .filter_map(|data| data.extras.bibliography_style.as_deref())
+        .find_map(CitationStyle::from_bibliography_style)
+        .unwrap_or(workspace.environment.options.citation.style)
+}
+
+/// Finds the label of a citation that the compiler has written to an `.aux` file with `\bibcite`.
+#[must_use]
+pub fn find_resolved_citation<'a>(
+    workspace: &'a Workspace,
+    key: &str,
+) -> Option<(&'a Document, &'a ResolvedCitation)> {
+    workspace.documents_by_uri.values().find_map(|document| {
+        let data = document.data.as_latex()?;
+        let citation = data.extras.citation_numbers_by_key.get(key)?;
+        Some((document, citation))
+    })
+}
+
+#[must_use]
+pub fn render(entry: &bibtex::Entry, style: CitationStyle) -> Option<String> {
+    render_data(entry, EntryData::from(entry), style, None)
+}
+
+/// Renders the entry like `render` but uses the label of the `.aux` file instead of guessing it
+/// from the entry if the style uses labels.
+///
+/// Entries referenced with `crossref` are looked up in the other BibTeX documents of the workspace
+/// if they are not part of the same file.
+#[must_use]
+pub fn render_with_number(
+    workspace: &Workspace,
+    entry: &bibtex::Entry,
+    style: CitationStyle,
+    number: Option<&str>,
+) -> Option<String> {
+    let data = EntryData::new(entry, |key| find_entry(workspace, key));
+    render_data(entry, data, style, number)
+}
+
+fn find_entry(workspace: &Workspace, key: &str) -> Option<bibtex::Entry> {
+    workspace
+        .documents_by_uri
+        .values()
+        .filter_map(|document| document.data.as_bibtex())
+        .find_map(|data| {
+            let root = bibtex::SyntaxNode::new_root(data.green().clone());
+            bibtex::Root::cast(root)?.find_entry(key)
+        })
+}
+
+fn render_data(
+    entry: &bibtex::Entry,
+    data: EntryData,
+    style: CitationStyle,
+    number: Option<&str>,
+) -> Option<String> {
+    let label = match (style, number) {
+        (CitationStyle::AuthorYear, _) => None,
+        (_, Some(number)) => Some(format!("[{}]", number)),
+        (_, None) => render_label(entry, &data, style),
+    };
+
     let mut output = String::new();
+    if let Some(label) = label {
+        output.push_str(&label);
+        output.push(' ');
+    }
+
+    let prefix_len = output.len();
     let mut driver = Driver::default();
-    driver.process(entry);
+    driver.process(data);
     driver.finish().for_each(|(inline, punct)| {
         let text = match inline {
             Inline::Regular(text) => text,
@@ -25,7 +107,7 @@ pub fn render(entry: &bibtex::Entry) -> Option<String> {
         output.push_str(punct.as_str());
     });
 
-    if output.is_empty() {
+    if output.len() == prefix_len {
         None
     } else {
         output.push('.');
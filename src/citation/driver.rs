@@ -3,8 +3,6 @@ use itertools::Itertools;
 use titlecase::titlecase;
 use url::Url;
 
-use crate::syntax::bibtex;
-
 use super::{
     entry::{EntryData, EntryKind},
     field::{
@@ -22,8 +20,7 @@ pub struct Driver {
 }
 
 impl Driver {
-    pub fn process(&mut self, entry: &bibtex::Entry) {
-        let entry = EntryData::from(entry);
+    pub fn process(&mut self, entry: EntryData) {
         match entry.kind {
             EntryKind::Article
             | EntryKind::DataSet
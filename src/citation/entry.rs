@@ -1,9 +1,10 @@
 use std::str::FromStr;
 
-use rustc_hash::FxHashMap;
+use rowan::ast::AstNode;
+use rustc_hash::{FxHashMap, FxHashSet};
 use strum::EnumString;
 
-use crate::syntax::bibtex::{Entry, Field, HasName, HasType, HasValue, Value};
+use crate::syntax::bibtex::{Entry, Field, HasName, HasType, HasValue, Root, Value};
 
 use super::field::{
     author::{AuthorField, AuthorFieldData},
@@ -69,6 +70,32 @@ pub struct EntryData {
 
 impl From<&Entry> for EntryData {
     fn from(entry: &Entry) -> Self {
+        Self::new(entry, |_| None)
+    }
+}
+
+/// Finds the entry referenced by the `crossref` field.
+fn find_crossref(entry: &Entry, find_entry: impl Fn(&str) -> Option<Entry>) -> Option<Entry> {
+    let value = entry
+        .fields()
+        .find(|field| {
+            field
+                .name_token()
+                .map_or(false, |name| name.text().eq_ignore_ascii_case("crossref"))
+        })?
+        .value()?;
+
+    let key = TextFieldData::parse(&value)?.text;
+    let key = key.trim();
+    let root = Root::cast(entry.syntax().ancestors().last()?)?;
+    root.find_entry(key).or_else(|| find_entry(key))
+}
+
+impl EntryData {
+    /// Parses the fields of the entry and inherits the fields of the entries referenced with `crossref`.
+    ///
+    /// Referenced entries are looked up in the same file first and with `find_entry` otherwise.
+    pub fn new(entry: &Entry, find_entry: impl Fn(&str) -> Option<Entry>) -> Self {
         let mut data = EntryData {
             kind: entry
                 .type_token()
@@ -81,11 +108,60 @@ impl From<&Entry> for EntryData {
             let _ = data.parse_field(&field);
         }
 
+        let mut visited = FxHashSet::default();
+        visited.extend(entry.name_token().map(|name| name.text().to_string()));
+
+        let mut child = entry.clone();
+        while let Some(parent) = find_crossref(&child, &find_entry).filter(|parent| {
+            parent
+                .name_token()
+                .map_or(false, |name| visited.insert(name.text().to_string()))
+        }) {
+            let mut parent_data = EntryData::default();
+            for field in parent.fields() {
+                let _ = parent_data.parse_field(&field);
+            }
+
+            data.inherit(parent_data);
+            child = parent;
+        }
+
         data
     }
-}
 
-impl EntryData {
+    /// Adds the fields of the parent entry that are missing in this entry.
+    ///
+    /// Like `biblatex`, the titles of the parent become the book titles of the child.
+    fn inherit(&mut self, parent: EntryData) {
+        let mut text = parent.text;
+        text.remove(&TextField::Unknown);
+        for (source, target) in [
+            (TextField::Title, TextField::BookTitle),
+            (TextField::Subtitle, TextField::BookSubtitle),
+            (TextField::TitleAddon, TextField::BookTitleAddon),
+        ] {
+            if let Some(data) = text.remove(&source) {
+                text.entry(target).or_insert(data);
+            }
+        }
+
+        for (field, data) in text {
+            self.text.entry(field).or_insert(data);
+        }
+
+        for (field, data) in parent.author {
+            self.author.entry(field).or_insert(data);
+        }
+
+        for (field, data) in parent.date {
+            self.date.entry(field).or_insert(data);
+        }
+
+        for (field, data) in parent.number {
+            self.number.entry(field).or_insert(data);
+        }
+    }
+
     fn parse_field(&mut self, field: &Field) -> Option<()> {
         let name = field.name_token()?;
         let name = name.text();
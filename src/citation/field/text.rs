@@ -128,21 +128,23 @@ impl TextFieldDataBuilder {
         let root = Root::cast(name.parent_ancestors().last()?)?;
         let name = name.text();
 
+        // BibTeX does not distinguish the case of string names.
         let value = root
             .strings()
             .filter(|string| {
                 string
                     .name_token()
-                    .map_or(false, |token| token.text() == name)
+                    .map_or(false, |token| token.text().eq_ignore_ascii_case(name))
             })
             .find_map(|string| string.value())?;
 
-        if !self.string_stack.insert(name.to_string()) {
+        let name = name.to_ascii_lowercase();
+        if !self.string_stack.insert(name.clone()) {
             return None;
         }
 
         let _ = self.visit_value(&value);
-        self.string_stack.remove(name);
+        self.string_stack.remove(&name);
         Some(())
     }
 
@@ -0,0 +1,150 @@
+use chrono::Datelike;
+use human_name::Name;
+use rowan::ast::AstNode;
+
+use crate::{
+    syntax::bibtex::{self, HasName, HasValue},
+    CitationStyle,
+};
+
+use super::{
+    entry::EntryData,
+    field::{
+        author::{AuthorField, AuthorFieldData},
+        date::{DateField, DateFieldData},
+    },
+};
+
+/// Renders the label of an entry like `[1]` or `[Knu84]`.
+///
+/// The author-year style does not use a label.
+pub fn render_label(
+    entry: &bibtex::Entry,
+    data: &EntryData,
+    style: CitationStyle,
+) -> Option<String> {
+    match style {
+        CitationStyle::AuthorYear => None,
+        CitationStyle::Numeric => numeric_label(entry),
+        CitationStyle::Alpha => alpha_label(data),
+    }
+}
+
+/// The order of the citations in the document is unknown,
+/// so the entries are numbered by their position in the file instead.
+fn numeric_label(entry: &bibtex::Entry) -> Option<String> {
+    let position = entry
+        .syntax()
+        .parent()?
+        .children()
+        .filter(|node| bibtex::Entry::can_cast(node.kind()))
+        .position(|node| &node == entry.syntax())?;
+
+    Some(format!("[{}]", position + 1))
+}
+
+fn alpha_label(entry: &EntryData) -> Option<String> {
+    let authors = &entry
+        .author
+        .get(&AuthorField::Author)
+        .or_else(|| entry.author.get(&AuthorField::Editor))?
+        .authors;
+
+    let names = match authors.as_slice() {
+        [] => return None,
+        [author] => surname_prefix(author, 3),
+        [_, _] | [_, _, _] | [_, _, _, _] => authors
+            .iter()
+            .map(|author| surname_prefix(author, 1))
+            .collect(),
+        [..] => {
+            let initials: String = authors
+                .iter()
+                .take(3)
+                .map(|author| surname_prefix(author, 1))
+                .collect();
+
+            format!("{}+", initials)
+        }
+    };
+
+    let year = entry
+        .date
+        .get(&DateField::Date)
+        .or_else(|| entry.date.get(&DateField::Year))
+        .and_then(year_of)
+        .map(|year| format!("{:02}", year.rem_euclid(100)))
+        .unwrap_or_default();
+
+    Some(format!("[{}{}]", names, year))
+}
+
+/// Renders a short description like `Knuth et al., 1984` that identifies an entry in a list.
+///
+/// Only the author and date fields are parsed since the description is shown for every entry.
+pub fn render_author_year(entry: &bibtex::Entry) -> Option<String> {
+    let mut authors = None;
+    let mut year = None;
+    for field in entry.fields() {
+        let (name, value) = match (field.name_token(), field.value()) {
+            (Some(name), Some(value)) => (name, value),
+            _ => continue,
+        };
+
+        // The author takes precedence over the editor and the date over the year.
+        match AuthorField::parse(name.text()) {
+            Some(AuthorField::Author) => {
+                authors = AuthorFieldData::parse(&value)
+                    .map(|data| data.authors)
+                    .or(authors);
+            }
+            Some(AuthorField::Editor) if authors.is_none() => {
+                authors = AuthorFieldData::parse(&value).map(|data| data.authors);
+            }
+            _ => {}
+        };
+
+        match DateField::parse(name.text()) {
+            Some(DateField::Date) => {
+                year = DateFieldData::parse(&value)
+                    .as_ref()
+                    .and_then(year_of)
+                    .or(year);
+            }
+            Some(DateField::Year) if year.is_none() => {
+                year = DateFieldData::parse(&value).as_ref().and_then(year_of);
+            }
+            _ => {}
+        };
+    }
+
+    let names = authors.and_then(|authors| match authors.as_slice() {
+        [] => None,
+        [author] => Some(author.surname().to_string()),
+        [first, second] => Some(format!("{} and {}", first.surname(), second.surname())),
+        [author, ..] => Some(format!("{} et al.", author.surname())),
+    });
+
+    match (names, year) {
+        (Some(names), Some(year)) => Some(format!("{}, {}", names, year)),
+        (Some(names), None) => Some(names),
+        (None, Some(year)) => Some(year.to_string()),
+        (None, None) => None,
+    }
+}
+
+fn year_of(date: &DateFieldData) -> Option<i32> {
+    match date {
+        DateFieldData::Date(date) => Some(date.year()),
+        DateFieldData::Year(year) => Some(*year),
+        DateFieldData::Month(_) | DateFieldData::Other(_) => None,
+    }
+}
+
+fn surname_prefix(name: &Name, length: usize) -> String {
+    name.surname()
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .take(length)
+        .collect()
+}
@@ -1,13 +1,20 @@
 use insta::assert_snapshot;
 use rowan::ast::AstNode;
 
-use crate::syntax::bibtex;
+use crate::{syntax::bibtex, CitationStyle};
 
 fn render_entry(input: &str) -> String {
     let green = bibtex::parse(input);
     let root = bibtex::Root::cast(bibtex::SyntaxNode::new_root(green)).unwrap();
     let entry = root.entries().next().unwrap();
-    super::render(&entry).unwrap()
+    super::render(&entry, CitationStyle::AuthorYear).unwrap()
+}
+
+fn render_entry_with_style(input: &str, key: &str, style: CitationStyle) -> String {
+    let green = bibtex::parse(input);
+    let root = bibtex::Root::cast(bibtex::SyntaxNode::new_root(green)).unwrap();
+    let entry = root.find_entry(key).unwrap();
+    super::render(&entry, style).unwrap()
 }
 
 #[test]
@@ -349,3 +356,128 @@ fn patent_almendro_1998() {
 }"#
     ));
 }
+
+#[test]
+fn string_concatenation() {
+    assert_snapshot!(render_entry(
+        r#"
+@book{foo,
+    author    = first # " and " # Second,
+    title     = {Foo},
+    publisher = pub # { Press},
+    year      = 2022,
+}
+
+@string{First  = {Doe, John}}
+@string{second = {Roe, Jane}}
+@string{pub    = acme}
+@string{acme   = {ACME}}"#
+    ));
+}
+
+#[test]
+fn string_cycle_and_missing() {
+    assert_snapshot!(render_entry(
+        r#"
+@string{foo = bar}
+@string{bar = foo}
+
+@book{foo,
+    author    = {Doe, John},
+    title     = foo,
+    publisher = baz,
+    year      = 2022,
+}"#
+    ));
+}
+
+#[test]
+fn inproceedings_crossref() {
+    assert_snapshot!(render_entry(
+        r#"
+@inproceedings{foo,
+    author   = {Doe, John},
+    title    = {Foo},
+    pages    = {1-10},
+    crossref = {bar},
+}
+
+@proceedings{bar,
+    editor    = {Roe, Jane},
+    title     = {Proceedings of Bar},
+    publisher = {ACME},
+    year      = 2022,
+    crossref  = {baz},
+}
+
+@proceedings{baz,
+    location = {Berlin},
+    crossref = {bar},
+}"#
+    ));
+}
+
+static STYLE_FIXTURE: &str = r#"
+@book{foo,
+    author = {Doe, John},
+    title  = {Foo},
+    year   = 2022,
+}
+
+@book{bar,
+    author = {Knuth, Donald E.},
+    title  = {The {\TeX}book},
+    year   = 1984,
+}"#;
+
+#[test]
+fn style_numeric() {
+    assert_eq!(
+        render_entry_with_style(STYLE_FIXTURE, "bar", CitationStyle::Numeric),
+        "[2] D. Knuth: \"The TeXbook\". 1984."
+    );
+}
+
+#[test]
+fn style_alpha() {
+    assert_eq!(
+        render_entry_with_style(STYLE_FIXTURE, "bar", CitationStyle::Alpha),
+        "[Knu84] D. Knuth: \"The TeXbook\". 1984."
+    );
+}
+
+#[test]
+fn style_alpha_multiple_authors() {
+    assert_eq!(
+        render_entry_with_style(
+            r#"@book{foo, author = {Aho, Alfred and Lam, Monica and Sethi, Ravi}, year = 2006}"#,
+            "foo",
+            CitationStyle::Alpha,
+        ),
+        "[ALS06] A. Aho, M. Lam, R. Sethi: 2006."
+    );
+}
+
+#[test]
+fn style_alpha_four_authors() {
+    assert_eq!(
+        render_entry_with_style(
+            r#"@book{foo, author = {Aho, Alfred and Lam, Monica and Sethi, Ravi and Ullman, Jeffrey}, year = 2006}"#,
+            "foo",
+            CitationStyle::Alpha,
+        ),
+        "[ALSU06] A. Aho, M. Lam, R. Sethi, J. Ullman: 2006."
+    );
+}
+
+#[test]
+fn style_alpha_five_authors() {
+    assert_eq!(
+        render_entry_with_style(
+            r#"@book{foo, author = {Aho, Alfred and Lam, Monica and Sethi, Ravi and Ullman, Jeffrey and Knuth, Donald}, year = 2006}"#,
+            "foo",
+            CitationStyle::Alpha,
+        ),
+        "[ALS+06] A. Aho, M. Lam, R. Sethi, J. Ullman, D. Knuth: 2006."
+    );
+}
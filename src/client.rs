@@ -1,12 +1,13 @@
 use std::sync::Mutex;
 
 use anyhow::{anyhow, Result};
-use crossbeam_channel::{Receiver, Sender};
+use crossbeam_channel::Sender;
 use lsp_server::{Message, ResponseError};
 use serde::{de::DeserializeOwned, Serialize};
 
 pub struct OutgoingData {
-    pub sender: Sender<Result<serde_json::Value, ResponseError>>,
+    /// Called by the main loop once the client responds to the request.
+    pub handler: Box<dyn FnOnce(Result<serde_json::Value, ResponseError>) + Send>,
 }
 
 pub type ReqQueue = lsp_server::ReqQueue<(), OutgoingData>;
@@ -30,28 +31,57 @@ where
     R::Params: Serialize,
     R::Result: DeserializeOwned,
 {
-    let receiver = register_outgoing_request::<R>(req_queue, lsp_sender, params)?;
+    let (sender, receiver) = crossbeam_channel::bounded(1);
+    register_outgoing_request::<R>(req_queue, lsp_sender, params, move |result| {
+        let _ = sender.send(result);
+    })?;
+
     let params = receiver.recv()?.map_err(|err| anyhow!(err.message))?;
     let result = serde_json::from_value(params)?;
     Ok(result)
 }
 
+/// Sends a request to the client without waiting for the response.
+///
+/// The handler is called from the main loop, so it should not block.
+pub fn send_request_with_handler<R>(
+    req_queue: &Mutex<ReqQueue>,
+    lsp_sender: &Sender<Message>,
+    params: R::Params,
+    handler: impl FnOnce(Result<R::Result>) + Send + 'static,
+) -> Result<()>
+where
+    R: lsp_types::request::Request,
+    R::Params: Serialize,
+    R::Result: DeserializeOwned,
+{
+    register_outgoing_request::<R>(req_queue, lsp_sender, params, move |result| {
+        handler(
+            result
+                .map_err(|err| anyhow!(err.message))
+                .and_then(|value| Ok(serde_json::from_value(value)?)),
+        );
+    })
+}
+
 fn register_outgoing_request<R>(
     req_queue: &Mutex<ReqQueue>,
     lsp_sender: &Sender<Message>,
     params: R::Params,
-) -> Result<Receiver<Result<serde_json::Value, ResponseError>>>
+    handler: impl FnOnce(Result<serde_json::Value, ResponseError>) + Send + 'static,
+) -> Result<()>
 where
     R: lsp_types::request::Request,
     R::Params: Serialize,
     R::Result: DeserializeOwned,
 {
     let mut req_queue = req_queue.lock().unwrap();
-    let (sender, receiver) = crossbeam_channel::bounded(1);
     let method = R::METHOD.to_string();
-    let data = OutgoingData { sender };
+    let data = OutgoingData {
+        handler: Box::new(handler),
+    };
     let req = req_queue.outgoing.register(method, params, data);
     drop(req_queue);
     lsp_sender.send(req.into())?;
-    Ok(receiver)
+    Ok(())
 }
@@ -0,0 +1,35 @@
+use lsp_types::Url;
+use rowan::TextSize;
+
+use crate::{syntax::latex::UserCommand, Document, Workspace};
+
+/// Finds the definition of a command that is in effect at the given offset of a document.
+///
+/// A command can be redefined with `\renewcommand` in the middle of a document,
+/// so the last definition before the offset takes precedence over the other definitions.
+pub fn find_command_definition<'a>(
+    workspace: &'a Workspace,
+    uri: &Url,
+    name: &str,
+    offset: TextSize,
+) -> Option<(&'a Document, &'a UserCommand)> {
+    workspace
+        .documents_by_uri
+        .get(uri)
+        .and_then(|document| {
+            let data = document.data.as_latex()?;
+            Some((document, data.extras.find_user_command(name, offset)?))
+        })
+        .or_else(|| {
+            workspace.documents_by_uri.values().find_map(|document| {
+                let data = document.data.as_latex()?;
+                let command = data
+                    .extras
+                    .user_commands
+                    .iter()
+                    .find(|command| command.name == name)?;
+
+                Some((document, command))
+            })
+        })
+}
@@ -1,9 +1,9 @@
-use std::io::Read;
+use std::{collections::VecDeque, io::Read};
 
 use flate2::read::GzDecoder;
-use itertools::Itertools;
 use lsp_types::{MarkupContent, MarkupKind};
 use once_cell::sync::Lazy;
+use rustc_hash::{FxHashMap, FxHashSet};
 use serde::{Deserialize, Serialize};
 use smol_str::SmolStr;
 
@@ -37,6 +37,7 @@ impl ComponentDatabase {
         })
     }
 
+    /// Returns the components that are included by the workspace, including the ones that they load themselves.
     #[must_use]
     pub fn linked_components(&self, workspace: &Workspace) -> Vec<&Component> {
         let mut start_components = vec![self.kernel()];
@@ -51,20 +52,23 @@ impl ComponentDatabase {
             }
         }
 
-        let mut all_components = Vec::new();
-        for component in start_components {
+        let mut all_components: Vec<&Component> = Vec::new();
+        let mut visited = FxHashSet::default();
+        let mut pending = start_components.into_iter().collect::<VecDeque<_>>();
+        while let Some(component) = pending.pop_front() {
+            if !visited.insert(&component.file_names) {
+                continue;
+            }
+
             all_components.push(component);
             component
                 .references
                 .iter()
                 .filter_map(|file| self.find(file))
-                .for_each(|component| all_components.push(component));
+                .for_each(|component| pending.push_back(component));
         }
 
         all_components
-            .into_iter()
-            .unique_by(|component| &component.file_names)
-            .collect()
     }
 
     #[must_use]
@@ -89,18 +93,93 @@ impl ComponentDatabase {
             .any(|component| component.file_names.iter().any(|f| f == file_name))
     }
 
+    /// Returns the component that provides the given command (without the backslash)
+    /// if it is not provided by the kernel.
+    ///
+    /// Commands that are provided by several components are ambiguous and yield `None`.
+    #[must_use]
+    pub fn find_command_provider(&self, name: &str) -> Option<&Component> {
+        static PROVIDERS: Lazy<FxHashMap<SmolStr, Option<usize>>> = Lazy::new(|| {
+            let mut providers: FxHashMap<SmolStr, Option<usize>> = FxHashMap::default();
+            for (index, component) in COMPONENT_DATABASE.components.iter().enumerate() {
+                if component.file_names.is_empty() {
+                    continue;
+                }
+
+                for command in &component.commands {
+                    providers
+                        .entry(command.name.clone())
+                        .and_modify(|provider| *provider = None)
+                        .or_insert(Some(index));
+                }
+            }
+
+            providers
+        });
+
+        if self
+            .kernel()
+            .commands
+            .iter()
+            .any(|command| command.name == name)
+        {
+            return None;
+        }
+
+        PROVIDERS
+            .get(name)
+            .copied()
+            .flatten()
+            .map(|index| &COMPONENT_DATABASE.components[index])
+    }
+
+    /// Returns the only package that provides the given command (with the backslash)
+    /// if none of the given components provides it.
+    #[must_use]
+    pub fn find_missing_package(
+        &self,
+        linked_components: &[&Component],
+        name: &str,
+    ) -> Option<&Component> {
+        let name = name.strip_prefix('\\')?;
+        if linked_components
+            .iter()
+            .flat_map(|component| &component.commands)
+            .any(|command| command.name == name)
+        {
+            return None;
+        }
+
+        self.find_command_provider(name).filter(|component| {
+            component
+                .file_names
+                .iter()
+                .any(|file_name| file_name.ends_with(".sty"))
+        })
+    }
+
     #[must_use]
     pub fn documentation(&self, name: &str) -> Option<MarkupContent> {
+        self.documentation_with_kind(name, MarkupKind::PlainText)
+    }
+
+    /// Returns the description of a package or class.
+    ///
+    /// The Markdown version starts with the caption of the component.
+    #[must_use]
+    pub fn documentation_with_kind(&self, name: &str, kind: MarkupKind) -> Option<MarkupContent> {
         let metadata = self
             .metadata
             .iter()
             .find(|metadata| metadata.name == name)?;
 
         let desc = metadata.description.clone()?;
-        Some(MarkupContent {
-            kind: MarkupKind::PlainText,
-            value: desc,
-        })
+        let value = match (&kind, &metadata.caption) {
+            (MarkupKind::Markdown, Some(caption)) => format!("**{}**\n\n{}", caption, desc),
+            _ => desc,
+        };
+
+        Some(MarkupContent { kind, value })
     }
 }
 
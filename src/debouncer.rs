@@ -37,6 +37,8 @@ impl<T> Clone for Receiver<T> {
 }
 
 impl<T> Receiver<T> {
+    /// Waits until no message has been sent for the delay of the last message
+    /// and returns that message, dropping the ones before it.
     pub fn recv(&self) -> Result<T> {
         let (mut last_msg, delay) = self.rx.recv()?;
         delay.recv()?;
@@ -53,3 +55,21 @@ pub fn unbounded<T>() -> (Sender<T>, Receiver<T>) {
     let (tx, rx) = crossbeam_channel::unbounded();
     (Sender { tx }, Receiver { rx })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coalesce_messages() {
+        let (tx, rx) = unbounded();
+        for i in 0..3 {
+            tx.send(i, Duration::from_millis(20)).unwrap();
+        }
+
+        assert_eq!(rx.recv().unwrap(), 2);
+
+        tx.send(3, Duration::ZERO).unwrap();
+        assert_eq!(rx.recv().unwrap(), 3);
+    }
+}
@@ -2,14 +2,26 @@ mod bibtex;
 mod build;
 mod chktex;
 mod latex;
+mod pull;
+mod special_characters;
+mod style;
 
 use std::sync::Arc;
 
-use dashmap::DashMap;
+use dashmap::{DashMap, DashSet};
 use lsp_types::{DiagnosticSeverity, NumberOrString, Range, Url};
-use regex::Regex;
 
-use crate::Workspace;
+use rustc_hash::FxHashSet;
+
+use crate::{DocumentData, Workspace};
+
+pub use self::pull::{
+    DiagnosticOptions, DocumentDiagnosticParams, DocumentDiagnosticReport,
+    DocumentDiagnosticRequest, WorkspaceDiagnosticParams, WorkspaceDiagnosticRefresh,
+    WorkspaceDiagnosticReport, WorkspaceDiagnosticRequest, WorkspaceDocumentDiagnosticReport,
+};
+
+pub use self::special_characters::find_unescaped_characters;
 
 use self::{
     bibtex::collect_bibtex_diagnostics, build::collect_build_diagnostics,
@@ -37,6 +49,16 @@ pub enum LatexCode {
     UnexpectedRCurly,
     RCurlyInserted,
     MismatchedEnvironment,
+    MissingPackage,
+    UnmatchedBegin,
+    UnexpectedEnd,
+    MisspelledEnvironment,
+    MissingDocumentClass,
+    LongWord,
+    MissingThinSpace,
+    SpaceAroundTilde,
+    UnexpectedToken,
+    UnescapedCharacter,
 }
 
 impl From<LatexCode> for String {
@@ -45,6 +67,20 @@ impl From<LatexCode> for String {
             LatexCode::UnexpectedRCurly => "Unexpected \"}\"".to_string(),
             LatexCode::RCurlyInserted => "Missing \"}\" inserted".to_string(),
             LatexCode::MismatchedEnvironment => "Mismatched environment".to_string(),
+            LatexCode::MissingPackage => "Missing package".to_string(),
+            LatexCode::UnmatchedBegin => "Unmatched \\begin".to_string(),
+            LatexCode::UnexpectedEnd => "Unexpected \\end".to_string(),
+            LatexCode::MisspelledEnvironment => "Unknown environment".to_string(),
+            LatexCode::MissingDocumentClass => "Missing \\documentclass".to_string(),
+            LatexCode::LongWord => "Long unbreakable word may cause an overfull line".to_string(),
+            LatexCode::MissingThinSpace => {
+                "Missing thin space \"\\,\" before the differential".to_string()
+            }
+            LatexCode::SpaceAroundTilde => {
+                "Non-breaking space \"~\" next to a regular space".to_string()
+            }
+            LatexCode::UnexpectedToken => "Unexpected token".to_string(),
+            LatexCode::UnescapedCharacter => "Unescaped special character".to_string(),
         }
     }
 }
@@ -55,6 +91,16 @@ impl From<LatexCode> for NumberOrString {
             LatexCode::UnexpectedRCurly => NumberOrString::Number(1),
             LatexCode::RCurlyInserted => NumberOrString::Number(2),
             LatexCode::MismatchedEnvironment => NumberOrString::Number(3),
+            LatexCode::MissingPackage => NumberOrString::Number(9),
+            LatexCode::UnmatchedBegin => NumberOrString::Number(10),
+            LatexCode::UnexpectedEnd => NumberOrString::Number(11),
+            LatexCode::MisspelledEnvironment => NumberOrString::Number(12),
+            LatexCode::MissingDocumentClass => NumberOrString::Number(13),
+            LatexCode::LongWord => NumberOrString::Number(17),
+            LatexCode::MissingThinSpace => NumberOrString::Number(18),
+            LatexCode::SpaceAroundTilde => NumberOrString::Number(19),
+            LatexCode::UnexpectedToken => NumberOrString::Number(20),
+            LatexCode::UnescapedCharacter => NumberOrString::Number(21),
         }
     }
 }
@@ -67,6 +113,9 @@ pub enum BibtexCode {
     ExpectingRCurly,
     ExpectingEq,
     ExpectingFieldValue,
+    InvalidYear,
+    InvalidPages,
+    InvalidDoi,
 }
 
 impl From<BibtexCode> for String {
@@ -77,6 +126,9 @@ impl From<BibtexCode> for String {
             BibtexCode::ExpectingRCurly => "Expecting a curly bracket: \"}\"".to_string(),
             BibtexCode::ExpectingEq => "Expecting an equality sign: \"=\"".to_string(),
             BibtexCode::ExpectingFieldValue => "Expecting a field value".to_string(),
+            BibtexCode::InvalidYear => "Invalid year: expecting a number".to_string(),
+            BibtexCode::InvalidPages => "Invalid pages: expecting a page range".to_string(),
+            BibtexCode::InvalidDoi => "Invalid DOI: expecting \"10.<prefix>/<suffix>\"".to_string(),
         }
     }
 }
@@ -89,6 +141,9 @@ impl From<BibtexCode> for NumberOrString {
             BibtexCode::ExpectingRCurly => NumberOrString::Number(6),
             BibtexCode::ExpectingEq => NumberOrString::Number(7),
             BibtexCode::ExpectingFieldValue => NumberOrString::Number(8),
+            BibtexCode::InvalidYear => NumberOrString::Number(14),
+            BibtexCode::InvalidPages => NumberOrString::Number(15),
+            BibtexCode::InvalidDoi => NumberOrString::Number(16),
         }
     }
 }
@@ -96,10 +151,25 @@ impl From<BibtexCode> for NumberOrString {
 #[derive(Default, Clone)]
 pub struct DiagnosticManager {
     all_diagnostics: Arc<DashMap<Arc<Url>, Vec<Diagnostic>>>,
+    built_logs: Arc<DashSet<Arc<Url>>>,
+    deferred_documents: Arc<DashSet<Arc<Url>>>,
 }
 
 impl DiagnosticManager {
     pub fn push_syntax(&self, workspace: &Workspace, uri: &Url) {
+        // Analyzing a lazily loaded BibTeX document would build its syntax tree,
+        // so this is deferred until the document is opened or a feature needs the tree.
+        if is_deferred(workspace, uri) {
+            self.all_diagnostics.alter(uri, |_, mut diagnostics| {
+                diagnostics.retain(|diag| !matches!(diag.code, DiagnosticCode::Bibtex(_)));
+                diagnostics
+            });
+
+            self.deferred_documents.insert(Arc::new(uri.clone()));
+            return;
+        }
+
+        self.deferred_documents.remove(uri);
         collect_bibtex_diagnostics(&self.all_diagnostics, workspace, uri)
             .or_else(|| collect_latex_diagnostics(&self.all_diagnostics, workspace, uri))
             .or_else(|| collect_build_diagnostics(&self.all_diagnostics, workspace, uri));
@@ -109,12 +179,103 @@ impl DiagnosticManager {
         collect_chktex_diagnostics(&self.all_diagnostics, workspace, uri);
     }
 
+    /// Forgets the diagnostics of a document that has been removed from the workspace,
+    /// including the diagnostics that its build log reported for other documents.
+    pub fn remove(&self, uri: &Url) {
+        self.all_diagnostics.remove(uri);
+        self.deferred_documents.remove(uri);
+        self.built_logs.remove(uri);
+        for mut entry in self.all_diagnostics.iter_mut() {
+            entry.value_mut().retain(|diag| match &diag.code {
+                DiagnosticCode::Build(log_uri) => log_uri.as_ref() != uri,
+                _ => true,
+            });
+        }
+    }
+
+    /// Marks the build logs of the given workspace as produced by a build of the server.
+    pub fn push_build(&self, workspace: &Workspace) {
+        for uri in workspace
+            .documents_by_uri
+            .values()
+            .filter_map(|document| document.data.as_latex())
+            .flat_map(|data| &data.extras.implicit_links.log)
+        {
+            self.built_logs.insert(Arc::clone(uri));
+        }
+    }
+
+    /// Returns the diagnostics of all documents in the workspace and of the files that are only
+    /// referenced by a build log like included documents that have not been loaded.
+    pub fn publish_all(
+        &self,
+        workspace: &Workspace,
+    ) -> Vec<(Arc<Url>, Vec<lsp_types::Diagnostic>)> {
+        let mut uris: FxHashSet<Arc<Url>> = workspace
+            .documents_by_uri
+            .values()
+            .filter(|document| !matches!(document.data, DocumentData::BuildLog(_)))
+            .map(|document| Arc::clone(&document.uri))
+            .collect();
+
+        uris.extend(
+            self.all_diagnostics
+                .iter()
+                .map(|entry| Arc::clone(entry.key())),
+        );
+
+        let excluded = workspace.excluded_documents();
+        uris.into_iter()
+            .map(|uri| {
+                let diagnostics = self.publish_impl(workspace, &uri, excluded.contains(&uri));
+                (uri, diagnostics)
+            })
+            .collect()
+    }
+
     pub fn publish(&self, workspace: &Workspace, uri: &Url) -> Vec<lsp_types::Diagnostic> {
+        self.publish_impl(workspace, uri, workspace.is_excluded(uri))
+    }
+
+    fn publish_impl(
+        &self,
+        workspace: &Workspace,
+        uri: &Url,
+        is_excluded: bool,
+    ) -> Vec<lsp_types::Diagnostic> {
         let options = &workspace.environment.options.diagnostics;
+        let chktex_options = &workspace.environment.options.chktex;
+
+        if self.deferred_documents.contains(uri) && !is_deferred(workspace, uri) {
+            self.push_syntax(workspace, uri);
+        }
 
         let mut results = Vec::new();
         if let Some(diagnostics) = self.all_diagnostics.get(uri) {
             for diagnostic in diagnostics.iter() {
+                // Documents that are skipped by `\includeonly` are not compiled,
+                // so only their syntax errors are still relevant.
+                if is_excluded
+                    && matches!(
+                        diagnostic.code,
+                        DiagnosticCode::Build(_) | DiagnosticCode::Chktex(_)
+                    )
+                {
+                    continue;
+                }
+
+                if let DiagnosticCode::Build(log_uri) = &diagnostic.code {
+                    if options.build_log_after_build_only && !self.built_logs.contains(log_uri) {
+                        continue;
+                    }
+                }
+
+                if let DiagnosticCode::Chktex(code) = &diagnostic.code {
+                    if chktex_options.disabled_codes.contains(code) {
+                        continue;
+                    }
+                }
+
                 if !options.allowed_patterns.is_empty()
                     && !options
                         .allowed_patterns
@@ -160,8 +321,45 @@ impl DiagnosticManager {
     }
 }
 
-#[derive(Debug, Default)]
-pub struct DiagnosticFilter {
-    pub allowed_patterns: Vec<Regex>,
-    pub ignored_patterns: Vec<Regex>,
+fn is_deferred(workspace: &Workspace, uri: &Url) -> bool {
+    !workspace.is_open(uri)
+        && workspace
+            .documents_by_uri
+            .get(uri)
+            .and_then(|document| document.data.as_bibtex())
+            .map_or(false, |data| !data.is_parsed())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DocumentLanguage, Environment, Options};
+
+    use super::*;
+
+    #[test]
+    fn test_lazy_bibtex_document_is_not_parsed() {
+        let environment = Environment {
+            options: Arc::new(Options {
+                lazy_loading: true,
+                ..Options::default()
+            }),
+            ..Environment::default()
+        };
+
+        let mut workspace = Workspace::new(environment);
+        let uri = Arc::new(Url::parse("file:///texlab/main.bib").unwrap());
+        let text = Arc::new("@article{foo,".to_string());
+        let document = workspace
+            .open(Arc::clone(&uri), text, DocumentLanguage::Bibtex)
+            .unwrap();
+
+        let manager = DiagnosticManager::default();
+        manager.push_syntax(&workspace, &uri);
+        assert!(manager.publish(&workspace, &uri).is_empty());
+        assert!(!document.data.as_bibtex().unwrap().is_parsed());
+
+        workspace.viewport.insert(Arc::clone(&uri));
+        assert!(!manager.publish(&workspace, &uri).is_empty());
+        assert!(document.data.as_bibtex().unwrap().is_parsed());
+    }
 }
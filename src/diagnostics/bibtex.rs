@@ -2,10 +2,12 @@ use std::sync::Arc;
 
 use dashmap::DashMap;
 use lsp_types::{DiagnosticSeverity, Url};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use rowan::{ast::AstNode, TextRange};
 
 use crate::{
-    syntax::bibtex::{self, HasDelims, HasEq, HasName, HasType, HasValue},
+    syntax::bibtex::{self, HasDelims, HasEq, HasInteger, HasName, HasType, HasValue},
     Document, LineIndexExt, Workspace,
 };
 
@@ -24,7 +26,7 @@ pub fn collect_bibtex_diagnostics(
         diagnostics
     });
 
-    let root = bibtex::SyntaxNode::new_root(data.green.clone());
+    let root = bibtex::SyntaxNode::new_root(data.green().clone());
     for node in root.descendants() {
         analyze_entry(all_diagnostics, document, node.clone())
             .or_else(|| analyze_field(all_diagnostics, document, node));
@@ -135,5 +137,72 @@ fn analyze_field(
         return Some(());
     }
 
+    analyze_field_value(all_diagnostics, document, &field)
+}
+
+static PAGES_REGEX: Lazy<Regex> = Lazy::new(|| {
+    let page = r"[\p{L}\p{N}.:]+\+?";
+    let range = format!(r"{page}(\s*(-{{1,3}}|–|—)\s*{page})?", page = page);
+    Regex::new(&format!(r"^{range}(\s*,\s*{range})*$", range = range)).unwrap()
+});
+
+static DOI_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^10\.\d{4,9}/\S+$").unwrap());
+
+/// The placeholders that bibliography styles accept instead of a year.
+const YEAR_PLACEHOLDERS: &[&str] = &["n.d.", "in press"];
+
+fn is_valid_year(text: &str) -> bool {
+    (!text.is_empty() && text.chars().all(|c| c.is_ascii_digit()))
+        || YEAR_PLACEHOLDERS
+            .iter()
+            .any(|placeholder| text.eq_ignore_ascii_case(placeholder))
+}
+
+fn analyze_field_value(
+    all_diagnostics: &DashMap<Arc<Url>, Vec<Diagnostic>>,
+    document: &Document,
+    field: &bibtex::Field,
+) -> Option<()> {
+    let name = field.name_token()?.text().to_lowercase();
+    let value = field.value()?;
+
+    // Only plain values are checked because string references, concatenations
+    // and commands cannot be evaluated reliably.
+    let text = match &value {
+        bibtex::Value::Literal(literal) => literal.integer_token()?.text().to_string(),
+        bibtex::Value::CurlyGroup(_) | bibtex::Value::QuoteGroup(_) => {
+            if value.syntax().children().next().is_some() {
+                return None;
+            }
+
+            let text = value.syntax().text().to_string();
+            let text = text.trim_end();
+            text.get(1..text.len().checked_sub(1)?)?.to_string()
+        }
+        _ => return None,
+    };
+
+    let text = text.trim();
+    let (code, severity) = match name.as_str() {
+        "year" if !is_valid_year(text) => (BibtexCode::InvalidYear, DiagnosticSeverity::WARNING),
+        "pages" if !PAGES_REGEX.is_match(text) => {
+            (BibtexCode::InvalidPages, DiagnosticSeverity::INFORMATION)
+        }
+        "doi" if !DOI_REGEX.is_match(text) => (BibtexCode::InvalidDoi, DiagnosticSeverity::WARNING),
+        _ => return None,
+    };
+
+    all_diagnostics
+        .entry(Arc::clone(&document.uri))
+        .or_default()
+        .push(Diagnostic {
+            severity,
+            range: document
+                .line_index
+                .line_col_lsp_range(bibtex::small_range(&value)),
+            code: DiagnosticCode::Bibtex(code),
+            message: String::from(code),
+        });
+
     Some(())
 }
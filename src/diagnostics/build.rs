@@ -95,7 +95,7 @@ pub fn collect_build_diagnostics(
             error
                 .relative_path
                 .to_str()
-                .and_then(|path| root_document_uri.join(path).map(Into::into).ok())
+                .and_then(|path| root_document_uri.join(path).ok())
                 .map_or_else(|| Arc::clone(root_document_uri), Arc::new)
         } else {
             Arc::clone(root_document_uri)
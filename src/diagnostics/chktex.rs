@@ -6,12 +6,13 @@ use std::{
 };
 
 use dashmap::DashMap;
-use lsp_types::{DiagnosticSeverity, Range, Url};
+use lsp_types::{DiagnosticSeverity, Url};
 use once_cell::sync::Lazy;
 use regex::Regex;
+use rowan::TextRange;
 use tempfile::tempdir;
 
-use crate::{Document, RangeExt, Workspace};
+use crate::{Document, LineCol, LineIndex, LineIndexExt, Workspace};
 
 use super::{Diagnostic, DiagnosticCode};
 
@@ -53,7 +54,7 @@ pub fn collect_chktex_diagnostics(
     all_diagnostics
         .entry(Arc::clone(&document.uri))
         .or_default()
-        .extend(lint(&document.text, &current_dir).unwrap_or_default());
+        .extend(lint(document, &current_dir).unwrap_or_default());
 
     Some(())
 }
@@ -80,32 +81,53 @@ fn find_chktexrc_directory(document: &Document) -> Option<PathBuf> {
 static LINE_REGEX: Lazy<Regex> =
     Lazy::new(|| Regex::new("(\\d+):(\\d+):(\\d+):(\\w+):(\\w+):(.*)").unwrap());
 
-fn lint(text: &str, current_dir: &Path) -> io::Result<Vec<Diagnostic>> {
+fn lint(document: &Document, current_dir: &Path) -> io::Result<Vec<Diagnostic>> {
     let directory = tempdir()?;
-    fs::write(directory.path().join("file.tex"), text)?;
+    fs::write(directory.path().join("file.tex"), document.text.as_bytes())?;
 
     for rc_file in CHKTEXRC_FILES {
         let _ = fs::copy(current_dir.join(rc_file), directory.path().join(rc_file));
     }
 
     let output = Command::new("chktex")
-        .args(&["-I0", "-f%l:%c:%d:%k:%n:%m\n", "file.tex"])
+        .args(["-I0", "-f%l:%c:%d:%k:%n:%m\n", "file.tex"])
         .stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .current_dir(directory.path())
         .output()?;
 
+    Ok(parse_output(
+        &String::from_utf8_lossy(&output.stdout),
+        &document.line_index,
+    ))
+}
+
+/// Converts the output of ChkTeX into diagnostics.
+///
+/// ChkTeX reports the column and the length in bytes, so the ranges are mapped to UTF-16
+/// positions with the line index of the document to stay aligned with multibyte characters.
+fn parse_output(output: &str, line_index: &LineIndex) -> Vec<Diagnostic> {
     let mut diagnostics = Vec::new();
-    for line in String::from_utf8_lossy(&output.stdout).lines() {
-        let captures = LINE_REGEX.captures(line).unwrap();
+    for line in output.lines() {
+        let captures = match LINE_REGEX.captures(line) {
+            Some(captures) => captures,
+            None => continue,
+        };
+
         let line = captures[1].parse::<u32>().unwrap() - 1;
-        let character = captures[2].parse::<u32>().unwrap() - 1;
+        let col = captures[2].parse::<u32>().unwrap() - 1;
         let digit = captures[3].parse::<u32>().unwrap();
         let kind = &captures[4];
         let code = &captures[5];
         let message = captures[6].into();
-        let range = Range::new_simple(line, character, line, character + digit);
+        let start = line_index.offset(LineCol { line, col });
+        let end = line_index.offset(LineCol {
+            line,
+            col: col + digit,
+        });
+
+        let range = line_index.line_col_lsp_range(TextRange::new(start, end));
         let severity = match kind {
             "Message" => DiagnosticSeverity::INFORMATION,
             "Warning" => DiagnosticSeverity::WARNING,
@@ -120,5 +142,56 @@ fn lint(text: &str, current_dir: &Path) -> io::Result<Vec<Diagnostic>> {
         });
     }
 
-    Ok(diagnostics)
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::Range;
+
+    use crate::RangeExt;
+
+    use super::*;
+
+    fn find_ranges(text: &str, output: &str) -> Vec<Range> {
+        parse_output(output, &LineIndex::new(text))
+            .into_iter()
+            .map(|diagnostic| diagnostic.range)
+            .collect()
+    }
+
+    #[test]
+    fn test_ascii() {
+        let ranges = find_ranges(
+            "foo\nbar baz",
+            "2:5:3:Warning:1:Command terminated with space.",
+        );
+
+        assert_eq!(ranges, [Range::new_simple(1, 4, 1, 7)]);
+    }
+
+    #[test]
+    fn test_multibyte_characters() {
+        let ranges = find_ranges(
+            "\u{e9}t\u{e9} \\ldots",
+            "1:7:6:Warning:11:You should use \\ldots to achieve an ellipsis.",
+        );
+
+        assert_eq!(ranges, [Range::new_simple(0, 4, 0, 10)]);
+    }
+
+    #[test]
+    fn test_tabs() {
+        let ranges = find_ranges(
+            "\t\u{fc}\tfoo",
+            "1:5:3:Warning:1:Command terminated with space.",
+        );
+        assert_eq!(ranges, [Range::new_simple(0, 3, 0, 6)]);
+    }
+
+    #[test]
+    fn test_clamp_to_line_end() {
+        let ranges = find_ranges("foo\nbar", "1:2:5:Warning:1:Command terminated with space.");
+        assert_eq!(ranges, [Range::new_simple(0, 1, 0, 3)]);
+    }
 }
@@ -3,10 +3,18 @@ use std::sync::Arc;
 use dashmap::DashMap;
 use lsp_types::{DiagnosticSeverity, Url};
 use rowan::{ast::AstNode, NodeOrToken, TextRange};
+use rustc_hash::FxHashSet;
 
-use crate::{syntax::latex, Document, LineIndexExt, Workspace};
+use crate::{
+    component_db::COMPONENT_DATABASE,
+    syntax::latex::{self, ExplicitLinkKind, HasCurly},
+    Document, KnownEnvironments, LineIndexExt, Workspace,
+};
 
-use super::{Diagnostic, DiagnosticCode, LatexCode};
+use super::{
+    special_characters::analyze_special_characters, style::analyze_style, Diagnostic,
+    DiagnosticCode, LatexCode,
+};
 
 pub fn collect_latex_diagnostics(
     all_diagnostics: &DashMap<Arc<Url>, Vec<Diagnostic>>,
@@ -14,7 +22,11 @@ pub fn collect_latex_diagnostics(
     uri: &Url,
 ) -> Option<()> {
     let document = workspace.documents_by_uri.get(uri)?;
-    if !document.uri.as_str().ends_with(".tex") {
+    let is_extra_file = document.uri.to_file_path().map_or(false, |path| {
+        workspace.environment.options.is_extra_latex_file(&path)
+    });
+
+    if !document.uri.as_str().ends_with(".tex") && !is_extra_file {
         return None;
     }
 
@@ -25,55 +37,223 @@ pub fn collect_latex_diagnostics(
         diagnostics
     });
 
-    for node in latex::SyntaxNode::new_root(data.green.clone()).descendants() {
-        analyze_environment(all_diagnostics, document, node.clone())
-            .or_else(|| analyze_curly_group(all_diagnostics, document, &node))
-            .or_else(|| {
-                if node.kind() == latex::ERROR && node.first_token()?.text() == "}" {
-                    let code = LatexCode::UnexpectedRCurly;
-                    all_diagnostics
-                        .entry(Arc::clone(&document.uri))
-                        .or_default()
-                        .push(Diagnostic {
-                            severity: DiagnosticSeverity::ERROR,
-                            range: document.line_index.line_col_lsp_range(node.text_range()),
-                            code: DiagnosticCode::Latex(code),
-                            message: String::from(code),
-                        });
-
-                    Some(())
-                } else {
-                    None
-                }
-            });
+    let root = latex::SyntaxNode::new_root(data.green.clone());
+    analyze_missing_packages(all_diagnostics, workspace, document, &root);
+    analyze_environments(all_diagnostics, document, &root);
+    analyze_misspelled_environments(all_diagnostics, workspace, document, &root);
+    analyze_missing_document_class(all_diagnostics, workspace, document);
+    analyze_style(all_diagnostics, workspace, document, &root);
+    analyze_special_characters(all_diagnostics, workspace, document, &root);
+    analyze_parser_errors(all_diagnostics, workspace, document, &root);
+
+    for node in root.descendants() {
+        analyze_curly_group(all_diagnostics, document, &node).or_else(|| {
+            if node.kind() == latex::ERROR && node.first_token()?.text() == "}" {
+                let code = LatexCode::UnexpectedRCurly;
+                all_diagnostics
+                    .entry(Arc::clone(&document.uri))
+                    .or_default()
+                    .push(Diagnostic {
+                        severity: DiagnosticSeverity::ERROR,
+                        range: document.line_index.line_col_lsp_range(node.text_range()),
+                        code: DiagnosticCode::Latex(code),
+                        message: String::from(code),
+                    });
+
+                Some(())
+            } else {
+                None
+            }
+        });
     }
 
     Some(())
 }
 
-fn analyze_environment(
+/// Reports the tokens that the parser skipped during error recovery if `diagnostics.showParserErrors` is set.
+///
+/// Unexpected closing braces are always reported by `collect_latex_diagnostics`.
+fn analyze_parser_errors(
     all_diagnostics: &DashMap<Arc<Url>, Vec<Diagnostic>>,
+    workspace: &Workspace,
     document: &Document,
-    node: latex::SyntaxNode,
+    root: &latex::SyntaxNode,
 ) -> Option<()> {
-    let environment = latex::Environment::cast(node)?;
-    let name1 = environment.begin()?.name()?.key()?;
-    let name2 = environment.end()?.name()?.key()?;
-    if name1 != name2 {
-        let code = LatexCode::MismatchedEnvironment;
+    if !workspace.environment.options.diagnostics.show_parser_errors {
+        return None;
+    }
+
+    let code = LatexCode::UnexpectedToken;
+    let mut diagnostics = all_diagnostics
+        .entry(Arc::clone(&document.uri))
+        .or_default();
+
+    for node in root
+        .descendants()
+        .filter(|node| node.kind() == latex::ERROR)
+        .filter(|node| {
+            node.first_token()
+                .map_or(false, |token| token.text() != "}")
+        })
+    {
+        diagnostics.push(Diagnostic {
+            severity: DiagnosticSeverity::INFORMATION,
+            range: document.line_index.line_col_lsp_range(node.text_range()),
+            code: DiagnosticCode::Latex(code),
+            message: format!("{}: \"{}\"", String::from(code), node.text()),
+        });
+    }
+
+    Some(())
+}
+
+/// Matches the `\begin` and `\end` commands of the document by stack order.
+///
+/// An `\end` that closes an outer environment leaves the inner environments unmatched
+/// while an `\end` that does not match any open environment is reported as a mismatch.
+fn analyze_environments(
+    all_diagnostics: &DashMap<Arc<Url>, Vec<Diagnostic>>,
+    document: &Document,
+    root: &latex::SyntaxNode,
+) {
+    let mut diagnostics = Vec::new();
+    let mut stack: Vec<(String, TextRange, TextRange)> = Vec::new();
+    for node in root
+        .descendants()
+        .filter(|node| !is_inside_definition(node))
+    {
+        if let Some(begin) = latex::Begin::cast(node.clone()).filter(|_| {
+            node.parent()
+                .map_or(false, |parent| parent.kind() == latex::ENVIRONMENT)
+        }) {
+            if let Some(name) = begin.name().and_then(|name| name.key()) {
+                stack.push((
+                    name.to_string(),
+                    latex::small_range(&name),
+                    latex::small_range(&begin),
+                ));
+            }
+        } else if let Some((name, range)) = find_end(&node) {
+            match stack.iter().rposition(|(other, _, _)| *other == name) {
+                Some(index) => {
+                    for (name, _, range) in stack.drain(index..).skip(1) {
+                        diagnostics.push(unmatched_begin(&name, range));
+                    }
+                }
+                None => match stack.pop() {
+                    Some((_, name_range, _)) => {
+                        let code = LatexCode::MismatchedEnvironment;
+                        diagnostics.push((
+                            name_range,
+                            DiagnosticCode::Latex(code),
+                            String::from(code),
+                        ));
+                    }
+                    None => {
+                        diagnostics.push((
+                            range,
+                            DiagnosticCode::Latex(LatexCode::UnexpectedEnd),
+                            format!("Unexpected \\end{{{}}}", name),
+                        ));
+                    }
+                },
+            }
+        }
+    }
+
+    for (name, _, range) in stack {
+        diagnostics.push(unmatched_begin(&name, range));
+    }
+
+    diagnostics.sort_by_key(|(range, _, _)| range.start());
+    for (range, code, message) in diagnostics {
         all_diagnostics
             .entry(Arc::clone(&document.uri))
             .or_default()
             .push(Diagnostic {
                 severity: DiagnosticSeverity::ERROR,
-                range: document
-                    .line_index
-                    .line_col_lsp_range(latex::small_range(&name1)),
-                code: DiagnosticCode::Latex(code),
-                message: String::from(code),
+                range: document.line_index.line_col_lsp_range(range),
+                code,
+                message,
             });
     }
-    Some(())
+}
+
+fn unmatched_begin(name: &str, range: TextRange) -> (TextRange, DiagnosticCode, String) {
+    (
+        range,
+        DiagnosticCode::Latex(LatexCode::UnmatchedBegin),
+        format!("Unmatched \\begin{{{}}}", name),
+    )
+}
+
+/// Returns the name and the range of an `\end` command that belongs to an environment
+/// or that is left over after all environments have been closed.
+fn find_end(node: &latex::SyntaxNode) -> Option<(String, TextRange)> {
+    if let Some(end) = latex::End::cast(node.clone()) {
+        node.parent()
+            .filter(|parent| parent.kind() == latex::ENVIRONMENT)?;
+        let name = end.name()?.key()?.to_string();
+        return Some((name, latex::small_range(&end)));
+    }
+
+    let command = latex::GenericCommand::cast(node.clone())?;
+    if command.syntax().first_token()?.kind() != latex::END_ENVIRONMENT_NAME {
+        return None;
+    }
+
+    let name = command
+        .syntax()
+        .children()
+        .find_map(latex::CurlyGroup::cast)
+        .and_then(|group| group.content_text())
+        .unwrap_or_default();
+
+    Some((name.trim().to_string(), latex::small_range(&command)))
+}
+
+/// Commands whose arguments define a command or an environment.
+const DEFINITION_COMMANDS: &[&str] = &[
+    "\\def",
+    "\\gdef",
+    "\\edef",
+    "\\xdef",
+    "\\providecommand",
+    "\\providecommand*",
+    "\\NewDocumentCommand",
+    "\\RenewDocumentCommand",
+    "\\ProvideDocumentCommand",
+    "\\DeclareDocumentCommand",
+    "\\NewDocumentEnvironment",
+    "\\RenewDocumentEnvironment",
+    "\\ProvideDocumentEnvironment",
+    "\\DeclareDocumentEnvironment",
+];
+
+/// Environments inside of definitions like `\newenvironment` do not have to match
+/// because `\begin` and `\end` are usually split across the arguments.
+fn is_inside_definition(node: &latex::SyntaxNode) -> bool {
+    node.ancestors()
+        .skip(1)
+        .any(|ancestor| match ancestor.kind() {
+            latex::ENVIRONMENT_DEFINITION | latex::COMMAND_DEFINITION | latex::MATH_OPERATOR => {
+                true
+            }
+            // Handles both `\NewDocumentEnvironment{foo}{...}` and `\def\foo{...}`.
+            latex::GENERIC_COMMAND => {
+                is_definition_command(&ancestor)
+                    || ancestor
+                        .prev_sibling()
+                        .map_or(false, |sibling| is_definition_command(&sibling))
+            }
+            _ => false,
+        })
+}
+
+fn is_definition_command(node: &latex::SyntaxNode) -> bool {
+    latex::GenericCommand::cast(node.clone())
+        .and_then(|command| command.name())
+        .map_or(false, |name| DEFINITION_COMMANDS.contains(&name.text()))
 }
 
 fn analyze_curly_group(
@@ -92,17 +272,7 @@ fn analyze_curly_group(
         return None;
     }
 
-    let is_inside_verbatim_environment = node
-        .ancestors()
-        .filter_map(latex::Environment::cast)
-        .filter_map(|env| env.begin())
-        .filter_map(|begin| begin.name())
-        .filter_map(|name| name.key())
-        .any(|name| {
-            ["asy", "lstlisting", "minted", "verbatim"].contains(&name.to_string().as_str())
-        });
-
-    if !is_inside_verbatim_environment
+    if !is_inside_verbatim_environment(node)
         && !node
             .children_with_tokens()
             .filter_map(NodeOrToken::into_token)
@@ -124,3 +294,149 @@ fn analyze_curly_group(
 
     Some(())
 }
+
+fn is_inside_verbatim_environment(node: &latex::SyntaxNode) -> bool {
+    node.ancestors()
+        .filter_map(latex::Environment::cast)
+        .filter_map(|env| env.begin())
+        .filter_map(|begin| begin.name())
+        .filter_map(|name| name.key())
+        .any(|name| {
+            ["asy", "lstlisting", "minted", "verbatim"].contains(&name.to_string().as_str())
+        })
+}
+
+/// Reports environments whose name is close to the name of a known environment.
+fn analyze_misspelled_environments(
+    all_diagnostics: &DashMap<Arc<Url>, Vec<Diagnostic>>,
+    workspace: &Workspace,
+    document: &Document,
+    root: &latex::SyntaxNode,
+) {
+    let workspace = workspace.slice(&document.uri);
+    let known_environments = KnownEnvironments::new(&workspace);
+    for name in root
+        .descendants()
+        .filter_map(latex::Environment::cast)
+        .filter_map(|environment| environment.begin()?.name()?.key())
+    {
+        let suggestion = match known_environments.find_similar(&name.to_string()) {
+            Some(suggestion) => suggestion,
+            None => continue,
+        };
+
+        all_diagnostics
+            .entry(Arc::clone(&document.uri))
+            .or_default()
+            .push(Diagnostic {
+                severity: DiagnosticSeverity::HINT,
+                range: document
+                    .line_index
+                    .line_col_lsp_range(latex::small_range(&name)),
+                code: DiagnosticCode::Latex(LatexCode::MisspelledEnvironment),
+                message: format!(
+                    "Unknown environment \"{}\", did you mean \"{}\"?",
+                    name, suggestion
+                ),
+            });
+    }
+}
+
+/// Reports documents that contain a `document` environment but do not load a class.
+///
+/// The class may also be loaded by another document that includes this one,
+/// for example when the preamble is kept in a separate file.
+fn analyze_missing_document_class(
+    all_diagnostics: &DashMap<Arc<Url>, Vec<Diagnostic>>,
+    workspace: &Workspace,
+    document: &Document,
+) -> Option<()> {
+    let data = document.data.as_latex()?;
+    if !data.extras.has_document_environment {
+        return None;
+    }
+
+    let has_class = workspace
+        .slice(&document.uri)
+        .documents_by_uri
+        .values()
+        .filter_map(|document| document.data.as_latex())
+        .flat_map(|data| data.extras.explicit_links.iter())
+        .any(|link| link.kind == ExplicitLinkKind::Class);
+
+    if has_class {
+        return None;
+    }
+
+    let code = LatexCode::MissingDocumentClass;
+    all_diagnostics
+        .entry(Arc::clone(&document.uri))
+        .or_default()
+        .push(Diagnostic {
+            severity: DiagnosticSeverity::WARNING,
+            range: document
+                .line_index
+                .line_col_lsp_range(TextRange::empty(0.into())),
+            code: DiagnosticCode::Latex(code),
+            message: String::from(code),
+        });
+
+    Some(())
+}
+
+/// Reports commands that are provided by a well-known package that is not included.
+///
+/// Commands that are unknown to the component database or provided by several packages are not reported
+/// because they are most likely defined somewhere we cannot see.
+fn analyze_missing_packages(
+    all_diagnostics: &DashMap<Arc<Url>, Vec<Diagnostic>>,
+    workspace: &Workspace,
+    document: &Document,
+    root: &latex::SyntaxNode,
+) -> Option<()> {
+    if !workspace.environment.options.diagnostics.missing_packages {
+        return None;
+    }
+
+    let workspace = workspace.slice(&document.uri);
+    let defined_commands: FxHashSet<_> = workspace
+        .documents_by_uri
+        .values()
+        .filter_map(|document| document.data.as_latex())
+        .flat_map(|data| data.extras.defined_commands.iter())
+        .map(|name| name.as_str())
+        .collect();
+
+    let linked_components = COMPONENT_DATABASE.linked_components(&workspace);
+    for name in root
+        .descendants()
+        .filter_map(|node| latex::package_command_name(&node).map(|name| (node, name)))
+        .filter(|(node, _)| !is_inside_verbatim_environment(node))
+        .map(|(_, name)| name)
+        .filter(|name| !defined_commands.contains(name.text()))
+    {
+        let package = match COMPONENT_DATABASE
+            .find_missing_package(&linked_components, name.text())
+            .and_then(|component| {
+                component
+                    .file_names
+                    .iter()
+                    .find_map(|file_name| file_name.strip_suffix(".sty"))
+            }) {
+            Some(package) => package,
+            None => continue,
+        };
+
+        all_diagnostics
+            .entry(Arc::clone(&document.uri))
+            .or_default()
+            .push(Diagnostic {
+                severity: DiagnosticSeverity::INFORMATION,
+                range: document.line_index.line_col_lsp_range(name.text_range()),
+                code: DiagnosticCode::Latex(LatexCode::MissingPackage),
+                message: format!("{} requires \\usepackage{{{}}}", name.text(), package),
+            });
+    }
+
+    Some(())
+}
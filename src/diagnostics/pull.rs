@@ -0,0 +1,73 @@
+//! Types of the pull model for diagnostics (LSP 3.17) which are not part of `lsp-types` yet.
+
+use lsp_types::{Diagnostic, TextDocumentIdentifier, Url};
+use serde::{Deserialize, Serialize};
+
+pub struct DocumentDiagnosticRequest;
+
+impl lsp_types::request::Request for DocumentDiagnosticRequest {
+    type Params = DocumentDiagnosticParams;
+
+    type Result = DocumentDiagnosticReport;
+
+    const METHOD: &'static str = "textDocument/diagnostic";
+}
+
+pub struct WorkspaceDiagnosticRequest;
+
+impl lsp_types::request::Request for WorkspaceDiagnosticRequest {
+    type Params = WorkspaceDiagnosticParams;
+
+    type Result = WorkspaceDiagnosticReport;
+
+    const METHOD: &'static str = "workspace/diagnostic";
+}
+
+pub struct WorkspaceDiagnosticRefresh;
+
+impl lsp_types::request::Request for WorkspaceDiagnosticRefresh {
+    type Params = ();
+
+    type Result = ();
+
+    const METHOD: &'static str = "workspace/diagnostic/refresh";
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticOptions {
+    pub inter_file_dependencies: bool,
+    pub workspace_diagnostics: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentDiagnosticParams {
+    pub text_document: TextDocumentIdentifier,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum DocumentDiagnosticReport {
+    Full { items: Vec<Diagnostic> },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceDiagnosticParams {}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceDiagnosticReport {
+    pub items: Vec<WorkspaceDocumentDiagnosticReport>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum WorkspaceDocumentDiagnosticReport {
+    Full {
+        uri: Url,
+        version: Option<i32>,
+        items: Vec<Diagnostic>,
+    },
+}
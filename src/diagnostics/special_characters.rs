@@ -0,0 +1,188 @@
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use lsp_types::{DiagnosticSeverity, Url};
+use rowan::{ast::AstNode, TextRange, TextSize};
+
+use crate::{syntax::latex, Document, LineIndexExt, Workspace};
+
+use super::{
+    style::{find_body_range, is_math, URL_COMMANDS},
+    Diagnostic, DiagnosticCode, LatexCode,
+};
+
+/// Environments that use `&` to separate the cells in text mode.
+const ALIGNMENT_ENVIRONMENTS: &[&str] = &[
+    "tabular",
+    "tabular*",
+    "tabularx",
+    "tabulary",
+    "longtable",
+    "supertabular",
+    "xtabular",
+    "xltabular",
+    "tabu",
+    "longtabu",
+    "tblr",
+    "longtblr",
+    "NiceTabular",
+    "NiceTabular*",
+    "IEEEeqnarray",
+    "IEEEeqnarray*",
+    "IEEEeqnarraybox",
+    "tikzcd",
+    "xy",
+];
+
+/// Environments whose content is drawing code like `\addplot table {data_1.csv}` instead of text.
+const PICTURE_ENVIRONMENTS: &[&str] = &["tikzpicture", "pgfpicture"];
+
+/// Commands whose argument is source code or a file name instead of text.
+const IGNORED_COMMANDS: &[&str] = &["\\lstinline", "\\mintinline", "\\bibliographystyle"];
+
+/// Commands whose curly arguments are part of the running text.
+/// The arguments of all other commands are usually names, keys or file names like `\pgfplotstableread{x_y.txt}`.
+const TEXT_COMMANDS: &[&str] = &[
+    "\\textbf",
+    "\\textit",
+    "\\textsl",
+    "\\textsc",
+    "\\textup",
+    "\\textmd",
+    "\\textrm",
+    "\\textsf",
+    "\\texttt",
+    "\\textnormal",
+    "\\emph",
+    "\\underline",
+    "\\mbox",
+    "\\fbox",
+    "\\footnote",
+    "\\footnotetext",
+    "\\marginpar",
+    "\\title",
+    "\\author",
+    "\\thanks",
+    "\\textcolor",
+];
+
+/// Reports the characters `&`, `_` and `#` that are used in text mode without a backslash.
+pub fn analyze_special_characters(
+    all_diagnostics: &DashMap<Arc<Url>, Vec<Diagnostic>>,
+    workspace: &Workspace,
+    document: &Document,
+    root: &latex::SyntaxNode,
+) {
+    if !workspace
+        .environment
+        .options
+        .diagnostics
+        .unescaped_characters
+    {
+        return;
+    }
+
+    let code = LatexCode::UnescapedCharacter;
+    let mut diagnostics = all_diagnostics
+        .entry(Arc::clone(&document.uri))
+        .or_default();
+
+    for range in find_unescaped_characters(root) {
+        let character = &document.text[std::ops::Range::<usize>::from(range)];
+        diagnostics.push(Diagnostic {
+            severity: DiagnosticSeverity::WARNING,
+            range: document.line_index.line_col_lsp_range(range),
+            code: DiagnosticCode::Latex(code),
+            message: format!("{}: \"{}\"", String::from(code), character),
+        });
+    }
+}
+
+/// Returns the ranges of the special characters in text mode that need to be escaped.
+///
+/// Only words of the running text are considered, so optional arguments like the one of `\hyperref[sec_a]` are skipped.
+pub fn find_unescaped_characters(root: &latex::SyntaxNode) -> Vec<TextRange> {
+    let body_range = find_body_range(root);
+    let mut ranges = Vec::new();
+    for token in root
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| token.kind() == latex::WORD)
+        .filter(|token| body_range.contains_range(token.text_range()))
+        .filter(|token| {
+            token
+                .parent()
+                .map_or(false, |parent| parent.kind() == latex::TEXT)
+        })
+        .filter(|token| !is_ignored(token))
+    {
+        let is_alignment = token.parent_ancestors().any(|node| is_alignment(&node));
+        let start = token.text_range().start();
+        let text = token.text();
+        for (index, c) in text.char_indices() {
+            let is_special = match c {
+                '&' => !is_alignment,
+                '_' => true,
+                // Parameters like `#1` are part of a definition.
+                '#' => {
+                    !text[index + 1..].starts_with(|c: char| c == '#' || c.is_ascii_digit())
+                        && !text[..index].ends_with('#')
+                }
+                _ => false,
+            };
+
+            if is_special {
+                let offset = start + TextSize::try_from(index).unwrap();
+                ranges.push(TextRange::at(offset, TextSize::of(c)));
+            }
+        }
+    }
+
+    ranges
+}
+
+fn is_ignored(token: &latex::SyntaxToken) -> bool {
+    token.parent_ancestors().any(|node| {
+        is_math(&node)
+            || matches!(
+                node.kind(),
+                latex::COMMAND_DEFINITION
+                    | latex::ENVIRONMENT_DEFINITION
+                    | latex::BRACK_GROUP
+                    | latex::MIXED_GROUP
+                    | latex::PAREN_GROUP
+            )
+            || is_picture(&node)
+            || is_command_argument(&node)
+            || latex::GenericCommand::cast(node)
+                .and_then(|command| command.name())
+                .map_or(false, |name| {
+                    URL_COMMANDS.contains(&name.text()) || IGNORED_COMMANDS.contains(&name.text())
+                })
+    })
+}
+
+fn is_command_argument(node: &latex::SyntaxNode) -> bool {
+    node.kind() == latex::CURLY_GROUP
+        && node
+            .parent()
+            .and_then(latex::GenericCommand::cast)
+            .and_then(|command| command.name())
+            .map_or(false, |name| !TEXT_COMMANDS.contains(&name.text()))
+}
+
+fn is_picture(node: &latex::SyntaxNode) -> bool {
+    latex::Environment::cast(node.clone())
+        .and_then(|environment| environment.begin()?.name()?.key())
+        .map_or(false, |name| {
+            PICTURE_ENVIRONMENTS.contains(&name.to_string().as_str())
+        })
+}
+
+fn is_alignment(node: &latex::SyntaxNode) -> bool {
+    latex::Environment::cast(node.clone())
+        .and_then(|environment| environment.begin()?.name()?.key())
+        .map_or(false, |name| {
+            ALIGNMENT_ENVIRONMENTS.contains(&name.to_string().as_str())
+        })
+}
@@ -0,0 +1,198 @@
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use lsp_types::{DiagnosticSeverity, Url};
+use rowan::{ast::AstNode, TextRange, TextSize};
+
+use crate::{syntax::latex, Document, LineIndexExt, Workspace, LANGUAGE_DATA};
+
+use super::{Diagnostic, DiagnosticCode, LatexCode};
+
+/// The number of characters after which a word is unlikely to fit into a line without hyphenation.
+const MAX_WORD_LENGTH: usize = 40;
+
+/// Commands whose argument is typeset with line breaks at special characters.
+pub(super) const URL_COMMANDS: &[&str] = &["\\url", "\\href", "\\nolinkurl", "\\path", "\\doi"];
+
+const INTEGRAL_COMMANDS: &[&str] = &["\\int", "\\iint", "\\iiint", "\\oint"];
+
+/// Reports source patterns that are likely to produce badly typeset output.
+///
+/// The checks only look at the source and do not need a build log or `chktex`.
+pub fn analyze_style(
+    all_diagnostics: &DashMap<Arc<Url>, Vec<Diagnostic>>,
+    workspace: &Workspace,
+    document: &Document,
+    root: &latex::SyntaxNode,
+) -> Option<()> {
+    let options = &workspace.environment.options.diagnostics.style_checks;
+    if !options.enabled {
+        return None;
+    }
+
+    let mut results = Vec::new();
+    let body_range = find_body_range(root);
+    for token in root
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| token.kind() == latex::WORD)
+        .filter(|token| body_range.contains_range(token.text_range()))
+    {
+        if options.long_words {
+            if let Some(range) = check_long_word(&token) {
+                results.push((range, LatexCode::LongWord));
+            }
+        }
+
+        if options.non_breaking_spaces {
+            results.extend(
+                check_non_breaking_space(&token)
+                    .into_iter()
+                    .map(|range| (range, LatexCode::SpaceAroundTilde)),
+            );
+        }
+    }
+
+    if options.math_spacing {
+        for node in root
+            .descendants()
+            .filter(is_math)
+            .filter(|node| !node.ancestors().skip(1).any(|node| is_math(&node)))
+        {
+            results.extend(
+                check_differentials(&node)
+                    .into_iter()
+                    .map(|range| (range, LatexCode::MissingThinSpace)),
+            );
+        }
+    }
+
+    let mut diagnostics = all_diagnostics
+        .entry(Arc::clone(&document.uri))
+        .or_default();
+
+    for (range, code) in results {
+        diagnostics.push(Diagnostic {
+            severity: DiagnosticSeverity::HINT,
+            range: document.line_index.line_col_lsp_range(range),
+            code: DiagnosticCode::Latex(code),
+            message: String::from(code),
+        });
+    }
+
+    Some(())
+}
+
+/// Returns the range of the `document` environment or the whole document if it is a child document.
+pub(super) fn find_body_range(root: &latex::SyntaxNode) -> TextRange {
+    root.descendants()
+        .filter_map(latex::Environment::cast)
+        .find(|environment| {
+            environment
+                .begin()
+                .and_then(|begin| begin.name())
+                .and_then(|name| name.key())
+                .map_or(false, |name| name.to_string() == "document")
+        })
+        .map_or_else(
+            || root.text_range(),
+            |environment| environment.syntax().text_range(),
+        )
+}
+
+pub(super) fn is_math(node: &latex::SyntaxNode) -> bool {
+    match node.kind() {
+        latex::FORMULA | latex::EQUATION => true,
+        latex::ENVIRONMENT => latex::Environment::cast(node.clone())
+            .and_then(|environment| environment.begin()?.name()?.key())
+            .map_or(false, |name| {
+                LANGUAGE_DATA.math_environments.contains(&name.to_string())
+            }),
+        _ => false,
+    }
+}
+
+fn check_long_word(token: &latex::SyntaxToken) -> Option<TextRange> {
+    if token.text().chars().count() <= MAX_WORD_LENGTH
+        || token.parent()?.kind() != latex::TEXT
+        || token.parent_ancestors().any(|node| {
+            is_math(&node)
+                || latex::GenericCommand::cast(node)
+                    .and_then(|command| command.name())
+                    .map_or(false, |name| URL_COMMANDS.contains(&name.text()))
+        })
+    {
+        return None;
+    }
+
+    Some(token.text_range())
+}
+
+fn check_non_breaking_space(token: &latex::SyntaxToken) -> Vec<TextRange> {
+    let is_space = |token: Option<latex::SyntaxToken>| {
+        token.map_or(false, |token| {
+            matches!(token.kind(), latex::WHITESPACE | latex::LINE_BREAK)
+        })
+    };
+
+    let text = token.text();
+    let start = token.text_range().start();
+    let end = token.text_range().end();
+    let tilde = TextSize::of('~');
+
+    let mut ranges = Vec::new();
+    if text.starts_with('~') && is_space(token.prev_token()) {
+        ranges.push(TextRange::at(start, tilde));
+    }
+
+    if text.ends_with('~') && is_space(token.next_token()) && (text.len() > 1 || ranges.is_empty())
+    {
+        ranges.push(TextRange::at(end - tilde, tilde));
+    }
+
+    ranges
+}
+
+/// Finds differentials like `dx` in formulas with integrals that are not separated by a thin space.
+fn check_differentials(node: &latex::SyntaxNode) -> Vec<TextRange> {
+    let tokens: Vec<_> = node
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .collect();
+
+    if !tokens.iter().any(|token| {
+        token.kind() == latex::GENERIC_COMMAND_NAME
+            && INTEGRAL_COMMANDS.contains(&token.text().trim_end_matches(['_', '^']))
+    }) {
+        return Vec::new();
+    }
+
+    tokens
+        .iter()
+        .filter(|token| token.kind() == latex::WORD)
+        .filter(|token| {
+            let mut chars = token.text().chars();
+            chars.next() == Some('d')
+                && chars.next().map_or(false, |c| c.is_ascii_alphabetic())
+                && chars.next().is_none()
+        })
+        .filter(|token| {
+            let mut previous = token.prev_token();
+            while let Some(current) = previous
+                .as_ref()
+                .filter(|token| matches!(token.kind(), latex::WHITESPACE | latex::LINE_BREAK))
+            {
+                previous = current.prev_token();
+            }
+
+            // Spacing commands like `\,` are lexed as commands and therefore never match here.
+            previous.map_or(false, |previous| {
+                matches!(
+                    previous.kind(),
+                    latex::WORD | latex::R_PAREN | latex::R_CURLY
+                )
+            })
+        })
+        .map(|token| token.text_range())
+        .collect()
+}
@@ -57,11 +57,14 @@ impl Distribution {
             }
         };
 
-        let resolver = match kind {
+        let mut resolver = match kind {
             DistributionKind::Texlive => Self::load_resolver(texlive::load_resolver),
             DistributionKind::Miktex => Self::load_resolver(miktex::load_resolver),
             DistributionKind::Tectonic | DistributionKind::Unknown => Resolver::default(),
         };
+
+        resolver.add_bibtex_search_paths(kpsewhich::bibinputs_directories());
+        resolver.add_search_paths(kpsewhich::texinputs_directories());
         Self { kind, resolver }
     }
 
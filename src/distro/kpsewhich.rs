@@ -7,20 +7,67 @@ use std::{
 };
 
 use anyhow::Result;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use smol_str::SmolStr;
 
 use crate::DocumentLanguage;
 
+/// The maximum depth of subdirectories that are searched for `//` entries of a search path.
+const MAX_SEARCH_DEPTH: usize = 8;
+
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub struct Resolver {
     pub files_by_name: FxHashMap<SmolStr, PathBuf>,
+    /// Directories outside of the file database whose files have been added to `files_by_name`.
+    pub search_paths: Vec<PathBuf>,
 }
 
 impl Resolver {
     #[must_use]
     pub fn new(files_by_name: FxHashMap<SmolStr, PathBuf>) -> Self {
-        Self { files_by_name }
+        Self {
+            files_by_name,
+            search_paths: Vec::new(),
+        }
+    }
+
+    /// Adds the files of the given directories to the resolver.
+    ///
+    /// Like kpathsea, the first directory containing a file wins.
+    /// The new directories take precedence over the files that are already known.
+    pub fn add_search_paths(&mut self, directories: Vec<PathBuf>) {
+        self.add_files(directories, |language| language.is_some());
+    }
+
+    /// Adds the BibTeX files of the given directories to the resolver.
+    ///
+    /// Search paths like `BIBINPUTS` only apply to bibliographies but not to packages or classes.
+    pub fn add_bibtex_search_paths(&mut self, directories: Vec<PathBuf>) {
+        self.add_files(directories, |language| {
+            language == Some(DocumentLanguage::Bibtex)
+        });
+    }
+
+    fn add_files(
+        &mut self,
+        directories: Vec<PathBuf>,
+        filter: impl Fn(Option<DocumentLanguage>) -> bool,
+    ) {
+        for directory in directories.iter().rev() {
+            for path in fs::read_dir(directory)
+                .into_iter()
+                .flatten()
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.is_file() && filter(DocumentLanguage::by_path(path)))
+            {
+                if let Some(name) = path.file_name().and_then(OsStr::to_str).map(Into::into) {
+                    self.files_by_name.insert(name, path);
+                }
+            }
+        }
+
+        self.search_paths.splice(0..0, directories);
     }
 }
 
@@ -29,8 +76,17 @@ pub fn parse_database(
     mut reader: impl FnMut(&Path) -> Result<Vec<PathBuf>>,
 ) -> Result<Resolver> {
     let mut files_by_name = FxHashMap::default();
+    let mut unindexed_directories = Vec::new();
     for directory in root_directories {
-        for path in reader(directory)? {
+        let paths = reader(directory)?;
+
+        // Trees like `TEXMFHOME` usually do not have a file database.
+        if paths.is_empty() {
+            unindexed_directories.push(directory.join("tex"));
+            unindexed_directories.push(directory.join("bibtex").join("bib"));
+        }
+
+        for path in paths {
             if DocumentLanguage::by_path(&path).is_some() {
                 if let Some(path) = make_absolute(root_directories, &path) {
                     if let Some(name) = path.file_name().and_then(OsStr::to_str).map(Into::into) {
@@ -40,12 +96,90 @@ pub fn parse_database(
             }
         }
     }
-    Ok(Resolver::new(files_by_name))
+
+    let mut resolver = Resolver::new(files_by_name);
+    resolver.add_search_paths(
+        unindexed_directories
+            .iter()
+            .flat_map(|directory| expand_directory(directory))
+            .collect(),
+    );
+
+    Ok(resolver)
+}
+
+/// Returns the directories of the `TEXINPUTS` environment variable.
+pub fn texinputs_directories() -> Vec<PathBuf> {
+    env::var_os("TEXINPUTS")
+        .map(|value| parse_search_paths(&value))
+        .unwrap_or_default()
+}
+
+/// Returns the directories of the `BIBINPUTS` environment variable that BibTeX and Biber search for `.bib` files.
+pub fn bibinputs_directories() -> Vec<PathBuf> {
+    env::var_os("BIBINPUTS")
+        .map(|value| parse_search_paths(&value))
+        .unwrap_or_default()
+}
+
+/// Splits a search path like `TEXINPUTS` into directories.
+///
+/// Entries ending with `//` are expanded to all of their subdirectories.
+/// Empty entries that refer to the default search path are skipped
+/// because the default search path is covered by the file database.
+fn parse_search_paths(value: &OsStr) -> Vec<PathBuf> {
+    let mut directories = Vec::new();
+    for path in env::split_paths(value) {
+        let path = path.to_string_lossy();
+        if let Some(directory) = path.strip_suffix("//") {
+            directories.extend(expand_directory(Path::new(directory)));
+        } else if !path.is_empty() {
+            directories.push(PathBuf::from(path.as_ref()));
+        }
+    }
+
+    directories
+}
+
+/// Returns the given directory followed by all of its subdirectories.
+///
+/// Symbolic links to directories are followed but every directory is only visited once
+/// so that links pointing to one of their ancestors do not cause a cycle.
+fn expand_directory(directory: &Path) -> Vec<PathBuf> {
+    let mut directories = Vec::new();
+    let mut visited_dirs = FxHashSet::default();
+    let mut stack = vec![(directory.to_path_buf(), 0)];
+    while let Some((directory, depth)) = stack.pop() {
+        if !directory.is_dir() {
+            continue;
+        }
+
+        let canonical_dir = fs::canonicalize(&directory).unwrap_or_else(|_| directory.clone());
+        if !visited_dirs.insert(canonical_dir) {
+            continue;
+        }
+
+        if depth < MAX_SEARCH_DEPTH {
+            for path in fs::read_dir(&directory)
+                .into_iter()
+                .flatten()
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+            {
+                stack.push((path, depth + 1));
+            }
+        }
+
+        directories.push(directory);
+    }
+
+    directories
 }
 
 fn make_absolute(root_directories: &[PathBuf], relative_path: &Path) -> Option<PathBuf> {
     for dir in root_directories.iter().rev() {
-        if let Ok(path) = fs::canonicalize(dir.join(&relative_path)) {
+        if let Ok(path) = fs::canonicalize(dir.join(relative_path)) {
             return Some(path);
         }
     }
@@ -53,9 +187,9 @@ fn make_absolute(root_directories: &[PathBuf], relative_path: &Path) -> Option<P
 }
 
 pub fn root_directories() -> Result<Vec<PathBuf>> {
-    let texmf = run(&["-var-value", "TEXMF"])?;
+    let texmf = run(["-var-value", "TEXMF"])?;
     let expand_arg = format!("--expand-braces={}", texmf);
-    let expanded = run(&[&expand_arg])?;
+    let expanded = run([&expand_arg])?;
     let directories = env::split_paths(&expanded.replace('!', ""))
         .filter(|path| path.exists())
         .collect();
@@ -73,3 +207,85 @@ fn run(args: impl IntoIterator<Item = impl AsRef<OsStr>>) -> Result<String> {
 
     Ok(result)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let local = dir.path().join("local");
+        let nested = dir.path().join("tree").join("latex").join("foo");
+        fs::create_dir_all(&local).unwrap();
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(local.join("foo.sty"), "").unwrap();
+        fs::write(nested.join("foo.sty"), "").unwrap();
+        fs::write(nested.join("bar.cls"), "").unwrap();
+
+        let value = env::join_paths([
+            local.clone(),
+            PathBuf::from(format!("{}//", dir.path().join("tree").display())),
+        ])
+        .unwrap();
+
+        let mut files_by_name = FxHashMap::default();
+        files_by_name.insert("bar.cls".into(), PathBuf::from("/distro/bar.cls"));
+        files_by_name.insert("baz.sty".into(), PathBuf::from("/distro/baz.sty"));
+        let mut resolver = Resolver::new(files_by_name);
+        resolver.add_search_paths(parse_search_paths(&value));
+
+        assert_eq!(resolver.files_by_name["foo.sty"], local.join("foo.sty"));
+        assert_eq!(resolver.files_by_name["bar.cls"], nested.join("bar.cls"));
+        assert_eq!(
+            resolver.files_by_name["baz.sty"],
+            PathBuf::from("/distro/baz.sty")
+        );
+        assert!(resolver.search_paths.contains(&nested));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_search_paths_symlink_cycle() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("tree").join("latex");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(nested.join("foo.sty"), "").unwrap();
+        std::os::unix::fs::symlink(dir.path().join("tree"), nested.join("loop")).unwrap();
+
+        let directories = expand_directory(&dir.path().join("tree"));
+        assert_eq!(directories.len(), 2);
+        assert!(directories.contains(&nested));
+    }
+
+    #[test]
+    fn test_unindexed_bibliography() {
+        let dir = tempfile::tempdir().unwrap();
+        let bib_dir = dir.path().join("bibtex").join("bib").join("local");
+        let tex_dir = dir.path().join("tex").join("latex");
+        fs::create_dir_all(&bib_dir).unwrap();
+        fs::create_dir_all(&tex_dir).unwrap();
+        fs::write(bib_dir.join("refs.bib"), "").unwrap();
+        fs::write(tex_dir.join("foo.sty"), "").unwrap();
+
+        let resolver = parse_database(&[dir.path().to_path_buf()], |_| Ok(Vec::new())).unwrap();
+        assert_eq!(resolver.files_by_name["refs.bib"], bib_dir.join("refs.bib"));
+        assert_eq!(resolver.files_by_name["foo.sty"], tex_dir.join("foo.sty"));
+    }
+
+    #[test]
+    fn test_bibtex_search_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("refs.bib"), "").unwrap();
+        fs::write(dir.path().join("foo.sty"), "").unwrap();
+
+        let mut resolver = Resolver::default();
+        resolver.add_bibtex_search_paths(vec![dir.path().to_path_buf()]);
+
+        assert_eq!(
+            resolver.files_by_name["refs.bib"],
+            dir.path().join("refs.bib")
+        );
+        assert!(!resolver.files_by_name.contains_key("foo.sty"));
+    }
+}
@@ -2,6 +2,7 @@ use std::{fmt, sync::Arc};
 
 use derive_more::From;
 use lsp_types::Url;
+use once_cell::sync::OnceCell;
 
 use crate::{
     line_index::LineIndex,
@@ -18,9 +19,41 @@ pub struct LatexDocumentData {
     pub extras: Arc<latex::Extras>,
 }
 
+/// The syntax tree of a BibTeX document.
+///
+/// If the `lazyLoading` option is set, the document is only parsed
+/// when the syntax tree is requested for the first time.
 #[derive(Debug, Clone)]
 pub struct BibtexDocumentData {
-    pub green: rowan::GreenNode,
+    source: Arc<String>,
+    green: Arc<OnceCell<rowan::GreenNode>>,
+}
+
+impl BibtexDocumentData {
+    #[must_use]
+    pub fn new(source: Arc<String>, is_lazy: bool) -> Self {
+        let data = Self {
+            source,
+            green: Arc::default(),
+        };
+
+        if !is_lazy {
+            let _ = data.green();
+        }
+
+        data
+    }
+
+    #[must_use]
+    pub fn green(&self) -> &rowan::GreenNode {
+        self.green.get_or_init(|| bibtex::parse(&self.source))
+    }
+
+    /// Checks whether the syntax tree has been built already.
+    #[must_use]
+    pub fn is_parsed(&self) -> bool {
+        self.green.get().is_some()
+    }
 }
 
 #[derive(Debug, Clone, From)]
@@ -74,6 +107,9 @@ pub struct Document {
     pub text: Arc<String>,
     pub line_index: Arc<LineIndex>,
     pub data: DocumentData,
+    /// The directory of the `\import` command that includes the document.
+    /// Relative paths in the document are resolved against it instead of the document itself.
+    pub import_directory: Option<Arc<Url>>,
 }
 
 impl fmt::Debug for Document {
@@ -89,38 +125,52 @@ impl Document {
         uri: Arc<Url>,
         text: Arc<String>,
         language: DocumentLanguage,
+        import_directory: Option<Arc<Url>>,
     ) -> Self {
         let line_index = Arc::new(LineIndex::new(&text));
+
+        // Huge documents are usually generated and would block the server for a long time.
+        let is_huge = environment.options.exceeds_max_file_size(&text);
+        if is_huge {
+            log::warn!("Skipping parsing of large document: {}", uri);
+        }
+
+        let source = if is_huge { "" } else { text.as_str() };
+
         let data = match language {
             DocumentLanguage::Latex => {
-                let green = latex::parse(&text).green;
+                let green = latex::parse(source).green;
                 let root = latex::SyntaxNode::new_root(green.clone());
 
-                let base_uri = match &environment.options.root_directory {
-                    Some(root_dir) => {
-                        let root_dir = environment.current_directory.join(&root_dir);
-                        Url::from_directory_path(root_dir)
-                            .map_or_else(|()| Arc::clone(&uri), Arc::new)
-                    }
-                    None => Arc::clone(&uri),
-                };
+                let base_uri = import_directory
+                    .clone()
+                    .unwrap_or_else(|| environment.base_uri(&uri));
 
                 let mut context = LatexAnalyzerContext {
                     environment,
                     extras: latex::Extras::default(),
                     document_uri: Arc::clone(&uri),
                     base_uri,
+                    import_directory: import_directory.clone(),
                 };
                 latex::analyze(&mut context, &root);
                 let extras = Arc::new(context.extras);
                 DocumentData::Latex(Box::new(LatexDocumentData { green, extras }))
             }
             DocumentLanguage::Bibtex => {
-                let green = bibtex::parse(&text);
-                DocumentData::Bibtex(BibtexDocumentData { green })
+                // BibTeX documents do not include other documents,
+                // so they can be parsed later without missing parts of the project.
+                let source = if is_huge {
+                    Arc::default()
+                } else {
+                    Arc::clone(&text)
+                };
+
+                let is_lazy = environment.options.lazy_loading;
+                DocumentData::Bibtex(BibtexDocumentData::new(source, is_lazy))
             }
             DocumentLanguage::BuildLog => {
-                let data = Arc::new(build_log::parse(&text));
+                let data = Arc::new(build_log::parse(source));
                 DocumentData::BuildLog(data)
             }
         };
@@ -130,6 +180,7 @@ impl Document {
             text,
             line_index,
             data,
+            import_directory,
         }
     }
 }
@@ -1,12 +1,16 @@
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
-use lsp_types::{ClientCapabilities, ClientInfo};
+use lsp_types::{ClientCapabilities, ClientInfo, Url};
 
 use crate::{distro::Resolver, Options};
 
 #[derive(Debug, Clone)]
 pub struct Environment {
     pub current_directory: Arc<PathBuf>,
+    pub workspace_folders: Arc<Vec<PathBuf>>,
     pub client_capabilities: Arc<ClientCapabilities>,
     pub client_info: Option<Arc<ClientInfo>>,
     pub options: Arc<Options>,
@@ -18,12 +22,51 @@ impl Environment {
     pub fn new(current_directory: Arc<PathBuf>) -> Self {
         Self {
             current_directory,
+            workspace_folders: Arc::default(),
             client_capabilities: Arc::new(ClientCapabilities::default()),
             client_info: None,
             options: Arc::new(Options::default()),
             resolver: Arc::new(Resolver::default()),
         }
     }
+
+    /// Returns the innermost workspace folder that contains the given document.
+    #[must_use]
+    pub fn find_workspace_folder(&self, uri: &Url) -> Option<&Path> {
+        if uri.scheme() != "file" {
+            return None;
+        }
+
+        let path = uri.to_file_path().ok()?;
+        self.workspace_folders
+            .iter()
+            .filter(|folder| path.starts_with(folder))
+            .max_by_key(|folder| folder.components().count())
+            .map(PathBuf::as_path)
+    }
+
+    /// Returns the directory that the relative paths of the options are resolved against.
+    ///
+    /// This is the workspace folder of the document or the working directory of the server
+    /// if the document is not part of a workspace folder.
+    #[must_use]
+    pub fn base_directory(&self, uri: &Url) -> &Path {
+        self.find_workspace_folder(uri)
+            .unwrap_or(&self.current_directory)
+    }
+
+    /// Returns the URI that relative paths in the given LaTeX document are resolved against.
+    #[must_use]
+    pub fn base_uri(&self, document_uri: &Arc<Url>) -> Arc<Url> {
+        match &self.options.root_directory {
+            Some(root_dir) => {
+                let root_dir = self.base_directory(document_uri).join(root_dir);
+                Url::from_directory_path(root_dir)
+                    .map_or_else(|()| Arc::clone(document_uri), Arc::new)
+            }
+            None => Arc::clone(document_uri),
+        }
+    }
 }
 
 impl Default for Environment {
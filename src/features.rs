@@ -1,8 +1,10 @@
 mod build;
+mod code_action;
 mod completion;
 mod cursor;
 mod definition;
 mod execute_command;
+mod expand_macros;
 mod folding;
 mod formatting;
 mod forward_search;
@@ -10,10 +12,16 @@ mod highlight;
 mod hover;
 mod inlay_hint;
 mod link;
+mod linked_editing_range;
 mod lsp_kinds;
+mod on_type_formatting;
 mod reference;
 mod rename;
+mod resolve_root;
+mod server_status;
+mod sort_bibliography;
 mod symbol;
+mod word_count;
 
 use std::sync::Arc;
 
@@ -22,20 +30,27 @@ use lsp_types::Url;
 use crate::{Document, Workspace};
 
 pub use self::{
-    build::{BuildEngine, BuildParams, BuildResult, BuildStatus},
+    build::{BuildEngine, BuildParams, BuildResult, BuildStatus, CancelBuildRequest},
+    code_action::find_code_actions,
     completion::{complete, CompletionItemData, COMPLETION_LIMIT},
-    definition::goto_definition,
+    definition::{goto_definition, goto_type_definition},
     execute_command::execute_command,
+    expand_macros::{expand_macros, ExpandMacrosParams, ExpandMacrosRequest, ExpandMacrosResult},
     folding::find_foldings,
-    formatting::format_source_code,
+    formatting::{format_source_code, normalize_bibtex_keys},
     forward_search::{execute_forward_search, ForwardSearchResult, ForwardSearchStatus},
     highlight::find_document_highlights,
     hover::find_hover,
     inlay_hint::find_inlay_hints,
-    link::find_document_links,
+    link::{find_document_links, resolve_document_link, DocumentLinkData},
+    linked_editing_range::find_linked_editing_ranges,
+    on_type_formatting::format_on_type,
     reference::find_all_references,
-    rename::{prepare_rename_all, rename_all},
+    rename::{prepare_rename_all, rename_all, rename_files},
+    resolve_root::{resolve_root, ResolveRootRequest, ResolveRootResult},
+    server_status::{collect_server_status, ServerStatus, ServerStatusRequest},
     symbol::{find_document_symbols, find_workspace_symbols},
+    word_count::{count_words, SectionWordCount, WordCount},
 };
 
 #[derive(Clone)]
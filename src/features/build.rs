@@ -1,13 +1,14 @@
 use std::{
-    io::{BufRead, BufReader, Read},
+    io::{self, BufRead, BufReader, Read},
     path::Path,
-    process::{Command, Stdio},
+    process::{Child, Command, Stdio},
     sync::{Arc, Mutex},
     thread::{self, JoinHandle},
+    time::Duration,
 };
 
 use anyhow::Result;
-use crossbeam_channel::{Receiver, Sender};
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
 use dashmap::DashMap;
 use encoding_rs_io::DecodeReaderBytesBuilder;
 use lsp_types::{
@@ -22,7 +23,7 @@ use uuid::Uuid;
 
 use crate::{
     client::{self, ReqQueue},
-    ClientCapabilitiesExt, DocumentLanguage,
+    ClientCapabilitiesExt, Document, DocumentLanguage,
 };
 
 use super::{forward_search, FeatureRequest};
@@ -48,6 +49,16 @@ pub struct BuildResult {
     pub status: BuildStatus,
 }
 
+pub struct CancelBuildRequest;
+
+impl lsp_types::request::Request for CancelBuildRequest {
+    type Params = BuildParams;
+
+    type Result = bool;
+
+    const METHOD: &'static str = "texlab/cancelBuild";
+}
+
 struct ProgressReporter<'a> {
     supports_progress: bool,
     req_queue: &'a Mutex<ReqQueue>,
@@ -104,6 +115,13 @@ impl<'a> Drop for ProgressReporter<'a> {
 pub struct BuildEngine {
     lock: Mutex<()>,
     pub positions_by_uri: DashMap<Arc<Url>, Position>,
+    cancellations_by_uri: DashMap<Arc<Url>, BuildHandle>,
+}
+
+/// Identifies a running (or waiting) build so that it can be cancelled.
+struct BuildHandle {
+    id: Uuid,
+    cancel_sender: Sender<()>,
 }
 
 impl BuildEngine {
@@ -113,20 +131,7 @@ impl BuildEngine {
         req_queue: &Mutex<ReqQueue>,
         lsp_sender: &Sender<lsp_server::Message>,
     ) -> Result<BuildResult> {
-        let lock = self.lock.lock().unwrap();
-
-        let document = request
-            .workspace
-            .documents_by_uri
-            .values()
-            .find(|document| {
-                if let Some(data) = document.data.as_latex() {
-                    data.extras.has_document_environment
-                } else {
-                    false
-                }
-            })
-            .unwrap_or_else(|| request.main_document());
+        let document = find_root_document(&request);
 
         if document.data.language() != DocumentLanguage::Latex {
             return Ok(BuildResult {
@@ -141,13 +146,36 @@ impl BuildEngine {
         }
         let path = document.uri.to_file_path().unwrap();
 
+        // Running the build tool twice on the same root at the same time corrupts the build artifacts,
+        // so a new build supersedes the running (or waiting) build of the same root.
+        let build_id = Uuid::new_v4();
+        let (cancel_sender, cancel_receiver) = crossbeam_channel::bounded(1);
+        let handle = BuildHandle {
+            id: build_id,
+            cancel_sender,
+        };
+
+        if let Some(previous) = self
+            .cancellations_by_uri
+            .insert(Arc::clone(&document.uri), handle)
+        {
+            let _ = previous.cancel_sender.send(());
+        }
+
+        let lock = self.lock.lock().unwrap();
+        if cancel_receiver.try_recv().is_ok() {
+            return Ok(BuildResult {
+                status: BuildStatus::CANCELLED,
+            });
+        }
+
         let supports_progress = request
             .workspace
             .environment
             .client_capabilities
             .has_work_done_progress_support();
 
-        let token = format!("texlab-build-{}", Uuid::new_v4());
+        let token = format!("texlab-build-{}", build_id);
         let progress_reporter = ProgressReporter {
             supports_progress,
             req_queue,
@@ -179,25 +207,28 @@ impl BuildEngine {
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .current_dir(build_dir)
-            .spawn()?;
+            .spawn()
+            .map_err(|why| {
+                self.finish(&document.uri, build_id);
+                why
+            })?;
 
         let (exit_sender, exit_receiver) = crossbeam_channel::bounded(1);
         let log_handle = capture_output(&mut process, lsp_sender, exit_receiver);
-        let success = process.wait().map(|status| status.success())?;
+
+        let status = wait_for_process(&mut process, &cancel_receiver);
+        self.finish(&document.uri, build_id);
+        let status = status?;
+
         exit_sender.send(())?;
         drop(exit_sender);
 
         log_handle.join().unwrap();
-        let status = if success {
-            BuildStatus::SUCCESS
-        } else {
-            BuildStatus::ERROR
-        };
 
         drop(progress_reporter);
         drop(lock);
 
-        if options.build.forward_search_after {
+        if options.build.forward_search_after && status != BuildStatus::CANCELLED {
             let request = FeatureRequest {
                 params: TextDocumentPositionParams {
                     position: self
@@ -215,6 +246,70 @@ impl BuildEngine {
 
         Ok(BuildResult { status })
     }
+
+    /// Unregisters the build unless it has already been superseded by a newer build.
+    fn finish(&self, uri: &Url, build_id: Uuid) {
+        self.cancellations_by_uri
+            .remove_if(uri, |_, handle| handle.id == build_id);
+    }
+
+    /// Checks whether the root document of the given document is being built or waiting to be built.
+    pub fn is_running<P>(&self, request: &FeatureRequest<P>) -> bool {
+        let document = find_root_document(request);
+        self.cancellations_by_uri.contains_key(&document.uri)
+    }
+
+    /// Kills the build of the root document of the given document.
+    ///
+    /// Returns `true` if there was a build to cancel.
+    pub fn cancel(&self, request: &FeatureRequest<BuildParams>) -> bool {
+        let document = find_root_document(request);
+        self.cancellations_by_uri
+            .remove(&document.uri)
+            .map_or(false, |(_, handle)| handle.cancel_sender.send(()).is_ok())
+    }
+}
+
+fn find_root_document<P>(request: &FeatureRequest<P>) -> &Document {
+    request
+        .workspace
+        .documents_by_uri
+        .values()
+        .find(|document| {
+            if let Some(data) = document.data.as_latex() {
+                data.extras.has_document_environment
+            } else {
+                false
+            }
+        })
+        .unwrap_or_else(|| request.main_document())
+}
+
+fn wait_for_process(
+    process: &mut Child,
+    cancel_receiver: &Receiver<()>,
+) -> io::Result<BuildStatus> {
+    let status = loop {
+        if let Some(status) = process.try_wait()? {
+            break status;
+        }
+
+        match cancel_receiver.recv_timeout(Duration::from_millis(50)) {
+            Ok(()) => {
+                process.kill()?;
+                process.wait()?;
+                return Ok(BuildStatus::CANCELLED);
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break process.wait()?,
+        }
+    };
+
+    Ok(if status.success() {
+        BuildStatus::SUCCESS
+    } else {
+        BuildStatus::ERROR
+    })
 }
 
 fn capture_output(
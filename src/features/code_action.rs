@@ -0,0 +1,21 @@
+mod missing_package;
+mod misspelled_environment;
+mod unescaped_character;
+
+use lsp_types::{CodeActionOrCommand, CodeActionParams};
+
+use self::{
+    missing_package::find_missing_package_actions,
+    misspelled_environment::find_misspelled_environment_actions,
+    unescaped_character::find_unescaped_character_actions,
+};
+
+use super::FeatureRequest;
+
+pub fn find_code_actions(request: FeatureRequest<CodeActionParams>) -> Vec<CodeActionOrCommand> {
+    let mut actions = Vec::new();
+    find_missing_package_actions(&request, &mut actions);
+    find_misspelled_environment_actions(&request, &mut actions);
+    find_unescaped_character_actions(&request, &mut actions);
+    actions
+}
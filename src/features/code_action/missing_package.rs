@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams, Range, TextEdit,
+    WorkspaceEdit,
+};
+use rowan::{ast::AstNode, TextSize};
+use rustc_hash::FxHashSet;
+use smol_str::SmolStr;
+
+use crate::{
+    component_db::COMPONENT_DATABASE, features::FeatureRequest, syntax::latex, Document,
+    LineIndexExt,
+};
+
+/// Offers to include the package that provides a command which is not available yet.
+///
+/// No action is offered if several packages provide the command or if it is already loaded by an included package.
+pub fn find_missing_package_actions(
+    request: &FeatureRequest<CodeActionParams>,
+    actions: &mut Vec<CodeActionOrCommand>,
+) -> Option<()> {
+    let main_document = request.main_document();
+    let data = main_document.data.as_latex()?;
+    let root = latex::SyntaxNode::new_root(data.green.clone());
+    let range = main_document
+        .line_index
+        .offset_lsp_range(request.params.range);
+
+    let defined_commands: FxHashSet<_> = request
+        .workspace
+        .documents_by_uri
+        .values()
+        .filter_map(|document| document.data.as_latex())
+        .flat_map(|data| data.extras.defined_commands.iter())
+        .map(SmolStr::as_str)
+        .collect();
+
+    let linked_components = COMPONENT_DATABASE.linked_components(&request.workspace);
+    let preamble = request
+        .workspace
+        .find_parent(&request.uri)
+        .unwrap_or_else(|| main_document.clone());
+
+    let mut packages = FxHashSet::default();
+    for name in root
+        .descendants()
+        .filter_map(|node| latex::package_command_name(&node))
+        .filter(|name| name.text_range().intersect(range).is_some())
+        .filter(|name| !defined_commands.contains(name.text()))
+    {
+        let component =
+            match COMPONENT_DATABASE.find_missing_package(&linked_components, name.text()) {
+                Some(component) => component,
+                None => continue,
+            };
+
+        let package = match component
+            .file_names
+            .iter()
+            .find_map(|file_name| file_name.strip_suffix(".sty"))
+        {
+            Some(package) => package,
+            None => continue,
+        };
+
+        if !packages.insert(package) {
+            continue;
+        }
+
+        let command_range = main_document
+            .line_index
+            .line_col_lsp_range(name.text_range());
+
+        let diagnostics: Vec<_> = request
+            .params
+            .context
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.range == command_range)
+            .cloned()
+            .collect();
+
+        let mut changes = HashMap::new();
+        changes.insert(
+            preamble.uri.as_ref().clone(),
+            vec![create_include_edit(&preamble, package)],
+        );
+
+        actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+            title: format!("Add \\usepackage{{{}}}", package),
+            kind: Some(CodeActionKind::QUICKFIX),
+            diagnostics: Some(diagnostics).filter(|diagnostics| !diagnostics.is_empty()),
+            edit: Some(WorkspaceEdit::new(changes)),
+            is_preferred: Some(true),
+            ..CodeAction::default()
+        }));
+    }
+
+    Some(())
+}
+
+/// Inserts the package after the last `\usepackage` or after `\documentclass`.
+fn create_include_edit(document: &Document, package: &str) -> TextEdit {
+    let offset = document.data.as_latex().and_then(|data| {
+        let root = latex::SyntaxNode::new_root(data.green.clone());
+        let includes: Vec<_> = root
+            .descendants()
+            .filter_map(latex::Include::cast)
+            .collect();
+
+        includes
+            .iter()
+            .rev()
+            .find(|include| include.syntax().kind() == latex::PACKAGE_INCLUDE)
+            .or_else(|| {
+                includes
+                    .iter()
+                    .find(|include| include.syntax().kind() == latex::CLASS_INCLUDE)
+            })
+            .map(|include| latex::small_range(include).end())
+    });
+
+    let (offset, text) = match offset {
+        Some(offset) => (offset, format!("\n\\usepackage{{{}}}", package)),
+        None => (TextSize::from(0), format!("\\usepackage{{{}}}\n", package)),
+    };
+
+    let position = document.line_index.line_col_lsp(offset);
+    TextEdit::new(Range::new(position, position), text)
+}
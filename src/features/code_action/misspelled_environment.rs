@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams, TextEdit, WorkspaceEdit,
+};
+use rowan::ast::AstNode;
+
+use crate::{features::FeatureRequest, syntax::latex, KnownEnvironments, LineIndexExt};
+
+/// Offers to replace a misspelled environment name in both the `\begin` and the `\end`.
+pub fn find_misspelled_environment_actions(
+    request: &FeatureRequest<CodeActionParams>,
+    actions: &mut Vec<CodeActionOrCommand>,
+) -> Option<()> {
+    let main_document = request.main_document();
+    let data = main_document.data.as_latex()?;
+    let root = latex::SyntaxNode::new_root(data.green.clone());
+    let range = main_document
+        .line_index
+        .offset_lsp_range(request.params.range);
+
+    let known_environments = KnownEnvironments::new(&request.workspace);
+    for environment in root.descendants().filter_map(latex::Environment::cast) {
+        let begin = match environment.begin().and_then(|begin| begin.name()?.key()) {
+            Some(begin) => begin,
+            None => continue,
+        };
+
+        let begin_range = latex::small_range(&begin);
+        if begin_range.intersect(range).is_none() {
+            continue;
+        }
+
+        let name = begin.to_string();
+        let suggestion = match known_environments.find_similar(&name) {
+            Some(suggestion) => suggestion,
+            None => continue,
+        };
+
+        let mut edits = vec![TextEdit::new(
+            main_document.line_index.line_col_lsp_range(begin_range),
+            suggestion.to_string(),
+        )];
+
+        if let Some(end) = environment
+            .end()
+            .and_then(|end| end.name()?.key())
+            .filter(|end| end.to_string() == name)
+        {
+            edits.push(TextEdit::new(
+                main_document
+                    .line_index
+                    .line_col_lsp_range(latex::small_range(&end)),
+                suggestion.to_string(),
+            ));
+        }
+
+        let name_range = main_document.line_index.line_col_lsp_range(begin_range);
+        let diagnostics: Vec<_> = request
+            .params
+            .context
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.range == name_range)
+            .cloned()
+            .collect();
+
+        let mut changes = HashMap::new();
+        changes.insert(main_document.uri.as_ref().clone(), edits);
+
+        actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+            title: format!("Change to \"{}\"", suggestion),
+            kind: Some(CodeActionKind::QUICKFIX),
+            diagnostics: Some(diagnostics).filter(|diagnostics| !diagnostics.is_empty()),
+            edit: Some(WorkspaceEdit::new(changes)),
+            is_preferred: Some(true),
+            ..CodeAction::default()
+        }));
+    }
+
+    Some(())
+}
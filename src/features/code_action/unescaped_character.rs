@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams, TextEdit, WorkspaceEdit,
+};
+
+use crate::{
+    diagnostics::find_unescaped_characters, features::FeatureRequest, syntax::latex, LineIndexExt,
+};
+
+/// Offers to escape a special character like `&` that is used in text mode.
+pub fn find_unescaped_character_actions(
+    request: &FeatureRequest<CodeActionParams>,
+    actions: &mut Vec<CodeActionOrCommand>,
+) -> Option<()> {
+    let options = &request.workspace.environment.options.diagnostics;
+    if !options.unescaped_characters {
+        return None;
+    }
+
+    let main_document = request.main_document();
+    let data = main_document.data.as_latex()?;
+    let root = latex::SyntaxNode::new_root(data.green.clone());
+    let range = main_document
+        .line_index
+        .offset_lsp_range(request.params.range);
+
+    for character_range in find_unescaped_characters(&root)
+        .into_iter()
+        .filter(|character_range| character_range.intersect(range).is_some())
+    {
+        let character = &main_document.text[std::ops::Range::<usize>::from(character_range)];
+        let lsp_range = main_document.line_index.line_col_lsp_range(character_range);
+
+        let diagnostics: Vec<_> = request
+            .params
+            .context
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.range == lsp_range)
+            .cloned()
+            .collect();
+
+        let mut changes = HashMap::new();
+        changes.insert(
+            main_document.uri.as_ref().clone(),
+            vec![TextEdit::new(lsp_range, format!("\\{}", character))],
+        );
+
+        actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+            title: format!("Escape \"{}\"", character),
+            kind: Some(CodeActionKind::QUICKFIX),
+            diagnostics: Some(diagnostics).filter(|diagnostics| !diagnostics.is_empty()),
+            edit: Some(WorkspaceEdit::new(changes)),
+            is_preferred: Some(true),
+            ..CodeAction::default()
+        }));
+    }
+
+    Some(())
+}
@@ -6,13 +6,18 @@ mod color;
 mod color_model;
 mod component_command;
 mod component_environment;
+mod counter;
 mod entry_type;
 mod field;
 mod glossary_ref;
 mod import;
 mod include;
 mod label;
+mod label_def;
+mod math_symbol;
+mod package_option;
 mod theorem;
+mod tikz_key;
 mod tikz_library;
 mod types;
 mod user_command;
@@ -27,15 +32,16 @@ use lsp_types::{
     CompletionItem, CompletionList, CompletionParams, CompletionTextEdit, Documentation,
     InsertTextFormat, MarkupContent, MarkupKind, TextEdit,
 };
-use rowan::{ast::AstNode, TextSize};
+use rowan::{ast::AstNode, TextRange, TextSize};
 use rustc_hash::FxHashSet;
 
 use crate::{
+    component_db::{Component, COMPONENT_DATABASE},
     syntax::{
         bibtex::{self},
         latex,
     },
-    LineIndexExt,
+    BibtexFieldRequirement, ClientCapabilitiesExt, LineIndexExt, LANGUAGE_DATA,
 };
 
 use self::{
@@ -47,13 +53,18 @@ use self::{
     color_model::complete_color_models,
     component_command::complete_component_commands,
     component_environment::complete_component_environments,
+    counter::complete_counters,
     entry_type::complete_entry_types,
     field::complete_fields,
     glossary_ref::complete_glossary_entries,
     import::complete_imports,
     include::complete_includes,
     label::complete_labels,
+    label_def::complete_label_definitions,
+    math_symbol::complete_math_symbols,
+    package_option::complete_package_options,
     theorem::complete_theorem_environments,
+    tikz_key::complete_tikz_keys,
     tikz_library::complete_tikz_libraries,
     types::{InternalCompletionItem, InternalCompletionItemData},
     user_command::complete_user_commands,
@@ -81,17 +92,22 @@ pub fn complete(request: FeatureRequest<CompletionParams>) -> Option<CompletionL
     complete_arguments(&context, &mut items);
     complete_citations(&context, &mut items);
     complete_imports(&context, &mut items);
+    complete_package_options(&context, &mut items);
     complete_colors(&context, &mut items);
     complete_color_models(&context, &mut items);
     complete_acronyms(&context, &mut items);
     complete_glossary_entries(&context, &mut items);
+    complete_counters(&context, &mut items);
     complete_includes(&context, &mut items);
     complete_labels(&context, &mut items);
+    complete_label_definitions(&context, &mut items);
     complete_tikz_libraries(&context, &mut items);
+    complete_tikz_keys(&context, &mut items);
     complete_component_environments(&context, &mut items);
     complete_theorem_environments(&context, &mut items);
     complete_user_environments(&context, &mut items);
     complete_begin_command(&context, &mut items);
+    complete_math_symbols(&context, &mut items);
     complete_component_commands(&context, &mut items);
     complete_user_commands(&context, &mut items);
 
@@ -103,8 +119,9 @@ pub fn complete(request: FeatureRequest<CompletionParams>) -> Option<CompletionL
         a.preselect
             .cmp(&b.preselect)
             .reverse()
+            .then_with(|| a.is_relevant.cmp(&b.is_relevant).reverse())
             .then_with(|| a.score.cmp(&b.score).reverse())
-            .then_with(|| a.data.label().cmp(b.data.label()))
+            .then_with(|| a.data.sort_key().cmp(b.data.sort_key()))
     });
 
     let items: Vec<_> = items
@@ -136,6 +153,32 @@ pub fn complete(request: FeatureRequest<CompletionParams>) -> Option<CompletionL
     })
 }
 
+/// Describes a component that provides a command but is not included yet.
+fn missing_component_detail(component: &Component) -> String {
+    let command = match component.file_names.first() {
+        Some(file_name) if file_name.ends_with(".cls") => "\\documentclass",
+        _ => "\\usepackage",
+    };
+
+    let names: Vec<_> = component
+        .file_names
+        .iter()
+        .map(|file_name| {
+            file_name
+                .strip_suffix(".sty")
+                .or_else(|| file_name.strip_suffix(".cls"))
+                .unwrap_or(file_name)
+        })
+        .collect();
+
+    format!(
+        "{} (requires {}{{{}}})",
+        component_detail(&component.file_names),
+        command,
+        names.join(",")
+    )
+}
+
 fn dedup(items: Vec<InternalCompletionItem>) -> Vec<InternalCompletionItem> {
     let mut labels = FxHashSet::default();
     let mut insert = vec![false; items.len()];
@@ -188,46 +231,62 @@ fn score(context: &CursorContext<CompletionParams>, items: &mut Vec<InternalComp
         Cursor::Latex(_) | Cursor::Bibtex(_) | Cursor::Nothing => "".into(),
     };
 
-    let file_pattern = pattern.split('/').last().unwrap();
+    let file_pattern = pattern.split('/').next_back().unwrap();
     let matcher = SkimMatcherV2::default().ignore_case();
     for item in items {
         item.score = match &item.data {
             InternalCompletionItemData::EntryType { ty } => {
                 matcher.fuzzy_match(&ty.name, &pattern[1..])
             }
-            InternalCompletionItemData::Field { field } => {
+            InternalCompletionItemData::Field { field, .. } => {
                 matcher.fuzzy_match(&field.name, &pattern)
             }
             InternalCompletionItemData::Argument { name, .. } => {
                 matcher.fuzzy_match(name, &pattern)
             }
             InternalCompletionItemData::BeginCommand => matcher.fuzzy_match("begin", &pattern[1..]),
-            InternalCompletionItemData::Citation { key, .. } => matcher.fuzzy_match(key, &pattern),
+            InternalCompletionItemData::Citation { key, keywords, .. } => matcher
+                .fuzzy_match(key, &pattern)
+                .or_else(|| matcher.fuzzy_match(keywords, &pattern)),
             InternalCompletionItemData::ComponentCommand { name, .. } => {
                 matcher.fuzzy_match(name, &pattern[1..])
             }
+            InternalCompletionItemData::MathSymbol { symbol } => {
+                matcher.fuzzy_match(&symbol.name, &pattern[1..])
+            }
             InternalCompletionItemData::ComponentEnvironment { name, .. } => {
                 matcher.fuzzy_match(name, &pattern)
             }
             InternalCompletionItemData::Class { name } => matcher.fuzzy_match(name, &pattern),
             InternalCompletionItemData::Package { name } => matcher.fuzzy_match(name, &pattern),
+            InternalCompletionItemData::PackageOption { name } => {
+                matcher.fuzzy_match(name, &pattern)
+            }
+            InternalCompletionItemData::PackageOptionValue { name } => {
+                matcher.fuzzy_match(name, &pattern)
+            }
             InternalCompletionItemData::Color { name } => matcher.fuzzy_match(name, &pattern),
             InternalCompletionItemData::ColorModel { name } => matcher.fuzzy_match(name, &pattern),
-            InternalCompletionItemData::Acronym { name } => matcher.fuzzy_match(name, &pattern),
-            InternalCompletionItemData::GlossaryEntry { name } => {
+            InternalCompletionItemData::Acronym { name, .. } => matcher.fuzzy_match(name, &pattern),
+            InternalCompletionItemData::GlossaryEntry { name, .. } => {
                 matcher.fuzzy_match(name, &pattern)
             }
+            InternalCompletionItemData::Counter { name, .. } => matcher.fuzzy_match(name, &pattern),
             InternalCompletionItemData::File { name } => matcher.fuzzy_match(name, file_pattern),
             InternalCompletionItemData::Directory { name } => {
                 matcher.fuzzy_match(name, file_pattern)
             }
             InternalCompletionItemData::Label { name, .. } => matcher.fuzzy_match(name, &pattern),
+            InternalCompletionItemData::LabelDefinition { name, .. } => {
+                matcher.fuzzy_match(name, &pattern)
+            }
             InternalCompletionItemData::UserCommand { name } => matcher.fuzzy_match(name, &pattern),
-            InternalCompletionItemData::UserEnvironment { name } => {
+            InternalCompletionItemData::UserEnvironment { name, .. } => {
                 matcher.fuzzy_match(name, &pattern)
             }
             InternalCompletionItemData::PgfLibrary { name } => matcher.fuzzy_match(name, &pattern),
             InternalCompletionItemData::TikzLibrary { name } => matcher.fuzzy_match(name, &pattern),
+            InternalCompletionItemData::TikzKey { name } => matcher.fuzzy_match(name, &pattern),
         };
     }
 }
@@ -250,6 +309,77 @@ fn preselect(
     Some(())
 }
 
+/// Completes the name of an environment and, if the client supports snippets,
+/// inserts the matching `\end` after an unclosed `\begin` as well.
+fn environment_text_edit(
+    context: &CursorContext<CompletionParams>,
+    range: TextRange,
+    name: &str,
+    arguments: &[&str],
+) -> (TextEdit, Option<InsertTextFormat>) {
+    let line_index = &context.request.main_document().line_index;
+    match environment_snippet(context, range, name, arguments) {
+        Some((range, text)) => (
+            TextEdit::new(line_index.line_col_lsp_range(range), text),
+            Some(InsertTextFormat::SNIPPET),
+        ),
+        None => (
+            TextEdit::new(line_index.line_col_lsp_range(range), name.into()),
+            None,
+        ),
+    }
+}
+
+fn environment_snippet(
+    context: &CursorContext<CompletionParams>,
+    range: TextRange,
+    name: &str,
+    arguments: &[&str],
+) -> Option<(TextRange, String)> {
+    if !context
+        .request
+        .workspace
+        .environment
+        .client_capabilities
+        .has_completion_snippet_support()
+    {
+        return None;
+    }
+
+    let begin = context
+        .cursor
+        .as_latex()?
+        .parent_ancestors()
+        .find_map(latex::Begin::cast)?;
+
+    let environment = latex::Environment::cast(begin.syntax().parent()?)?;
+    let is_closed = environment
+        .end()
+        .and_then(|end| end.name())
+        .and_then(|group| group.key())
+        .map_or(false, |key| key.to_string() == name);
+
+    if is_closed {
+        return None;
+    }
+
+    // Replace the closing brace as well to place the arguments after it.
+    let group_end = begin.name()?.syntax().text_range().end();
+    let range = TextRange::new(range.start(), group_end.max(range.end()));
+
+    let mut text = format!("{}}}", name);
+    for (i, argument) in arguments.iter().enumerate() {
+        if argument.is_empty() {
+            text.push_str(&format!("{{${}}}", i + 1));
+        } else {
+            text.push_str(&format!("{{${{{}:{}}}}}", i + 1, argument));
+        }
+    }
+
+    text.push_str(&format!("\n\t$0\n\\end{{{}}}", name));
+    Some((range, text))
+}
+
 fn convert_internal_items(
     context: &CursorContext<CompletionParams>,
     item: InternalCompletionItem,
@@ -278,11 +408,15 @@ fn convert_internal_items(
                 ..CompletionItem::default()
             }
         }
-        InternalCompletionItemData::Field { field } => {
+        InternalCompletionItemData::Field { field, requirement } => {
             let text_edit = TextEdit::new(range, (&field.name).into());
             CompletionItem {
                 label: (&field.name).into(),
                 kind: Some(Structure::Field.completion_kind()),
+                detail: requirement.map(|requirement| match requirement {
+                    BibtexFieldRequirement::Required => "required field".into(),
+                    BibtexFieldRequirement::Optional => "optional field".into(),
+                }),
                 documentation: Some(Documentation::MarkupContent(MarkupContent {
                     kind: MarkupKind::Markdown,
                     value: field.documentation.clone(),
@@ -310,12 +444,7 @@ fn convert_internal_items(
                 .workspace
                 .environment
                 .client_capabilities
-                .text_document
-                .as_ref()
-                .and_then(|cap| cap.completion.as_ref())
-                .and_then(|cap| cap.completion_item.as_ref())
-                .and_then(|cap| cap.snippet_support)
-                == Some(true)
+                .has_completion_snippet_support()
             {
                 let text_edit = TextEdit::new(range, "begin{$1}\n\t$0\n\\end{$1}".into());
                 CompletionItem {
@@ -338,11 +467,19 @@ fn convert_internal_items(
                 }
             }
         }
-        InternalCompletionItemData::Citation { uri, key, text, ty } => {
+        InternalCompletionItemData::Citation {
+            uri,
+            key,
+            text,
+            ty,
+            detail,
+            ..
+        } => {
             let text_edit = TextEdit::new(range, key.to_string());
             CompletionItem {
                 label: key.to_string(),
                 kind: Some(ty.completion_kind()),
+                detail,
                 filter_text: Some(text.clone()),
                 sort_text: Some(text),
                 data: Some(
@@ -377,12 +514,47 @@ fn convert_internal_items(
                 ..CompletionItem::new_simple(name.to_string(), detail)
             }
         }
+        InternalCompletionItemData::MathSymbol { symbol } => {
+            let detail = format!("{}, {}", symbol.glyph, symbol.category.description());
+            let documentation = context
+                .request
+                .workspace
+                .environment
+                .client_capabilities
+                .has_completion_markdown_support()
+                .then(|| {
+                    Documentation::MarkupContent(MarkupContent {
+                        kind: MarkupKind::Markdown,
+                        value: format!("# {}", symbol.glyph),
+                    })
+                });
+
+            let text_edit = TextEdit::new(range, symbol.name.clone());
+            CompletionItem {
+                kind: Some(Structure::Command.completion_kind()),
+                data: Some(serde_json::to_value(CompletionItemData::Command).unwrap()),
+                documentation,
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                ..CompletionItem::new_simple(symbol.name.clone(), detail)
+            }
+        }
         InternalCompletionItemData::ComponentEnvironment { name, file_names } => {
-            let text_edit = TextEdit::new(range, name.to_string());
+            let arguments: Vec<_> = LANGUAGE_DATA
+                .environment_arguments
+                .get(name.as_str())
+                .into_iter()
+                .flatten()
+                .map(String::as_str)
+                .collect();
+
+            let (text_edit, insert_text_format) =
+                environment_text_edit(context, item.range, name, &arguments);
+
             CompletionItem {
                 kind: Some(Structure::Environment.completion_kind()),
                 data: Some(serde_json::to_value(CompletionItemData::Environment).unwrap()),
                 text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                insert_text_format,
                 ..CompletionItem::new_simple(name.to_string(), component_detail(file_names))
             }
         }
@@ -406,6 +578,26 @@ fn convert_internal_items(
                 ..CompletionItem::default()
             }
         }
+        InternalCompletionItemData::PackageOption { name } => {
+            let text_edit = TextEdit::new(range, name.into());
+            CompletionItem {
+                label: name.into(),
+                kind: Some(Structure::PackageOption.completion_kind()),
+                data: Some(serde_json::to_value(CompletionItemData::PackageOption).unwrap()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                ..CompletionItem::default()
+            }
+        }
+        InternalCompletionItemData::PackageOptionValue { name } => {
+            let text_edit = TextEdit::new(range, name.into());
+            CompletionItem {
+                label: name.into(),
+                kind: Some(Structure::Argument.completion_kind()),
+                data: Some(serde_json::to_value(CompletionItemData::PackageOption).unwrap()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                ..CompletionItem::default()
+            }
+        }
         InternalCompletionItemData::Color { name } => {
             let text_edit = TextEdit::new(range, name.into());
             CompletionItem {
@@ -426,26 +618,46 @@ fn convert_internal_items(
                 ..CompletionItem::default()
             }
         }
-        InternalCompletionItemData::Acronym { name } => {
+        InternalCompletionItemData::Acronym { name, description } => {
             let text_edit = TextEdit::new(range, name.to_string());
             CompletionItem {
-                label: name,
+                label: name.into(),
+                detail: description.map(Into::into),
                 kind: Some(Structure::GlossaryEntry.completion_kind()),
                 data: Some(serde_json::to_value(CompletionItemData::Acronym).unwrap()),
                 text_edit: Some(CompletionTextEdit::Edit(text_edit)),
                 ..CompletionItem::default()
             }
         }
-        InternalCompletionItemData::GlossaryEntry { name } => {
+        InternalCompletionItemData::GlossaryEntry { name, description } => {
             let text_edit = TextEdit::new(range, name.to_string());
             CompletionItem {
-                label: name,
+                label: name.into(),
+                detail: description.map(Into::into),
                 kind: Some(Structure::GlossaryEntry.completion_kind()),
                 data: Some(serde_json::to_value(CompletionItemData::GlossaryEntry).unwrap()),
                 text_edit: Some(CompletionTextEdit::Edit(text_edit)),
                 ..CompletionItem::default()
             }
         }
+        InternalCompletionItemData::Counter { name, is_builtin } => {
+            let text_edit = TextEdit::new(range, name.into());
+            CompletionItem {
+                label: name.into(),
+                detail: Some(
+                    if is_builtin {
+                        "built-in"
+                    } else {
+                        "user-defined"
+                    }
+                    .into(),
+                ),
+                kind: Some(Structure::Counter.completion_kind()),
+                data: Some(serde_json::to_value(CompletionItemData::Counter).unwrap()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                ..CompletionItem::default()
+            }
+        }
         InternalCompletionItemData::File { name } => {
             let text_edit = TextEdit::new(range, name.to_string());
             CompletionItem {
@@ -486,9 +698,48 @@ fn convert_internal_items(
                 ..CompletionItem::default()
             }
         }
+        InternalCompletionItemData::LabelDefinition { name, prefix, kind } => {
+            let supports_snippets = context
+                .request
+                .workspace
+                .environment
+                .client_capabilities
+                .has_completion_snippet_support();
+
+            let (text, insert_text_format) = if supports_snippets && name.len() > prefix.len() {
+                let text = format!("{}${{1:{}}}", prefix, &name[prefix.len()..]);
+                (text, Some(InsertTextFormat::SNIPPET))
+            } else {
+                (name.clone(), None)
+            };
+
+            let text_edit = TextEdit::new(range, text);
+            CompletionItem {
+                label: name,
+                kind: Some(kind.completion_kind()),
+                detail: Some("suggested name".into()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                insert_text_format,
+                data: Some(serde_json::to_value(CompletionItemData::Label).unwrap()),
+                ..CompletionItem::default()
+            }
+        }
         InternalCompletionItemData::UserCommand { name } => {
-            let detail = "user-defined".into();
+            // Commands defined in the workspace shadow the ones of packages that are not included.
+            let is_defined = context
+                .request
+                .workspace
+                .documents_by_uri
+                .values()
+                .filter_map(|document| document.data.as_latex())
+                .any(|data| data.extras.defined_commands.contains(name.as_str()));
+
             let name = &name[1..];
+            let detail = Some(name)
+                .filter(|_| !is_defined)
+                .and_then(|name| COMPONENT_DATABASE.find_command_provider(name))
+                .map_or_else(|| "user-defined".into(), missing_component_detail);
+
             let text_edit = TextEdit::new(range, name.to_string());
             CompletionItem {
                 kind: Some(Structure::Command.completion_kind()),
@@ -497,13 +748,20 @@ fn convert_internal_items(
                 ..CompletionItem::new_simple(name.into(), detail)
             }
         }
-        InternalCompletionItemData::UserEnvironment { name } => {
+        InternalCompletionItemData::UserEnvironment {
+            name,
+            argument_count,
+        } => {
             let detail = "user-defined".into();
-            let text_edit = TextEdit::new(range, name.clone());
+            let arguments = vec![""; argument_count];
+            let (text_edit, insert_text_format) =
+                environment_text_edit(context, item.range, &name, &arguments);
+
             CompletionItem {
                 kind: Some(Structure::Environment.completion_kind()),
                 data: Some(serde_json::to_value(CompletionItemData::Environment).unwrap()),
                 text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                insert_text_format,
                 ..CompletionItem::new_simple(name, detail)
             }
         }
@@ -527,6 +785,16 @@ fn convert_internal_items(
                 ..CompletionItem::default()
             }
         }
+        InternalCompletionItemData::TikzKey { name } => {
+            let text_edit = TextEdit::new(range, name.into());
+            CompletionItem {
+                label: name.into(),
+                kind: Some(Structure::PackageOption.completion_kind()),
+                data: Some(serde_json::to_value(CompletionItemData::TikzKey).unwrap()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                ..CompletionItem::default()
+            }
+        }
     };
 
     new_item.kind = new_item
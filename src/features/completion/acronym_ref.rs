@@ -1,7 +1,10 @@
 use lsp_types::CompletionParams;
 use rowan::ast::AstNode;
 
-use crate::{features::cursor::CursorContext, syntax::latex};
+use crate::{
+    features::cursor::CursorContext,
+    syntax::latex::{self, GlossaryEntryKind},
+};
 
 use super::types::{InternalCompletionItem, InternalCompletionItemData};
 
@@ -14,16 +17,18 @@ pub fn complete_acronyms<'a>(
 
     for document in context.request.workspace.documents_by_uri.values() {
         if let Some(data) = document.data.as_latex() {
-            for name in latex::SyntaxNode::new_root(data.green.clone())
-                .descendants()
-                .filter_map(latex::AcronymDefinition::cast)
-                .filter_map(|node| node.name())
-                .filter_map(|name| name.key())
-                .map(|name| name.to_string())
+            for entry in data
+                .extras
+                .glossary_entries
+                .iter()
+                .filter(|entry| entry.kind == GlossaryEntryKind::Acronym)
             {
                 items.push(InternalCompletionItem::new(
                     range,
-                    InternalCompletionItemData::Acronym { name },
+                    InternalCompletionItemData::Acronym {
+                        name: &entry.name,
+                        description: entry.description.as_deref(),
+                    },
                 ));
             }
         }
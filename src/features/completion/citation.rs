@@ -6,9 +6,10 @@ use regex::Regex;
 use rowan::{ast::AstNode, TextRange};
 
 use crate::{
+    citation,
     features::{cursor::CursorContext, lsp_kinds::Structure},
     syntax::{
-        bibtex::{self, HasName, HasType},
+        bibtex::{self, HasName, HasType, HasValue},
         latex,
     },
     BibtexEntryTypeCategory, Document, LANGUAGE_DATA,
@@ -38,7 +39,7 @@ pub fn complete_citations<'a>(
     check_citation(context).or_else(|| check_acronym(context))?;
     for document in context.request.workspace.documents_by_uri.values() {
         if let Some(data) = document.data.as_bibtex() {
-            for entry in bibtex::SyntaxNode::new_root(data.green.clone())
+            for entry in bibtex::SyntaxNode::new_root(data.green().clone())
                 .children()
                 .filter_map(bibtex::Entry::cast)
             {
@@ -86,20 +87,28 @@ fn make_item<'a>(
         );
 
     let entry_code = entry.syntax().text().to_string();
-    let text = format!(
-        "{} {}",
-        key,
-        WHITESPACE_REGEX
-            .replace_all(
-                &entry_code
-                    .replace('{', " ")
-                    .replace('}', " ")
-                    .replace(',', " ")
-                    .replace('=', " "),
-                " "
-            )
-            .trim(),
-    );
+    let text = format!("{} {}", key, clean_text(&entry_code));
+
+    // Only the author and the title are matched so that a pattern does not hit every entry of a journal.
+    let keywords = entry
+        .fields()
+        .filter(|field| {
+            field.name_token().map_or(false, |name| {
+                name.text().eq_ignore_ascii_case("author")
+                    || name.text().eq_ignore_ascii_case("title")
+            })
+        })
+        .filter_map(|field| field.value())
+        .map(|value| clean_text(&value.syntax().text().to_string()))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    // The entries are ordered like a bibliography while the rendered reference is resolved lazily.
+    let detail = citation::render_author_year(entry);
+    let sort_key = match &detail {
+        Some(detail) => format!("{} {}", detail, key).to_lowercase(),
+        None => key.to_lowercase(),
+    };
 
     Some(InternalCompletionItem::new(
         range,
@@ -107,9 +116,19 @@ fn make_item<'a>(
             uri: Arc::clone(&document.uri),
             key,
             text,
+            keywords,
             ty,
+            detail,
+            sort_key,
         },
     ))
 }
 
+fn clean_text(text: &str) -> String {
+    WHITESPACE_REGEX
+        .replace_all(&text.replace(['{', '}', ',', '='], " "), " ")
+        .trim()
+        .to_string()
+}
+
 static WHITESPACE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new("\\s+").unwrap());
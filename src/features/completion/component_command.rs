@@ -1,6 +1,9 @@
 use lsp_types::CompletionParams;
 
-use crate::{component_db::COMPONENT_DATABASE, features::cursor::CursorContext};
+use crate::{
+    component_db::{ComponentCommand, COMPONENT_DATABASE},
+    features::cursor::CursorContext,
+};
 
 use super::types::{InternalCompletionItem, InternalCompletionItemData};
 
@@ -9,10 +12,11 @@ pub fn complete_component_commands<'a>(
     items: &mut Vec<InternalCompletionItem<'a>>,
 ) -> Option<()> {
     let range = context.cursor.command_range(context.offset)?;
+    let is_in_math_mode = context.is_in_math_mode();
 
     for component in COMPONENT_DATABASE.linked_components(&context.request.workspace) {
         for command in &component.commands {
-            items.push(InternalCompletionItem::new(
+            let mut item = InternalCompletionItem::new(
                 range,
                 InternalCompletionItemData::ComponentCommand {
                     name: &command.name,
@@ -20,9 +24,18 @@ pub fn complete_component_commands<'a>(
                     glyph: command.glyph.as_deref(),
                     file_names: &component.file_names,
                 },
-            ));
+            );
+
+            item.is_relevant = is_in_math_mode || !is_math_symbol(command);
+            items.push(item);
         }
     }
 
     Some(())
 }
+
+/// Symbols like `\alpha` can only be used in math mode
+/// so they are sorted after the text commands outside of math mode.
+fn is_math_symbol(command: &ComponentCommand) -> bool {
+    command.glyph.is_some() && command.name.chars().all(|c| c.is_ascii_alphabetic())
+}
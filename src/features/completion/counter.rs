@@ -0,0 +1,40 @@
+use lsp_types::CompletionParams;
+use rowan::ast::AstNode;
+
+use crate::{features::cursor::CursorContext, syntax::latex, LANGUAGE_DATA};
+
+use super::types::{InternalCompletionItem, InternalCompletionItemData};
+
+pub fn complete_counters<'a>(
+    context: &'a CursorContext<CompletionParams>,
+    items: &mut Vec<InternalCompletionItem<'a>>,
+) -> Option<()> {
+    let (_, range, group) = context.find_curly_group_word()?;
+    latex::CounterReference::cast(group.syntax().parent()?)?;
+
+    for document in context.request.workspace.documents_by_uri.values() {
+        if let Some(data) = document.data.as_latex() {
+            for counter in &data.extras.counters {
+                items.push(InternalCompletionItem::new(
+                    range,
+                    InternalCompletionItemData::Counter {
+                        name: &counter.name,
+                        is_builtin: false,
+                    },
+                ));
+            }
+        }
+    }
+
+    for name in &LANGUAGE_DATA.counters {
+        items.push(InternalCompletionItem::new(
+            range,
+            InternalCompletionItemData::Counter {
+                name,
+                is_builtin: true,
+            },
+        ));
+    }
+
+    Some(())
+}
@@ -3,7 +3,7 @@ use rowan::{ast::AstNode, TextRange};
 
 use crate::{
     features::cursor::CursorContext,
-    syntax::bibtex::{self, HasName},
+    syntax::bibtex::{self, HasName, HasType},
     LANGUAGE_DATA,
 };
 
@@ -22,16 +22,24 @@ pub fn complete_fields<'a>(
     };
 
     let parent = token.parent()?;
-    if let Some(entry) = bibtex::Entry::cast(parent.clone()) {
+    let entry = if let Some(entry) = bibtex::Entry::cast(parent.clone()) {
         if entry.name_token()?.text_range() == token.text_range() {
             return None;
         }
+
+        entry
     } else {
-        bibtex::Field::cast(parent)?;
-    }
+        bibtex::Field::cast(parent.clone())?;
+        bibtex::Entry::cast(parent.parent()?)?
+    };
+
+    let ty = entry
+        .type_token()
+        .and_then(|token| LANGUAGE_DATA.find_entry_type(&token.text()[1..]));
 
     for field in &LANGUAGE_DATA.fields {
-        let data = InternalCompletionItemData::Field { field };
+        let requirement = ty.and_then(|ty| ty.field_requirement(&field.name));
+        let data = InternalCompletionItemData::Field { field, requirement };
         let item = InternalCompletionItem::new(range, data);
         items.push(item);
     }
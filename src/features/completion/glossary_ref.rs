@@ -1,7 +1,10 @@
 use lsp_types::CompletionParams;
 use rowan::ast::AstNode;
 
-use crate::{features::cursor::CursorContext, syntax::latex};
+use crate::{
+    features::cursor::CursorContext,
+    syntax::latex::{self, GlossaryEntryKind},
+};
 
 use super::types::{InternalCompletionItem, InternalCompletionItemData};
 
@@ -14,26 +17,19 @@ pub fn complete_glossary_entries<'a>(
 
     for document in context.request.workspace.documents_by_uri.values() {
         if let Some(data) = document.data.as_latex() {
-            for node in latex::SyntaxNode::new_root(data.green.clone()).descendants() {
-                if let Some(name) = latex::GlossaryEntryDefinition::cast(node.clone())
-                    .and_then(|entry| entry.name())
-                    .and_then(|name| name.key())
-                    .map(|name| name.to_string())
-                {
-                    items.push(InternalCompletionItem::new(
-                        range,
-                        InternalCompletionItemData::GlossaryEntry { name },
-                    ));
-                } else if let Some(name) = latex::AcronymDefinition::cast(node)
-                    .and_then(|entry| entry.name())
-                    .and_then(|name| name.key())
-                    .map(|name| name.to_string())
-                {
-                    items.push(InternalCompletionItem::new(
-                        range,
-                        InternalCompletionItemData::Acronym { name },
-                    ));
-                }
+            for entry in &data.extras.glossary_entries {
+                let name = &entry.name;
+                let description = entry.description.as_deref();
+                let data = match entry.kind {
+                    GlossaryEntryKind::Entry => {
+                        InternalCompletionItemData::GlossaryEntry { name, description }
+                    }
+                    GlossaryEntryKind::Acronym => {
+                        InternalCompletionItemData::Acronym { name, description }
+                    }
+                };
+
+                items.push(InternalCompletionItem::new(range, data));
             }
         }
     }
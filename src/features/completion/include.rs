@@ -38,6 +38,7 @@ pub fn complete_includes<'a>(
         latex::SVG_INCLUDE => (true, &["svg"]),
         latex::INKSCAPE_INCLUDE => (true, &["pdf", "eps", "ps", "png"]),
         latex::VERBATIM_INCLUDE => (true, &[]),
+        latex::XR_INCLUDE => (false, &["tex"]),
         _ => return None,
     };
 
@@ -45,7 +46,7 @@ pub fn complete_includes<'a>(
         path_range
     } else {
         let start =
-            path_range.end() - TextSize::try_from(path_text.split('/').last()?.len()).ok()?;
+            path_range.end() - TextSize::try_from(path_text.split('/').next_back()?.len()).ok()?;
         TextRange::new(start, path_range.end())
     };
 
@@ -112,7 +113,7 @@ fn current_dir(
                     .request
                     .workspace
                     .environment
-                    .current_directory
+                    .base_directory(&context.request.main_document().uri)
                     .join(root_directory)
             },
         );
@@ -123,7 +124,7 @@ fn current_dir(
             path.push(graphics_path);
         }
 
-        path.push(&path_text);
+        path.push(path_text);
         if !path_text.ends_with('/') {
             path.pop();
         }
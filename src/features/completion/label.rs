@@ -1,11 +1,11 @@
-use lsp_types::CompletionParams;
+use lsp_types::{CompletionParams, Url};
 use rowan::{ast::AstNode, TextRange};
 
 use crate::{
     features::{cursor::CursorContext, lsp_kinds::Structure},
     render_label,
-    syntax::latex,
-    LabelledObject,
+    syntax::latex::{self, ExplicitLinkKind},
+    LabelledObject, Workspace,
 };
 
 use super::types::{InternalCompletionItem, InternalCompletionItemData};
@@ -14,9 +14,11 @@ pub fn complete_labels<'a>(
     context: &'a CursorContext<CompletionParams>,
     items: &mut Vec<InternalCompletionItem<'a>>,
 ) -> Option<()> {
-    let (range, is_math) = find_reference(context).or_else(|| find_reference_range(context))?;
+    let (range, command) = find_reference(context).or_else(|| find_reference_range(context))?;
 
-    for document in context.request.workspace.documents_by_uri.values() {
+    let workspace = &context.request.workspace;
+    for document in workspace.documents_by_uri.values() {
+        let prefix = find_external_prefix(workspace, &document.uri).unwrap_or_default();
         if let Some(data) = document.data.as_latex() {
             for label in latex::SyntaxNode::new_root(data.green.clone())
                 .descendants()
@@ -27,7 +29,9 @@ pub fn complete_labels<'a>(
                     .and_then(|name| name.key())
                     .map(|name| name.to_string())
                 {
-                    match render_label(&context.request.workspace, &name, Some(label)) {
+                    let rendered_label = render_label(workspace, &name, Some(label));
+                    let name = format!("{}{}", prefix, name);
+                    match rendered_label {
                         Some(rendered_label) => {
                             let kind = match &rendered_label.object {
                                 LabelledObject::Section { .. } => Structure::Section,
@@ -37,19 +41,15 @@ pub fn complete_labels<'a>(
                                 LabelledObject::EnumItem => Structure::Item,
                             };
 
-                            if is_math && kind != Structure::Equation {
-                                continue;
-                            }
-
                             let header = rendered_label.detail();
                             let footer = match &rendered_label.object {
-                                LabelledObject::Float { caption, .. } => Some(caption.clone()),
+                                LabelledObject::Float { caption, .. } => caption.clone(),
                                 _ => None,
                             };
 
                             let text = format!("{} {}", name, rendered_label.reference());
 
-                            let item = InternalCompletionItem::new(
+                            let mut item = InternalCompletionItem::new(
                                 range,
                                 InternalCompletionItemData::Label {
                                     name,
@@ -59,6 +59,7 @@ pub fn complete_labels<'a>(
                                     text,
                                 },
                             );
+                            item.is_relevant = is_relevant(command.as_deref(), kind);
                             items.push(item);
                         }
                         None => {
@@ -66,7 +67,7 @@ pub fn complete_labels<'a>(
                             let header = None;
                             let footer = None;
                             let text = name.to_string();
-                            let item = InternalCompletionItem::new(
+                            let mut item = InternalCompletionItem::new(
                                 range,
                                 InternalCompletionItemData::Label {
                                     name,
@@ -76,6 +77,7 @@ pub fn complete_labels<'a>(
                                     text,
                                 },
                             );
+                            item.is_relevant = is_relevant(command.as_deref(), kind);
                             items.push(item);
                         }
                     }
@@ -87,15 +89,43 @@ pub fn complete_labels<'a>(
     Some(())
 }
 
-fn find_reference(context: &CursorContext<CompletionParams>) -> Option<(TextRange, bool)> {
+/// Returns the prefix of the `\externaldocument` command that imports the labels of the given document.
+fn find_external_prefix<'a>(workspace: &'a Workspace, uri: &Url) -> Option<&'a str> {
+    workspace
+        .documents_by_uri
+        .values()
+        .filter_map(|document| document.data.as_latex())
+        .flat_map(|data| data.extras.explicit_links.iter())
+        .filter(|link| link.kind == ExplicitLinkKind::External)
+        .find(|link| link.targets.iter().any(|target| target.as_ref() == uri))?
+        .prefix
+        .as_deref()
+}
+
+/// Returns `true` if a label of the given kind is a typical target of the reference command.
+///
+/// `\eqref` refers to equations while other reference commands like `\cref` or `\pageref`
+/// can refer to any label.
+fn is_relevant(command: Option<&str>, kind: Structure) -> bool {
+    match command {
+        Some("\\eqref") => kind == Structure::Equation,
+        _ => true,
+    }
+}
+
+fn find_reference(
+    context: &CursorContext<CompletionParams>,
+) -> Option<(TextRange, Option<String>)> {
     let (_, range, group) = context.find_curly_group_word_list()?;
     let reference = latex::LabelReference::cast(group.syntax().parent()?)?;
-    let is_math = reference.command()?.text() == "\\eqref";
-    Some((range, is_math))
+    let command = reference.command()?.text().to_string();
+    Some((range, Some(command)))
 }
 
-fn find_reference_range(context: &CursorContext<CompletionParams>) -> Option<(TextRange, bool)> {
+fn find_reference_range(
+    context: &CursorContext<CompletionParams>,
+) -> Option<(TextRange, Option<String>)> {
     let (_, range, group) = context.find_curly_group_word()?;
     latex::LabelReferenceRange::cast(group.syntax().parent()?)?;
-    Some((range, false))
+    Some((range, None))
 }
@@ -0,0 +1,61 @@
+use lsp_types::CompletionParams;
+use rowan::ast::AstNode;
+
+use crate::{
+    features::{cursor::CursorContext, lsp_kinds::Structure},
+    render_label,
+    syntax::latex,
+    LabelledFloatKind, LabelledObject,
+};
+
+use super::types::{InternalCompletionItem, InternalCompletionItemData};
+
+pub fn complete_label_definitions<'a>(
+    context: &'a CursorContext<CompletionParams>,
+    items: &mut Vec<InternalCompletionItem<'a>>,
+) -> Option<()> {
+    let (name, range, group) = context.find_curly_group_word()?;
+    let label = latex::LabelDefinition::cast(group.syntax().parent()?)?;
+    let object = render_label(&context.request.workspace, &name, Some(label))?.object;
+
+    let (prefix, text, kind) = match &object {
+        LabelledObject::Section { prefix, text } => {
+            let prefix = match *prefix {
+                "Chapter" => "chap:",
+                _ => "sec:",
+            };
+            (prefix, Some(text.as_str()), Structure::Section)
+        }
+        LabelledObject::Float { kind, caption } => {
+            let prefix = match kind {
+                LabelledFloatKind::Figure => "fig:",
+                LabelledFloatKind::Table => "tab:",
+                LabelledFloatKind::Listing => "lst:",
+                LabelledFloatKind::Algorithm => "alg:",
+            };
+
+            (prefix, caption.as_deref(), Structure::Float)
+        }
+        LabelledObject::Theorem { description, .. } => {
+            ("thm:", description.as_deref(), Structure::Theorem)
+        }
+        LabelledObject::Equation => ("eq:", None, Structure::Equation),
+        LabelledObject::EnumItem => ("item:", None, Structure::Item),
+    };
+
+    let name = format!("{}{}", prefix, text.map(slugify).unwrap_or_default());
+    items.push(InternalCompletionItem::new(
+        range,
+        InternalCompletionItemData::LabelDefinition { name, prefix, kind },
+    ));
+
+    Some(())
+}
+
+fn slugify(text: &str) -> String {
+    text.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
@@ -0,0 +1,24 @@
+use lsp_types::CompletionParams;
+
+use crate::{features::cursor::CursorContext, LANGUAGE_DATA};
+
+use super::types::{InternalCompletionItem, InternalCompletionItemData};
+
+pub fn complete_math_symbols<'a>(
+    context: &'a CursorContext<CompletionParams>,
+    items: &mut Vec<InternalCompletionItem<'a>>,
+) -> Option<()> {
+    let range = context.cursor.command_range(context.offset)?;
+    if !context.is_in_math_mode() {
+        return None;
+    }
+
+    for symbol in &LANGUAGE_DATA.math_symbols {
+        items.push(InternalCompletionItem::new(
+            range,
+            InternalCompletionItemData::MathSymbol { symbol },
+        ));
+    }
+
+    Some(())
+}
@@ -0,0 +1,89 @@
+use lsp_types::CompletionParams;
+use rowan::{ast::AstNode, TextRange};
+
+use crate::{
+    features::cursor::CursorContext,
+    syntax::latex::{self, HasKeyValueBody},
+    LANGUAGE_DATA,
+};
+
+use super::types::{InternalCompletionItem, InternalCompletionItemData};
+
+pub fn complete_package_options<'a>(
+    context: &'a CursorContext<CompletionParams>,
+    items: &mut Vec<InternalCompletionItem<'a>>,
+) -> Option<()> {
+    let token = context.cursor.as_latex()?;
+
+    let group = token
+        .parent_ancestors()
+        .find_map(latex::BrackGroupKeyValue::cast)
+        .filter(|group| context.is_inside_latex_brack(group))?;
+
+    let include = latex::Include::cast(group.syntax().parent()?)?;
+    let options_by_name = match include.syntax().kind() {
+        latex::PACKAGE_INCLUDE => &LANGUAGE_DATA.package_options,
+        latex::CLASS_INCLUDE => &LANGUAGE_DATA.class_options,
+        _ => return None,
+    };
+
+    let packages: Vec<_> = include
+        .path_list()?
+        .keys()
+        .filter_map(|name| options_by_name.get(&name.to_string()))
+        .collect();
+
+    let pair = group.body().and_then(|body| {
+        body.pairs()
+            .find(|pair| latex::small_range(pair).contains_inclusive(context.offset))
+    });
+
+    let value_start = pair.as_ref().and_then(|pair| {
+        pair.syntax()
+            .children_with_tokens()
+            .filter_map(|element| element.into_token())
+            .find(|token| token.kind() == latex::EQUALITY_SIGN)
+            .map(|token| token.text_range().end())
+            .filter(|&end| end <= context.offset)
+    });
+
+    match (pair, value_start) {
+        (Some(pair), Some(_)) => {
+            let key = pair.key()?.to_string();
+            let range = pair
+                .value()
+                .map(|value| latex::small_range(&value))
+                .filter(|range| range.contains_inclusive(context.offset))
+                .unwrap_or_else(|| TextRange::empty(context.offset));
+
+            for option in packages
+                .into_iter()
+                .flatten()
+                .filter(|option| option.name == key)
+            {
+                for name in &option.values {
+                    items.push(InternalCompletionItem::new(
+                        range,
+                        InternalCompletionItemData::PackageOptionValue { name },
+                    ));
+                }
+            }
+        }
+        (pair, _) => {
+            let range = pair
+                .and_then(|pair| pair.key())
+                .map(|key| latex::small_range(&key))
+                .filter(|range| range.contains_inclusive(context.offset))
+                .unwrap_or_else(|| TextRange::empty(context.offset));
+
+            for option in packages.into_iter().flatten() {
+                items.push(InternalCompletionItem::new(
+                    range,
+                    InternalCompletionItemData::PackageOption { name: &option.name },
+                ));
+            }
+        }
+    };
+
+    Some(())
+}
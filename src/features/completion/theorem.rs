@@ -17,6 +17,7 @@ pub fn complete_theorem_environments<'a>(
                     range,
                     InternalCompletionItemData::UserEnvironment {
                         name: environment.name.clone(),
+                        argument_count: 0,
                     },
                 ));
             }
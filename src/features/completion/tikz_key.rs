@@ -0,0 +1,101 @@
+use lsp_types::CompletionParams;
+use rowan::{ast::AstNode, TextRange, TextSize};
+
+use crate::{
+    features::cursor::CursorContext,
+    syntax::latex::{self, HasBrack},
+    LANGUAGE_DATA,
+};
+
+use super::types::{InternalCompletionItem, InternalCompletionItemData};
+
+/// The commands whose optional argument is a list of TikZ keys.
+const TIKZ_COMMANDS: &[&str] = &[
+    "\\draw",
+    "\\fill",
+    "\\filldraw",
+    "\\path",
+    "\\node",
+    "\\coordinate",
+    "\\clip",
+    "\\shade",
+    "\\shadedraw",
+    "\\matrix",
+    "\\pic",
+    "\\tikz",
+];
+
+/// The environments whose optional argument is a list of TikZ keys.
+const TIKZ_ENVIRONMENTS: &[&str] = &["tikzpicture", "scope"];
+
+pub fn complete_tikz_keys<'a>(
+    context: &'a CursorContext<CompletionParams>,
+    items: &mut Vec<InternalCompletionItem<'a>>,
+) -> Option<()> {
+    let start = find_option_list(context)?;
+    let range = find_key_range(context, start)?;
+
+    for name in &LANGUAGE_DATA.tikz_keys {
+        items.push(InternalCompletionItem::new(
+            range,
+            InternalCompletionItemData::TikzKey { name },
+        ));
+    }
+
+    Some(())
+}
+
+/// Returns the start of the option list of a TikZ command or environment around the cursor.
+fn find_option_list(context: &CursorContext<CompletionParams>) -> Option<TextSize> {
+    let token = context.cursor.as_latex()?;
+    let node = token
+        .parent_ancestors()
+        .find(|node| matches!(node.kind(), latex::MIXED_GROUP | latex::BRACK_GROUP))?;
+
+    if let Some(group) = latex::MixedGroup::cast(node.clone()) {
+        let delim = group
+            .left_delim()
+            .filter(|delim| delim.kind() == latex::L_BRACK)?;
+
+        let is_inside =
+            latex::small_range(&group).contains(context.offset) || group.right_delim().is_none();
+
+        let command = latex::GenericCommand::cast(node.parent()?)?;
+        let name = command.name()?;
+        if is_inside && TIKZ_COMMANDS.contains(&name.text()) {
+            return Some(delim.text_range().end());
+        }
+    } else if let Some(group) = latex::BrackGroup::cast(node.clone()) {
+        let begin = latex::Begin::cast(node.parent()?)?;
+        let name = begin.name()?.key()?.to_string();
+        if context.is_inside_latex_brack(&group) && TIKZ_ENVIRONMENTS.contains(&name.as_str()) {
+            return Some(group.left_brack()?.text_range().end());
+        }
+    }
+
+    None
+}
+
+/// Finds the key under the cursor, which may consist of multiple words like `node distance`.
+///
+/// Values after an `=` are not completed.
+fn find_key_range(context: &CursorContext<CompletionParams>, start: TextSize) -> Option<TextRange> {
+    let text = context.request.main_document().text.as_str();
+    let offset = usize::from(context.offset);
+    let before = text.get(usize::from(start)..offset)?;
+    let key = &before[before.rfind(',').map_or(0, |i| i + 1)..];
+    if key.contains(['=', '{', '}', '(', ')']) {
+        return None;
+    }
+
+    let key_start = offset - key.trim_start().len();
+    let key_end = offset
+        + text[offset..]
+            .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '>' || c == '<'))
+            .unwrap_or(text.len() - offset);
+
+    Some(TextRange::new(
+        TextSize::try_from(key_start).ok()?,
+        TextSize::try_from(key_end).ok()?,
+    ))
+}
@@ -1,5 +1,6 @@
 use lsp_types::CompletionParams;
 use rowan::ast::AstNode;
+use rustc_hash::FxHashSet;
 
 use crate::{features::cursor::CursorContext, syntax::latex, LANGUAGE_DATA};
 
@@ -13,15 +14,29 @@ pub fn complete_tikz_libraries<'a>(
 
     let import = latex::TikzLibraryImport::cast(group.syntax().parent()?)?;
 
+    let listed_names: FxHashSet<_> = group
+        .keys()
+        .filter(|key| !latex::small_range(key).contains_inclusive(context.offset))
+        .map(|key| key.to_string())
+        .collect();
+
     if import.command()?.text() == "\\usepgflibrary" {
-        for name in &LANGUAGE_DATA.pgf_libraries {
+        for name in LANGUAGE_DATA
+            .pgf_libraries
+            .iter()
+            .filter(|name| !listed_names.contains(*name))
+        {
             items.push(InternalCompletionItem::new(
                 range,
                 InternalCompletionItemData::PgfLibrary { name },
             ));
         }
     } else {
-        for name in &LANGUAGE_DATA.tikz_libraries {
+        for name in LANGUAGE_DATA
+            .tikz_libraries
+            .iter()
+            .filter(|name| !listed_names.contains(*name))
+        {
             items.push(InternalCompletionItem::new(
                 range,
                 InternalCompletionItemData::TikzLibrary { name },
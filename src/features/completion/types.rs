@@ -5,13 +5,18 @@ use rowan::TextRange;
 use serde::{Deserialize, Serialize};
 use smol_str::SmolStr;
 
-use crate::{features::lsp_kinds::Structure, BibtexEntryTypeDoc, BibtexFieldDoc};
+use crate::{
+    features::lsp_kinds::Structure, BibtexEntryTypeDoc, BibtexFieldDoc, BibtexFieldRequirement,
+    MathSymbolDoc,
+};
 
 #[derive(Debug, Clone)]
 pub struct InternalCompletionItem<'a> {
     pub range: TextRange,
     pub data: InternalCompletionItemData<'a>,
     pub preselect: bool,
+    /// Whether the item fits the context of the cursor. Relevant items are sorted first.
+    pub is_relevant: bool,
     pub score: Option<i64>,
 }
 
@@ -21,6 +26,7 @@ impl<'a> InternalCompletionItem<'a> {
             range,
             data,
             preselect: false,
+            is_relevant: true,
             score: None,
         }
     }
@@ -33,6 +39,7 @@ pub enum InternalCompletionItemData<'a> {
     },
     Field {
         field: &'a BibtexFieldDoc,
+        requirement: Option<BibtexFieldRequirement>,
     },
     Argument {
         name: &'a str,
@@ -43,7 +50,10 @@ pub enum InternalCompletionItemData<'a> {
         uri: Arc<Url>,
         key: String,
         text: String,
+        keywords: String,
         ty: Structure,
+        detail: Option<String>,
+        sort_key: String,
     },
     ComponentCommand {
         name: &'a SmolStr,
@@ -51,6 +61,9 @@ pub enum InternalCompletionItemData<'a> {
         glyph: Option<&'a str>,
         file_names: &'a [SmolStr],
     },
+    MathSymbol {
+        symbol: &'a MathSymbolDoc,
+    },
     ComponentEnvironment {
         name: &'a SmolStr,
         file_names: &'a [SmolStr],
@@ -61,6 +74,12 @@ pub enum InternalCompletionItemData<'a> {
     Package {
         name: SmolStr,
     },
+    PackageOption {
+        name: &'a str,
+    },
+    PackageOptionValue {
+        name: &'a str,
+    },
     Color {
         name: &'a str,
     },
@@ -68,10 +87,16 @@ pub enum InternalCompletionItemData<'a> {
         name: &'a str,
     },
     Acronym {
-        name: String,
+        name: &'a str,
+        description: Option<&'a str>,
     },
     GlossaryEntry {
-        name: String,
+        name: &'a str,
+        description: Option<&'a str>,
+    },
+    Counter {
+        name: &'a str,
+        is_builtin: bool,
     },
     File {
         name: SmolStr,
@@ -86,11 +111,17 @@ pub enum InternalCompletionItemData<'a> {
         footer: Option<String>,
         text: String,
     },
+    LabelDefinition {
+        name: String,
+        prefix: &'static str,
+        kind: Structure,
+    },
     UserCommand {
         name: SmolStr,
     },
     UserEnvironment {
         name: String,
+        argument_count: usize,
     },
     PgfLibrary {
         name: &'a str,
@@ -98,31 +129,48 @@ pub enum InternalCompletionItemData<'a> {
     TikzLibrary {
         name: &'a str,
     },
+    TikzKey {
+        name: &'a str,
+    },
 }
 
 impl<'a> InternalCompletionItemData<'a> {
     pub fn label<'b: 'a>(&'b self) -> &'a str {
         match self {
             Self::EntryType { ty } => &ty.name,
-            Self::Field { field } => &field.name,
+            Self::Field { field, .. } => &field.name,
             Self::Argument { name, .. } => name,
             Self::BeginCommand => "begin",
             Self::Citation { key, .. } => key,
             Self::ComponentCommand { name, .. } => name,
+            Self::MathSymbol { symbol } => &symbol.name,
             Self::ComponentEnvironment { name, .. } => name,
             Self::Class { name } => name,
             Self::Package { name } => name,
+            Self::PackageOption { name } => name,
+            Self::PackageOptionValue { name } => name,
             Self::Color { name } => name,
             Self::ColorModel { name } => name,
-            Self::Acronym { name } => name,
-            Self::GlossaryEntry { name } => name,
+            Self::Acronym { name, .. } => name,
+            Self::GlossaryEntry { name, .. } => name,
+            Self::Counter { name, .. } => name,
             Self::File { name } => name,
             Self::Directory { name } => name,
             Self::Label { name, .. } => name,
+            Self::LabelDefinition { name, .. } => name,
             Self::UserCommand { name } => name,
-            Self::UserEnvironment { name } => name,
+            Self::UserEnvironment { name, .. } => name,
             Self::PgfLibrary { name } => name,
             Self::TikzLibrary { name } => name,
+            Self::TikzKey { name } => name,
+        }
+    }
+
+    /// Returns the text that orders items with the same score.
+    pub fn sort_key<'b: 'a>(&'b self) -> &'a str {
+        match self {
+            Self::Citation { sort_key, .. } => sort_key,
+            _ => self.label(),
         }
     }
 }
@@ -138,9 +186,11 @@ pub enum CompletionItemData {
     File,
     PgfLibrary,
     TikzLibrary,
+    TikzKey,
     Color,
     ColorModel,
     Package,
+    PackageOption,
     Class,
     EntryType,
     FieldName,
@@ -148,4 +198,5 @@ pub enum CompletionItemData {
     Argument,
     Acronym,
     GlossaryEntry,
+    Counter,
 }
@@ -10,21 +10,45 @@ pub fn complete_user_environments<'a>(
 ) -> Option<()> {
     let (name, range) = context.find_environment_name()?;
 
-    for document in context.request.workspace.documents_by_uri.values() {
-        if let Some(data) = document.data.as_latex() {
-            for name in data
-                .extras
-                .environment_names
-                .iter()
-                .filter(|n| n.as_str() != name)
-                .cloned()
-            {
-                items.push(InternalCompletionItem::new(
-                    range,
-                    InternalCompletionItemData::UserEnvironment { name },
-                ));
-            }
-        }
+    let documents = context.request.workspace.documents_by_uri.values();
+    let all_extras: Vec<_> = documents
+        .filter_map(|document| document.data.as_latex())
+        .map(|data| &data.extras)
+        .collect();
+
+    // Definitions come first so that their arguments are kept when removing duplicates.
+    for environment in all_extras
+        .iter()
+        .flat_map(|extras| &extras.user_environments)
+        .filter(|environment| environment.name != name)
+    {
+        let argument_count = environment
+            .argument_count
+            .unwrap_or_default()
+            .saturating_sub(usize::from(environment.has_optional_argument));
+
+        items.push(InternalCompletionItem::new(
+            range,
+            InternalCompletionItemData::UserEnvironment {
+                name: environment.name.to_string(),
+                argument_count,
+            },
+        ));
+    }
+
+    for name in all_extras
+        .iter()
+        .flat_map(|extras| &extras.environment_names)
+        .filter(|n| n.as_str() != name)
+        .cloned()
+    {
+        items.push(InternalCompletionItem::new(
+            range,
+            InternalCompletionItemData::UserEnvironment {
+                name,
+                argument_count: 0,
+            },
+        ));
     }
 
     Some(())
@@ -1,16 +1,65 @@
 use lsp_types::{
-    CompletionParams, DocumentHighlightParams, GotoDefinitionParams, HoverParams, Position,
-    ReferenceParams, RenameParams, TextDocumentPositionParams,
+    CompletionParams, DocumentHighlightParams, GotoDefinitionParams, HoverParams,
+    LinkedEditingRangeParams, Position, ReferenceParams, RenameParams, TextDocumentPositionParams,
 };
 use rowan::{ast::AstNode, TextRange, TextSize};
 
 use crate::{
     syntax::{bibtex, latex},
-    DocumentData, LineIndexExt,
+    DocumentData, LineIndexExt, LANGUAGE_DATA,
 };
 
 use super::FeatureRequest;
 
+const TEXT_COMMANDS: &[&str] = &[
+    "text",
+    "textrm",
+    "textit",
+    "textbf",
+    "textsf",
+    "texttt",
+    "textnormal",
+    "mbox",
+    "intertext",
+];
+
+/// Returns the bounds of the math content of a formula or a math environment.
+/// The end is missing if the node is not closed.
+fn find_math_body(node: &latex::SyntaxNode) -> Option<(TextSize, Option<TextSize>)> {
+    match node.kind() {
+        latex::FORMULA | latex::EQUATION => {
+            let mut delimiters = node
+                .children_with_tokens()
+                .filter_map(|element| element.into_token())
+                .filter(|token| {
+                    matches!(
+                        token.kind(),
+                        latex::DOLLAR | latex::BEGIN_EQUATION_NAME | latex::END_EQUATION_NAME
+                    )
+                });
+
+            let start = delimiters.next()?.text_range().end();
+            let end = delimiters.next().map(|token| token.text_range().start());
+            Some((start, end))
+        }
+        latex::ENVIRONMENT => {
+            let environment = latex::Environment::cast(node.clone())?;
+            let begin = environment.begin()?;
+            let name = begin.name()?.key()?.to_string();
+            if !LANGUAGE_DATA.math_environments.contains(&name) {
+                return None;
+            }
+
+            let start = begin.syntax().text_range().end();
+            let end = environment
+                .end()
+                .map(|end| end.syntax().text_range().start());
+            Some((start, end))
+        }
+        _ => None,
+    }
+}
+
 #[derive(Debug)]
 pub enum Cursor {
     Latex(latex::SyntaxToken),
@@ -144,7 +193,7 @@ impl<P: HasPosition> CursorContext<P> {
                 Cursor::new_latex(left, right)
             }
             DocumentData::Bibtex(data) => {
-                let root = bibtex::SyntaxNode::new_root(data.green.clone());
+                let root = bibtex::SyntaxNode::new_root(data.green().clone());
                 let left = root.token_at_offset(offset).left_biased();
                 let right = root.token_at_offset(offset).right_biased();
                 Cursor::new_bibtex(left, right)
@@ -159,10 +208,53 @@ impl<P: HasPosition> CursorContext<P> {
         }
     }
 
+    /// Checks whether the cursor is inside of an inline formula, a display formula (`\[...\]`)
+    /// or a math environment like `equation` or `align`.
+    ///
+    /// The argument of a text command like `\text` switches back to text mode.
+    pub fn is_in_math_mode(&self) -> bool {
+        let data = match self.request.main_document().data.as_latex() {
+            Some(data) => data,
+            None => return false,
+        };
+
+        let root = latex::SyntaxNode::new_root(data.green.clone());
+        let token = match root.token_at_offset(self.offset).left_biased() {
+            Some(token) => token,
+            None => return false,
+        };
+
+        for node in token.parent_ancestors() {
+            if let Some(group) = latex::CurlyGroup::cast(node.clone()) {
+                let is_text_command = node
+                    .parent()
+                    .and_then(latex::GenericCommand::cast)
+                    .and_then(|command| command.name())
+                    .map_or(false, |name| TEXT_COMMANDS.contains(&&name.text()[1..]));
+
+                if is_text_command && self.is_inside_latex_curly(&group) {
+                    return false;
+                }
+            }
+
+            if let Some((start, end)) = find_math_body(&node) {
+                if start <= self.offset && end.map_or(true, |end| self.offset <= end) {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
     pub fn is_inside_latex_curly(&self, group: &impl latex::HasCurly) -> bool {
         latex::small_range(group).contains(self.offset) || group.right_curly().is_none()
     }
 
+    pub fn is_inside_latex_brack(&self, group: &impl latex::HasBrack) -> bool {
+        latex::small_range(group).contains(self.offset) || group.right_brack().is_none()
+    }
+
     pub fn find_citation_key_word(&self) -> Option<(String, TextRange)> {
         let word = self
             .cursor
@@ -212,6 +304,45 @@ impl<P: HasPosition> CursorContext<P> {
         }
     }
 
+    pub fn find_glossary_entry_name_key(&self) -> Option<(String, TextRange)> {
+        let name = self
+            .cursor
+            .as_latex()
+            .filter(|token| token.kind() == latex::WORD)?;
+
+        let key = latex::Key::cast(name.parent()?)?;
+
+        if matches!(
+            key.syntax().parent()?.parent()?.kind(),
+            latex::GLOSSARY_ENTRY_DEFINITION
+                | latex::GLOSSARY_ENTRY_REFERENCE
+                | latex::ACRONYM_DEFINITION
+                | latex::ACRONYM_REFERENCE
+        ) {
+            Some((key.to_string(), latex::small_range(&key)))
+        } else {
+            None
+        }
+    }
+
+    pub fn find_counter_name_key(&self) -> Option<(String, TextRange)> {
+        let name = self
+            .cursor
+            .as_latex()
+            .filter(|token| token.kind() == latex::WORD)?;
+
+        let key = latex::Key::cast(name.parent()?)?;
+
+        if matches!(
+            key.syntax().parent()?.parent()?.kind(),
+            latex::COUNTER_DEFINITION | latex::COUNTER_REFERENCE
+        ) {
+            Some((key.to_string(), latex::small_range(&key)))
+        } else {
+            None
+        }
+    }
+
     pub fn find_label_name_command(&self) -> Option<(String, TextRange)> {
         let node = self.cursor.as_latex()?.parent()?;
         if let Some(label) = latex::LabelDefinition::cast(node.clone()) {
@@ -331,3 +462,9 @@ impl HasPosition for DocumentHighlightParams {
         self.text_document_position_params.position
     }
 }
+
+impl HasPosition for LinkedEditingRangeParams {
+    fn position(&self) -> Position {
+        self.text_document_position_params.position
+    }
+}
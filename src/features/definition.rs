@@ -1,19 +1,28 @@
 mod command;
+mod counter;
 mod document;
 mod entry;
+mod environment;
+mod glossary;
 mod label;
 mod string;
+mod theorem;
 
 use std::sync::Arc;
 
-use lsp_types::{GotoDefinitionParams, GotoDefinitionResponse, LocationLink, Url};
+use lsp_types::{
+    request::{GotoTypeDefinitionParams, GotoTypeDefinitionResponse},
+    GotoDefinitionParams, GotoDefinitionResponse, LocationLink, Url,
+};
 use rowan::TextRange;
 
 use crate::LineIndexExt;
 
 use self::{
-    command::goto_command_definition, document::goto_document_definition,
-    entry::goto_entry_definition, label::goto_label_definition, string::goto_string_definition,
+    command::goto_command_definition, counter::goto_counter_definition,
+    document::goto_document_definition, entry::goto_entry_definition,
+    environment::goto_environment_definition, glossary::goto_glossary_entry_definition,
+    label::goto_label_definition, string::goto_string_definition, theorem::goto_theorem_definition,
 };
 
 use super::{cursor::CursorContext, FeatureRequest};
@@ -24,12 +33,34 @@ pub fn goto_definition(
     let context = CursorContext::new(request);
     log::debug!("[Definition] Cursor: {:?}", context.cursor);
 
-    let origin_document = context.request.main_document();
-    let links: Vec<_> = goto_command_definition(&context)
+    let results = goto_command_definition(&context)
         .or_else(|| goto_document_definition(&context))
         .or_else(|| goto_entry_definition(&context))
         .or_else(|| goto_label_definition(&context))
-        .or_else(|| goto_string_definition(&context))?
+        .or_else(|| goto_glossary_entry_definition(&context))
+        .or_else(|| goto_counter_definition(&context))
+        .or_else(|| goto_string_definition(&context))
+        .or_else(|| goto_environment_definition(&context))?;
+
+    Some(convert_results(&context, results))
+}
+
+pub fn goto_type_definition(
+    request: FeatureRequest<GotoTypeDefinitionParams>,
+) -> Option<GotoTypeDefinitionResponse> {
+    let context = CursorContext::new(request);
+    log::debug!("[TypeDefinition] Cursor: {:?}", context.cursor);
+
+    let results = goto_theorem_definition(&context)?;
+    Some(convert_results(&context, results))
+}
+
+fn convert_results(
+    context: &CursorContext<GotoDefinitionParams>,
+    results: Vec<DefinitionResult>,
+) -> GotoDefinitionResponse {
+    let origin_document = context.request.main_document();
+    let links: Vec<_> = results
         .into_iter()
         .map(|result| {
             let origin_selection_range = Some(
@@ -38,14 +69,23 @@ pub fn goto_definition(
                     .line_col_lsp_range(result.origin_selection_range),
             );
 
-            let target_document = &context.request.workspace.documents_by_uri[&result.target_uri];
+            // Files of the TeX distribution are not part of the workspace.
+            let (target_range, target_selection_range) = context
+                .request
+                .workspace
+                .documents_by_uri
+                .get(&result.target_uri)
+                .map(|document| {
+                    (
+                        document.line_index.line_col_lsp_range(result.target_range),
+                        document
+                            .line_index
+                            .line_col_lsp_range(result.target_selection_range),
+                    )
+                })
+                .unwrap_or_default();
+
             let target_uri = result.target_uri.as_ref().clone();
-            let target_range = target_document
-                .line_index
-                .line_col_lsp_range(result.target_range);
-            let target_selection_range = target_document
-                .line_index
-                .line_col_lsp_range(result.target_selection_range);
 
             LocationLink {
                 origin_selection_range,
@@ -56,7 +96,7 @@ pub fn goto_definition(
         })
         .collect();
 
-    Some(GotoDefinitionResponse::Link(links))
+    GotoDefinitionResponse::Link(links)
 }
 
 #[derive(Debug, Clone)]
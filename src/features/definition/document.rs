@@ -1,9 +1,13 @@
 use std::sync::Arc;
 
 use lsp_types::GotoDefinitionParams;
-use rowan::TextRange;
+use rowan::{TextRange, TextSize};
 
-use crate::features::cursor::CursorContext;
+use crate::{
+    features::cursor::CursorContext,
+    syntax::latex::{self, ExplicitLinkKind},
+    Document, DocumentLanguage,
+};
 
 use super::DefinitionResult;
 
@@ -19,12 +23,23 @@ pub(super) fn goto_document_definition(
         .filter(|link| link.stem_range.contains_inclusive(context.offset))
     {
         for target in &include.targets {
-            if context
-                .request
-                .workspace
-                .documents_by_uri
-                .values()
-                .any(|document| document.uri.as_ref() == target.as_ref())
+            if let Some(document) = context.request.workspace.documents_by_uri.get(target) {
+                let range = TextRange::empty(find_content_start(document));
+                return Some(vec![DefinitionResult {
+                    origin_selection_range: include.stem_range,
+                    target_uri: Arc::clone(target),
+                    target_range: range,
+                    target_selection_range: range,
+                }]);
+            }
+        }
+
+        // Files that are included verbatim are not part of the workspace.
+        if include.kind == ExplicitLinkKind::Verbatim {
+            if let Some(target) = include
+                .targets
+                .iter()
+                .find(|target| target.to_file_path().map_or(false, |path| path.is_file()))
             {
                 return Some(vec![DefinitionResult {
                     origin_selection_range: include.stem_range,
@@ -33,8 +48,50 @@ pub(super) fn goto_document_definition(
                     target_selection_range: TextRange::default(),
                 }]);
             }
+
+            continue;
+        }
+
+        let extension = match include.kind {
+            ExplicitLinkKind::Package => "sty",
+            ExplicitLinkKind::Class => "cls",
+            ExplicitLinkKind::Latex
+            | ExplicitLinkKind::Bibtex
+            | ExplicitLinkKind::Verbatim
+            | ExplicitLinkKind::External => continue,
+        };
+
+        let resolver = &context.request.workspace.environment.resolver;
+        if let Some(target) = latex::resolve_distro_file(resolver, &include.stem, &[extension])
+            .filter(|uri| uri.to_file_path().map_or(false, |path| path.is_file()))
+        {
+            return Some(vec![DefinitionResult {
+                origin_selection_range: include.stem_range,
+                target_uri: Arc::new(target),
+                target_range: TextRange::default(),
+                target_selection_range: TextRange::default(),
+            }]);
         }
     }
 
     None
 }
+
+/// Finds the start of the first line that is neither blank nor a comment.
+///
+/// BibTeX does not have line comments, so only blank lines are skipped in BibTeX documents.
+fn find_content_start(document: &Document) -> TextSize {
+    let is_latex = document.data.language() == DocumentLanguage::Latex;
+    let mut offset = 0;
+    for line in document.text.split_inclusive('\n') {
+        let content = line.trim_start();
+        let is_comment = is_latex && content.starts_with('%');
+        if !content.is_empty() && !is_comment {
+            return TextSize::try_from(offset + line.len() - content.len()).unwrap_or_default();
+        }
+
+        offset += line.len();
+    }
+
+    TextSize::default()
+}
@@ -4,6 +4,7 @@ use lsp_types::GotoDefinitionParams;
 use rowan::ast::AstNode;
 
 use crate::{
+    citation,
     features::cursor::CursorContext,
     syntax::{
         bibtex::{self, HasName},
@@ -29,7 +30,7 @@ pub(super) fn goto_entry_definition(
 
     for document in context.request.workspace.documents_by_uri.values() {
         if let Some(data) = document.data.as_bibtex() {
-            for entry in bibtex::SyntaxNode::new_root(data.green.clone())
+            for entry in bibtex::SyntaxNode::new_root(data.green().clone())
                 .children()
                 .filter_map(bibtex::Entry::cast)
             {
@@ -45,5 +46,15 @@ pub(super) fn goto_entry_definition(
         }
     }
 
-    None
+    // Citations of a `thebibliography` environment or a bibliography outside of the workspace
+    // can still be resolved through the `\bibcite` entries of the `.aux` file.
+    let (document, citation) =
+        citation::find_resolved_citation(&context.request.workspace, word.text())?;
+
+    Some(vec![DefinitionResult {
+        origin_selection_range,
+        target_uri: Arc::clone(&document.uri),
+        target_selection_range: citation.range,
+        target_range: citation.full_range,
+    }])
 }
@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use lsp_types::GotoDefinitionParams;
+
+use crate::features::cursor::CursorContext;
+
+use super::DefinitionResult;
+
+pub(super) fn goto_environment_definition(
+    context: &CursorContext<GotoDefinitionParams>,
+) -> Option<Vec<DefinitionResult>> {
+    let (name_text, origin_selection_range) = context.find_environment_name()?;
+
+    for document in context.request.workspace.documents_by_uri.values() {
+        if let Some(data) = document.data.as_latex() {
+            if let Some(environment) = data
+                .extras
+                .user_environments
+                .iter()
+                .find(|environment| environment.name == name_text)
+            {
+                return Some(vec![DefinitionResult {
+                    origin_selection_range,
+                    target_uri: Arc::clone(&document.uri),
+                    target_range: environment.full_range,
+                    target_selection_range: environment.range,
+                }]);
+            }
+        }
+    }
+
+    None
+}
@@ -17,11 +17,15 @@ pub(super) fn goto_label_definition(
         if let Some(data) = document.data.as_latex() {
             let root = latex::SyntaxNode::new_root(data.green.clone());
             if let Some(definition) = find_label_definition(&root, &name_text) {
-                let target_selection_range = latex::small_range(&definition.name()?.key()?);
-                let target_range =
-                    render_label(&context.request.workspace, &name_text, Some(definition))
-                        .map(|label| label.range)
-                        .unwrap_or(target_selection_range);
+                let name_range = latex::small_range(&definition.name()?.key()?);
+                let label = render_label(&context.request.workspace, &name_text, Some(definition));
+
+                let target_range = label.as_ref().map_or(name_range, |label| label.range);
+
+                // The caption of a float describes the target better than the name of the label.
+                let target_selection_range = label
+                    .and_then(|label| label.caption_range)
+                    .unwrap_or(name_range);
 
                 return Some(vec![DefinitionResult {
                     origin_selection_range,
@@ -25,7 +25,7 @@ pub(super) fn goto_string_definition(
 
     let origin_selection_range = key.text_range();
 
-    for string in bibtex::SyntaxNode::new_root(data.green.clone())
+    for string in bibtex::SyntaxNode::new_root(data.green().clone())
         .children()
         .filter_map(bibtex::StringDef::cast)
     {
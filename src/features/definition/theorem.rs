@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use lsp_types::GotoDefinitionParams;
+use rowan::ast::AstNode;
+
+use crate::{features::cursor::CursorContext, syntax::latex};
+
+use super::DefinitionResult;
+
+pub(super) fn goto_theorem_definition(
+    context: &CursorContext<GotoDefinitionParams>,
+) -> Option<Vec<DefinitionResult>> {
+    let (name_text, origin_selection_range) = context.find_environment_name()?;
+
+    for document in context.request.workspace.documents_by_uri.values() {
+        if let Some(data) = document.data.as_latex() {
+            let root = latex::SyntaxNode::new_root(data.green.clone());
+
+            if let Some(result) = root
+                .descendants()
+                .filter_map(latex::TheoremDefinition::cast)
+                .find_map(|def| {
+                    let name = def.name()?.key()?;
+                    if name.to_string() != name_text {
+                        return None;
+                    }
+
+                    Some(DefinitionResult {
+                        origin_selection_range,
+                        target_uri: Arc::clone(&document.uri),
+                        target_range: latex::small_range(&def),
+                        target_selection_range: latex::small_range(&name),
+                    })
+                })
+            {
+                return Some(vec![result]);
+            }
+        }
+    }
+
+    None
+}
@@ -1,15 +1,20 @@
 use std::{path::PathBuf, process::Stdio, sync::Arc};
 
 use anyhow::Result;
-use lsp_types::{TextDocumentIdentifier, Url};
+use lsp_types::{Location, Position, Range, TextDocumentIdentifier, Url};
 
 use crate::Workspace;
 
+use super::{
+    count_words,
+    sort_bibliography::{sort_bibliography, BibtexSortKey},
+};
+
 pub fn execute_command(
     workspace: &Workspace,
     name: &str,
     args: Vec<serde_json::Value>,
-) -> Result<()> {
+) -> Result<serde_json::Value> {
     match name {
         "texlab.cleanAuxiliary" => {
             let params = args
@@ -27,10 +32,82 @@ pub fn execute_command(
 
             clean_output_files(workspace, CleanOptions::Artifacts, params)?;
         }
+        "texlab.wordCount" => {
+            let params = args
+                .into_iter()
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("texlab.wordCount requires one argument"))?;
+
+            let params: TextDocumentIdentifier = serde_json::from_value(params)?;
+            let count = count_words(workspace, &params.uri);
+            return Ok(serde_json::to_value(count)?);
+        }
+        "texlab.openRoot" => {
+            let params = args
+                .into_iter()
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("texlab.openRoot requires one argument"))?;
+
+            let params: TextDocumentIdentifier = serde_json::from_value(params)?;
+            let location = find_root_location(workspace, &params.uri)?;
+            return Ok(serde_json::to_value(location)?);
+        }
+        "texlab.sortBibliography" => {
+            let mut args = args.into_iter();
+            let params = args.next().ok_or_else(|| {
+                anyhow::anyhow!("texlab.sortBibliography requires at least one argument")
+            })?;
+
+            let params: TextDocumentIdentifier = serde_json::from_value(params)?;
+            let sort_key: BibtexSortKey = args
+                .next()
+                .map(serde_json::from_value)
+                .transpose()?
+                .unwrap_or_default();
+
+            let edit = sort_bibliography(workspace, &params.uri, sort_key)
+                .ok_or_else(|| anyhow::anyhow!("Not a BibTeX document: {}", params.uri))?;
+
+            return Ok(serde_json::to_value(edit)?);
+        }
         _ => anyhow::bail!("Unknown command: {}", name),
     }
 
-    Ok(())
+    Ok(serde_json::Value::Null)
+}
+
+/// Returns the beginning of the document that gets compiled when building the given document.
+///
+/// A document that is a root itself is preferred over the other roots that include it.
+fn find_root_location(workspace: &Workspace, uri: &Url) -> Result<Location> {
+    let document = workspace
+        .documents_by_uri
+        .get(uri)
+        .ok_or_else(|| anyhow::anyhow!("Unknown document: {}", uri))?;
+
+    let is_root = document.data.as_latex().map_or(false, |data| {
+        data.extras.has_document_environment
+            && !data
+                .extras
+                .explicit_links
+                .iter()
+                .filter_map(|link| link.as_component_name())
+                .any(|name| name == "subfiles.cls")
+    });
+
+    let root_uri = if is_root {
+        Arc::clone(&document.uri)
+    } else {
+        workspace
+            .find_parent(uri)
+            .map_or_else(|| Arc::clone(&document.uri), |parent| parent.uri)
+    };
+
+    let position = Position::new(0, 0);
+    Ok(Location::new(
+        root_uri.as_ref().clone(),
+        Range::new(position, position),
+    ))
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
@@ -57,8 +134,13 @@ fn clean_output_files(
             CleanOptions::Artifacts => "-C",
         };
 
-        std::process::Command::new("latexmk")
-            .arg(format!("-outdir={}", cx.output_dir.to_string_lossy()))
+        let mut command = std::process::Command::new("latexmk");
+        command.arg(format!("-outdir={}", cx.output_dir.to_string_lossy()));
+        if let Some(aux_dir) = &cx.aux_dir {
+            command.arg(format!("-auxdir={}", aux_dir.to_string_lossy()));
+        }
+
+        command
             .arg(flag)
             .arg(cx.input_file)
             .stdin(Stdio::null())
@@ -73,6 +155,7 @@ fn clean_output_files(
 struct BuildContext {
     input_file: PathBuf,
     output_dir: PathBuf,
+    aux_dir: Option<PathBuf>,
 }
 
 impl BuildContext {
@@ -83,21 +166,30 @@ impl BuildContext {
 
         let input_file = uri.to_file_path().ok()?;
         let options = &workspace.environment.options;
-        let current_dir = &workspace.environment.current_directory;
+        let current_dir = workspace.environment.base_directory(uri);
         let output_dir = match (
             options.root_directory.as_ref(),
             options.aux_directory.as_ref(),
+            options.output_directory.as_ref(),
         ) {
-            (_, Some(aux_dir)) => current_dir.join(aux_dir),
-            (Some(root_dir), None) => current_dir.join(root_dir),
-            (None, None) => input_file.parent()?.to_path_buf(),
+            (_, _, Some(output_dir)) => current_dir.join(output_dir),
+            (_, Some(aux_dir), None) => current_dir.join(aux_dir),
+            (Some(root_dir), None, None) => current_dir.join(root_dir),
+            (None, None, None) => input_file.parent()?.to_path_buf(),
         };
 
+        let aux_dir = options
+            .aux_directory
+            .as_ref()
+            .filter(|_| options.output_directory.is_some())
+            .map(|aux_dir| current_dir.join(aux_dir));
+
         log::info!("Output = {:#?}", output_dir);
 
         Some(Self {
             input_file,
             output_dir,
+            aux_dir,
         })
     }
 }
@@ -0,0 +1,348 @@
+use lsp_types::{Range, TextDocumentIdentifier};
+use rowan::{ast::AstNode, TextRange};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    syntax::latex::{self, HasBrack, HasCurly},
+    LineIndexExt,
+};
+
+use super::FeatureRequest;
+
+/// The maximum number of expansion rounds to stop recursive macros like `\newcommand{\foo}{\foo}`.
+const MAX_DEPTH: usize = 16;
+
+/// The maximum length of the expanded text to stop macros that duplicate themselves.
+const MAX_LENGTH: usize = 1 << 20;
+
+pub struct ExpandMacrosRequest;
+
+impl lsp_types::request::Request for ExpandMacrosRequest {
+    type Params = ExpandMacrosParams;
+
+    type Result = ExpandMacrosResult;
+
+    const METHOD: &'static str = "texlab/expandMacros";
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpandMacrosParams {
+    pub text_document: TextDocumentIdentifier,
+
+    /// The range of the text that is expanded.
+    pub range: Range,
+
+    /// The number of expansion rounds, defaults to a single level.
+    #[serde(default)]
+    pub depth: Option<usize>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpandMacrosResult {
+    /// The text of the range with the user-defined macros expanded.
+    pub text: String,
+
+    /// Whether the text still contains expandable macros after reaching the depth limit.
+    pub truncated: bool,
+}
+
+#[derive(Debug)]
+struct Macro {
+    argument_count: usize,
+    default_argument: Option<String>,
+    body: String,
+}
+
+/// Expands the macros defined with `\newcommand` and `\renewcommand` inside the requested range.
+pub fn expand_macros(request: FeatureRequest<ExpandMacrosParams>) -> ExpandMacrosResult {
+    let macros = find_macros(&request);
+    let document = request.main_document();
+    let range = document.line_index.offset_lsp_range(request.params.range);
+    let mut text = document.text[std::ops::Range::<usize>::from(range)].to_string();
+
+    let depth = request.params.depth.unwrap_or(1).min(MAX_DEPTH);
+    for _ in 0..depth {
+        match expand_once(&text, &macros) {
+            Some(new_text) => text = new_text,
+            None => {
+                return ExpandMacrosResult {
+                    text,
+                    truncated: false,
+                }
+            }
+        };
+
+        if text.len() > MAX_LENGTH {
+            break;
+        }
+    }
+
+    let truncated = contains_macro(&text, &macros);
+    ExpandMacrosResult { text, truncated }
+}
+
+fn find_macros(request: &FeatureRequest<ExpandMacrosParams>) -> FxHashMap<String, Macro> {
+    let mut macros = FxHashMap::default();
+    for data in request
+        .workspace
+        .documents_by_uri
+        .values()
+        .filter_map(|document| document.data.as_latex())
+    {
+        let root = latex::SyntaxNode::new_root(data.green.clone());
+        for definition in root
+            .descendants()
+            .filter(|node| node.kind() == latex::COMMAND_DEFINITION)
+            .filter_map(latex::CommandDefinition::cast)
+        {
+            let name = match definition.name().and_then(|name| name.command()) {
+                Some(name) => name.text()[1..].to_string(),
+                None => continue,
+            };
+
+            let body = match definition.implementation() {
+                Some(group) => inner_text(group.syntax(), group.left_curly(), group.right_curly()),
+                None => continue,
+            };
+
+            let argument_count = definition
+                .argument_count()
+                .and_then(|count| count.key())
+                .and_then(|count| count.to_string().parse().ok())
+                .unwrap_or(0);
+
+            let default_argument = definition
+                .default_argument()
+                .map(|group| inner_text(group.syntax(), group.left_brack(), group.right_brack()));
+
+            macros.insert(
+                name,
+                Macro {
+                    argument_count,
+                    default_argument,
+                    body,
+                },
+            );
+        }
+    }
+
+    macros
+}
+
+/// Returns the text of a group without the surrounding delimiters and the trailing trivia.
+fn inner_text(
+    group: &latex::SyntaxNode,
+    left: Option<latex::SyntaxToken>,
+    right: Option<latex::SyntaxToken>,
+) -> String {
+    let range = group.text_range();
+    let start = left.map_or(range.start(), |token| token.text_range().end());
+    let end = right.map_or(range.end(), |token| token.text_range().start());
+    let text = group.text().to_string();
+    text[std::ops::Range::<usize>::from(TextRange::new(start, end) - range.start())].to_string()
+}
+
+/// Returns the length of the name of the command at the beginning of the text without the backslash.
+///
+/// Returns zero for control symbols like `\%`.
+fn command_name_len(text: &str) -> usize {
+    text[1..]
+        .find(|c: char| !c.is_ascii_alphabetic() && c != '@')
+        .unwrap_or(text.len() - 1)
+}
+
+/// Returns `true` if the text outside of comments still contains one of the macros.
+fn contains_macro(text: &str, macros: &FxHashMap<String, Macro>) -> bool {
+    let mut rest = text;
+    while let Some(index) = rest.find(['\\', '%']) {
+        rest = &rest[index..];
+        if rest.starts_with('%') {
+            rest = &rest[rest.find('\n').unwrap_or(rest.len())..];
+            continue;
+        }
+
+        let name_len = command_name_len(rest);
+        if name_len == 0 {
+            let end = rest[1..].chars().next().map_or(1, |c| 1 + c.len_utf8());
+            rest = &rest[end..];
+            continue;
+        }
+
+        if macros.contains_key(&rest[1..=name_len]) {
+            return true;
+        }
+
+        rest = &rest[1 + name_len..];
+    }
+
+    false
+}
+
+/// Replaces every macro of the text with its body once. Returns `None` if the text does not contain any macros.
+///
+/// The remaining macros are not expanded once the result exceeds `MAX_LENGTH`.
+fn expand_once(text: &str, macros: &FxHashMap<String, Macro>) -> Option<String> {
+    let mut result = String::new();
+    let mut has_expanded = false;
+    let mut rest = text;
+    while let Some(index) = rest.find(['\\', '%']) {
+        result.push_str(&rest[..index]);
+        rest = &rest[index..];
+
+        // Comments are copied verbatim
+        if rest.starts_with('%') {
+            let end = rest.find('\n').unwrap_or(rest.len());
+            result.push_str(&rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+
+        let name_len = command_name_len(rest);
+        if name_len == 0 {
+            let end = rest[1..].chars().next().map_or(1, |c| 1 + c.len_utf8());
+            result.push_str(&rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+
+        let name = &rest[1..=name_len];
+        let command = &rest[..=name_len];
+        rest = &rest[1 + name_len..];
+
+        let macro_ = match macros.get(name) {
+            Some(macro_) => macro_,
+            None => {
+                result.push_str(command);
+                continue;
+            }
+        };
+
+        let mut arguments = Vec::new();
+        let mut remaining = rest;
+        if let Some(default_argument) = &macro_.default_argument {
+            match read_optional_argument(remaining) {
+                Some((argument, next)) => {
+                    arguments.push(argument);
+                    remaining = next;
+                }
+                None => arguments.push(default_argument.as_str()),
+            };
+        }
+
+        while arguments.len() < macro_.argument_count {
+            match read_argument(remaining) {
+                Some((argument, next)) => {
+                    arguments.push(argument);
+                    remaining = next;
+                }
+                None => break,
+            };
+        }
+
+        if arguments.len() < macro_.argument_count {
+            result.push_str(command);
+            continue;
+        }
+
+        result.push_str(&substitute(&macro_.body, &arguments));
+        rest = remaining;
+        has_expanded = true;
+        if result.len() > MAX_LENGTH {
+            break;
+        }
+    }
+
+    result.push_str(rest);
+    Some(result).filter(|_| has_expanded)
+}
+
+fn read_optional_argument(text: &str) -> Option<(&str, &str)> {
+    let text = text.trim_start();
+    if !text.starts_with('[') {
+        return None;
+    }
+
+    let end = find_group_end(text, '[', ']')?;
+    Some((&text[1..end], &text[end + 1..]))
+}
+
+/// Reads a group in curly braces or a single token.
+fn read_argument(text: &str) -> Option<(&str, &str)> {
+    let text = text.trim_start();
+    let c = text.chars().next()?;
+    match c {
+        '{' => {
+            let end = find_group_end(text, '{', '}')?;
+            Some((&text[1..end], &text[end + 1..]))
+        }
+        '\\' => {
+            let name_len = command_name_len(text);
+            let end = if name_len == 0 {
+                1 + text[1..].chars().next()?.len_utf8()
+            } else {
+                1 + name_len
+            };
+
+            Some((&text[..end], &text[end..]))
+        }
+        '}' => None,
+        _ => Some((&text[..c.len_utf8()], &text[c.len_utf8()..])),
+    }
+}
+
+fn find_group_end(text: &str, open: char, close: char) -> Option<usize> {
+    let mut level = 0;
+    let mut is_escaped = false;
+    for (index, c) in text.char_indices() {
+        if is_escaped {
+            is_escaped = false;
+        } else if c == '\\' {
+            is_escaped = true;
+        } else if c == open {
+            level += 1;
+        } else if c == close {
+            level -= 1;
+            if level == 0 {
+                return Some(index);
+            }
+        }
+    }
+
+    None
+}
+
+/// Replaces the placeholders `#1` to `#9` with the arguments and `##` with `#`.
+fn substitute(body: &str, arguments: &[&str]) -> String {
+    let mut result = String::new();
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '#' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.peek().copied() {
+            Some('#') => {
+                chars.next();
+                result.push('#');
+            }
+            Some(digit @ '1'..='9') => {
+                chars.next();
+                let index = digit as usize - '1' as usize;
+                match arguments.get(index) {
+                    Some(argument) => result.push_str(argument),
+                    None => {
+                        result.push('#');
+                        result.push(digit);
+                    }
+                };
+            }
+            _ => result.push('#'),
+        };
+    }
+
+    result
+}
@@ -1,5 +1,5 @@
 use lsp_types::{FoldingRange, FoldingRangeKind, FoldingRangeParams, Range};
-use rowan::ast::AstNode;
+use rowan::{ast::AstNode, TextRange, TextSize};
 
 use crate::{
     syntax::{bibtex, latex},
@@ -13,12 +13,10 @@ pub fn find_foldings(request: FeatureRequest<FoldingRangeParams>) -> Vec<Folding
     let main_document = request.main_document();
     match &main_document.data {
         DocumentData::Latex(data) => {
-            for node in latex::SyntaxNode::new_root(data.green.clone()).descendants() {
+            let root = latex::SyntaxNode::new_root(data.green.clone());
+            for node in root.descendants() {
                 if let Some(folding) = latex::Environment::cast(node.clone())
                     .map(|node| latex::small_range(&node))
-                    .or_else(|| {
-                        latex::Section::cast(node.clone()).map(|node| latex::small_range(&node))
-                    })
                     .or_else(|| latex::EnumItem::cast(node).map(|node| latex::small_range(&node)))
                     .map(|node| main_document.line_index.line_col_lsp_range(node))
                     .map(create_range)
@@ -26,9 +24,14 @@ pub fn find_foldings(request: FeatureRequest<FoldingRangeParams>) -> Vec<Folding
                     foldings.push(folding);
                 }
             }
+
+            for range in find_section_ranges(&root) {
+                let range = main_document.line_index.line_col_lsp_range(range);
+                foldings.push(create_range(range));
+            }
         }
         DocumentData::Bibtex(data) => {
-            for node in bibtex::SyntaxNode::new_root(data.green.clone()).descendants() {
+            for node in bibtex::SyntaxNode::new_root(data.green().clone()).descendants() {
                 if matches!(
                     node.kind(),
                     bibtex::PREAMBLE | bibtex::STRING | bibtex::ENTRY
@@ -46,6 +49,57 @@ pub fn find_foldings(request: FeatureRequest<FoldingRangeParams>) -> Vec<Folding
     foldings
 }
 
+/// Computes the extent of each sectioning command in document order.
+///
+/// A section ends before the next sectioning command of the same or a higher level
+/// but never leaves the group or environment that contains it.
+/// If the next sectioning command is nested inside an environment of the section,
+/// the section ends before that environment.
+fn find_section_ranges(root: &latex::SyntaxNode) -> Vec<TextRange> {
+    let sections: Vec<_> = root
+        .descendants()
+        .filter_map(latex::Section::cast)
+        .collect();
+    let mut ranges = Vec::new();
+    for (i, section) in sections.iter().enumerate() {
+        let range = latex::small_range(section);
+        let end = sections[i + 1..]
+            .iter()
+            .find(|next| next.level() <= section.level())
+            .and_then(|next| {
+                next.syntax()
+                    .ancestors()
+                    .take_while(|node| node != section.syntax())
+                    .last()
+                    .filter(|node| node.parent().as_ref() == Some(section.syntax()))
+            })
+            .and_then(|child| find_content_end(root, child.text_range().start()))
+            .unwrap_or_else(|| range.end());
+
+        ranges.push(TextRange::new(range.start(), end.max(range.start())));
+    }
+
+    ranges
+}
+
+/// Finds the end of the last token before the given offset that is not trivia.
+fn find_content_end(root: &latex::SyntaxNode, offset: TextSize) -> Option<TextSize> {
+    let mut token = root.token_at_offset(offset).left_biased();
+    while let Some(current) = token {
+        if !matches!(
+            current.kind(),
+            latex::WHITESPACE | latex::LINE_BREAK | latex::COMMENT
+        ) && current.text_range().end() <= offset
+        {
+            return Some(current.text_range().end());
+        }
+
+        token = current.prev_token();
+    }
+
+    None
+}
+
 fn create_range(range: Range) -> FoldingRange {
     FoldingRange {
         start_line: range.start.line,
@@ -1,26 +1,67 @@
 mod bibtex_internal;
+mod bibtex_keys;
 mod latexindent;
 
+use std::fmt;
+
 use lsp_types::{DocumentFormattingParams, TextEdit};
+use rustc_hash::FxHashMap;
 
-use crate::{BibtexFormatter, LatexFormatter};
+use crate::{BibtexFormatter, DocumentLanguage, LatexFormatter};
 
 use self::{bibtex_internal::format_bibtex_internal, latexindent::format_with_latexindent};
 
+pub use self::bibtex_keys::normalize_bibtex_keys;
+
 use super::FeatureRequest;
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormattingError {
+    LatexindentNotFound {
+        executable: String,
+        language: DocumentLanguage,
+    },
+}
+
+impl fmt::Display for FormattingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LatexindentNotFound {
+                executable,
+                language,
+            } => {
+                // The built-in LaTeX formatter does not change the document yet
+                let alternative = if *language == DocumentLanguage::Bibtex {
+                    "set texlab.bibtexFormatter to \"texlab\" to use the built-in formatter"
+                } else {
+                    "set texlab.latexFormatter to \"texlab\" to turn off formatting"
+                };
+
+                write!(
+                    f,
+                    "Formatting requires latexindent but \"{}\" could not be found. \
+                    Install latexindent, change texlab.latexindent.executable or {}.",
+                    executable, alternative
+                )
+            }
+        }
+    }
+}
+
 pub fn format_source_code(
     request: FeatureRequest<DocumentFormattingParams>,
-) -> Option<Vec<TextEdit>> {
+) -> Result<Option<Vec<TextEdit>>, FormattingError> {
     let mut edits = None;
     if request.workspace.environment.options.bibtex_formatter == BibtexFormatter::Texlab {
-        edits = edits.or_else(|| format_bibtex_internal(&request));
+        edits = edits.or_else(|| format_bibtex_internal(&request, &FxHashMap::default()));
     }
 
     if request.workspace.environment.options.latex_formatter == LatexFormatter::Texlab {
         edits = edits.or_else(|| Some(vec![]));
     }
 
-    edits = edits.or_else(|| format_with_latexindent(&request));
-    edits
+    match edits {
+        Some(edits) => Ok(Some(edits)),
+        None => format_with_latexindent(&request),
+    }
 }
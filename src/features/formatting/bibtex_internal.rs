@@ -1,5 +1,6 @@
 use lsp_types::{DocumentFormattingParams, TextEdit};
 use rowan::{ast::AstNode, NodeOrToken};
+use rustc_hash::FxHashMap;
 
 use crate::{
     features::FeatureRequest,
@@ -9,6 +10,7 @@ use crate::{
 
 pub fn format_bibtex_internal(
     request: &FeatureRequest<DocumentFormattingParams>,
+    keys: &FxHashMap<String, String>,
 ) -> Option<Vec<TextEdit>> {
     let mut indent = String::new();
     if request.params.options.insert_spaces {
@@ -37,7 +39,7 @@ pub fn format_bibtex_internal(
     let data = document.data.as_bibtex()?;
     let mut edits = Vec::new();
 
-    for node in bibtex::SyntaxNode::new_root(data.green.clone())
+    for node in bibtex::SyntaxNode::new_root(data.green().clone())
         .children()
         .filter(|node| {
             matches!(
@@ -52,6 +54,7 @@ pub fn format_bibtex_internal(
             indent.clone(),
             request.params.options.tab_size,
             line_length,
+            keys,
             &document.line_index,
         );
 
@@ -71,17 +74,25 @@ struct Formatter<'a> {
     line_length: usize,
     output: String,
     align: Vec<usize>,
+    keys: &'a FxHashMap<String, String>,
     line_index: &'a LineIndex,
 }
 
 impl<'a> Formatter<'a> {
-    fn new(indent: String, tab_size: u32, line_length: usize, line_index: &'a LineIndex) -> Self {
+    fn new(
+        indent: String,
+        tab_size: u32,
+        line_length: usize,
+        keys: &'a FxHashMap<String, String>,
+        line_index: &'a LineIndex,
+    ) -> Self {
         Self {
             indent,
             tab_size,
             line_length,
             output: String::new(),
             align: Vec::new(),
+            keys,
             line_index,
         }
     }
@@ -140,7 +151,9 @@ impl<'a> Formatter<'a> {
                 self.visit_token_lowercase(&entry.type_token().unwrap());
                 self.output.push('{');
                 if let Some(key) = entry.name_token() {
-                    self.output.push_str(&key.to_string());
+                    let key = key.text();
+                    self.output
+                        .push_str(self.keys.get(key).map_or(key, String::as_str));
                     self.output.push(',');
                     self.output.push('\n');
                     for field in entry.fields() {
@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+
+use chrono::Datelike;
+use lsp_types::{DocumentFormattingParams, TextEdit, WorkspaceEdit};
+use rowan::ast::AstNode;
+use rustc_hash::{FxHashMap, FxHashSet};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{
+    citation::field::{author::AuthorFieldData, date::DateFieldData},
+    features::FeatureRequest,
+    syntax::{
+        bibtex::{self, HasName, HasValue},
+        latex,
+    },
+    BibtexFormatter, DocumentData, LineIndexExt,
+};
+
+use super::bibtex_internal::format_bibtex_internal;
+
+pub fn normalize_bibtex_keys(
+    request: &FeatureRequest<DocumentFormattingParams>,
+) -> Option<WorkspaceEdit> {
+    let environment = &request.workspace.environment;
+    if !environment.options.normalize_bibtex_keys
+        || environment
+            .client_capabilities
+            .workspace
+            .as_ref()
+            .and_then(|cap| cap.apply_edit)
+            != Some(true)
+    {
+        return None;
+    }
+
+    let main_document = request.main_document();
+    let data = main_document.data.as_bibtex()?;
+    let root = bibtex::SyntaxNode::new_root(data.green().clone());
+    let keys = find_normalized_keys(&root);
+    if keys.is_empty() {
+        return None;
+    }
+
+    let mut changes = HashMap::new();
+    let bibtex_edits = if environment.options.bibtex_formatter == BibtexFormatter::Texlab {
+        format_bibtex_internal(request, &keys)?
+    } else {
+        root.descendants()
+            .filter_map(bibtex::Entry::cast)
+            .filter_map(|entry| entry.name_token())
+            .filter_map(|key| {
+                let new_key = keys.get(key.text())?;
+                let range = main_document
+                    .line_index
+                    .line_col_lsp_range(key.text_range());
+                Some(TextEdit::new(range, new_key.clone()))
+            })
+            .collect()
+    };
+
+    changes.insert(main_document.uri.as_ref().clone(), bibtex_edits);
+
+    // The workspace of the request only contains the compilation unit of the BibTeX file.
+    for document in request.workspace.documents_by_uri.values() {
+        if let DocumentData::Latex(data) = &document.data {
+            let root = latex::SyntaxNode::new_root(data.green.clone());
+            let edits: Vec<_> = root
+                .descendants()
+                .filter_map(latex::Citation::cast)
+                .flat_map(|citation| citation.key_lists())
+                .flat_map(|keys| keys.keys())
+                .filter_map(|key| {
+                    let new_key = keys.get(&key.to_string())?;
+                    let range = document
+                        .line_index
+                        .line_col_lsp_range(latex::small_range(&key));
+                    Some(TextEdit::new(range, new_key.clone()))
+                })
+                .collect();
+
+            if !edits.is_empty() {
+                changes.insert(document.uri.as_ref().clone(), edits);
+            }
+        }
+    }
+
+    Some(WorkspaceEdit::new(changes))
+}
+
+fn find_normalized_keys(root: &bibtex::SyntaxNode) -> FxHashMap<String, String> {
+    let entries: Vec<_> = root
+        .children()
+        .filter_map(bibtex::Entry::cast)
+        .filter_map(|entry| {
+            let key = entry.name_token()?.text().to_string();
+            let slug = slugify_entry(&entry);
+            Some((key, slug))
+        })
+        .collect();
+
+    let mut used_keys: FxHashSet<String> = entries
+        .iter()
+        .filter(|(_, slug)| slug.is_none())
+        .map(|(key, _)| key.clone())
+        .collect();
+
+    let mut keys = FxHashMap::default();
+    for (key, slug) in entries {
+        let slug = match slug {
+            Some(slug) => slug,
+            None => continue,
+        };
+
+        let new_key = std::iter::once(String::new())
+            .chain(('b'..='z').map(String::from))
+            .map(|suffix| format!("{}{}", slug, suffix))
+            .find(|new_key| !used_keys.contains(new_key));
+
+        match new_key {
+            Some(new_key) => {
+                used_keys.insert(new_key.clone());
+                if new_key != key {
+                    keys.insert(key, new_key);
+                }
+            }
+            None => {
+                used_keys.insert(key);
+            }
+        }
+    }
+
+    keys
+}
+
+fn slugify_entry(entry: &bibtex::Entry) -> Option<String> {
+    let mut author = None;
+    let mut year = None;
+    for field in entry.fields() {
+        let (name, value) = match (field.name_token(), field.value()) {
+            (Some(name), Some(value)) => (name, value),
+            _ => continue,
+        };
+
+        match name.text().to_lowercase().as_str() {
+            "author" => {
+                author = AuthorFieldData::parse(&value)
+                    .and_then(|data| data.authors.into_iter().next())
+                    .map(|name| slugify(name.surname()));
+            }
+            "year" | "date" if year.is_none() => {
+                year = DateFieldData::parse(&value).and_then(|data| match data {
+                    DateFieldData::Year(year) => Some(year),
+                    DateFieldData::Date(date) => Some(date.year()),
+                    DateFieldData::Month(_) | DateFieldData::Other(_) => None,
+                });
+            }
+            _ => {}
+        };
+    }
+
+    let author = author.filter(|author| !author.is_empty())?;
+    match year {
+        Some(year) => Some(format!("{}_{}", author, year)),
+        None => Some(author),
+    }
+}
+
+fn slugify(text: &str) -> String {
+    text.nfkd()
+        .filter(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_lowercase()
+}
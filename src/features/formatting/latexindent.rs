@@ -1,5 +1,5 @@
 use std::{
-    fs,
+    fs, io,
     process::{Command, Stdio},
 };
 
@@ -9,10 +9,20 @@ use tempfile::tempdir;
 
 use crate::{features::FeatureRequest, DocumentLanguage, LineIndexExt};
 
+use super::FormattingError;
+
+/// Formats the document with `latexindent`.
+///
+/// Returns an error if the executable cannot be found so that the user can be notified
+/// instead of silently ignoring the request.
 pub fn format_with_latexindent(
     request: &FeatureRequest<DocumentFormattingParams>,
-) -> Option<Vec<TextEdit>> {
-    let directory = tempdir().ok()?;
+) -> Result<Option<Vec<TextEdit>>, FormattingError> {
+    let directory = match tempdir() {
+        Ok(directory) => directory,
+        Err(_) => return Ok(None),
+    };
+
     let document = request.main_document();
 
     let options = &request.workspace.environment.options;
@@ -61,16 +71,24 @@ pub fn format_with_latexindent(
         "file.tex"
     };
 
-    fs::write(directory.path().join(name), document.text.as_str()).ok()?;
+    if fs::write(directory.path().join(name), document.text.as_str()).is_err() {
+        return Ok(None);
+    }
 
-    let mut args = Vec::new();
+    let mut args: Vec<_> = options
+        .latexindent
+        .args
+        .iter()
+        .map(String::as_str)
+        .collect();
     if modify_line_breaks {
         args.push("--modifylinebreaks");
     }
     args.push(&local);
     args.push(name);
 
-    let output = Command::new("latexindent")
+    let executable = &options.latexindent.executable.0;
+    let output = match Command::new(executable)
         .args(&args)
         .current_dir(current_dir)
         .stdin(Stdio::null())
@@ -78,17 +96,32 @@ pub fn format_with_latexindent(
         .stderr(Stdio::null())
         .current_dir(directory.path())
         .output()
-        .ok()?;
+    {
+        Ok(output) => output,
+        Err(why) if why.kind() == io::ErrorKind::NotFound => {
+            return Err(FormattingError::LatexindentNotFound {
+                executable: executable.clone(),
+                language: document.data.language(),
+            });
+        }
+        Err(why) => {
+            log::warn!("Failed to run {}: {}", executable, why);
+            return Ok(None);
+        }
+    };
 
     let new_text = String::from_utf8_lossy(&output.stdout).into_owned();
     if new_text.is_empty() {
-        None
+        log::warn!("{} exited with {} and no output", executable, output.status);
+        Ok(None)
+    } else if new_text == document.text.as_str() {
+        Ok(Some(Vec::new()))
     } else {
-        Some(vec![TextEdit {
+        Ok(Some(vec![TextEdit {
             range: document
                 .line_index
                 .line_col_lsp_range(TextRange::new(0.into(), document.text.text_len())),
             new_text,
-        }])
+        }]))
     }
 }
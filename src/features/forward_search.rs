@@ -1,7 +1,7 @@
 use std::{
     io,
-    path::Path,
-    process::{Command, Stdio},
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus, Stdio},
 };
 
 use log::error;
@@ -21,22 +21,56 @@ pub enum ForwardSearchStatus {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ForwardSearchResult {
     pub status: ForwardSearchStatus,
+
+    /// Explains why the forward search did not succeed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+
+    /// The PDF file that was passed to the previewer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pdf_path: Option<PathBuf>,
+
+    /// The one-based line number that was passed to the previewer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+
+    /// The process ID of the spawned previewer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+}
+
+impl ForwardSearchResult {
+    #[must_use]
+    pub fn new(status: ForwardSearchStatus, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: Some(message.into()),
+            pdf_path: None,
+            line: None,
+            pid: None,
+        }
+    }
 }
 
 pub fn execute_forward_search(
     request: FeatureRequest<TextDocumentPositionParams>,
-) -> Option<ForwardSearchResult> {
+) -> ForwardSearchResult {
     let options = &request.workspace.environment.options.forward_search;
 
-    if options.executable.is_none() || options.args.is_none() {
-        return Some(ForwardSearchResult {
-            status: ForwardSearchStatus::UNCONFIGURED,
-        });
-    }
+    let (executable, args) = match (&options.executable, &options.args) {
+        (Some(executable), Some(args)) => (executable, args),
+        _ => {
+            return ForwardSearchResult::new(
+                ForwardSearchStatus::UNCONFIGURED,
+                "The previewer is not configured",
+            );
+        }
+    };
 
-    let root_document = request
+    let root_document = match request
         .workspace
         .documents_by_uri
         .values()
@@ -53,37 +87,76 @@ pub fn execute_forward_search(
                 false
             }
         })
-        .filter(|document| document.uri.scheme() == "file")?;
+        .filter(|document| document.uri.scheme() == "file")
+    {
+        Some(document) => document,
+        None => {
+            return ForwardSearchResult::new(
+                ForwardSearchStatus::ERROR,
+                "Unable to find a root document on the file system",
+            );
+        }
+    };
 
-    let data = root_document.data.as_latex()?;
-    let pdf_path = data
-        .extras
-        .implicit_links
-        .pdf
-        .iter()
-        .filter_map(|uri| uri.to_file_path().ok())
-        .find(|path| path.exists())?;
+    let pdf_path = match root_document.data.as_latex().and_then(|data| {
+        data.extras
+            .implicit_links
+            .pdf
+            .iter()
+            .filter_map(|uri| uri.to_file_path().ok())
+            .find(|path| path.exists())
+    }) {
+        Some(pdf_path) => pdf_path,
+        None => {
+            return ForwardSearchResult::new(
+                ForwardSearchStatus::ERROR,
+                format!("Unable to find the PDF file of {}", root_document.uri),
+            );
+        }
+    };
 
-    let tex_path = request.main_document().uri.to_file_path().ok()?;
+    let tex_path = match request.main_document().uri.to_file_path() {
+        Ok(tex_path) => tex_path,
+        Err(()) => {
+            return ForwardSearchResult::new(
+                ForwardSearchStatus::ERROR,
+                format!("{} is not a local file", request.main_document().uri),
+            );
+        }
+    };
 
-    let args: Vec<String> = options
-        .args
-        .as_ref()
-        .unwrap()
+    let line = request.params.position.line;
+    let args: Vec<String> = args
         .iter()
-        .flat_map(|arg| {
-            replace_placeholder(&tex_path, &pdf_path, request.params.position.line, arg)
-        })
+        .flat_map(|arg| replace_placeholder(&tex_path, &pdf_path, line, arg))
         .collect();
 
-    let status = match run_process(options.executable.as_ref().unwrap(), args) {
-        Ok(()) => ForwardSearchStatus::SUCCESS,
+    let (status, message, pid) = match run_process(executable, args) {
+        Ok((pid, exit_status)) if exit_status.success() => {
+            (ForwardSearchStatus::SUCCESS, None, Some(pid))
+        }
+        Ok((pid, exit_status)) => (
+            ForwardSearchStatus::ERROR,
+            Some(format!("The previewer exited with {}", exit_status)),
+            Some(pid),
+        ),
         Err(why) => {
             error!("Unable to execute forward search: {}", why);
-            ForwardSearchStatus::FAILURE
+            (
+                ForwardSearchStatus::FAILURE,
+                Some(format!("Unable to start {}: {}", executable, why)),
+                None,
+            )
         }
     };
-    Some(ForwardSearchResult { status })
+
+    ForwardSearchResult {
+        status,
+        message,
+        pdf_path: Some(pdf_path),
+        line: Some(line + 1),
+        pid,
+    }
 }
 
 /// Iterate overs chunks of a string. Either returns a slice of the
@@ -139,7 +212,7 @@ impl<'a> Iterator for PlaceHolderIterator<'a> {
     type Item = &'a str;
 
     fn next(&mut self) -> Option<Self::Item> {
-        return if self.remainder.is_empty() {
+        if self.remainder.is_empty() {
             None
         } else if self.remainder.starts_with('%') {
             self.yield_placeholder()
@@ -149,7 +222,7 @@ impl<'a> Iterator for PlaceHolderIterator<'a> {
                 None => self.yield_remainder(),
                 Some(end) => self.yield_str(end),
             }
-        };
+        }
     }
 }
 
@@ -169,13 +242,16 @@ fn replace_placeholder(
     Some(result)
 }
 
-fn run_process(executable: &str, args: Vec<String>) -> io::Result<()> {
+/// Runs the previewer and returns its process ID together with its exit status.
+fn run_process(executable: &str, args: Vec<String>) -> io::Result<(u32, ExitStatus)> {
     log::debug!("Executing forward search: {} {:?}", executable, args);
-    Command::new(executable)
+    let mut child = Command::new(executable)
         .args(args)
         .stdin(Stdio::null())
         .stdout(Stdio::null())
         .stderr(Stdio::null())
-        .status()?;
-    Ok(())
+        .spawn()?;
+
+    let pid = child.id();
+    Ok((pid, child.wait()?))
 }
@@ -1,9 +1,13 @@
 mod citation;
+mod command;
 mod component;
 mod entry_type;
 mod field;
+mod glossary;
+mod include;
 mod label;
 mod string_ref;
+mod tikz_library;
 
 use lsp_types::{Hover, HoverContents, HoverParams, MarkupContent, MarkupKind};
 use rowan::TextRange;
@@ -14,8 +18,10 @@ use crate::{
 };
 
 use self::{
-    component::find_component_hover, entry_type::find_entry_type_hover, field::find_field_hover,
-    label::find_label_hover, string_ref::find_string_reference_hover,
+    command::find_command_hover, component::find_component_hover,
+    entry_type::find_entry_type_hover, field::find_field_hover,
+    glossary::find_glossary_entry_hover, include::find_include_hover, label::find_label_hover,
+    string_ref::find_string_reference_hover, tikz_library::find_tikz_library_hover,
 };
 
 use super::FeatureRequest;
@@ -26,6 +32,10 @@ pub fn find_hover(request: FeatureRequest<HoverParams>) -> Option<Hover> {
     let result = find_label_hover(&context)
         .or_else(|| find_citation_hover(&context))
         .or_else(|| find_component_hover(&context))
+        .or_else(|| find_include_hover(&context))
+        .or_else(|| find_glossary_entry_hover(&context))
+        .or_else(|| find_command_hover(&context))
+        .or_else(|| find_tikz_library_hover(&context))
         .or_else(|| find_string_reference_hover(&context))
         .or_else(|| find_field_hover(&context))
         .or_else(|| find_entry_type_hover(&context))?;
@@ -11,18 +11,23 @@ pub(super) fn find_citation_hover(context: &CursorContext<HoverParams>) -> Optio
         .or_else(|| context.find_citation_key_command())
         .or_else(|| context.find_entry_key())?;
 
-    let value = context
-        .request
-        .workspace
+    let workspace = &context.request.workspace;
+    let style = citation::find_style(workspace);
+    let number = citation::find_resolved_citation(workspace, &key)
+        .map(|(_, citation)| citation.number.as_str());
+
+    // Without a bibliography, the label of the `.aux` file is shown on its own.
+    let value = workspace
         .documents_by_uri
         .values()
         .find_map(|document| {
             let data = document.data.as_bibtex()?;
-            let root = bibtex::SyntaxNode::new_root(data.green.clone());
+            let root = bibtex::SyntaxNode::new_root(data.green().clone());
             let root = bibtex::Root::cast(root)?;
             let entry = root.find_entry(&key)?;
-            citation::render(&entry)
-        })?;
+            citation::render_with_number(workspace, &entry, style, number)
+        })
+        .or_else(|| Some(format!("[{}]", number?)))?;
 
     Some(HoverResult {
         range,
@@ -0,0 +1,36 @@
+use lsp_types::{HoverParams, MarkupKind};
+
+use crate::{features::cursor::CursorContext, find_command_definition, ClientCapabilitiesExt};
+
+use super::HoverResult;
+
+pub(super) fn find_command_hover(context: &CursorContext<HoverParams>) -> Option<HoverResult> {
+    let name = context
+        .cursor
+        .as_latex()
+        .filter(|token| token.kind().is_command_name())?;
+
+    let (document, command) = find_command_definition(
+        &context.request.workspace,
+        &context.request.uri,
+        name.text(),
+        name.text_range().start(),
+    )?;
+
+    let definition = &document.text[command.full_range];
+    let environment = &context.request.workspace.environment;
+    let (value, value_kind) = if environment.client_capabilities.has_hover_markdown_support() {
+        (
+            format!("```latex\n{}\n```", definition),
+            MarkupKind::Markdown,
+        )
+    } else {
+        (definition.to_string(), MarkupKind::PlainText)
+    };
+
+    Some(HoverResult {
+        range: name.text_range(),
+        value,
+        value_kind,
+    })
+}
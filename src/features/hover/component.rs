@@ -1,21 +1,36 @@
 use lsp_types::{HoverParams, MarkupKind};
 
-use crate::{component_db::COMPONENT_DATABASE, features::cursor::CursorContext, syntax::latex};
+use crate::{
+    component_db::COMPONENT_DATABASE, features::cursor::CursorContext, syntax::latex,
+    ClientCapabilitiesExt,
+};
 
 use super::HoverResult;
 
 pub(super) fn find_component_hover(context: &CursorContext<HoverParams>) -> Option<HoverResult> {
     let data = context.request.main_document().data.as_latex()?;
+    let kind = if context
+        .request
+        .workspace
+        .environment
+        .client_capabilities
+        .has_hover_markdown_support()
+    {
+        MarkupKind::Markdown
+    } else {
+        MarkupKind::PlainText
+    };
+
     for link in &data.extras.explicit_links {
         if matches!(
             link.kind,
             latex::ExplicitLinkKind::Package | latex::ExplicitLinkKind::Class
         ) && link.stem_range.contains_inclusive(context.offset)
         {
-            let value = COMPONENT_DATABASE.documentation(&link.stem)?.value;
+            let content = COMPONENT_DATABASE.documentation_with_kind(&link.stem, kind)?;
             return Some(HoverResult {
-                value,
-                value_kind: MarkupKind::PlainText,
+                value: content.value,
+                value_kind: content.kind,
                 range: link.stem_range,
             });
         }
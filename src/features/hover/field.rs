@@ -1,22 +1,49 @@
 use lsp_types::{HoverParams, MarkupKind};
 use rowan::ast::AstNode;
 
-use crate::{features::cursor::CursorContext, syntax::bibtex, LANGUAGE_DATA};
+use crate::{
+    features::cursor::CursorContext,
+    syntax::bibtex::{self, HasType},
+    BibtexFieldRequirement, LANGUAGE_DATA,
+};
 
 use super::HoverResult;
 
+/// Shows the documentation of a field and whether the type of the entry requires it.
 pub(super) fn find_field_hover(context: &CursorContext<HoverParams>) -> Option<HoverResult> {
     let name = context
         .cursor
         .as_bibtex()
         .filter(|token| token.kind() == bibtex::NAME)?;
 
-    bibtex::Field::cast(name.parent()?)?;
-
+    let field = bibtex::Field::cast(name.parent()?)?;
     let docs = LANGUAGE_DATA.field_documentation(name.text())?;
+
+    let entry_type = field
+        .syntax()
+        .parent()
+        .and_then(bibtex::Entry::cast)
+        .and_then(|entry| entry.type_token())
+        .map(|token| token.text()[1..].to_lowercase());
+
+    let requirement = entry_type.as_deref().and_then(|entry_type| {
+        LANGUAGE_DATA
+            .find_entry_type(entry_type)?
+            .field_requirement(name.text())
+            .map(|requirement| match requirement {
+                BibtexFieldRequirement::Required => format!("Required field of `@{}`", entry_type),
+                BibtexFieldRequirement::Optional => format!("Optional field of `@{}`", entry_type),
+            })
+    });
+
+    let value = match requirement {
+        Some(requirement) => format!("{}\n\n{}", docs, requirement),
+        None => docs.to_string(),
+    };
+
     Some(HoverResult {
         range: name.text_range(),
-        value: docs.to_string(),
+        value,
         value_kind: MarkupKind::Markdown,
     })
 }
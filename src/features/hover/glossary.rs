@@ -0,0 +1,28 @@
+use lsp_types::{HoverParams, MarkupKind};
+
+use crate::features::cursor::CursorContext;
+
+use super::HoverResult;
+
+pub(super) fn find_glossary_entry_hover(
+    context: &CursorContext<HoverParams>,
+) -> Option<HoverResult> {
+    let (name_text, range) = context.find_glossary_entry_name_key()?;
+
+    let description = context
+        .request
+        .workspace
+        .documents_by_uri
+        .values()
+        .filter_map(|document| document.data.as_latex())
+        .flat_map(|data| data.extras.glossary_entries.iter())
+        .find(|entry| entry.name == name_text)?
+        .description
+        .clone()?;
+
+    Some(HoverResult {
+        range,
+        value: description,
+        value_kind: MarkupKind::PlainText,
+    })
+}
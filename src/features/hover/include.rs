@@ -0,0 +1,63 @@
+use lsp_types::{HoverParams, MarkupKind};
+
+use crate::{
+    features::cursor::CursorContext, syntax::latex::ExplicitLinkKind, ClientCapabilitiesExt,
+};
+
+use super::HoverResult;
+
+const PREVIEW_LINE_COUNT: usize = 10;
+
+pub(super) fn find_include_hover(context: &CursorContext<HoverParams>) -> Option<HoverResult> {
+    let data = context.request.main_document().data.as_latex()?;
+    let link = data.extras.explicit_links.iter().find(|link| {
+        link.kind == ExplicitLinkKind::Latex && link.stem_range.contains_inclusive(context.offset)
+    })?;
+
+    let documents_by_uri = &context.request.workspace.documents_by_uri;
+    let text = link
+        .targets
+        .iter()
+        .find_map(|target| documents_by_uri.get(target))
+        .map(|document| document.text.to_string())
+        .or_else(|| {
+            link.targets
+                .iter()
+                .filter(|target| target.scheme() == "file")
+                .filter_map(|target| target.to_file_path().ok())
+                .find_map(|path| std::fs::read_to_string(path).ok())
+        })?;
+
+    let preview = create_preview(&text)?;
+    let environment = &context.request.workspace.environment;
+    let (value, value_kind) = if environment.client_capabilities.has_hover_markdown_support() {
+        (format!("```latex\n{}\n```", preview), MarkupKind::Markdown)
+    } else {
+        (preview, MarkupKind::PlainText)
+    };
+
+    Some(HoverResult {
+        range: link.stem_range,
+        value,
+        value_kind,
+    })
+}
+
+/// Returns the first lines of the text,
+/// skipping the blank lines and comments at the beginning.
+fn create_preview(text: &str) -> Option<String> {
+    let lines: Vec<_> = text
+        .lines()
+        .skip_while(|line| {
+            let line = line.trim_start();
+            line.is_empty() || line.starts_with('%')
+        })
+        .take(PREVIEW_LINE_COUNT)
+        .collect();
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n").trim_end().to_string())
+    }
+}
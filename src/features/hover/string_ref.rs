@@ -23,7 +23,7 @@ pub(super) fn find_string_reference_hover(
             bibtex::Value::can_cast(parent.kind()) || bibtex::StringDef::can_cast(parent.kind())
         })?;
 
-    for string in bibtex::SyntaxNode::new_root(data.green.clone())
+    for string in bibtex::SyntaxNode::new_root(data.green().clone())
         .children()
         .filter_map(bibtex::StringDef::cast)
     {
@@ -0,0 +1,33 @@
+use lsp_types::{HoverParams, MarkupKind};
+use rowan::ast::AstNode;
+
+use crate::{features::cursor::CursorContext, syntax::latex, ClientCapabilitiesExt, LANGUAGE_DATA};
+
+use super::HoverResult;
+
+pub(super) fn find_tikz_library_hover(context: &CursorContext<HoverParams>) -> Option<HoverResult> {
+    let (name, range, group) = context.find_curly_group_word_list()?;
+    let import = latex::TikzLibraryImport::cast(group.syntax().parent()?)?;
+    if import.command()?.text() != "\\usetikzlibrary" {
+        return None;
+    }
+
+    let value = LANGUAGE_DATA.tikz_library_docs.get(&name)?.clone();
+    let value_kind = if context
+        .request
+        .workspace
+        .environment
+        .client_capabilities
+        .has_hover_markdown_support()
+    {
+        MarkupKind::Markdown
+    } else {
+        MarkupKind::PlainText
+    };
+
+    Some(HoverResult {
+        range,
+        value,
+        value_kind,
+    })
+}
@@ -1,13 +1,18 @@
 mod include;
+mod url;
 
 use std::sync::Arc;
 
 use lsp_types::{DocumentLink, DocumentLinkParams, Url};
 use rowan::TextRange;
+use serde::{Deserialize, Serialize};
 
 use crate::LineIndexExt;
 
-use self::include::find_include_links;
+use self::{
+    include::{find_include_links, resolve_include_link},
+    url::find_url_links,
+};
 
 use super::FeatureRequest;
 
@@ -15,19 +20,42 @@ pub fn find_document_links(request: FeatureRequest<DocumentLinkParams>) -> Vec<D
     let document = request.main_document();
     let mut results = Vec::new();
     find_include_links(&request, &mut results);
+    find_url_links(&request, &mut results);
     results
         .into_iter()
         .map(|result| DocumentLink {
             range: document.line_index.line_col_lsp_range(result.range),
-            target: Some(result.target.as_ref().clone()),
+            target: result.target.map(|target| target.as_ref().clone()),
             tooltip: None,
-            data: None,
+            data: result.data.map(|data| serde_json::to_value(data).unwrap()),
         })
         .collect()
 }
 
+pub fn resolve_document_link(request: FeatureRequest<DocumentLink>) -> DocumentLink {
+    let mut link = request.params.clone();
+    if let Some(data) = link
+        .data
+        .take()
+        .and_then(|data| serde_json::from_value::<DocumentLinkData>(data).ok())
+    {
+        link.target = resolve_include_link(&request, &data);
+    }
+
+    link
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentLinkData {
+    pub uri: Url,
+    pub targets: Vec<Url>,
+    pub distro_file: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 struct LinkResult {
     range: TextRange,
-    target: Arc<Url>,
+    target: Option<Arc<Url>>,
+    data: Option<DocumentLinkData>,
 }
@@ -1,10 +1,8 @@
-use std::sync::Arc;
+use lsp_types::{DocumentLink, DocumentLinkParams, Url};
 
-use lsp_types::DocumentLinkParams;
+use crate::{features::FeatureRequest, syntax::latex};
 
-use crate::features::FeatureRequest;
-
-use super::LinkResult;
+use super::{DocumentLinkData, LinkResult};
 
 pub(super) fn find_include_links(
     request: &FeatureRequest<DocumentLinkParams>,
@@ -12,22 +10,47 @@ pub(super) fn find_include_links(
 ) -> Option<()> {
     let data = request.main_document().data.as_latex()?;
 
-    for include in &data.extras.explicit_links {
-        for target in &include.targets {
-            if request
-                .workspace
-                .documents_by_uri
-                .values()
-                .any(|document| document.uri.as_ref() == target.as_ref())
-            {
-                results.push(LinkResult {
-                    range: include.stem_range,
-                    target: Arc::clone(target),
-                });
-                break;
-            }
-        }
+    for include in data
+        .extras
+        .explicit_links
+        .iter()
+        .filter(|include| !include.targets.is_empty())
+    {
+        results.push(LinkResult {
+            range: include.stem_range,
+            target: None,
+            data: Some(DocumentLinkData {
+                uri: request.uri.as_ref().clone(),
+                targets: include
+                    .targets
+                    .iter()
+                    .map(|target| target.as_ref().clone())
+                    .collect(),
+                distro_file: include.as_component_name(),
+            }),
+        });
     }
 
     Some(())
 }
+
+pub(super) fn resolve_include_link(
+    request: &FeatureRequest<DocumentLink>,
+    data: &DocumentLinkData,
+) -> Option<Url> {
+    let workspace = &request.workspace;
+    data.targets
+        .iter()
+        .find(|target| workspace.documents_by_uri.contains_key(*target))
+        .or_else(|| {
+            data.targets
+                .iter()
+                .find(|target| target.to_file_path().map_or(false, |path| path.is_file()))
+        })
+        .cloned()
+        .or_else(|| {
+            let name = data.distro_file.as_deref()?;
+            latex::resolve_distro_file(&workspace.environment.resolver, name, &[])
+                .filter(|uri| uri.to_file_path().map_or(false, |path| path.is_file()))
+        })
+}
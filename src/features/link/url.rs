@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use lsp_types::{DocumentLinkParams, Url};
+use rowan::{ast::AstNode, TextRange};
+
+use crate::{
+    features::FeatureRequest,
+    syntax::latex::{self, HasCurly},
+};
+
+use super::LinkResult;
+
+pub(super) fn find_url_links(
+    request: &FeatureRequest<DocumentLinkParams>,
+    results: &mut Vec<LinkResult>,
+) -> Option<()> {
+    let data = request.main_document().data.as_latex()?;
+    let root = latex::SyntaxNode::new_root(data.green.clone());
+
+    for group in root
+        .descendants()
+        .filter_map(latex::GenericCommand::cast)
+        .filter(|command| {
+            command
+                .name()
+                .map_or(false, |name| matches!(name.text(), "\\href" | "\\url"))
+        })
+        .filter_map(|command| {
+            command
+                .syntax()
+                .children()
+                .find_map(latex::CurlyGroup::cast)
+        })
+    {
+        let range = TextRange::new(
+            group.left_curly()?.text_range().end(),
+            group.right_curly()?.text_range().start(),
+        );
+
+        let text = &request.main_document().text[std::ops::Range::<usize>::from(range)];
+        if let Ok(target) = Url::parse(text.trim()) {
+            results.push(LinkResult {
+                range,
+                target: Some(Arc::new(target)),
+                data: None,
+            });
+        }
+    }
+
+    Some(())
+}
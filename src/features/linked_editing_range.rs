@@ -0,0 +1,35 @@
+use lsp_types::{LinkedEditingRangeParams, LinkedEditingRanges};
+use rowan::ast::AstNode;
+
+use crate::{syntax::latex, LineIndexExt};
+
+use super::{cursor::CursorContext, FeatureRequest};
+
+/// Returns the names of the `\begin` and the `\end` of the environment under the cursor
+/// so that the client can edit both names at once.
+pub fn find_linked_editing_ranges(
+    request: FeatureRequest<LinkedEditingRangeParams>,
+) -> Option<LinkedEditingRanges> {
+    let context = CursorContext::new(request);
+    context.find_environment_name()?;
+
+    let name = context.cursor.as_latex()?;
+    let environment = name.parent_ancestors().find_map(latex::Environment::cast)?;
+    let begin = environment.begin()?.name()?.key()?;
+    let end = environment.end()?.name()?.key()?;
+    if begin.to_string() != end.to_string() {
+        return None;
+    }
+
+    let line_index = &context.request.main_document().line_index;
+    let ranges = [begin, end]
+        .into_iter()
+        .map(|name| line_index.line_col_lsp_range(latex::small_range(&name)))
+        .collect();
+
+    // Names like `figure*` contain characters that are not part of a word for most clients.
+    Some(LinkedEditingRanges {
+        ranges,
+        word_pattern: Some(r"[^\s{}\\%]+".to_string()),
+    })
+}
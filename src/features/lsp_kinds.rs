@@ -20,11 +20,13 @@ pub enum Structure {
     Color,
     ColorModel,
     Package,
+    PackageOption,
     Class,
     Entry(BibtexEntryTypeCategory),
     Field,
     Argument,
     GlossaryEntry,
+    Counter,
 }
 
 impl Structure {
@@ -46,6 +48,7 @@ impl Structure {
             Self::Color => CompletionItemKind::COLOR,
             Self::ColorModel => CompletionItemKind::COLOR,
             Self::Package => CompletionItemKind::CLASS,
+            Self::PackageOption => CompletionItemKind::PROPERTY,
             Self::Class => CompletionItemKind::CLASS,
             Self::Entry(BibtexEntryTypeCategory::Misc) => CompletionItemKind::INTERFACE,
             Self::Entry(BibtexEntryTypeCategory::String) => CompletionItemKind::TEXT,
@@ -57,6 +60,7 @@ impl Structure {
             Self::Field => CompletionItemKind::FIELD,
             Self::Argument => CompletionItemKind::VALUE,
             Self::GlossaryEntry => CompletionItemKind::KEYWORD,
+            Self::Counter => CompletionItemKind::VARIABLE,
         }
     }
 
@@ -78,6 +82,7 @@ impl Structure {
             Self::Color => unimplemented!(),
             Self::ColorModel => unimplemented!(),
             Self::Package => SymbolKind::CLASS,
+            Self::PackageOption => SymbolKind::PROPERTY,
             Self::Class => SymbolKind::CLASS,
             Self::Entry(BibtexEntryTypeCategory::Misc) => SymbolKind::INTERFACE,
             Self::Entry(BibtexEntryTypeCategory::String) => SymbolKind::STRING,
@@ -89,6 +94,7 @@ impl Structure {
             Self::Field => SymbolKind::FIELD,
             Self::Argument => SymbolKind::NUMBER,
             Self::GlossaryEntry => unimplemented!(),
+            Self::Counter => SymbolKind::VARIABLE,
         }
     }
 }
@@ -0,0 +1,69 @@
+use lsp_types::{DocumentOnTypeFormattingParams, Range, TextEdit};
+use rowan::{ast::AstNode, TextSize};
+
+use crate::{
+    syntax::latex::{self, HasCurly},
+    LineIndexExt,
+};
+
+use super::FeatureRequest;
+
+/// Inserts the matching `\end` after typing the closing brace of a `\begin`.
+///
+/// The `\end` is placed on the next line with the same indentation as the `\begin`.
+pub fn format_on_type(
+    request: FeatureRequest<DocumentOnTypeFormattingParams>,
+) -> Option<Vec<TextEdit>> {
+    let options = &request.workspace.environment.options;
+    if !options.auto_close_environments || request.params.ch != "}" {
+        return None;
+    }
+
+    let main_document = request.main_document();
+    let data = main_document.data.as_latex()?;
+    let root = latex::SyntaxNode::new_root(data.green.clone());
+    let offset = main_document
+        .line_index
+        .offset_lsp(request.params.text_document_position.position);
+
+    let brace = root
+        .token_at_offset(offset)
+        .left_biased()
+        .filter(|token| token.kind() == latex::R_CURLY && token.text_range().end() == offset)?;
+
+    let group = latex::CurlyGroupWord::cast(brace.parent()?)?;
+    group.right_curly()?;
+    let begin = latex::Begin::cast(group.syntax().parent()?)?;
+    let environment = latex::Environment::cast(begin.syntax().parent()?)?;
+    let name = group.key()?.to_string();
+
+    let is_closed = environment
+        .end()
+        .and_then(|end| end.name()?.key())
+        .map_or(false, |end| end.to_string() == name);
+
+    if is_closed {
+        return None;
+    }
+
+    let text = main_document.text.as_str();
+    let begin_start = usize::from(begin.syntax().text_range().start());
+    let line_start = text[..begin_start].rfind('\n').map_or(0, |i| i + 1);
+    let indent: String = text[line_start..begin_start]
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect();
+
+    let line_end = text[usize::from(offset)..]
+        .find(['\r', '\n'])
+        .map_or(text.len(), |i| usize::from(offset) + i);
+
+    let position = main_document
+        .line_index
+        .line_col_lsp(TextSize::try_from(line_end).ok()?);
+
+    Some(vec![TextEdit::new(
+        Range::new(position, position),
+        format!("\n{}\\end{{{}}}", indent, name),
+    )])
+}
@@ -1,3 +1,4 @@
+mod command;
 mod entry;
 mod label;
 mod string;
@@ -10,7 +11,8 @@ use rowan::TextRange;
 use crate::LineIndexExt;
 
 use self::{
-    entry::find_entry_references, label::find_label_references, string::find_string_references,
+    command::find_command_references, entry::find_entry_references, label::find_label_references,
+    string::find_string_references,
 };
 
 use super::{cursor::CursorContext, FeatureRequest};
@@ -22,6 +24,7 @@ pub fn find_all_references(request: FeatureRequest<ReferenceParams>) -> Vec<Loca
     find_label_references(&context, &mut results);
     find_entry_references(&context, &mut results);
     find_string_references(&context, &mut results);
+    find_command_references(&context, &mut results);
 
     results
         .into_iter()
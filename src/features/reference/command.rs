@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use lsp_types::ReferenceParams;
+
+use crate::{features::cursor::CursorContext, find_command_definition};
+
+use super::ReferenceResult;
+
+pub(super) fn find_command_references(
+    context: &CursorContext<ReferenceParams>,
+    results: &mut Vec<ReferenceResult>,
+) -> Option<()> {
+    let name = context
+        .cursor
+        .as_latex()
+        .filter(|token| token.kind().is_command_name())?;
+
+    let workspace = &context.request.workspace;
+    let find_definition = |uri, offset| {
+        find_command_definition(workspace, uri, name.text(), offset)
+            .map(|(document, command)| (Arc::clone(&document.uri), command.range))
+    };
+
+    // Only the references that refer to the same definition belong together
+    // if the command is redefined with `\renewcommand`.
+    let definition = find_definition(&context.request.uri, name.text_range().start());
+    for document in workspace.documents_by_uri.values() {
+        if let Some(data) = document.data.as_latex() {
+            for range in data
+                .extras
+                .command_ranges_by_name
+                .get(name.text())
+                .into_iter()
+                .flatten()
+            {
+                let is_definition = data
+                    .extras
+                    .user_commands
+                    .iter()
+                    .any(|command| command.range == *range);
+
+                if (!is_definition || context.request.params.context.include_declaration)
+                    && find_definition(&document.uri, range.start()) == definition
+                {
+                    results.push(ReferenceResult {
+                        uri: Arc::clone(&document.uri),
+                        range: *range,
+                    });
+                }
+            }
+        }
+    }
+
+    Some(())
+}
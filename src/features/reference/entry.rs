@@ -5,10 +5,7 @@ use rowan::ast::AstNode;
 
 use crate::{
     features::cursor::CursorContext,
-    syntax::{
-        bibtex::{self, HasName},
-        latex,
-    },
+    syntax::bibtex::{self, HasName},
     DocumentData,
 };
 
@@ -26,20 +23,18 @@ pub(super) fn find_entry_references(
     for document in context.request.workspace.documents_by_uri.values() {
         match &document.data {
             DocumentData::Latex(data) => {
-                latex::SyntaxNode::new_root(data.green.clone())
-                    .descendants()
-                    .filter_map(latex::Citation::cast)
-                    .filter_map(|citation| citation.key_list())
-                    .flat_map(|keys| keys.keys())
-                    .filter(|key| key.to_string() == key_text)
-                    .map(|key| latex::small_range(&key))
-                    .for_each(|range| {
+                data.extras
+                    .citation_ranges_by_key
+                    .get(key_text.as_str())
+                    .into_iter()
+                    .flatten()
+                    .for_each(|&range| {
                         let uri = Arc::clone(&document.uri);
                         results.push(ReferenceResult { uri, range });
                     });
             }
             DocumentData::Bibtex(data) if context.request.params.context.include_declaration => {
-                bibtex::SyntaxNode::new_root(data.green.clone())
+                bibtex::SyntaxNode::new_root(data.green().clone())
                     .children()
                     .filter_map(bibtex::Entry::cast)
                     .filter_map(|entry| entry.name_token())
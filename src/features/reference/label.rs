@@ -18,9 +18,10 @@ pub(super) fn find_label_references(
         if let Some(data) = document.data.as_latex() {
             for name in data
                 .extras
-                .label_names
-                .iter()
-                .filter(|name| name.text == name_text)
+                .label_names_by_text
+                .get(name_text.as_str())
+                .into_iter()
+                .flatten()
                 .filter(|name| {
                     !name.is_definition || context.request.params.context.include_declaration
                 })
@@ -26,7 +26,7 @@ pub(super) fn find_string_references(
 
     let document = context.request.main_document();
     let data = document.data.as_bibtex()?;
-    for node in bibtex::SyntaxNode::new_root(data.green.clone()).descendants() {
+    for node in bibtex::SyntaxNode::new_root(data.green().clone()).descendants() {
         if let Some(name) = bibtex::StringDef::cast(node.clone())
             .and_then(|string| string.name_token())
             .filter(|name| {
@@ -1,45 +1,119 @@
 mod command;
 mod entry;
+mod environment;
+mod include;
 mod label;
 
 use std::sync::Arc;
 
-use lsp_types::{Range, RenameParams, TextDocumentPositionParams, TextEdit, Url, WorkspaceEdit};
+use lsp_types::{
+    DocumentChangeOperation, DocumentChanges, OneOf, OptionalVersionedTextDocumentIdentifier,
+    Range, RenameFile, RenameFilesParams, RenameParams, ResourceOp, TextDocumentEdit,
+    TextDocumentPositionParams, TextEdit, Url, WorkspaceEdit,
+};
 use rowan::TextRange;
 use rustc_hash::FxHashMap;
 
-use crate::LineIndexExt;
+use crate::{LineIndexExt, Workspace};
 
 use self::{
     command::{prepare_command_rename, rename_command},
     entry::{prepare_entry_rename, rename_entry},
+    environment::{prepare_environment_rename, rename_environment},
+    include::{prepare_include_rename, rename_include, rename_links},
     label::{prepare_label_rename, rename_label},
 };
 
-use super::{cursor::CursorContext, FeatureRequest};
+use super::{
+    cursor::{CursorContext, HasPosition},
+    FeatureRequest,
+};
 
 pub fn prepare_rename_all(request: FeatureRequest<TextDocumentPositionParams>) -> Option<Range> {
     let context = CursorContext::new(request);
-    let range = prepare_entry_rename(&context)
-        .or_else(|| prepare_label_rename(&context))
-        .or_else(|| prepare_command_rename(&context))?;
-
+    let (_, range) = find_rename_target(&context)?;
     let line_index = &context.request.main_document().line_index;
     Some(line_index.line_col_lsp_range(range))
 }
 
 pub fn rename_all(request: FeatureRequest<RenameParams>) -> Option<WorkspaceEdit> {
     let context = CursorContext::new(request);
-    let result = rename_entry(&context)
-        .or_else(|| rename_label(&context))
-        .or_else(|| rename_command(&context))?;
+    let (kind, _) = find_rename_target(&context)?;
+    let result = match kind {
+        RenameKind::Entry => rename_entry(&context),
+        RenameKind::Label => rename_label(&context),
+        RenameKind::Command => rename_command(&context),
+        RenameKind::Environment => rename_environment(&context),
+        RenameKind::Include => rename_include(&context),
+    }?;
+
+    let changes = convert_changes(&context.request.workspace, result.changes);
+
+    match result.renamed_file {
+        Some((old_uri, new_uri)) => {
+            let mut operations: Vec<_> = changes
+                .into_iter()
+                .filter(|(_, edits)| !edits.is_empty())
+                .map(|(uri, edits)| {
+                    DocumentChangeOperation::Edit(TextDocumentEdit {
+                        text_document: OptionalVersionedTextDocumentIdentifier {
+                            uri,
+                            version: None,
+                        },
+                        edits: edits.into_iter().map(OneOf::Left).collect(),
+                    })
+                })
+                .collect();
+
+            operations.push(DocumentChangeOperation::Op(ResourceOp::Rename(
+                RenameFile {
+                    old_uri: old_uri.as_ref().clone(),
+                    new_uri,
+                    options: None,
+                    annotation_id: None,
+                },
+            )));
 
-    let changes = result
-        .changes
+            Some(WorkspaceEdit {
+                document_changes: Some(DocumentChanges::Operations(operations)),
+                ..WorkspaceEdit::default()
+            })
+        }
+        None => Some(WorkspaceEdit::new(changes.into_iter().collect())),
+    }
+}
+
+/// Updates the links to the files that are about to be renamed by the client.
+pub fn rename_files(workspace: &Workspace, params: &RenameFilesParams) -> Option<WorkspaceEdit> {
+    let renames: Vec<_> = params
+        .files
+        .iter()
+        .filter_map(|file| {
+            let old_uri = Url::parse(&file.old_uri).ok()?;
+            let new_uri = Url::parse(&file.new_uri).ok()?;
+            Some((Arc::new(old_uri), new_uri))
+        })
+        .collect();
+
+    let changes = rename_links(workspace, &renames);
+    if changes.is_empty() {
+        return None;
+    }
+
+    Some(WorkspaceEdit::new(
+        convert_changes(workspace, changes).into_iter().collect(),
+    ))
+}
+
+fn convert_changes(
+    workspace: &Workspace,
+    changes: FxHashMap<Arc<Url>, Vec<Indel>>,
+) -> Vec<(Url, Vec<TextEdit>)> {
+    changes
         .into_iter()
         .map(|(uri, old_edits)| {
-            let document = &context.request.workspace.documents_by_uri[&uri];
-            let new_edits = old_edits
+            let document = &workspace.documents_by_uri[&uri];
+            let new_edits: Vec<_> = old_edits
                 .into_iter()
                 .map(|Indel { delete, insert }| {
                     TextEdit::new(document.line_index.line_col_lsp_range(delete), insert)
@@ -48,9 +122,31 @@ pub fn rename_all(request: FeatureRequest<RenameParams>) -> Option<WorkspaceEdit
 
             (uri.as_ref().clone(), new_edits)
         })
-        .collect();
+        .collect()
+}
 
-    Some(WorkspaceEdit::new(changes))
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum RenameKind {
+    Entry,
+    Label,
+    Command,
+    Environment,
+    Include,
+}
+
+/// Classifies the entity under the cursor so that `prepare_rename_all` only accepts
+/// positions that `rename_all` can handle.
+fn find_rename_target<P: HasPosition>(
+    context: &CursorContext<P>,
+) -> Option<(RenameKind, TextRange)> {
+    prepare_entry_rename(context)
+        .map(|range| (RenameKind::Entry, range))
+        .or_else(|| prepare_label_rename(context).map(|range| (RenameKind::Label, range)))
+        .or_else(|| prepare_command_rename(context).map(|range| (RenameKind::Command, range)))
+        .or_else(|| {
+            prepare_environment_rename(context).map(|range| (RenameKind::Environment, range))
+        })
+        .or_else(|| prepare_include_rename(context).map(|range| (RenameKind::Include, range)))
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -62,4 +158,6 @@ struct Indel {
 #[derive(Debug, PartialEq, Eq, Clone)]
 struct RenameResult {
     changes: FxHashMap<Arc<Url>, Vec<Indel>>,
+    /// The file that is moved from the first to the second URI.
+    renamed_file: Option<(Arc<Url>, Url)>,
 }
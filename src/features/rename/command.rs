@@ -4,16 +4,15 @@ use lsp_types::RenameParams;
 use rowan::{TextRange, TextSize};
 use rustc_hash::FxHashMap;
 
-use crate::{
-    features::cursor::{CursorContext, HasPosition},
-    syntax::latex,
-};
+use crate::features::cursor::{CursorContext, HasPosition};
 
 use super::{Indel, RenameResult};
 
 pub(super) fn prepare_command_rename<P: HasPosition>(
     context: &CursorContext<P>,
 ) -> Option<TextRange> {
+    // Commands inside BibTeX documents are not indexed.
+    context.cursor.as_latex()?;
     context.cursor.command_range(context.offset)
 }
 
@@ -23,17 +22,15 @@ pub(super) fn rename_command(context: &CursorContext<RenameParams>) -> Option<Re
     let mut changes = FxHashMap::default();
     for document in context.request.workspace.documents_by_uri.values() {
         if let Some(data) = document.data.as_latex() {
-            let root = latex::SyntaxNode::new_root(data.green.clone());
-            let edits = root
-                .descendants_with_tokens()
-                .filter_map(|element| element.into_token())
-                .filter(|token| token.kind().is_command_name() && token.text() == name)
-                .map(|token| {
-                    let range = token.text_range();
-                    Indel {
-                        delete: TextRange::new(range.start() + TextSize::from(1), range.end()),
-                        insert: context.request.params.new_name.clone(),
-                    }
+            let edits = data
+                .extras
+                .command_ranges_by_name
+                .get(name)
+                .into_iter()
+                .flatten()
+                .map(|range| Indel {
+                    delete: TextRange::new(range.start() + TextSize::from(1), range.end()),
+                    insert: context.request.params.new_name.clone(),
                 })
                 .collect();
 
@@ -41,5 +38,8 @@ pub(super) fn rename_command(context: &CursorContext<RenameParams>) -> Option<Re
         }
     }
 
-    Some(RenameResult { changes })
+    Some(RenameResult {
+        changes,
+        renamed_file: None,
+    })
 }
@@ -6,10 +6,7 @@ use rustc_hash::FxHashMap;
 
 use crate::{
     features::cursor::{CursorContext, HasPosition},
-    syntax::{
-        bibtex::{self, HasName},
-        latex,
-    },
+    syntax::bibtex::{self, HasName},
     DocumentData,
 };
 
@@ -36,22 +33,21 @@ pub(super) fn rename_entry(context: &CursorContext<RenameParams>) -> Option<Rena
         let uri = Arc::clone(&document.uri);
         match &document.data {
             DocumentData::Latex(data) => {
-                let root = latex::SyntaxNode::new_root(data.green.clone());
-                let edits: Vec<_> = root
-                    .descendants()
-                    .filter_map(latex::Citation::cast)
-                    .filter_map(|citation| citation.key_list())
-                    .flat_map(|keys| keys.keys())
-                    .filter(|key| key.to_string() == key_text)
-                    .map(|key| Indel {
-                        delete: latex::small_range(&key),
+                let edits: Vec<_> = data
+                    .extras
+                    .citation_ranges_by_key
+                    .get(key_text.as_str())
+                    .into_iter()
+                    .flatten()
+                    .map(|&range| Indel {
+                        delete: range,
                         insert: context.request.params.new_name.clone(),
                     })
                     .collect();
                 changes.insert(uri, edits);
             }
             DocumentData::Bibtex(data) => {
-                let root = bibtex::SyntaxNode::new_root(data.green.clone());
+                let root = bibtex::SyntaxNode::new_root(data.green().clone());
                 let edits: Vec<_> = root
                     .descendants()
                     .filter_map(bibtex::Entry::cast)
@@ -68,5 +64,8 @@ pub(super) fn rename_entry(context: &CursorContext<RenameParams>) -> Option<Rena
         }
     }
 
-    Some(RenameResult { changes })
+    Some(RenameResult {
+        changes,
+        renamed_file: None,
+    })
 }
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use lsp_types::RenameParams;
+use rowan::{ast::AstNode, TextRange};
+use rustc_hash::FxHashMap;
+
+use crate::{
+    features::cursor::{CursorContext, HasPosition},
+    syntax::latex,
+};
+
+use super::{Indel, RenameResult};
+
+pub(super) fn prepare_environment_rename<P: HasPosition>(
+    context: &CursorContext<P>,
+) -> Option<TextRange> {
+    let (_, range) = context.find_environment_name()?;
+    find_matching_names(context)?;
+    Some(range)
+}
+
+/// Renames the `\begin` and the `\end` of the environment under the cursor.
+pub(super) fn rename_environment(context: &CursorContext<RenameParams>) -> Option<RenameResult> {
+    let (begin, end) = find_matching_names(context)?;
+    let edits = [begin, end]
+        .into_iter()
+        .map(|name| Indel {
+            delete: latex::small_range(&name),
+            insert: context.request.params.new_name.clone(),
+        })
+        .collect();
+
+    let mut changes = FxHashMap::default();
+    changes.insert(Arc::clone(&context.request.main_document().uri), edits);
+    Some(RenameResult {
+        changes,
+        renamed_file: None,
+    })
+}
+
+/// Finds the names of the `\begin` and the `\end` of the environment under the cursor.
+/// Environments that are not closed or whose names do not match cannot be renamed.
+fn find_matching_names<P: HasPosition>(
+    context: &CursorContext<P>,
+) -> Option<(latex::Key, latex::Key)> {
+    let name = context.cursor.as_latex()?;
+    let environment = name.parent_ancestors().find_map(latex::Environment::cast)?;
+
+    let begin = environment.begin()?.name()?.key()?;
+    let end = environment.end()?.name()?.key()?;
+    if begin.to_string() != end.to_string() {
+        return None;
+    }
+
+    Some((begin, end))
+}
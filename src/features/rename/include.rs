@@ -0,0 +1,178 @@
+use std::sync::Arc;
+
+use lsp_types::{RenameParams, Url};
+use rowan::TextRange;
+use rustc_hash::FxHashMap;
+
+use crate::{
+    features::cursor::{CursorContext, HasPosition},
+    syntax::latex::{self, ExplicitLink, ExplicitLinkKind},
+    ClientCapabilitiesExt, Workspace,
+};
+
+use super::{Indel, RenameResult};
+
+pub(super) fn prepare_include_rename<P: HasPosition>(
+    context: &CursorContext<P>,
+) -> Option<TextRange> {
+    let (link, _) = find_include(context)?;
+    Some(link.stem_range)
+}
+
+pub(super) fn rename_include(context: &CursorContext<RenameParams>) -> Option<RenameResult> {
+    let (link, old_uri) = find_include(context)?;
+    let (base_uri, has_implicit_extension) = find_base_uri(link, &old_uri, ".tex")?;
+
+    let new_name = &context.request.params.new_name;
+    let mut new_path = new_name.clone();
+    if has_implicit_extension && !new_name.ends_with(".tex") {
+        new_path.push_str(".tex");
+    }
+
+    let new_uri = base_uri.join(&new_path).ok()?;
+    if new_uri == *old_uri {
+        return None;
+    }
+
+    let changes = rename_links(
+        &context.request.workspace,
+        &[(Arc::clone(&old_uri), new_uri.clone())],
+    );
+    Some(RenameResult {
+        changes,
+        renamed_file: Some((old_uri, new_uri)),
+    })
+}
+
+/// Finds the `\input` or `\include` under the cursor together with the file it refers to.
+///
+/// The parent of a subfile given by `\documentclass[main]{subfiles}` is not renamed from the subfile.
+fn find_include<P: HasPosition>(context: &CursorContext<P>) -> Option<(&ExplicitLink, Arc<Url>)> {
+    let environment = &context.request.workspace.environment;
+    if !environment.client_capabilities.has_rename_file_support() {
+        return None;
+    }
+
+    let is_class_option = context.cursor.as_latex().map_or(false, |token| {
+        token
+            .parent_ancestors()
+            .any(|node| node.kind() == latex::CLASS_INCLUDE)
+    });
+
+    if is_class_option {
+        return None;
+    }
+
+    let data = context.request.main_document().data.as_latex()?;
+    let link = data.extras.explicit_links.iter().find(|link| {
+        link.kind == ExplicitLinkKind::Latex && link.stem_range.contains_inclusive(context.offset)
+    })?;
+
+    let documents_by_uri = &context.request.workspace.documents_by_uri;
+    let candidates = || link.targets.iter();
+    let target = candidates()
+        .find(|target| documents_by_uri.contains_key(*target))
+        .or_else(|| {
+            candidates().find(|target| {
+                target.scheme() == "file"
+                    && target.to_file_path().map_or(false, |path| path.is_file())
+            })
+        })?;
+
+    Some((link, Arc::clone(target)))
+}
+
+/// Updates the `\input`, `\include` and bibliography links that refer to one of the renamed files
+/// or to a file inside one of the renamed directories.
+pub(super) fn rename_links(
+    workspace: &Workspace,
+    renames: &[(Arc<Url>, Url)],
+) -> FxHashMap<Arc<Url>, Vec<Indel>> {
+    let mut changes = FxHashMap::default();
+    for document in workspace.documents_by_uri.values() {
+        if let Some(data) = document.data.as_latex() {
+            let edits: Vec<_> = data
+                .extras
+                .explicit_links
+                .iter()
+                .filter_map(|link| rename_link(link, renames))
+                .collect();
+
+            if !edits.is_empty() {
+                changes.insert(Arc::clone(&document.uri), edits);
+            }
+        }
+    }
+
+    changes
+}
+
+fn rename_link(link: &ExplicitLink, renames: &[(Arc<Url>, Url)]) -> Option<Indel> {
+    let extension = match link.kind {
+        ExplicitLinkKind::Latex | ExplicitLinkKind::External => ".tex",
+        ExplicitLinkKind::Bibtex => ".bib",
+        ExplicitLinkKind::Package | ExplicitLinkKind::Class | ExplicitLinkKind::Verbatim => {
+            return None
+        }
+    };
+
+    let (target, new_uri) = link.targets.iter().find_map(|target| {
+        let new_uri = renames
+            .iter()
+            .find_map(|(old_uri, new_uri)| rename_target(target, old_uri, new_uri))?;
+        Some((target, new_uri))
+    })?;
+
+    let (base_uri, has_implicit_extension) = find_base_uri(link, target, extension)?;
+    let mut stem = base_uri.make_relative(&new_uri)?;
+    if has_implicit_extension && stem.ends_with(extension) {
+        stem.truncate(stem.len() - extension.len());
+    }
+
+    Some(Indel {
+        delete: link.stem_range,
+        insert: stem,
+    })
+}
+
+/// Finds the directory that the stem of the link is resolved against to get the target
+/// and whether the target adds the extension to the stem.
+///
+/// This is the root directory for `\input` but the import directory for `\import` and `\subimport`.
+/// Targets that are found elsewhere, like in the TeX distribution, are resolved against one of their parent directories.
+fn find_base_uri(link: &ExplicitLink, target: &Url, extension: &str) -> Option<(Url, bool)> {
+    let stem_with_extension = format!("{}{}", link.stem, extension);
+    let parents = std::iter::successors(target.join(".").ok(), |directory| {
+        directory
+            .join("..")
+            .ok()
+            .filter(|parent| parent != directory)
+    });
+
+    std::iter::once(link.base_uri.as_ref().clone())
+        .chain(parents)
+        .find_map(|directory| {
+            if directory.join(&link.stem).ok().as_ref() == Some(target) {
+                Some((directory, false))
+            } else if directory.join(&stem_with_extension).ok().as_ref() == Some(target) {
+                Some((directory, true))
+            } else {
+                None
+            }
+        })
+}
+
+/// Returns the new location of the target if the target itself or one of its parent directories is renamed.
+fn rename_target(target: &Url, old_uri: &Url, new_uri: &Url) -> Option<Url> {
+    if target == old_uri {
+        return Some(new_uri.clone());
+    }
+
+    let path = target
+        .as_str()
+        .strip_prefix(old_uri.as_str().trim_end_matches('/'))?
+        .strip_prefix('/')?;
+
+    let directory = format!("{}/", new_uri.as_str().trim_end_matches('/'));
+    Url::parse(&directory).ok()?.join(path).ok()
+}
@@ -1,13 +1,10 @@
 use std::sync::Arc;
 
 use lsp_types::RenameParams;
-use rowan::{ast::AstNode, TextRange};
+use rowan::TextRange;
 use rustc_hash::FxHashMap;
 
-use crate::{
-    features::cursor::{CursorContext, HasPosition},
-    syntax::latex,
-};
+use crate::features::cursor::{CursorContext, HasPosition};
 
 use super::{Indel, RenameResult};
 
@@ -25,60 +22,24 @@ pub(super) fn rename_label(context: &CursorContext<RenameParams>) -> Option<Rena
     let mut changes = FxHashMap::default();
     for document in context.request.workspace.documents_by_uri.values() {
         if let Some(data) = document.data.as_latex() {
-            let mut edits = Vec::new();
-            for node in latex::SyntaxNode::new_root(data.green.clone()).descendants() {
-                if let Some(range) = latex::LabelDefinition::cast(node.clone())
-                    .and_then(|label| label.name())
-                    .and_then(|name| name.key())
-                    .filter(|name| name.to_string() == name_text)
-                    .map(|name| latex::small_range(&name))
-                {
-                    edits.push(Indel {
-                        delete: range,
-                        insert: context.request.params.new_name.clone(),
-                    });
-                }
-
-                latex::LabelReference::cast(node.clone())
-                    .and_then(|label| label.name_list())
-                    .into_iter()
-                    .flat_map(|label| label.keys())
-                    .filter(|name| name.to_string() == name_text)
-                    .for_each(|name| {
-                        edits.push(Indel {
-                            delete: latex::small_range(&name),
-                            insert: context.request.params.new_name.clone(),
-                        });
-                    });
-
-                if let Some(label) = latex::LabelReferenceRange::cast(node.clone()) {
-                    if let Some(name1) = label
-                        .from()
-                        .and_then(|name| name.key())
-                        .filter(|name| name.to_string() == name_text)
-                    {
-                        edits.push(Indel {
-                            delete: latex::small_range(&name1),
-                            insert: context.request.params.new_name.clone(),
-                        });
-                    }
-
-                    if let Some(name2) = label
-                        .from()
-                        .and_then(|name| name.key())
-                        .filter(|name| name.to_string() == name_text)
-                    {
-                        edits.push(Indel {
-                            delete: latex::small_range(&name2),
-                            insert: context.request.params.new_name.clone(),
-                        });
-                    }
-                }
-            }
+            let edits = data
+                .extras
+                .label_names_by_text
+                .get(name_text.as_str())
+                .into_iter()
+                .flatten()
+                .map(|name| Indel {
+                    delete: name.range,
+                    insert: context.request.params.new_name.clone(),
+                })
+                .collect();
 
             changes.insert(Arc::clone(&document.uri), edits);
         }
     }
 
-    Some(RenameResult { changes })
+    Some(RenameResult {
+        changes,
+        renamed_file: None,
+    })
 }
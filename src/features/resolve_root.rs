@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+
+use lsp_types::{TextDocumentIdentifier, Url};
+use serde::{Deserialize, Serialize};
+
+use super::FeatureRequest;
+
+pub struct ResolveRootRequest;
+
+impl lsp_types::request::Request for ResolveRootRequest {
+    type Params = TextDocumentIdentifier;
+
+    type Result = ResolveRootResult;
+
+    const METHOD: &'static str = "texlab/resolveRoot";
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolveRootResult {
+    /// The document that gets compiled when building the requested document.
+    pub root_uri: Url,
+
+    /// The directory in which the compiler is run and the PDF is expected.
+    pub output_directory: Option<PathBuf>,
+
+    /// The directory in which the `.aux` and `.log` files are expected.
+    pub aux_directory: Option<PathBuf>,
+}
+
+pub fn resolve_root(request: FeatureRequest<TextDocumentIdentifier>) -> ResolveRootResult {
+    let root_document = request
+        .workspace
+        .find_parent(&request.uri)
+        .unwrap_or_else(|| request.main_document().clone());
+
+    let environment = &request.workspace.environment;
+    let options = &environment.options;
+    let base_directory = environment.base_directory(&root_document.uri);
+    let output_directory = match options
+        .output_directory
+        .as_ref()
+        .or(options.root_directory.as_ref())
+    {
+        Some(output_dir) => Some(base_directory.join(output_dir)),
+        None => root_document
+            .uri
+            .to_file_path()
+            .ok()
+            .and_then(|path| path.parent().map(ToOwned::to_owned)),
+    };
+
+    let aux_directory = options
+        .aux_directory
+        .as_ref()
+        .map(|aux_dir| base_directory.join(aux_dir))
+        .or_else(|| output_directory.clone());
+
+    ResolveRootResult {
+        root_uri: root_document.uri.as_ref().clone(),
+        output_directory,
+        aux_directory,
+    }
+}
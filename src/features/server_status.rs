@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use threadpool::ThreadPool;
+
+use crate::{DocumentData, Workspace};
+
+pub struct ServerStatusRequest;
+
+impl lsp_types::request::Request for ServerStatusRequest {
+    type Params = ();
+
+    type Result = ServerStatus;
+
+    const METHOD: &'static str = "texlab/serverStatus";
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerStatus {
+    /// The number of documents known to the server, including the ones that are not opened.
+    pub documents: usize,
+
+    /// The number of documents that are opened in the editor.
+    pub open_documents: usize,
+
+    pub latex_documents: usize,
+
+    pub bibtex_documents: usize,
+
+    pub build_logs: usize,
+
+    /// The total size of the known documents in bytes.
+    pub text_bytes: usize,
+
+    /// The number of requests that are waiting for a worker thread.
+    pub queued_jobs: usize,
+
+    /// The number of requests that are currently processed.
+    pub active_jobs: usize,
+}
+
+pub fn collect_server_status(workspace: &Workspace, pool: &ThreadPool) -> ServerStatus {
+    let mut status = ServerStatus {
+        documents: workspace.documents_by_uri.len(),
+        open_documents: workspace.viewport.len(),
+        queued_jobs: pool.queued_count(),
+        active_jobs: pool.active_count(),
+        ..ServerStatus::default()
+    };
+
+    for document in workspace.documents_by_uri.values() {
+        status.text_bytes += document.text.len();
+        match document.data {
+            DocumentData::Latex(_) => status.latex_documents += 1,
+            DocumentData::Bibtex(_) => status.bibtex_documents += 1,
+            DocumentData::BuildLog(_) => status.build_logs += 1,
+        };
+    }
+
+    status
+}
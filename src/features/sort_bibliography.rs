@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use chrono::Datelike;
+use lsp_types::{Position, Range, TextEdit, Url, WorkspaceEdit};
+use rowan::{ast::AstNode, NodeOrToken, TextSize};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    citation::field::date::DateFieldData,
+    syntax::bibtex::{self, HasName, HasValue},
+    LineIndexExt, Workspace,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BibtexSortKey {
+    Key,
+    Year,
+}
+
+impl Default for BibtexSortKey {
+    fn default() -> Self {
+        Self::Key
+    }
+}
+
+#[derive(Debug)]
+struct Block {
+    text: String,
+    key: String,
+    year: Option<i32>,
+}
+
+/// Reorders the entries of a BibTeX document and returns an edit that rewrites the whole document.
+///
+/// `@string` and `@preamble` blocks are moved to the top in their original order.
+/// Comments directly preceding an entry are moved together with the entry.
+pub fn sort_bibliography(
+    workspace: &Workspace,
+    uri: &Url,
+    sort_key: BibtexSortKey,
+) -> Option<WorkspaceEdit> {
+    let document = workspace.documents_by_uri.get(uri)?;
+    let data = document.data.as_bibtex()?;
+    let root = bibtex::SyntaxNode::new_root(data.green().clone());
+
+    let mut header = None;
+    let mut definitions = Vec::new();
+    let mut entries = Vec::new();
+    let mut pending = String::new();
+    for child in root.children_with_tokens() {
+        let node = match child {
+            NodeOrToken::Node(node) => node,
+            NodeOrToken::Token(token) => {
+                pending.push_str(token.text());
+                continue;
+            }
+        };
+
+        // Text that is separated from the first block by an empty line is treated as a file header.
+        if header.is_none() {
+            let (file_header, leading) = split_header(pending.trim());
+            header = Some(file_header);
+            pending = leading;
+        }
+
+        let leading = pending.trim();
+        let text = if leading.is_empty() {
+            node.text().to_string()
+        } else {
+            format!("{}\n{}", leading, node.text())
+        };
+
+        pending.clear();
+        match bibtex::Entry::cast(node) {
+            Some(entry) => entries.push(Block {
+                text,
+                key: entry
+                    .name_token()
+                    .map(|token| token.text().to_string())
+                    .unwrap_or_default(),
+                year: find_year(&entry),
+            }),
+            None => definitions.push(text),
+        };
+    }
+
+    match sort_key {
+        BibtexSortKey::Key => {
+            entries.sort_by_cached_key(|block| block.key.to_lowercase());
+        }
+        BibtexSortKey::Year => {
+            entries.sort_by_cached_key(|block| {
+                (block.year.is_none(), block.year, block.key.to_lowercase())
+            });
+        }
+    };
+
+    let blocks: Vec<_> = header
+        .into_iter()
+        .chain(definitions)
+        .chain(entries.into_iter().map(|block| block.text))
+        .chain(std::iter::once(pending.trim().to_string()))
+        .filter(|text| !text.is_empty())
+        .collect();
+
+    let mut new_text = blocks.join("\n\n");
+    new_text.push('\n');
+
+    let end = document
+        .line_index
+        .line_col_lsp(TextSize::of(document.text.as_str()));
+
+    let range = Range::new(Position::new(0, 0), end);
+    let mut changes = HashMap::new();
+    changes.insert(
+        document.uri.as_ref().clone(),
+        vec![TextEdit::new(range, new_text)],
+    );
+
+    Some(WorkspaceEdit::new(changes))
+}
+
+/// Splits the text before the first block at the last empty line.
+///
+/// The lines are joined with `\n` so that both `\n` and `\r\n` line endings are supported.
+fn split_header(text: &str) -> (String, String) {
+    let lines: Vec<_> = text.lines().collect();
+    match lines.iter().rposition(|line| line.trim().is_empty()) {
+        Some(index) => (
+            lines[..index].join("\n").trim().to_string(),
+            lines[index + 1..].join("\n"),
+        ),
+        None => (String::new(), lines.join("\n")),
+    }
+}
+
+fn find_year(entry: &bibtex::Entry) -> Option<i32> {
+    entry
+        .fields()
+        .filter(|field| {
+            field.name_token().map_or(false, |name| {
+                matches!(name.text().to_lowercase().as_str(), "year" | "date")
+            })
+        })
+        .find_map(|field| match DateFieldData::parse(&field.value()?)? {
+            DateFieldData::Year(year) => Some(year),
+            DateFieldData::Date(date) => Some(date.year()),
+            DateFieldData::Month(_) | DateFieldData::Other(_) => None,
+        })
+}
@@ -16,7 +16,7 @@ pub fn find_bibtex_symbols(
     let main_document = request.main_document();
     let data = main_document.data.as_bibtex()?;
 
-    for node in bibtex::SyntaxNode::new_root(data.green.clone()).children() {
+    for node in bibtex::SyntaxNode::new_root(data.green().clone()).children() {
         if let Some(string) = bibtex::StringDef::cast(node.clone()) {
             if let Some(name) = string.name_token() {
                 buf.push(InternalSymbol {
@@ -33,6 +33,20 @@ pub fn find_bibtex_symbols(
                     children: Vec::new(),
                 })
             }
+        } else if let Some(preamble) = bibtex::Preamble::cast(node.clone()) {
+            if let Some(ty) = preamble.type_token() {
+                buf.push(InternalSymbol {
+                    name: ty.text().into(),
+                    label: None,
+                    kind: InternalSymbolKind::Preamble,
+                    deprecated: false,
+                    full_range: main_document
+                        .line_index
+                        .line_col_lsp_range(preamble.syntax().text_range()),
+                    selection_range: main_document.line_index.line_col_lsp_range(ty.text_range()),
+                    children: Vec::new(),
+                })
+            }
         } else if let Some(entry) = bibtex::Entry::cast(node) {
             if let Some(ty) = entry.type_token() {
                 if let Some(key) = entry.name_token() {
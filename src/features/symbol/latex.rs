@@ -8,7 +8,7 @@ use titlecase::titlecase;
 use crate::{
     features::FeatureRequest,
     find_caption_by_parent, find_label_number,
-    syntax::latex::{self, HasBrack, HasCurly},
+    syntax::latex::{self, CustomSection, HasBrack, HasCurly},
     LabelledFloatKind, LatexDocumentData, LineIndexExt, LANGUAGE_DATA,
 };
 
@@ -22,7 +22,8 @@ pub fn find_latex_symbols(
     let mut context = Context { request, data };
 
     let root = context.data.green.clone();
-    let mut symbols = visit(&mut context, latex::SyntaxNode::new_root(root));
+    let items = visit(&mut context, latex::SyntaxNode::new_root(root));
+    let (mut symbols, _) = nest(items, None);
     buf.append(&mut symbols);
     Some(())
 }
@@ -32,7 +33,22 @@ struct Context<'a> {
     data: &'a LatexDocumentData,
 }
 
-fn visit(context: &mut Context, node: latex::SyntaxNode) -> Vec<InternalSymbol> {
+/// Describes how a symbol takes part in the sectioning of the document.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Nesting {
+    Other,
+    /// A standard sectioning command whose content is already nested by the parser.
+    Section(usize),
+    /// A sectioning command from `symbols.customSectionCommands` that contains the symbols following it.
+    CustomSection(usize),
+}
+
+fn visit(context: &mut Context, node: latex::SyntaxNode) -> Vec<(Nesting, InternalSymbol)> {
+    if let Some(section) = find_custom_section(context, &node) {
+        let symbol = visit_custom_section(context, section);
+        return vec![(Nesting::CustomSection(section.level), symbol)];
+    }
+
     let symbol = match node.kind() {
         latex::PART
         | latex::CHAPTER
@@ -70,21 +86,124 @@ fn visit(context: &mut Context, node: latex::SyntaxNode) -> Vec<InternalSymbol>
         _ => None,
     };
 
+    let items: Vec<_> = node
+        .children()
+        .flat_map(|child| visit(context, child))
+        .collect();
+
     match symbol {
         Some(mut parent) => {
-            for child in node.children() {
-                parent.children.append(&mut visit(context, child));
-            }
-            vec![parent]
+            let level = latex::Section::cast(node).map(|section| section.level());
+            let (mut children, following) = nest(items, level);
+            parent.children.append(&mut children);
+
+            let nesting = level.map_or(Nesting::Other, Nesting::Section);
+            std::iter::once((nesting, parent))
+                .chain(following)
+                .collect()
         }
-        None => {
-            let mut symbols = Vec::new();
-            for child in node.children() {
-                symbols.append(&mut visit(context, child));
+        None => items,
+    }
+}
+
+/// Nests the symbols following a custom sectioning command inside of it
+/// until the next sectioning command of the same or a higher level.
+///
+/// A custom sectioning command with the same or a higher level than the parent section ends the parent section.
+/// It is returned together with the symbols following it so that they can be nested by the enclosing node.
+fn nest(
+    items: Vec<(Nesting, InternalSymbol)>,
+    parent_level: Option<usize>,
+) -> (Vec<InternalSymbol>, Vec<(Nesting, InternalSymbol)>) {
+    let mut symbols = Vec::new();
+    let mut custom_sections = Vec::new();
+    let mut following = Vec::new();
+    for (nesting, symbol) in items {
+        if !following.is_empty() {
+            following.push((nesting, symbol));
+            continue;
+        }
+
+        match nesting {
+            Nesting::CustomSection(level)
+                if parent_level.map_or(false, |parent| level <= parent) =>
+            {
+                following.push((nesting, symbol));
+            }
+            Nesting::CustomSection(level) => {
+                close_custom_sections(&mut symbols, &mut custom_sections, level);
+                custom_sections.push((level, symbol));
             }
-            symbols
+            Nesting::Section(level) => {
+                close_custom_sections(&mut symbols, &mut custom_sections, level);
+                push_symbol(&mut symbols, &mut custom_sections, symbol);
+            }
+            Nesting::Other => push_symbol(&mut symbols, &mut custom_sections, symbol),
         }
     }
+
+    close_custom_sections(&mut symbols, &mut custom_sections, 0);
+    (symbols, following)
+}
+
+fn close_custom_sections(
+    symbols: &mut Vec<InternalSymbol>,
+    custom_sections: &mut Vec<(usize, InternalSymbol)>,
+    level: usize,
+) {
+    while custom_sections
+        .last()
+        .map_or(false, |(other_level, _)| *other_level >= level)
+    {
+        let (_, section) = custom_sections.pop().unwrap();
+        push_symbol(symbols, custom_sections, section);
+    }
+}
+
+fn push_symbol(
+    symbols: &mut Vec<InternalSymbol>,
+    custom_sections: &mut [(usize, InternalSymbol)],
+    symbol: InternalSymbol,
+) {
+    match custom_sections.last_mut() {
+        Some((_, parent)) => parent.children.push(symbol),
+        None => symbols.push(symbol),
+    }
+}
+
+fn find_custom_section<'a>(
+    context: &Context<'a>,
+    node: &latex::SyntaxNode,
+) -> Option<&'a CustomSection> {
+    if node.kind() != latex::GENERIC_COMMAND {
+        return None;
+    }
+
+    let start = node.text_range().start();
+    context
+        .data
+        .extras
+        .custom_sections
+        .iter()
+        .find(|section| section.range.start() == start)
+}
+
+fn visit_custom_section(context: &mut Context, section: &CustomSection) -> InternalSymbol {
+    let range = context
+        .request
+        .main_document()
+        .line_index
+        .line_col_lsp_range(section.range);
+
+    InternalSymbol {
+        name: section.title.clone(),
+        label: None,
+        kind: InternalSymbolKind::Section,
+        deprecated: false,
+        full_range: range,
+        selection_range: range,
+        children: Vec::new(),
+    }
 }
 
 fn visit_section(context: &mut Context, node: latex::SyntaxNode) -> Option<InternalSymbol> {
@@ -321,16 +440,21 @@ fn visit_float(
         LabelledFloatKind::Table => ("Table", InternalSymbolKind::Table),
     };
 
-    let caption = find_caption_by_parent(environment.syntax())?;
+    // Floats without a caption are still part of the outline, they are only named by their kind.
+    let caption = find_caption_by_parent(environment.syntax());
     let symbol = match find_label_by_parent(context, environment.syntax()) {
         Some(NumberedLabel {
             name: label,
             range: selection_range,
             number,
         }) => {
-            let name = match number {
-                Some(number) => format!("{} {}: {}", float_kind, number, caption),
-                None => format!("{}: {}", float_kind, caption),
+            let name = match (number, caption) {
+                (Some(number), Some(caption)) => {
+                    format!("{} {}: {}", float_kind, number, caption)
+                }
+                (Some(number), None) => format!("{} {}", float_kind, number),
+                (None, Some(caption)) => format!("{}: {}", float_kind, caption),
+                (None, None) => float_kind.to_string(),
             };
 
             InternalSymbol {
@@ -344,7 +468,10 @@ fn visit_float(
             }
         }
         None => InternalSymbol {
-            name: format!("{}: {}", float_kind, caption),
+            name: match caption {
+                Some(caption) => format!("{}: {}", float_kind, caption),
+                None => float_kind.to_string(),
+            },
             label: None,
             kind: symbol_kind,
             deprecated: false,
@@ -443,7 +570,17 @@ fn find_label_by_parent(
     context: &mut Context,
     parent: &latex::SyntaxNode,
 ) -> Option<NumberedLabel> {
-    let node = parent.children().find_map(latex::LabelDefinition::cast)?;
+    // The label of a float is often placed inside of its caption.
+    let node = parent
+        .children()
+        .find_map(latex::LabelDefinition::cast)
+        .or_else(|| {
+            parent
+                .children()
+                .filter_map(latex::Caption::cast)
+                .flat_map(|caption| caption.syntax().descendants())
+                .find_map(latex::LabelDefinition::cast)
+        })?;
 
     let name = node.name()?.key()?.to_string();
     let range = context
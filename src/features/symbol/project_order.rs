@@ -1,4 +1,4 @@
-use std::{sync::Arc, usize};
+use std::sync::Arc;
 
 use lsp_types::Url;
 use petgraph::{algo::tarjan_scc, Directed, Graph};
@@ -16,6 +16,7 @@ pub enum InternalSymbolKind {
     Entry(BibtexEntryTypeCategory),
     Field,
     String,
+    Preamble,
 }
 
 impl InternalSymbolKind {
@@ -31,7 +32,8 @@ impl InternalSymbolKind {
             Self::Equation => Structure::Equation.symbol_kind(),
             Self::Entry(category) => Structure::Entry(category).symbol_kind(),
             Self::Field => Structure::Field.symbol_kind(),
-            Self::String => Structure::Entry(BibtexEntryTypeCategory::String).symbol_kind(),
+            Self::String => SymbolKind::CONSTANT,
+            Self::Preamble => SymbolKind::NAMESPACE,
         }
     }
 }
@@ -62,6 +64,7 @@ impl InternalSymbol {
             InternalSymbolKind::Entry(_) => "bibtex entry",
             InternalSymbolKind::Field => "bibtex field",
             InternalSymbolKind::String => "bibtex string",
+            InternalSymbolKind::Preamble => "bibtex preamble",
         };
         format!("{} {}", kind, self.name).to_lowercase()
     }
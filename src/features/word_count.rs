@@ -0,0 +1,274 @@
+use std::{process::Stdio, sync::Arc};
+
+use lsp_types::Url;
+use rowan::ast::AstNode;
+use rustc_hash::FxHashSet;
+use serde::Serialize;
+
+use crate::{
+    syntax::latex::{self, HasCurly},
+    Document, Workspace, LANGUAGE_DATA,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WordCount {
+    pub words: usize,
+    pub characters: usize,
+    /// The counts of the sectioning commands in document order.
+    /// Only computed if `texcount` is not available.
+    pub sections: Vec<SectionWordCount>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SectionWordCount {
+    pub title: String,
+    pub level: usize,
+    pub words: usize,
+    pub characters: usize,
+}
+
+/// Counts the words of the compilation unit that contains the given document.
+///
+/// Prefers `texcount` and falls back to counting the text of the syntax tree without markup.
+pub fn count_words(workspace: &Workspace, uri: &Url) -> WordCount {
+    let root = match workspace
+        .find_parent(uri)
+        .or_else(|| workspace.documents_by_uri.get(uri).cloned())
+    {
+        Some(root) => root,
+        None => return WordCount::default(),
+    };
+
+    run_texcount(&root.uri).unwrap_or_else(|| {
+        let mut counter = WordCounter {
+            workspace,
+            visited: FxHashSet::default(),
+            result: WordCount::default(),
+        };
+
+        counter.visit_document(&root);
+        counter.result
+    })
+}
+
+fn run_texcount(uri: &Url) -> Option<WordCount> {
+    if uri.scheme() != "file" {
+        return None;
+    }
+
+    let path = uri.to_file_path().ok()?;
+    let run = |extra_args: &[&str]| -> Option<usize> {
+        let output = std::process::Command::new("texcount")
+            .args(["-1", "-sum", "-merge", "-utf8"])
+            .args(extra_args)
+            .arg(path.file_name()?)
+            .current_dir(path.parent()?)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+
+        let stdout = String::from_utf8(output.stdout).ok()?;
+        stdout.split_whitespace().next()?.parse().ok()
+    };
+
+    Some(WordCount {
+        words: run(&[])?,
+        characters: run(&["-char"])?,
+        sections: Vec::new(),
+    })
+}
+
+struct WordCounter<'a> {
+    workspace: &'a Workspace,
+    visited: FxHashSet<Arc<Url>>,
+    result: WordCount,
+}
+
+impl<'a> WordCounter<'a> {
+    fn visit_document(&mut self, document: &Document) {
+        if !self.visited.insert(Arc::clone(&document.uri)) {
+            return;
+        }
+
+        let data = match document.data.as_latex() {
+            Some(data) => data,
+            None => return,
+        };
+
+        let root = latex::SyntaxNode::new_root(data.green.clone());
+
+        // The preamble does not contribute to the text of the document.
+        let body = root
+            .descendants()
+            .filter_map(latex::Environment::cast)
+            .find(|environment| environment_name(environment).as_deref() == Some("document"))
+            .map_or(root, |environment| environment.syntax().clone());
+
+        self.visit_node(document, &body);
+    }
+
+    fn visit_node(&mut self, document: &Document, node: &latex::SyntaxNode) {
+        for child in node.children_with_tokens() {
+            let child = match child {
+                latex::SyntaxElement::Node(child) => child,
+                latex::SyntaxElement::Token(token) => {
+                    if node.kind() == latex::TEXT && token.kind() == latex::WORD {
+                        self.add_word(token.text());
+                    }
+
+                    continue;
+                }
+            };
+
+            match child.kind() {
+                latex::BEGIN
+                | latex::END
+                | latex::FORMULA
+                | latex::EQUATION
+                | latex::COMMAND_DEFINITION
+                | latex::MATH_OPERATOR
+                | latex::ENVIRONMENT_DEFINITION
+                | latex::THEOREM_DEFINITION
+                | latex::GLOSSARY_ENTRY_DEFINITION
+                | latex::ACRONYM_DEFINITION
+                | latex::ACRONYM_DECLARATION
+                | latex::COLOR_DEFINITION
+                | latex::COLOR_SET_DEFINITION
+                | latex::GRAPHICS_PATH
+                | latex::BLOCK_COMMENT => {}
+                latex::LATEX_INCLUDE => self.visit_include(document, &child),
+                latex::ENVIRONMENT => {
+                    let is_math = latex::Environment::cast(child.clone())
+                        .and_then(|environment| environment_name(&environment))
+                        .map_or(false, |name| {
+                            LANGUAGE_DATA.math_environments.contains(&name)
+                        });
+
+                    if !is_math {
+                        self.visit_node(document, &child);
+                    }
+                }
+                _ => {
+                    if let Some(section) = latex::Section::cast(child.clone()) {
+                        self.result.sections.push(SectionWordCount {
+                            title: section
+                                .name()
+                                .and_then(|name| name.content_text())
+                                .unwrap_or_default(),
+                            level: section.level(),
+                            words: 0,
+                            characters: 0,
+                        });
+                    }
+
+                    self.visit_node(document, &child);
+                }
+            }
+        }
+    }
+
+    fn visit_include(&mut self, document: &Document, node: &latex::SyntaxNode) {
+        let data = match document.data.as_latex() {
+            Some(data) => data,
+            None => return,
+        };
+
+        let targets = data
+            .extras
+            .explicit_links
+            .iter()
+            .filter(|link| !link.is_excluded && node.text_range().contains_range(link.stem_range))
+            .filter_map(|link| {
+                link.targets
+                    .iter()
+                    .find_map(|target| self.workspace.documents_by_uri.get(target))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for target in targets {
+            self.visit_document(&target);
+        }
+    }
+
+    fn add_word(&mut self, word: &str) {
+        let characters = word.chars().filter(|c| c.is_alphanumeric()).count();
+        if characters == 0 {
+            return;
+        }
+
+        self.result.words += 1;
+        self.result.characters += characters;
+        if let Some(section) = self.result.sections.last_mut() {
+            section.words += 1;
+            section.characters += characters;
+        }
+    }
+}
+
+fn environment_name(environment: &latex::Environment) -> Option<String> {
+    Some(environment.begin()?.name()?.key()?.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use crate::DocumentLanguage;
+
+    use super::*;
+
+    #[test]
+    fn test_count_words() -> Result<()> {
+        let mut workspace = Workspace::default();
+        workspace.open(
+            Arc::new(Url::parse("http://example.com/main.tex")?),
+            Arc::new(
+                r#"\documentclass{article}
+\newcommand{\foo}{Not counted}
+\begin{document}
+\section{Introduction}
+Hello \emph{World}, % a comment
+$a + b$ and \cite{foo}.
+\include{chapter}
+\end{document}"#
+                    .to_string(),
+            ),
+            DocumentLanguage::Latex,
+        )?;
+
+        workspace.open(
+            Arc::new(Url::parse("http://example.com/chapter.tex")?),
+            Arc::new("\\subsection{Details}\nMore text\n\\begin{equation}x\\end{equation}".into()),
+            DocumentLanguage::Latex,
+        )?;
+
+        let count = count_words(&workspace, &Url::parse("http://example.com/chapter.tex")?);
+        assert_eq!(
+            count,
+            WordCount {
+                words: 7,
+                characters: 40,
+                sections: vec![
+                    SectionWordCount {
+                        title: "Introduction".into(),
+                        level: 2,
+                        words: 4,
+                        characters: 25,
+                    },
+                    SectionWordCount {
+                        title: "Details".into(),
+                        level: 3,
+                        words: 3,
+                        characters: 15,
+                    },
+                ],
+            }
+        );
+
+        Ok(())
+    }
+}
@@ -51,7 +51,7 @@ pub enum LabelledObject {
     },
     Float {
         kind: LabelledFloatKind,
-        caption: String,
+        caption: Option<String>,
     },
     Theorem {
         kind: String,
@@ -64,6 +64,8 @@ pub enum LabelledObject {
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct RenderedLabel {
     pub range: TextRange,
+    /// The range of the caption text if the label belongs to a float with a caption.
+    pub caption_range: Option<TextRange>,
     pub number: Option<String>,
     pub object: LabelledObject,
 }
@@ -74,7 +76,14 @@ impl RenderedLabel {
         match &self.number {
             Some(number) => match &self.object {
                 Section { prefix, text } => format!("{} {} ({})", prefix, number, text),
-                Float { kind, caption } => format!("{} {}: {}", kind.as_str(), number, caption),
+                Float {
+                    kind,
+                    caption: None,
+                } => format!("{} {}", kind.as_str(), number),
+                Float {
+                    kind,
+                    caption: Some(caption),
+                } => format!("{} {}: {}", kind.as_str(), number, caption),
                 Theorem {
                     kind,
                     description: None,
@@ -88,7 +97,14 @@ impl RenderedLabel {
             },
             None => match &self.object {
                 Section { prefix, text } => format!("{} ({})", prefix, text),
-                Float { kind, caption } => format!("{}: {}", kind.as_str(), caption),
+                Float {
+                    kind,
+                    caption: None,
+                } => kind.as_str().into(),
+                Float {
+                    kind,
+                    caption: Some(caption),
+                } => format!("{}: {}", kind.as_str(), caption),
                 Theorem {
                     kind,
                     description: None,
@@ -166,14 +182,24 @@ pub fn find_label_definition(
         })
 }
 
+/// Finds the number of a label, preferring the `\newlabel` entries of the `.aux` files
+/// produced by the compiler over the ones written in the sources.
 pub fn find_label_number<'a>(workspace: &'a Workspace, label_name: &str) -> Option<&'a str> {
-    workspace.documents_by_uri.values().find_map(|document| {
-        document
-            .data
-            .as_latex()
-            .and_then(|data| data.extras.label_numbers_by_name.get(label_name))
-            .map(|number| number.as_str())
-    })
+    let find_number = |is_aux: bool| {
+        workspace
+            .documents_by_uri
+            .values()
+            .filter(|document| document.uri.path().ends_with(".aux") == is_aux)
+            .find_map(|document| {
+                document
+                    .data
+                    .as_latex()
+                    .and_then(|data| data.extras.label_numbers_by_name.get(label_name))
+                    .map(|number| number.as_str())
+            })
+    };
+
+    find_number(true).or_else(|| find_number(false))
 }
 
 fn render_label_float(
@@ -183,9 +209,10 @@ fn render_label_float(
     let environment = latex::Environment::cast(parent.clone())?;
     let environment_name = environment.begin()?.name()?.key()?.to_string();
     let kind = LabelledFloatKind::from_str(&environment_name).ok()?;
-    let caption = find_caption_by_parent(&parent)?;
+    let caption = find_caption_by_parent(&parent);
     Some(RenderedLabel {
         range: latex::small_range(&environment),
+        caption_range: find_caption_range_by_parent(&parent),
         number: number.take(),
         object: LabelledObject::Float { caption, kind },
     })
@@ -201,6 +228,7 @@ fn render_label_section(
 
     Some(RenderedLabel {
         range: latex::small_range(&section),
+        caption_range: None,
         number: number.take(),
         object: LabelledObject::Section {
             prefix: match section.syntax().kind() {
@@ -225,6 +253,7 @@ fn render_label_enum_item(
     let enum_item = latex::EnumItem::cast(parent)?;
     Some(RenderedLabel {
         range: latex::small_range(&enum_item),
+        caption_range: None,
         number: enum_item
             .label()
             .and_then(|number| number.content_text())
@@ -237,24 +266,35 @@ fn render_label_equation(
     parent: latex::SyntaxNode,
     number: &mut Option<String>,
 ) -> Option<RenderedLabel> {
-    let environment = latex::Environment::cast(parent)?;
-    let environment_name = environment.begin()?.name()?.key()?.to_string();
+    let environment = latex::Environment::cast(parent).filter(is_math_environment)?;
 
-    if !LANGUAGE_DATA
-        .math_environments
-        .iter()
-        .any(|name| name == &environment_name)
-    {
-        return None;
-    }
+    // Nested environments like `aligned` belong to the surrounding equation.
+    let environment = environment
+        .syntax()
+        .ancestors()
+        .map_while(latex::Environment::cast)
+        .take_while(is_math_environment)
+        .last()
+        .unwrap_or(environment);
 
     Some(RenderedLabel {
         range: latex::small_range(&environment),
+        caption_range: None,
         number: number.take(),
         object: LabelledObject::Equation,
     })
 }
 
+fn is_math_environment(environment: &latex::Environment) -> bool {
+    environment
+        .begin()
+        .and_then(|begin| begin.name())
+        .and_then(|name| name.key())
+        .map_or(false, |name| {
+            LANGUAGE_DATA.math_environments.contains(&name.to_string())
+        })
+}
+
 fn render_label_theorem(
     workspace: &Workspace,
     parent: latex::SyntaxNode,
@@ -277,6 +317,7 @@ fn render_label_theorem(
 
     Some(RenderedLabel {
         range: latex::small_range(&environment),
+        caption_range: None,
         number: number.take(),
         object: LabelledObject::Theorem {
             kind: theorem.description.clone(),
@@ -286,9 +327,40 @@ fn render_label_theorem(
 }
 
 pub fn find_caption_by_parent(parent: &latex::SyntaxNode) -> Option<String> {
-    parent
+    let group = parent
+        .children()
+        .filter_map(latex::Caption::cast)
+        .find_map(|node| node.long())?;
+
+    group.left_curly()?;
+    group.right_curly()?;
+
+    // A label inside of the caption is not part of its text.
+    let text: String = group
+        .syntax()
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| token.kind() != latex::COMMENT)
+        .filter(|token| {
+            !token
+                .parent_ancestors()
+                .any(|node| latex::LabelDefinition::can_cast(node.kind()))
+        })
+        .map(|token| token.text().to_string())
+        .collect();
+
+    let text = text.trim_end();
+    Some(text[1..text.len() - 1].trim().to_string())
+}
+
+fn find_caption_range_by_parent(parent: &latex::SyntaxNode) -> Option<TextRange> {
+    let group = parent
         .children()
         .filter_map(latex::Caption::cast)
-        .find_map(|node| node.long())
-        .and_then(|node| node.content_text())
+        .find_map(|node| node.long())?;
+
+    Some(TextRange::new(
+        group.left_curly()?.text_range().end(),
+        group.right_curly()?.text_range().start(),
+    ))
 }
@@ -1,4 +1,5 @@
 use once_cell::sync::Lazy;
+use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
@@ -19,6 +20,30 @@ pub struct BibtexEntryTypeDoc {
     pub name: String,
     pub category: BibtexEntryTypeCategory,
     pub documentation: Option<String>,
+    #[serde(default)]
+    pub required_fields: Vec<String>,
+    #[serde(default)]
+    pub optional_fields: Vec<String>,
+}
+
+impl BibtexEntryTypeDoc {
+    #[must_use]
+    pub fn field_requirement(&self, name: &str) -> Option<BibtexFieldRequirement> {
+        let name = name.to_lowercase();
+        if self.required_fields.contains(&name) {
+            Some(BibtexFieldRequirement::Required)
+        } else if self.optional_fields.contains(&name) {
+            Some(BibtexFieldRequirement::Optional)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BibtexFieldRequirement {
+    Required,
+    Optional,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -28,6 +53,47 @@ pub struct BibtexFieldDoc {
     pub documentation: String,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageOptionDoc {
+    pub name: String,
+    #[serde(default)]
+    pub values: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MathSymbolCategory {
+    GreekLetter,
+    BinaryOperator,
+    LargeOperator,
+    Relation,
+    Arrow,
+    Miscellaneous,
+}
+
+impl MathSymbolCategory {
+    #[must_use]
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::GreekLetter => "greek letter",
+            Self::BinaryOperator => "binary operator",
+            Self::LargeOperator => "large operator",
+            Self::Relation => "relation",
+            Self::Arrow => "arrow",
+            Self::Miscellaneous => "symbol",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MathSymbolDoc {
+    pub name: String,
+    pub glyph: String,
+    pub category: MathSymbolCategory,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LanguageData {
@@ -38,6 +104,21 @@ pub struct LanguageData {
     pub tikz_libraries: Vec<String>,
     pub math_environments: Vec<String>,
     pub enum_environments: Vec<String>,
+    #[serde(default)]
+    pub package_options: FxHashMap<String, Vec<PackageOptionDoc>>,
+    #[serde(default)]
+    pub class_options: FxHashMap<String, Vec<PackageOptionDoc>>,
+    #[serde(default)]
+    pub tikz_library_docs: FxHashMap<String, String>,
+    #[serde(default)]
+    pub environment_arguments: FxHashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub tikz_keys: Vec<String>,
+    /// The counters that are defined by the LaTeX kernel and the standard classes.
+    #[serde(default)]
+    pub counters: Vec<String>,
+    #[serde(default)]
+    pub math_symbols: Vec<MathSymbolDoc>,
 }
 
 impl LanguageData {
@@ -1,6 +1,7 @@
 mod capabilities;
 pub mod citation;
 mod client;
+mod command;
 pub mod component_db;
 mod debouncer;
 mod diagnostics;
@@ -14,14 +15,17 @@ mod lang_data;
 mod language;
 mod line_index;
 mod line_index_ext;
+pub mod logging;
 mod options;
 mod range;
 mod server;
 pub mod syntax;
+mod typo;
 mod workspace;
 
 pub use self::{
     capabilities::ClientCapabilitiesExt,
+    command::*,
     document::*,
     environment::Environment,
     label::*,
@@ -32,5 +36,6 @@ pub use self::{
     options::*,
     range::RangeExt,
     server::Server,
+    typo::KnownEnvironments,
     workspace::{Workspace, WorkspaceEvent},
 };
@@ -11,6 +11,8 @@ use rustc_hash::FxHashMap;
 pub struct LineIndex {
     /// Offset the the beginning of each line, zero-based
     pub(crate) newlines: Vec<TextSize>,
+    /// Offset of the end of each line excluding the line terminator, zero-based
+    pub(crate) line_ends: Vec<TextSize>,
     /// List of non-ASCII characters on each line
     pub(crate) utf16_lines: FxHashMap<u32, Vec<Utf16Char>>,
 }
@@ -61,13 +63,19 @@ impl LineIndex {
         let mut utf16_chars = Vec::new();
 
         let mut newlines = vec![0.into()];
+        let mut line_ends = Vec::new();
         let mut curr_row = 0.into();
         let mut curr_col = 0.into();
         let mut line = 0;
-        for c in text.chars() {
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
             let c_len = TextSize::of(c);
             curr_row += c_len;
-            if c == '\n' {
+
+            // `\r\n` is terminated by the `\n` but a lone `\r` ends a line as well
+            let is_crlf = c == '\r' && chars.peek() == Some(&'\n');
+            if c == '\n' || (c == '\r' && !is_crlf) {
+                line_ends.push(newlines[line as usize] + curr_col);
                 newlines.push(curr_row);
 
                 // Save any utf-16 characters seen in the previous line
@@ -89,9 +97,13 @@ impl LineIndex {
                 });
             }
 
-            curr_col += c_len;
+            if !is_crlf {
+                curr_col += c_len;
+            }
         }
 
+        line_ends.push(newlines[line as usize] + curr_col);
+
         // Save any utf-16 characters seen in the last line
         if !utf16_chars.is_empty() {
             utf16_lines.insert(line, utf16_chars);
@@ -99,10 +111,82 @@ impl LineIndex {
 
         LineIndex {
             newlines,
+            line_ends,
             utf16_lines,
         }
     }
 
+    /// Updates the index after `range` of the previous text has been replaced with `len` bytes.
+    ///
+    /// `text` is the text after applying the edit. Only the lines touched by the edit are rescanned.
+    pub fn apply_edit(&mut self, text: &str, range: TextRange, len: TextSize) {
+        let delta = i64::from(u32::from(len)) - i64::from(u32::from(range.len()));
+        let shift =
+            |offset: TextSize| TextSize::from((i64::from(u32::from(offset)) + delta) as u32);
+
+        // Inserting a `\n` after a lone `\r` merges both into a single line terminator
+        let mut start_line = self.line_col(range.start()).line as usize;
+        if start_line > 0 && text[..usize::from(self.newlines[start_line])].ends_with('\r') {
+            start_line -= 1;
+        }
+
+        let end_line = self.line_col(range.end()).line as usize;
+        let region_start = self.newlines[start_line];
+        let region_end = self
+            .newlines
+            .get(end_line + 1)
+            .map_or(TextSize::of(text), |&end| shift(end));
+
+        let mut region = LineIndex::new(&text[TextRange::new(region_start, region_end)]);
+        if end_line + 1 < self.newlines.len() {
+            // The last line of the region is the start of the next unchanged line
+            region.newlines.pop();
+            region.line_ends.pop();
+        }
+
+        let old_count = end_line + 1 - start_line;
+        let new_count = region.newlines.len();
+        let tail = end_line + 1;
+
+        let newlines = self.newlines[..start_line]
+            .iter()
+            .copied()
+            .chain(region.newlines.iter().map(|&offset| region_start + offset))
+            .chain(self.newlines[tail..].iter().map(|&offset| shift(offset)))
+            .collect();
+
+        let line_ends = self.line_ends[..start_line]
+            .iter()
+            .copied()
+            .chain(region.line_ends.iter().map(|&offset| region_start + offset))
+            .chain(self.line_ends[tail..].iter().map(|&offset| shift(offset)))
+            .collect();
+
+        let utf16_lines = std::mem::take(&mut self.utf16_lines)
+            .into_iter()
+            .filter_map(|(line, chars)| {
+                let line = line as usize;
+                if line < start_line {
+                    Some((line as u32, chars))
+                } else if line >= tail {
+                    Some(((line + new_count - old_count) as u32, chars))
+                } else {
+                    None
+                }
+            })
+            .chain(
+                region
+                    .utf16_lines
+                    .into_iter()
+                    .map(|(line, chars)| (line + start_line as u32, chars)),
+            )
+            .collect();
+
+        self.newlines = newlines;
+        self.line_ends = line_ends;
+        self.utf16_lines = utf16_lines;
+    }
+
     pub fn line_col(&self, offset: TextSize) -> LineCol {
         let line = partition_point(&self.newlines, |&it| it <= offset) - 1;
         let line_start_offset = self.newlines[line];
@@ -113,8 +197,14 @@ impl LineIndex {
         }
     }
 
+    /// Positions after the end of a line or the document are clamped to the end of the line
+    /// or the document respectively.
     pub fn offset(&self, line_col: LineCol) -> TextSize {
-        self.newlines[line_col.line as usize] + TextSize::from(line_col.col)
+        let line = line_col.line as usize;
+        match (self.newlines.get(line), self.line_ends.get(line)) {
+            (Some(&start), Some(&end)) => (start + TextSize::from(line_col.col)).min(end),
+            _ => self.line_ends.last().copied().unwrap_or_default(),
+        }
     }
 
     pub fn to_utf16(&self, line_col: LineCol) -> LineColUtf16 {
@@ -215,3 +305,80 @@ where
 
     left
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_endings() {
+        let index = LineIndex::new("a\nbc\r\nd\re");
+        assert_eq!(index.newlines, vec![0.into(), 2.into(), 6.into(), 8.into()]);
+        assert_eq!(
+            index.line_ends,
+            vec![1.into(), 4.into(), 7.into(), 9.into()]
+        );
+
+        assert_eq!(index.line_col(7.into()), LineCol { line: 2, col: 1 });
+        assert_eq!(index.line_col(8.into()), LineCol { line: 3, col: 0 });
+    }
+
+    #[test]
+    fn test_offset_clamped() {
+        let index = LineIndex::new("foo\r\nbar");
+        assert_eq!(index.offset(LineCol { line: 0, col: 3 }), 3.into());
+        assert_eq!(index.offset(LineCol { line: 0, col: 4 }), 3.into());
+        assert_eq!(index.offset(LineCol { line: 1, col: 10 }), 8.into());
+        assert_eq!(index.offset(LineCol { line: 5, col: 0 }), 8.into());
+    }
+
+    fn assert_apply_edit(text: &str, range: std::ops::Range<u32>, replace_with: &str) {
+        let range = TextRange::new(range.start.into(), range.end.into());
+        let mut new_text = text.to_string();
+        new_text.replace_range(std::ops::Range::<usize>::from(range), replace_with);
+
+        let mut index = LineIndex::new(text);
+        index.apply_edit(&new_text, range, TextSize::of(replace_with));
+        assert_eq!(index, LineIndex::new(&new_text));
+    }
+
+    #[test]
+    fn test_apply_edit_same_line() {
+        assert_apply_edit("foo\nbar\nbaz", 4..7, "qux");
+    }
+
+    #[test]
+    fn test_apply_edit_insert_lines() {
+        assert_apply_edit("foo\nbar\nbaz", 5..5, "\n\nä\n");
+    }
+
+    #[test]
+    fn test_apply_edit_delete_lines() {
+        assert_apply_edit("foo\nbär\nbaz\nqüx", 2..10, "");
+    }
+
+    #[test]
+    fn test_apply_edit_last_line() {
+        assert_apply_edit("foo\nbar", 7..7, "\r\n𐐀");
+    }
+
+    #[test]
+    fn test_apply_edit_merge_crlf() {
+        assert_apply_edit("foo\rbar\nbaz", 4..4, "\n");
+    }
+
+    #[test]
+    fn test_apply_edit_split_crlf() {
+        assert_apply_edit("foo\r\nbar\nbaz", 4..4, "x");
+    }
+
+    #[test]
+    fn test_apply_edit_merge_crlf_by_deletion() {
+        assert_apply_edit("a\rb\n\nc", 2..3, "");
+    }
+
+    #[test]
+    fn test_apply_edit_empty() {
+        assert_apply_edit("", 0..0, "foo\nbar");
+    }
+}
@@ -0,0 +1,217 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use anyhow::Result;
+use log::{LevelFilter, Log, Metadata, Record};
+use once_cell::sync::OnceCell;
+
+use crate::{LogLevel, LogOptions};
+
+static LOGGER: OnceCell<&'static Logger> = OnceCell::new();
+
+/// Installs the global logger.
+///
+/// The given level and file are used as long as they are not overridden by the `log` options.
+/// In quiet mode, the logger is disabled regardless of the options.
+pub fn init(level: LevelFilter, quiet: bool, file: Option<PathBuf>) -> Result<()> {
+    let logger: &'static Logger = Box::leak(Box::new(Logger::new(level, quiet, file)));
+    log::set_logger(logger).map_err(|why| anyhow::anyhow!("{}", why))?;
+    log::set_max_level(logger.state.lock().unwrap().level);
+    let _ = LOGGER.set(logger);
+    Ok(())
+}
+
+/// Applies the `log` options to the global logger if it has been installed.
+pub fn configure(options: &LogOptions) {
+    if let Some(logger) = LOGGER.get() {
+        log::set_max_level(logger.configure(options));
+    }
+}
+
+pub struct Logger {
+    quiet: bool,
+    default_level: LevelFilter,
+    default_file: Option<PathBuf>,
+    state: Mutex<LoggerState>,
+}
+
+struct LoggerState {
+    level: LevelFilter,
+    file: Option<LogFile>,
+}
+
+struct LogFile {
+    path: PathBuf,
+    writer: File,
+}
+
+impl LogFile {
+    fn open(path: &Path) -> io::Result<Self> {
+        let writer = OpenOptions::new().append(true).create(true).open(path)?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            writer,
+        })
+    }
+}
+
+impl Logger {
+    #[must_use]
+    pub fn new(level: LevelFilter, quiet: bool, file: Option<PathBuf>) -> Self {
+        let state = LoggerState {
+            level: if quiet { LevelFilter::Off } else { level },
+            file: file.as_deref().and_then(|path| LogFile::open(path).ok()),
+        };
+
+        Self {
+            quiet,
+            default_level: level,
+            default_file: file,
+            state: Mutex::new(state),
+        }
+    }
+
+    /// Updates the level and the log file and returns the new level.
+    pub fn configure(&self, options: &LogOptions) -> LevelFilter {
+        let mut state = self.state.lock().unwrap();
+        state.level = if self.quiet {
+            LevelFilter::Off
+        } else {
+            options
+                .level
+                .map_or(self.default_level, LogLevel::to_level_filter)
+        };
+
+        let path = options.file.as_ref().or(self.default_file.as_ref());
+        let is_unchanged = match (&state.file, path) {
+            (Some(file), Some(path)) => file.path == *path,
+            (None, None) => true,
+            _ => false,
+        };
+
+        let mut error = None;
+        if !is_unchanged {
+            state.file = path.and_then(|path| match LogFile::open(path) {
+                Ok(file) => Some(file),
+                Err(why) => {
+                    error = Some(format!(
+                        "Failed to open log file {}: {}",
+                        path.display(),
+                        why
+                    ));
+                    None
+                }
+            });
+        }
+
+        let level = state.level;
+        drop(state);
+
+        // The state must be unlocked before logging because `log` locks it as well.
+        if let Some(error) = error {
+            log::error!("{}", error);
+        }
+
+        level
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        (metadata.target().contains("texlab") || metadata.target().contains("lsp_server"))
+            && metadata.level() <= self.state.lock().unwrap().level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let line = format!("{} - {}\n", record.level(), record.args());
+        let _ = io::stderr().write_all(line.as_bytes());
+
+        if let Some(file) = &mut self.state.lock().unwrap().file {
+            let _ = file.writer.write_all(line.as_bytes());
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(file) = &mut self.state.lock().unwrap().file {
+            let _ = file.writer.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use log::Level;
+
+    use super::*;
+
+    #[test]
+    fn test_write_to_configured_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("texlab.log");
+        let logger = Logger::new(LevelFilter::Error, false, None);
+        logger.configure(&LogOptions {
+            level: Some(LogLevel::Info),
+            file: Some(path.clone()),
+        });
+
+        for (level, message) in [(Level::Info, "foo"), (Level::Debug, "bar")] {
+            logger.log(
+                &Record::builder()
+                    .args(format_args!("{}", message))
+                    .level(level)
+                    .target("texlab::server")
+                    .build(),
+            );
+        }
+
+        logger.flush();
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "INFO - foo\n");
+    }
+
+    #[test]
+    fn test_append_to_existing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("texlab.log");
+        std::fs::write(&path, "ERROR - foo\n").unwrap();
+        let logger = Logger::new(LevelFilter::Error, false, Some(path.clone()));
+        logger.log(
+            &Record::builder()
+                .args(format_args!("bar"))
+                .level(Level::Error)
+                .target("texlab::server")
+                .build(),
+        );
+
+        logger.flush();
+        assert_eq!(
+            std::fs::read_to_string(path).unwrap(),
+            "ERROR - foo\nERROR - bar\n"
+        );
+    }
+
+    #[test]
+    fn test_quiet_ignores_configured_level() {
+        let logger = Logger::new(LevelFilter::Error, true, None);
+        let level = logger.configure(&LogOptions {
+            level: Some(LogLevel::Info),
+            file: None,
+        });
+
+        assert_eq!(level, LevelFilter::Off);
+        assert!(!logger.enabled(
+            &Metadata::builder()
+                .level(Level::Error)
+                .target("texlab::server")
+                .build()
+        ));
+    }
+}
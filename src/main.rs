@@ -1,10 +1,10 @@
-use std::{env, fs::OpenOptions, io, path::PathBuf};
+use std::{env, path::PathBuf};
 
 use anyhow::Result;
 use clap::{ArgAction, Parser};
 use log::LevelFilter;
 use lsp_server::Connection;
-use texlab::Server;
+use texlab::{logging, Server};
 
 /// An implementation of the Language Server Protocol for LaTeX
 #[derive(Debug, Parser)]
@@ -14,7 +14,7 @@ struct Opts {
     #[clap(short, long, action = ArgAction::Count)]
     verbosity: u8,
 
-    /// No output printed to stderr
+    /// No output printed to stderr or the log file
     #[clap(short, long)]
     quiet: bool,
 
@@ -39,36 +39,13 @@ fn main() -> Result<()> {
 }
 
 fn setup_logger(opts: Opts) {
-    let verbosity_level = if !opts.quiet {
-        match opts.verbosity {
-            0 => LevelFilter::Error,
-            1 => LevelFilter::Warn,
-            2 => LevelFilter::Info,
-            3 => LevelFilter::Debug,
-            _ => LevelFilter::Trace,
-        }
-    } else {
-        LevelFilter::Off
+    let verbosity_level = match opts.verbosity {
+        0 => LevelFilter::Error,
+        1 => LevelFilter::Warn,
+        2 => LevelFilter::Info,
+        3 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
     };
 
-    let logger = fern::Dispatch::new()
-        .format(|out, message, record| out.finish(format_args!("{} - {}", record.level(), message)))
-        .level(verbosity_level)
-        .filter(|metadata| {
-            metadata.target().contains("texlab") || metadata.target().contains("lsp_server")
-        })
-        .chain(io::stderr());
-
-    let logger = match opts.log_file {
-        Some(log_file) => logger.chain(
-            OpenOptions::new()
-                .write(true)
-                .create(true)
-                .open(log_file)
-                .expect("failed to open log file"),
-        ),
-        None => logger,
-    };
-
-    logger.apply().expect("failed to initialize logger");
+    logging::init(verbosity_level, opts.quiet, opts.log_file).expect("failed to initialize logger");
 }
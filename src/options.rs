@@ -1,23 +1,92 @@
-use std::{path::PathBuf, time::Duration};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
+use log::LevelFilter;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
+use crate::DocumentLanguage;
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(default)]
 pub struct Options {
     pub root_directory: Option<PathBuf>,
     pub aux_directory: Option<PathBuf>,
+    pub output_directory: Option<PathBuf>,
+    pub exclude_globs: ExcludeGlobs,
+    pub max_file_size_bytes: Option<u64>,
+    pub lazy_loading: bool,
+    pub single_file_mode: bool,
+    pub resolve_symlinks: bool,
+    pub extra_latex_extensions: Vec<String>,
+    pub extra_bibtex_extensions: Vec<String>,
+    pub bibtex_directories: Vec<PathBuf>,
     pub bibtex_formatter: BibtexFormatter,
     pub latex_formatter: LatexFormatter,
     pub formatter_line_length: Option<i32>,
+    pub normalize_bibtex_keys: bool,
+    pub auto_close_environments: bool,
+    pub citation: CitationOptions,
     pub diagnostics: DiagnosticsOptions,
     pub diagnostics_delay: DiagnosticsDelay,
     pub build: BuildOptions,
     pub chktex: ChktexOptions,
     pub latexindent: LatexindentOptions,
     pub forward_search: ForwardSearchOptions,
+    pub symbols: SymbolOptions,
+    pub log: LogOptions,
+}
+
+impl Options {
+    /// Returns `true` if the text exceeds the `maxFileSizeBytes` option and should not be parsed.
+    #[must_use]
+    pub fn exceeds_max_file_size(&self, text: &str) -> bool {
+        self.max_file_size_bytes
+            .map_or(false, |max_size| text.len() as u64 > max_size)
+    }
+
+    /// Determines the language of a file from its extension.
+    ///
+    /// `extraLatexExtensions` and `extraBibtexExtensions` take precedence over the built-in extensions.
+    #[must_use]
+    pub fn language_by_path(&self, path: &Path) -> Option<DocumentLanguage> {
+        if self.is_extra_latex_file(path) {
+            Some(DocumentLanguage::Latex)
+        } else if has_extension(path, &self.extra_bibtex_extensions) {
+            Some(DocumentLanguage::Bibtex)
+        } else {
+            DocumentLanguage::by_path(path)
+        }
+    }
+
+    /// Returns `true` if the file has one of the extensions of `extraLatexExtensions`.
+    #[must_use]
+    pub fn is_extra_latex_file(&self, path: &Path) -> bool {
+        has_extension(path, &self.extra_latex_extensions)
+    }
+
+    /// Returns the delay before publishing diagnostics.
+    ///
+    /// `diagnostics.delay` takes precedence over the older `diagnosticsDelay` option.
+    #[must_use]
+    pub fn diagnostics_delay(&self) -> Duration {
+        self.diagnostics.delay.unwrap_or(self.diagnostics_delay).0
+    }
+}
+
+fn has_extension(path: &Path, extensions: &[String]) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .map_or(false, |extension| {
+            extensions.iter().any(|other| {
+                other
+                    .trim_start_matches('.')
+                    .eq_ignore_ascii_case(extension)
+            })
+        })
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
@@ -55,14 +124,64 @@ impl Default for LatexFormatter {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct CitationOptions {
+    pub style: CitationStyle,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CitationStyle {
+    Numeric,
+    Alpha,
+    AuthorYear,
+}
+
+impl Default for CitationStyle {
+    fn default() -> Self {
+        Self::AuthorYear
+    }
+}
+
+impl CitationStyle {
+    /// Maps a BibTeX style given by `\bibliographystyle` to the citation style it produces.
+    #[must_use]
+    pub fn from_bibliography_style(name: &str) -> Option<Self> {
+        match name {
+            "plain" | "plainurl" | "unsrt" | "unsrturl" | "abbrv" | "abbrvurl" | "acm"
+            | "ieeetr" | "IEEEtran" | "siam" | "splncs04" => Some(Self::Numeric),
+            "alpha" | "alphaurl" | "amsalpha" => Some(Self::Alpha),
+            "plainnat" | "unsrtnat" | "abbrvnat" | "apalike" | "agsm" | "chicago" | "apa"
+            | "authordate1" | "authordate2" | "authordate3" | "authordate4" => {
+                Some(Self::AuthorYear)
+            }
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(default)]
 pub struct LatexindentOptions {
+    pub executable: LatexindentExecutable,
+    pub args: Vec<String>,
     pub local: Option<String>,
     pub modify_line_breaks: bool,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct LatexindentExecutable(pub String);
+
+impl Default for LatexindentExecutable {
+    fn default() -> Self {
+        Self("latexindent".to_string())
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(default)]
@@ -70,6 +189,7 @@ pub struct BuildOptions {
     pub executable: BuildExecutable,
     pub args: BuildArgs,
     pub on_save: bool,
+    pub on_open_if_missing: bool,
     pub forward_search_after: bool,
 }
 
@@ -104,6 +224,7 @@ impl Default for BuildArgs {
 pub struct ChktexOptions {
     pub on_open_and_save: bool,
     pub on_edit: bool,
+    pub disabled_codes: Vec<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
@@ -114,13 +235,315 @@ pub struct ForwardSearchOptions {
     pub args: Option<Vec<String>>,
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct SymbolOptions {
+    pub custom_section_commands: Vec<CustomSectionCommand>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct CustomSectionCommand {
+    /// The name of the command without the leading backslash.
+    pub name: String,
+    pub level: SectionLevel,
+}
+
+/// The standard sectioning command whose nesting level is used for a custom sectioning command.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SectionLevel {
+    Part,
+    Chapter,
+    Section,
+    Subsection,
+    Subsubsection,
+    Paragraph,
+    Subparagraph,
+}
+
+impl SectionLevel {
+    /// Returns the nesting level, starting with 0 for `\part` like `Section::level`.
+    #[must_use]
+    pub fn depth(self) -> usize {
+        self as usize
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(default)]
 pub struct DiagnosticsOptions {
     pub allowed_patterns: Vec<DiagnosticsPattern>,
     pub ignored_patterns: Vec<DiagnosticsPattern>,
+    pub build_log_after_build_only: bool,
+    pub delay: Option<DiagnosticsDelay>,
+    pub style_checks: StyleChecksOptions,
+    pub show_parser_errors: bool,
+    pub missing_packages: bool,
+    pub unescaped_characters: bool,
+}
+
+impl Default for DiagnosticsOptions {
+    fn default() -> Self {
+        Self {
+            allowed_patterns: Vec::new(),
+            ignored_patterns: Vec::new(),
+            build_log_after_build_only: false,
+            delay: None,
+            style_checks: StyleChecksOptions::default(),
+            show_parser_errors: false,
+            missing_packages: false,
+            unescaped_characters: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct StyleChecksOptions {
+    pub enabled: bool,
+    pub long_words: bool,
+    pub math_spacing: bool,
+    pub non_breaking_spaces: bool,
+}
+
+impl Default for StyleChecksOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            long_words: true,
+            math_spacing: true,
+            non_breaking_spaces: true,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiagnosticsPattern(#[serde(with = "serde_regex")] pub Regex);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ExcludeGlobs(pub Vec<GlobPattern>);
+
+impl ExcludeGlobs {
+    /// Checks whether the file or directory is matched by a glob or lies inside of an excluded directory.
+    ///
+    /// Only the part of the path below the base directory is matched,
+    /// so a project that is located inside of a directory like `build` is not excluded.
+    /// Paths outside of the base directory are matched as a whole.
+    #[must_use]
+    pub fn is_excluded(&self, path: &Path, base_dir: &Path) -> bool {
+        let candidates: Vec<_> = match path.strip_prefix(base_dir) {
+            Ok(relative_path) => relative_path
+                .ancestors()
+                .filter(|path| !path.as_os_str().is_empty())
+                .collect(),
+            Err(_) => vec![path],
+        };
+
+        candidates.into_iter().any(|path| {
+            // Appending a separator matches the directory against globs like `**/node_modules/**`.
+            let contents = path.join("");
+            self.0
+                .iter()
+                .any(|glob| glob.is_match(path) || glob.is_match(&contents))
+        })
+    }
+}
+
+impl Default for ExcludeGlobs {
+    fn default() -> Self {
+        Self(
+            ["**/node_modules/**", "**/.git/**"]
+                .into_iter()
+                .map(|glob| GlobPattern::new(glob.to_string()).unwrap())
+                .collect(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct GlobPattern {
+    glob: String,
+    regex: Regex,
+}
+
+impl GlobPattern {
+    pub fn new(glob: String) -> Result<Self, regex::Error> {
+        let mut pattern = String::from("^");
+        let mut chars = glob.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '*' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    if chars.peek() == Some(&'/') {
+                        chars.next();
+                        pattern.push_str("(?:.*/)?");
+                    } else {
+                        pattern.push_str(".*");
+                    }
+                }
+                '*' => pattern.push_str("[^/]*"),
+                '?' => pattern.push_str("[^/]"),
+                _ => pattern.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+            };
+        }
+
+        pattern.push('$');
+        let regex = Regex::new(&pattern)?;
+        Ok(Self { glob, regex })
+    }
+
+    #[must_use]
+    pub fn is_match(&self, path: &Path) -> bool {
+        self.regex
+            .is_match(&path.to_string_lossy().replace('\\', "/"))
+    }
+}
+
+impl TryFrom<String> for GlobPattern {
+    type Error = regex::Error;
+
+    fn try_from(glob: String) -> Result<Self, Self::Error> {
+        Self::new(glob)
+    }
+}
+
+impl From<GlobPattern> for String {
+    fn from(pattern: GlobPattern) -> Self {
+        pattern.glob
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct LogOptions {
+    pub level: Option<LogLevel>,
+    pub file: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    #[must_use]
+    pub fn to_level_filter(self) -> LevelFilter {
+        match self {
+            Self::Off => LevelFilter::Off,
+            Self::Error => LevelFilter::Error,
+            Self::Warn => LevelFilter::Warn,
+            Self::Info => LevelFilter::Info,
+            Self::Debug => LevelFilter::Debug,
+            Self::Trace => LevelFilter::Trace,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_pattern() {
+        let glob = GlobPattern::new("**/node_modules/**".to_string()).unwrap();
+        assert!(glob.is_match(Path::new("/foo/node_modules/bar/baz.tex")));
+        assert!(!glob.is_match(Path::new("/foo/node_module/bar.tex")));
+
+        let glob = GlobPattern::new("/foo/*.tex".to_string()).unwrap();
+        assert!(glob.is_match(Path::new("/foo/bar.tex")));
+        assert!(!glob.is_match(Path::new("/foo/bar/baz.tex")));
+    }
+
+    #[test]
+    fn test_exclude_globs_directory() {
+        let globs = ExcludeGlobs(vec![
+            GlobPattern::new("**/build".to_string()).unwrap(),
+            GlobPattern::new("**/node_modules/**".to_string()).unwrap(),
+        ]);
+
+        let base_dir = Path::new("/foo");
+        assert!(globs.is_excluded(Path::new("/foo/build"), base_dir));
+        assert!(globs.is_excluded(Path::new("/foo/build/bar/baz.tex"), base_dir));
+        assert!(globs.is_excluded(Path::new("/foo/node_modules"), base_dir));
+        assert!(!globs.is_excluded(Path::new("/foo/builder/bar.tex"), base_dir));
+    }
+
+    #[test]
+    fn test_exclude_globs_base_directory() {
+        let globs = ExcludeGlobs(vec![
+            GlobPattern::new("**/build".to_string()).unwrap(),
+            GlobPattern::new("**/node_modules/**".to_string()).unwrap(),
+        ]);
+
+        let base_dir = Path::new("/build/node_modules/foo");
+        assert!(!globs.is_excluded(Path::new("/build/node_modules/foo/main.tex"), base_dir));
+        assert!(globs.is_excluded(
+            Path::new("/build/node_modules/foo/build/main.tex"),
+            base_dir
+        ));
+    }
+
+    #[test]
+    fn test_diagnostics_delay() {
+        let options: Options = serde_json::from_value(serde_json::json!({
+            "diagnosticsDelay": 500,
+        }))
+        .unwrap();
+        assert_eq!(options.diagnostics_delay(), Duration::from_millis(500));
+
+        let options: Options = serde_json::from_value(serde_json::json!({
+            "diagnosticsDelay": 500,
+            "diagnostics": { "delay": 50 },
+        }))
+        .unwrap();
+        assert_eq!(options.diagnostics_delay(), Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_latexindent() {
+        let options: Options = serde_json::from_value(serde_json::json!({
+            "latexindent": { "local": "indent.yaml" },
+        }))
+        .unwrap();
+        assert_eq!(options.latexindent.executable.0, "latexindent");
+        assert!(options.latexindent.args.is_empty());
+
+        let options: Options = serde_json::from_value(serde_json::json!({
+            "latexindent": {
+                "executable": "perl",
+                "args": ["latexindent.pl", "-m"],
+            },
+        }))
+        .unwrap();
+        assert_eq!(options.latexindent.executable.0, "perl");
+        assert_eq!(options.latexindent.args, vec!["latexindent.pl", "-m"]);
+    }
+
+    #[test]
+    fn test_language_by_path() {
+        let options: Options = serde_json::from_value(serde_json::json!({
+            "extraLatexExtensions": ["tikz", ".PGF"],
+            "extraBibtexExtensions": ["bibl"],
+        }))
+        .unwrap();
+
+        let language = |path: &str| options.language_by_path(Path::new(path));
+        assert_eq!(language("foo.tikz"), Some(DocumentLanguage::Latex));
+        assert_eq!(language("foo.pgf"), Some(DocumentLanguage::Latex));
+        assert_eq!(language("foo.bibl"), Some(DocumentLanguage::Bibtex));
+        assert_eq!(language("foo.tex"), Some(DocumentLanguage::Latex));
+        assert_eq!(language("foo.txt"), None);
+    }
+}
@@ -1,6 +1,9 @@
 use std::{
     path::PathBuf,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
 };
 
 use anyhow::Result;
@@ -8,28 +11,37 @@ use crossbeam_channel::{Receiver, Sender};
 use log::{error, info, warn};
 use lsp_server::{Connection, Message, RequestId};
 use lsp_types::{notification::*, request::*, *};
-use rowan::ast::AstNode;
+use rowan::{ast::AstNode, TextSize};
+use rustc_hash::FxHashSet;
 use serde::Serialize;
 use threadpool::ThreadPool;
 
 use crate::{
     citation,
-    client::{send_notification, send_request, ReqQueue},
+    client::{send_notification, send_request, send_request_with_handler, ReqQueue},
     component_db::COMPONENT_DATABASE,
     debouncer,
-    diagnostics::DiagnosticManager,
+    diagnostics::{
+        DiagnosticManager, DiagnosticOptions, DocumentDiagnosticParams, DocumentDiagnosticReport,
+        DocumentDiagnosticRequest, WorkspaceDiagnosticParams, WorkspaceDiagnosticRefresh,
+        WorkspaceDiagnosticReport, WorkspaceDiagnosticRequest, WorkspaceDocumentDiagnosticReport,
+    },
     dispatch::{NotificationDispatcher, RequestDispatcher},
     distro::Distribution,
     features::{
-        execute_command, find_all_references, find_document_highlights, find_document_links,
-        find_document_symbols, find_foldings, find_hover, find_inlay_hints, find_workspace_symbols,
-        format_source_code, goto_definition, prepare_rename_all, rename_all, BuildEngine,
-        BuildParams, BuildResult, BuildStatus, CompletionItemData, FeatureRequest,
-        ForwardSearchResult, ForwardSearchStatus,
+        collect_server_status, execute_command, execute_forward_search, expand_macros,
+        find_all_references, find_code_actions, find_document_highlights, find_document_links,
+        find_document_symbols, find_foldings, find_hover, find_inlay_hints,
+        find_linked_editing_ranges, find_workspace_symbols, format_on_type, format_source_code,
+        goto_definition, goto_type_definition, normalize_bibtex_keys, prepare_rename_all,
+        rename_all, rename_files, resolve_document_link, resolve_root, BuildEngine, BuildParams,
+        BuildResult, BuildStatus, CancelBuildRequest, CompletionItemData, DocumentLinkData,
+        ExpandMacrosParams, ExpandMacrosRequest, FeatureRequest, ForwardSearchResult,
+        ResolveRootRequest, ServerStatusRequest,
     },
     syntax::bibtex,
-    ClientCapabilitiesExt, Document, DocumentData, DocumentLanguage, Environment, LineIndex,
-    LineIndexExt, Options, Workspace, WorkspaceEvent,
+    ClientCapabilitiesExt, Document, DocumentLanguage, Environment, LineIndex, LineIndexExt,
+    Options, Workspace, WorkspaceEvent,
 };
 
 #[derive(Debug)]
@@ -37,6 +49,7 @@ enum InternalMessage {
     SetDistro(Distribution),
     SetOptions(Arc<Options>),
     FileEvent(notify::Event),
+    ReloadWorkspace(RequestId, Vec<(Arc<Url>, Option<Arc<String>>)>),
 }
 
 #[derive(Clone)]
@@ -48,9 +61,11 @@ pub struct Server {
     workspace: Workspace,
     diagnostic_tx: debouncer::Sender<Workspace>,
     diagnostic_manager: DiagnosticManager,
+    pull_diagnostics: Arc<AtomicBool>,
     pool: Arc<Mutex<ThreadPool>>,
     load_resolver: bool,
     build_engine: Arc<BuildEngine>,
+    formatting_error_shown: Arc<AtomicBool>,
 }
 
 impl Server {
@@ -59,11 +74,17 @@ impl Server {
         current_dir: PathBuf,
         load_resolver: bool,
     ) -> Self {
-        let req_queue = Arc::default();
+        let req_queue: Arc<Mutex<ReqQueue>> = Arc::default();
         let workspace = Workspace::new(Environment::new(Arc::new(current_dir)));
         let (internal_tx, internal_rx) = crossbeam_channel::unbounded();
         let diagnostic_manager = DiagnosticManager::default();
-        let diagnostic_tx = create_debouncer(connection.sender.clone(), diagnostic_manager.clone());
+        let pull_diagnostics = Arc::new(AtomicBool::new(false));
+        let diagnostic_tx = create_debouncer(
+            connection.sender.clone(),
+            Arc::clone(&req_queue),
+            diagnostic_manager.clone(),
+            Arc::clone(&pull_diagnostics),
+        );
         Self {
             connection: Arc::new(connection),
             internal_tx,
@@ -72,9 +93,11 @@ impl Server {
             workspace,
             diagnostic_tx,
             diagnostic_manager,
+            pull_diagnostics,
             pool: Arc::new(Mutex::new(threadpool::Builder::new().build())),
             load_resolver,
             build_engine: Arc::default(),
+            formatting_error_shown: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -97,11 +120,12 @@ impl Server {
                 },
             )),
             document_link_provider: Some(DocumentLinkOptions {
-                resolve_provider: Some(false),
+                resolve_provider: Some(true),
                 work_done_progress_options: WorkDoneProgressOptions::default(),
             }),
             folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
             definition_provider: Some(OneOf::Left(true)),
+            type_definition_provider: Some(TypeDefinitionProviderCapability::Simple(true)),
             references_provider: Some(OneOf::Left(true)),
             hover_provider: Some(HoverProviderCapability::Simple(true)),
             completion_provider: Some(CompletionOptions {
@@ -123,25 +147,81 @@ impl Server {
                 work_done_progress_options: WorkDoneProgressOptions::default(),
             })),
             document_highlight_provider: Some(OneOf::Left(true)),
+            linked_editing_range_provider: Some(LinkedEditingRangeServerCapabilities::Simple(true)),
             document_formatting_provider: Some(OneOf::Left(true)),
+            document_on_type_formatting_provider: Some(DocumentOnTypeFormattingOptions {
+                first_trigger_character: "}".into(),
+                more_trigger_character: None,
+            }),
             execute_command_provider: Some(ExecuteCommandOptions {
                 commands: vec![
                     "texlab.cleanAuxiliary".into(),
                     "texlab.cleanArtifacts".into(),
+                    "texlab.reloadWorkspace".into(),
+                    "texlab.wordCount".into(),
+                    "texlab.openRoot".into(),
+                    "texlab.sortBibliography".into(),
                 ],
                 ..Default::default()
             }),
             inlay_hint_provider: Some(OneOf::Left(true)),
+            code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+            workspace: Some(WorkspaceServerCapabilities {
+                workspace_folders: Some(WorkspaceFoldersServerCapabilities {
+                    supported: Some(true),
+                    change_notifications: Some(OneOf::Left(true)),
+                }),
+                file_operations: Some(WorkspaceFileOperationsServerCapabilities {
+                    will_rename: Some(FileOperationRegistrationOptions {
+                        filters: vec![
+                            FileOperationFilter {
+                                scheme: Some("file".into()),
+                                pattern: FileOperationPattern {
+                                    glob: "**/*.{tex,bib}".into(),
+                                    matches: Some(FileOperationPatternKind::File),
+                                    options: None,
+                                },
+                            },
+                            FileOperationFilter {
+                                scheme: Some("file".into()),
+                                pattern: FileOperationPattern {
+                                    glob: "**".into(),
+                                    matches: Some(FileOperationPatternKind::Folder),
+                                    options: None,
+                                },
+                            },
+                        ],
+                    }),
+                    ..WorkspaceFileOperationsServerCapabilities::default()
+                }),
+            }),
             ..ServerCapabilities::default()
         }
     }
 
     fn initialize(&mut self) -> Result<()> {
         let (id, params) = self.connection.initialize_start()?;
+
+        // Clients that pull diagnostics have to be notified when the diagnostics change.
+        // The capabilities of the pull model are not part of `lsp-types` yet.
+        let pull_diagnostics = params
+            .pointer("/capabilities/textDocument/diagnostic")
+            .is_some()
+            && params.pointer("/capabilities/workspace/diagnostics/refreshSupport")
+                == Some(&serde_json::Value::Bool(true));
+
         let params: InitializeParams = serde_json::from_value(params)?;
 
         self.workspace.environment.client_capabilities = Arc::new(params.capabilities);
         self.workspace.environment.client_info = params.client_info.map(Arc::new);
+        self.workspace.environment.workspace_folders = Arc::new(
+            params
+                .workspace_folders
+                .iter()
+                .flatten()
+                .filter_map(|folder| folder.uri.to_file_path().ok())
+                .collect(),
+        );
 
         let result = InitializeResult {
             capabilities: self.capabilities(),
@@ -151,8 +231,18 @@ impl Server {
             }),
             offset_encoding: None,
         };
-        self.connection
-            .initialize_finish(id, serde_json::to_value(result)?)?;
+
+        let mut result = serde_json::to_value(result)?;
+        if pull_diagnostics {
+            self.pull_diagnostics.store(true, Ordering::SeqCst);
+            result["capabilities"]["diagnosticProvider"] =
+                serde_json::to_value(DiagnosticOptions {
+                    inter_file_dependencies: true,
+                    workspace_diagnostics: true,
+                })?;
+        }
+
+        self.connection.initialize_finish(id, result)?;
 
         if self.load_resolver {
             self.spawn(move |server| {
@@ -171,6 +261,7 @@ impl Server {
 
         self.spawn(move |server| {
             server.register_config_capability();
+            server.register_file_watching_capability();
             let _ = server.pull_config();
         });
 
@@ -219,17 +310,77 @@ impl Server {
         }
     }
 
+    fn register_file_watching_capability(&self) {
+        if self
+            .workspace
+            .environment
+            .client_capabilities
+            .has_file_watching_support()
+        {
+            let options = DidChangeWatchedFilesRegistrationOptions {
+                watchers: ["**/*.{aux,log}", "**/*.bib", "**/*.tex"]
+                    .into_iter()
+                    .map(|pattern| FileSystemWatcher {
+                        glob_pattern: pattern.to_string(),
+                        kind: None,
+                    })
+                    .collect(),
+            };
+
+            let reg = Registration {
+                id: "file-watching".to_string(),
+                method: DidChangeWatchedFiles::METHOD.to_string(),
+                register_options: Some(serde_json::to_value(options).unwrap()),
+            };
+
+            let params = RegistrationParams {
+                registrations: vec![reg],
+            };
+
+            if let Err(why) =
+                send_request::<RegisterCapability>(&self.req_queue, &self.connection.sender, params)
+            {
+                error!(
+                    "Failed to register \"{}\" notification: {}",
+                    DidChangeWatchedFiles::METHOD,
+                    why
+                );
+            }
+        }
+    }
+
     fn register_diagnostics_handler(&mut self) {
         let (event_sender, event_receiver) = crossbeam_channel::unbounded();
         let diagnostic_tx = self.diagnostic_tx.clone();
         let diagnostic_manager = self.diagnostic_manager.clone();
+        let lsp_sender = self.connection.sender.clone();
         std::thread::spawn(move || {
+            let mut large_documents = FxHashSet::default();
             for event in event_receiver {
                 match event {
                     WorkspaceEvent::Changed(workspace, document) => {
+                        if workspace
+                            .environment
+                            .options
+                            .exceeds_max_file_size(&document.text)
+                            && large_documents.insert(Arc::clone(&document.uri))
+                        {
+                            let message = format!(
+                                "{} exceeds maxFileSizeBytes and will not be analyzed.",
+                                document.uri
+                            );
+
+                            let params = ShowMessageParams {
+                                typ: MessageType::WARNING,
+                                message,
+                            };
+
+                            let _ = send_notification::<ShowMessage>(&lsp_sender, params);
+                        }
+
                         diagnostic_manager.push_syntax(&workspace, &document.uri);
-                        let delay = workspace.environment.options.diagnostics_delay;
-                        diagnostic_tx.send(workspace, delay.0).unwrap();
+                        let delay = workspace.environment.options.diagnostics_delay();
+                        diagnostic_tx.send(workspace, delay).unwrap();
                     }
                 };
             }
@@ -300,7 +451,24 @@ impl Server {
         Ok(())
     }
 
-    fn did_change_watched_files(&mut self, _params: DidChangeWatchedFilesParams) -> Result<()> {
+    fn did_change_watched_files(&mut self, params: DidChangeWatchedFilesParams) -> Result<()> {
+        for change in params.changes {
+            let path = match change.uri.to_file_path() {
+                Ok(path) => path,
+                Err(()) => continue,
+            };
+
+            match change.typ {
+                FileChangeType::CREATED | FileChangeType::CHANGED => {
+                    let _ = self.workspace.reload(path);
+                }
+                FileChangeType::DELETED if !self.workspace.is_open(&change.uri) => {
+                    self.workspace.documents_by_uri.remove(&change.uri);
+                }
+                _ => {}
+            }
+        }
+
         Ok(())
     }
 
@@ -316,6 +484,7 @@ impl Server {
             });
         } else {
             let options = self.parse_options(params.settings)?;
+            crate::logging::configure(&options.log);
             self.workspace.environment.options = Arc::new(options);
             self.reparse_all()?;
         }
@@ -323,16 +492,64 @@ impl Server {
         Ok(())
     }
 
+    fn did_change_workspace_folders(
+        &mut self,
+        params: DidChangeWorkspaceFoldersParams,
+    ) -> Result<()> {
+        let removed: Vec<_> = params
+            .event
+            .removed
+            .iter()
+            .filter_map(|folder| folder.uri.to_file_path().ok())
+            .collect();
+
+        let mut folders: Vec<_> = self
+            .workspace
+            .environment
+            .workspace_folders
+            .iter()
+            .filter(|folder| !removed.contains(folder))
+            .cloned()
+            .collect();
+
+        folders.extend(
+            params
+                .event
+                .added
+                .iter()
+                .filter_map(|folder| folder.uri.to_file_path().ok()),
+        );
+
+        self.workspace.environment.workspace_folders = Arc::new(folders);
+        self.reparse_all()?;
+        Ok(())
+    }
+
     fn did_open(&mut self, params: DidOpenTextDocumentParams) -> Result<()> {
         let language_id = &params.text_document.language_id;
-        let language = DocumentLanguage::by_language_id(language_id);
+        let language = DocumentLanguage::by_language_id(language_id).or_else(|| {
+            let path = params.text_document.uri.to_file_path().ok()?;
+            self.workspace.environment.options.language_by_path(&path)
+        });
+
+        // The document is marked as open first so that the listeners see it as open.
+        let uri = Arc::new(params.text_document.uri);
+        self.workspace.viewport.insert(Arc::clone(&uri));
         let document = self.workspace.open(
-            Arc::new(params.text_document.uri),
+            uri,
             Arc::new(params.text_document.text),
             language.unwrap_or(DocumentLanguage::Latex),
         )?;
 
-        self.workspace.viewport.insert(Arc::clone(&document.uri));
+        // Opening another document of a project that is being built must not restart the build.
+        if self.workspace.environment.options.build.on_open_if_missing
+            && self.is_pdf_missing(&document)
+            && !self
+                .build_engine
+                .is_running(&self.feature_request(Arc::clone(&document.uri), ()))
+        {
+            self.spawn_build(Arc::clone(&document.uri));
+        }
 
         if self.workspace.environment.options.chktex.on_open_and_save {
             self.run_chktex(document);
@@ -341,12 +558,53 @@ impl Server {
         Ok(())
     }
 
+    /// Checks whether the document belongs to a LaTeX file on disk whose root has not been compiled yet.
+    fn is_pdf_missing(&self, document: &Document) -> bool {
+        if document.uri.scheme() != "file" || document.data.language() != DocumentLanguage::Latex {
+            return false;
+        }
+
+        self.workspace
+            .find_parent(&document.uri)
+            .as_ref()
+            .and_then(|root| root.data.as_latex())
+            .map_or(false, |data| {
+                !data
+                    .extras
+                    .implicit_links
+                    .pdf
+                    .iter()
+                    .filter_map(|uri| uri.to_file_path().ok())
+                    .any(|path| path.exists())
+            })
+    }
+
+    fn spawn_build(&self, uri: Arc<Url>) {
+        let text_document = TextDocumentIdentifier::new(uri.as_ref().clone());
+        let request = self.feature_request(uri, BuildParams { text_document });
+        self.build_engine.cancel(&request);
+        self.spawn(move |server| {
+            let workspace = request.workspace.clone();
+            server
+                .build_engine
+                .build(request, &server.req_queue, &server.connection.sender)
+                .unwrap_or_else(|why| {
+                    error!("Build failed: {}", why);
+                    BuildResult {
+                        status: BuildStatus::FAILURE,
+                    }
+                });
+
+            server.publish_build_diagnostics(workspace);
+        });
+    }
+
     fn did_change(&mut self, params: DidChangeTextDocumentParams) -> Result<()> {
         let uri = Arc::new(params.text_document.uri);
         match self.workspace.documents_by_uri.get(&uri).cloned() {
             Some(old_document) => {
                 let mut text = old_document.text.to_string();
-                apply_document_edit(&mut text, params.content_changes);
+                let edit_offset = apply_document_edit(&mut text, params.content_changes);
                 let language = old_document.data.language();
                 let new_document =
                     self.workspace
@@ -355,18 +613,23 @@ impl Server {
                     .viewport
                     .insert(Arc::clone(&new_document.uri));
 
-                self.build_engine.positions_by_uri.insert(
-                    Arc::clone(&uri),
-                    Position::new(
-                        old_document
+                let position = match edit_offset {
+                    Some(offset) => new_document.line_index.line_col_lsp(offset),
+                    None => {
+                        let mut old_lines = old_document.text.lines();
+                        let line = new_document
                             .text
                             .lines()
-                            .zip(new_document.text.lines())
-                            .position(|(a, b)| a != b)
-                            .unwrap_or_default() as u32,
-                        0,
-                    ),
-                );
+                            .take_while(|line| old_lines.next() == Some(line))
+                            .count();
+
+                        Position::new(line as u32, 0)
+                    }
+                };
+
+                self.build_engine
+                    .positions_by_uri
+                    .insert(Arc::clone(&uri), position);
 
                 if self.workspace.environment.options.chktex.on_edit {
                     self.run_chktex(new_document);
@@ -386,31 +649,13 @@ impl Server {
     fn did_save(&mut self, params: DidSaveTextDocumentParams) -> Result<()> {
         let uri = params.text_document.uri;
 
-        if let Some(request) = self
+        if let Some(document) = self
             .workspace
             .documents_by_uri
             .get(&uri)
             .filter(|_| self.workspace.environment.options.build.on_save)
-            .map(|document| {
-                self.feature_request(
-                    Arc::clone(&document.uri),
-                    BuildParams {
-                        text_document: TextDocumentIdentifier::new(uri.clone()),
-                    },
-                )
-            })
         {
-            self.spawn(move |server| {
-                server
-                    .build_engine
-                    .build(request, &server.req_queue, &server.connection.sender)
-                    .unwrap_or_else(|why| {
-                        error!("Build failed: {}", why);
-                        BuildResult {
-                            status: BuildStatus::FAILURE,
-                        }
-                    });
-            });
+            self.spawn_build(Arc::clone(&document.uri));
         }
 
         if let Some(document) = self
@@ -432,15 +677,24 @@ impl Server {
     }
 
     fn run_chktex(&mut self, document: Document) {
+        if self
+            .workspace
+            .environment
+            .options
+            .exceeds_max_file_size(&document.text)
+        {
+            return;
+        }
+
         self.spawn(move |server| {
             server
                 .diagnostic_manager
                 .push_chktex(&server.workspace, &document.uri);
 
-            let delay = server.workspace.environment.options.diagnostics_delay;
+            let delay = server.workspace.environment.options.diagnostics_delay();
             server
                 .diagnostic_tx
-                .send(server.workspace.clone(), delay.0)
+                .send(server.workspace.clone(), delay)
                 .unwrap();
         });
     }
@@ -491,6 +745,25 @@ impl Server {
         Ok(())
     }
 
+    fn document_link_resolve(&self, id: RequestId, link: DocumentLink) -> Result<()> {
+        match link
+            .data
+            .clone()
+            .and_then(|data| serde_json::from_value::<DocumentLinkData>(data).ok())
+        {
+            Some(data) => {
+                let uri = Arc::new(data.uri);
+                self.handle_feature_request(id, link, uri, resolve_document_link)?;
+            }
+            None => {
+                let response = lsp_server::Response::new_ok(id, link);
+                self.connection.sender.send(response.into()).unwrap();
+            }
+        };
+
+        Ok(())
+    }
+
     fn document_symbols(&self, id: RequestId, params: DocumentSymbolParams) -> Result<()> {
         let uri = Arc::new(params.text_document.uri.clone());
         self.handle_feature_request(id, params, uri, find_document_symbols)?;
@@ -509,6 +782,18 @@ impl Server {
         Ok(())
     }
 
+    fn will_rename_files(&self, id: RequestId, params: RenameFilesParams) -> Result<()> {
+        self.spawn(move |server| {
+            let result = rename_files(&server.workspace, &params);
+            server
+                .connection
+                .sender
+                .send(lsp_server::Response::new_ok(id, result).into())
+                .unwrap();
+        });
+        Ok(())
+    }
+
     fn completion(&self, id: RequestId, params: CompletionParams) -> Result<()> {
         let uri = Arc::new(params.text_document_position.text_document.uri.clone());
 
@@ -535,10 +820,17 @@ impl Server {
                         .get(&uri)
                         .and_then(|document| document.data.as_bibtex())
                     {
-                        let root = bibtex::SyntaxNode::new_root(data.green.clone());
+                        let workspace = server.workspace.slice(&uri);
+                        let style = citation::find_style(&workspace);
+                        let number = citation::find_resolved_citation(&workspace, &key)
+                            .map(|(_, citation)| citation.number.as_str());
+
+                        let root = bibtex::SyntaxNode::new_root(data.green().clone());
                         item.documentation = bibtex::Root::cast(root)
                             .and_then(|root| root.find_entry(&key))
-                            .and_then(|entry| citation::render(&entry))
+                            .and_then(|entry| {
+                                citation::render_with_number(&workspace, &entry, style, number)
+                            })
                             .map(|value| {
                                 Documentation::MarkupContent(MarkupContent {
                                     kind: MarkupKind::Markdown,
@@ -600,6 +892,18 @@ impl Server {
         Ok(())
     }
 
+    fn goto_type_definition(&self, id: RequestId, params: GotoTypeDefinitionParams) -> Result<()> {
+        let uri = Arc::new(
+            params
+                .text_document_position_params
+                .text_document
+                .uri
+                .clone(),
+        );
+        self.handle_feature_request(id, params, uri, goto_type_definition)?;
+        Ok(())
+    }
+
     fn prepare_rename(&self, id: RequestId, params: TextDocumentPositionParams) -> Result<()> {
         let uri = Arc::new(params.text_document.uri.clone());
         self.handle_feature_request(id, params, uri, prepare_rename_all)?;
@@ -624,17 +928,108 @@ impl Server {
         Ok(())
     }
 
+    fn linked_editing_range(&self, id: RequestId, params: LinkedEditingRangeParams) -> Result<()> {
+        let uri = Arc::new(
+            params
+                .text_document_position_params
+                .text_document
+                .uri
+                .clone(),
+        );
+        self.handle_feature_request(id, params, uri, find_linked_editing_ranges)?;
+        Ok(())
+    }
+
     fn formatting(&self, id: RequestId, params: DocumentFormattingParams) -> Result<()> {
         let uri = Arc::new(params.text_document.uri.clone());
-        self.handle_feature_request(id, params, uri, format_source_code)?;
+        let lsp_sender = self.connection.sender.clone();
+        let req_queue = Arc::clone(&self.req_queue);
+        let formatting_error_shown = Arc::clone(&self.formatting_error_shown);
+        self.handle_feature_request(id, params, uri, move |request| {
+            let edit = match normalize_bibtex_keys(&request) {
+                Some(edit) => edit,
+                None => {
+                    return format_source_code(request).unwrap_or_else(|why| {
+                        warn!("{}", why);
+
+                        // Do not bother the user every time the document is formatted (e.g. on save).
+                        if !formatting_error_shown.swap(true, Ordering::SeqCst) {
+                            let params = ShowMessageParams {
+                                typ: MessageType::WARNING,
+                                message: why.to_string(),
+                            };
+
+                            let _ = send_notification::<ShowMessage>(&lsp_sender, params);
+                        }
+
+                        None
+                    });
+                }
+            };
+
+            // The BibTeX document is formatted by the response while the citations are updated
+            // by a separate edit. Waiting for the client to apply it would block the response.
+            let mut changes = edit.changes.unwrap_or_default();
+            let bibtex_edits = changes.remove(request.uri.as_ref()).unwrap_or_default();
+            if !changes.is_empty() {
+                let params = ApplyWorkspaceEditParams {
+                    label: Some("Normalize BibTeX keys".to_string()),
+                    edit: WorkspaceEdit::new(changes),
+                };
+
+                let result = send_request_with_handler::<ApplyWorkspaceEdit>(
+                    &req_queue,
+                    &lsp_sender,
+                    params,
+                    |result| match result {
+                        Ok(response) if response.applied => {}
+                        Ok(response) => warn!(
+                            "Failed to update the citations: {}",
+                            response.failure_reason.unwrap_or_default()
+                        ),
+                        Err(why) => warn!("Failed to update the citations: {}", why),
+                    },
+                );
+
+                if let Err(why) = result {
+                    error!("Failed to normalize BibTeX keys: {}", why);
+                }
+            }
+
+            Some(bibtex_edits)
+        })?;
+        Ok(())
+    }
+
+    fn on_type_formatting(
+        &self,
+        id: RequestId,
+        params: DocumentOnTypeFormattingParams,
+    ) -> Result<()> {
+        let uri = Arc::new(params.text_document_position.text_document.uri.clone());
+        self.handle_feature_request(id, params, uri, format_on_type)?;
         Ok(())
     }
 
-    fn execute_command(&self, id: RequestId, params: ExecuteCommandParams) -> Result<()> {
+    fn execute_command(&mut self, id: RequestId, params: ExecuteCommandParams) -> Result<()> {
+        // Reloading the workspace needs to update the documents of the server itself,
+        // so only the files are read in the background.
+        if params.command == "texlab.reloadWorkspace" {
+            self.spawn(move |server| {
+                let texts = server.workspace.read_all();
+                server
+                    .internal_tx
+                    .send(InternalMessage::ReloadWorkspace(id, texts))
+                    .unwrap();
+            });
+
+            return Ok(());
+        }
+
         self.spawn(move |server| {
             let result = execute_command(&server.workspace, &params.command, params.arguments);
             let response = match result {
-                Ok(()) => lsp_server::Response::new_ok(id, ()),
+                Ok(value) => lsp_server::Response::new_ok(id, value),
                 Err(why) => lsp_server::Response::new_err(
                     id,
                     lsp_server::ErrorCode::InternalError as i32,
@@ -648,6 +1043,12 @@ impl Server {
         Ok(())
     }
 
+    fn code_actions(&self, id: RequestId, params: CodeActionParams) -> Result<()> {
+        let uri = Arc::new(params.text_document.uri.clone());
+        self.handle_feature_request(id, params, uri, find_code_actions)?;
+        Ok(())
+    }
+
     fn inlay_hints(&self, id: RequestId, params: InlayHintParams) -> Result<()> {
         let uri = Arc::new(params.text_document.uri.clone());
         self.handle_feature_request(id, params, uri, find_inlay_hints)?;
@@ -670,33 +1071,164 @@ impl Server {
 
     fn build(&self, id: RequestId, params: BuildParams) -> Result<()> {
         let uri = Arc::new(params.text_document.uri.clone());
-        let lsp_sender = self.connection.sender.clone();
-        let req_queue = Arc::clone(&self.req_queue);
-        let build_engine = Arc::clone(&self.build_engine);
+
+        // Cancel the running build right away since it might block the worker thread of the new one.
+        let request = self.feature_request(Arc::clone(&uri), params.clone());
+        self.build_engine.cancel(&request);
+
+        let server = self.clone();
         self.handle_feature_request(id, params, uri, move |request| {
-            build_engine
-                .build(request, &req_queue, &lsp_sender)
+            let workspace = request.workspace.clone();
+            let result = server
+                .build_engine
+                .build(request, &server.req_queue, &server.connection.sender)
                 .unwrap_or_else(|why| {
                     error!("Build failed: {}", why);
                     BuildResult {
                         status: BuildStatus::FAILURE,
                     }
-                })
+                });
+
+            server.publish_build_diagnostics(workspace);
+            result
         })?;
         Ok(())
     }
 
-    fn forward_search(&self, id: RequestId, params: TextDocumentPositionParams) -> Result<()> {
+    fn cancel_build(&self, id: RequestId, params: BuildParams) -> Result<()> {
         let uri = Arc::new(params.text_document.uri.clone());
-        self.handle_feature_request(id, params, uri, |req| {
-            crate::features::execute_forward_search(req).unwrap_or(ForwardSearchResult {
-                status: ForwardSearchStatus::ERROR,
+        let request = self.feature_request(uri, params);
+        let cancelled =
+            !request.workspace.documents_by_uri.is_empty() && self.build_engine.cancel(&request);
+
+        let response = lsp_server::Response::new_ok(id, cancelled);
+        self.connection.sender.send(response.into())?;
+        Ok(())
+    }
+
+    fn document_diagnostic(&self, id: RequestId, params: DocumentDiagnosticParams) -> Result<()> {
+        let items = self
+            .diagnostic_manager
+            .publish(&self.workspace, &params.text_document.uri);
+
+        let report = DocumentDiagnosticReport::Full { items };
+        let response = lsp_server::Response::new_ok(id, report);
+        self.connection.sender.send(response.into())?;
+        Ok(())
+    }
+
+    fn workspace_diagnostic(
+        &self,
+        id: RequestId,
+        _params: WorkspaceDiagnosticParams,
+    ) -> Result<()> {
+        let items = self
+            .diagnostic_manager
+            .publish_all(&self.workspace)
+            .into_iter()
+            .map(|(uri, items)| WorkspaceDocumentDiagnosticReport::Full {
+                uri: uri.as_ref().clone(),
+                version: None,
+                items,
             })
-        })?;
+            .collect();
+
+        let report = WorkspaceDiagnosticReport { items };
+        let response = lsp_server::Response::new_ok(id, report);
+        self.connection.sender.send(response.into())?;
+        Ok(())
+    }
+
+    fn publish_build_diagnostics(&self, workspace: Workspace) {
+        self.diagnostic_manager.push_build(&workspace);
+        let delay = workspace.environment.options.diagnostics_delay();
+        self.diagnostic_tx.send(workspace, delay).unwrap();
+    }
+
+    fn forward_search(&self, id: RequestId, params: TextDocumentPositionParams) -> Result<()> {
+        let uri = Arc::new(params.text_document.uri.clone());
+        self.handle_feature_request(id, params, uri, execute_forward_search)?;
+        Ok(())
+    }
+
+    fn resolve_root(&self, id: RequestId, params: TextDocumentIdentifier) -> Result<()> {
+        let uri = Arc::new(params.uri.clone());
+        self.handle_feature_request(id, params, uri, resolve_root)?;
+        Ok(())
+    }
+
+    fn expand_macros(&self, id: RequestId, params: ExpandMacrosParams) -> Result<()> {
+        let uri = Arc::new(params.text_document.uri.clone());
+        self.handle_feature_request(id, params, uri, expand_macros)?;
+        Ok(())
+    }
+
+    fn server_status(&self, id: RequestId) -> Result<()> {
+        let status = collect_server_status(&self.workspace, &self.pool.lock().unwrap());
+        let response = lsp_server::Response::new_ok(id, status);
+        self.connection.sender.send(response.into())?;
+        Ok(())
+    }
+
+    fn clear_diagnostics(&self, uri: &Url) -> Result<()> {
+        self.diagnostic_manager.remove(uri);
+        send_notification::<PublishDiagnostics>(
+            &self.connection.sender,
+            PublishDiagnosticsParams {
+                uri: uri.clone(),
+                version: None,
+                diagnostics: Vec::new(),
+            },
+        )?;
+        Ok(())
+    }
+
+    fn reload_workspace(
+        &mut self,
+        id: RequestId,
+        texts: Vec<(Arc<Url>, Option<Arc<String>>)>,
+    ) -> Result<()> {
+        let deleted_uris: Vec<_> = texts
+            .iter()
+            .filter(|(uri, text)| text.is_none() && !self.workspace.is_open(uri))
+            .map(|(uri, _)| Arc::clone(uri))
+            .collect();
+
+        let response = match self.workspace.reload_all(texts) {
+            Ok(count) => lsp_server::Response::new_ok(id, count),
+            Err(why) => lsp_server::Response::new_err(
+                id,
+                lsp_server::ErrorCode::InternalError as i32,
+                why.to_string(),
+            ),
+        };
+
+        for uri in deleted_uris {
+            self.clear_diagnostics(&uri)?;
+        }
+
+        self.connection.sender.send(response.into())?;
         Ok(())
     }
 
     fn reparse_all(&mut self) -> Result<()> {
+        // The documents that were loaded to find the parents and children of the open documents
+        // are no longer needed if single file mode has been enabled.
+        if self.workspace.environment.options.single_file_mode {
+            let dropped_uris: Vec<_> = self
+                .workspace
+                .documents_by_uri
+                .keys()
+                .filter(|uri| !self.workspace.is_open(uri))
+                .cloned()
+                .collect();
+
+            for uri in dropped_uris {
+                self.workspace.documents_by_uri.remove(&uri);
+                self.clear_diagnostics(&uri)?;
+            }
+        }
+
         for document in self
             .workspace
             .documents_by_uri
@@ -711,11 +1243,26 @@ impl Server {
             )?;
         }
 
-        match &self.workspace.environment.options.aux_directory {
-            Some(path) => self.workspace.watch_dir(path),
-            None => self.workspace.watch_dir(&PathBuf::from(".")),
+        // Relative output directories are watched inside of every workspace folder.
+        let environment = &self.workspace.environment;
+        let base_dirs = if environment.workspace_folders.is_empty() {
+            vec![environment.current_directory.as_ref().clone()]
+        } else {
+            environment.workspace_folders.as_ref().clone()
         };
 
+        let options = Arc::clone(&environment.options);
+        for base_dir in base_dirs {
+            match &options.aux_directory {
+                Some(path) => self.workspace.watch_dir(&base_dir.join(path)),
+                None => self.workspace.watch_dir(&base_dir),
+            };
+
+            if let Some(path) = &options.output_directory {
+                self.workspace.watch_dir(&base_dir.join(path));
+            }
+        }
+
         Ok(())
     }
 
@@ -731,6 +1278,9 @@ impl Server {
 
                             if let Some(response) = RequestDispatcher::new(request)
                                 .on::<DocumentLinkRequest, _>(|id, params| self.document_link(id, params))?
+                                .on::<DocumentLinkResolve, _>(|id, params| {
+                                    self.document_link_resolve(id, params)
+                                })?
                                 .on::<FoldingRangeRequest, _>(|id, params| self.folding_range(id, params))?
                                 .on::<References, _>(|id, params| self.references(id, params))?
                                 .on::<HoverRequest, _>(|id, params| self.hover(id, params))?
@@ -738,6 +1288,9 @@ impl Server {
                                     self.document_symbols(id, params)
                                 })?
                                 .on::<WorkspaceSymbol, _>(|id, params| self.workspace_symbols(id, params))?
+                                .on::<WillRenameFiles, _>(|id, params| {
+                                    self.will_rename_files(id, params)
+                                })?
                                 .on::<Completion, _>(|id, params| {
                                     self.completion(id, params)?;
                                     Ok(())
@@ -747,6 +1300,9 @@ impl Server {
                                     Ok(())
                                 })?
                                 .on::<GotoDefinition, _>(|id, params| self.goto_definition(id, params))?
+                                .on::<GotoTypeDefinition, _>(|id, params| {
+                                    self.goto_type_definition(id, params)
+                                })?
                                 .on::<PrepareRenameRequest, _>(|id, params| {
                                     self.prepare_rename(id, params)
                                 })?
@@ -754,11 +1310,33 @@ impl Server {
                                 .on::<DocumentHighlightRequest, _>(|id, params| {
                                     self.document_highlight(id, params)
                                 })?
+                                .on::<LinkedEditingRange, _>(|id, params| {
+                                    self.linked_editing_range(id, params)
+                                })?
                                 .on::<Formatting, _>(|id, params| self.formatting(id, params))?
+                                .on::<OnTypeFormatting, _>(|id, params| {
+                                    self.on_type_formatting(id, params)
+                                })?
                                 .on::<BuildRequest, _>(|id, params| self.build(id, params))?
+                                .on::<CancelBuildRequest, _>(|id, params| {
+                                    self.cancel_build(id, params)
+                                })?
                                 .on::<ForwardSearchRequest, _>(|id, params| {
                                     self.forward_search(id, params)
                                 })?
+                                .on::<ResolveRootRequest, _>(|id, params| {
+                                    self.resolve_root(id, params)
+                                })?
+                                .on::<ExpandMacrosRequest, _>(|id, params| {
+                                    self.expand_macros(id, params)
+                                })?
+                                .on::<ServerStatusRequest, _>(|id, ()| self.server_status(id))?
+                                .on::<DocumentDiagnosticRequest, _>(|id, params| {
+                                    self.document_diagnostic(id, params)
+                                })?
+                                .on::<WorkspaceDiagnosticRequest, _>(|id, params| {
+                                    self.workspace_diagnostic(id, params)
+                                })?
                                 .on::<ExecuteCommand,_>(|id, params| self.execute_command(id, params))?
                                 .on::<SemanticTokensRangeRequest, _>(|id, params| {
                                     self.semantic_tokens_range(id, params)
@@ -769,6 +1347,9 @@ impl Server {
                                 .on::<InlayHintResolveRequest,_>(|id, params| {
                                     self.inlay_hint_resolve(id, params)
                                 })?
+                                .on::<CodeActionRequest, _>(|id, params| {
+                                    self.code_actions(id, params)
+                                })?
                                 .default()
                             {
                                 self.connection.sender.send(response.into())?;
@@ -783,6 +1364,9 @@ impl Server {
                                 .on::<DidChangeWatchedFiles, _>(|params| {
                                     self.did_change_watched_files(params)
                                 })?
+                                .on::<DidChangeWorkspaceFolders, _>(|params| {
+                                    self.did_change_workspace_folders(params)
+                                })?
                                 .on::<DidOpenTextDocument, _>(|params| self.did_open(params))?
                                 .on::<DidChangeTextDocument, _>(|params| self.did_change(params))?
                                 .on::<DidSaveTextDocument, _>(|params| self.did_save(params))?
@@ -790,13 +1374,20 @@ impl Server {
                                 .default();
                         }
                         Message::Response(response) => {
-                            let mut req_queue = self.req_queue.lock().unwrap();
-                            if let Some(data) = req_queue.outgoing.complete(response.id) {
+                            // The lock is released before calling the handler.
+                            let data = self
+                                .req_queue
+                                .lock()
+                                .unwrap()
+                                .outgoing
+                                .complete(response.id);
+
+                            if let Some(data) = data {
                                 let result = match response.error {
                                     Some(error) => Err(error),
                                     None => Ok(response.result.unwrap_or_default()),
                                 };
-                                data.sender.send(result)?;
+                                (data.handler)(result);
                             }
                         }
                     };
@@ -808,9 +1399,13 @@ impl Server {
                             self.reparse_all()?;
                         }
                         InternalMessage::SetOptions(options) => {
+                            crate::logging::configure(&options.log);
                             self.workspace.environment.options = options;
                             self.reparse_all()?;
                         }
+                        InternalMessage::ReloadWorkspace(id, texts) => {
+                            self.reload_workspace(id, texts)?;
+                        }
                         InternalMessage::FileEvent(ev) => {
                             match ev.kind {
                                 notify::EventKind::Create(_) | notify::EventKind::Modify(_) => {
@@ -822,7 +1417,9 @@ impl Server {
                                     for uri in
                                         ev.paths.iter().flat_map(Url::from_file_path)
                                     {
-                                        self.workspace.documents_by_uri.remove(&uri);
+                                        if self.workspace.documents_by_uri.remove(&uri).is_some() {
+                                            self.clear_diagnostics(&uri)?;
+                                        }
                                     }
                                 }
                                 notify::EventKind::Any
@@ -846,12 +1443,19 @@ impl Server {
 
 fn create_debouncer(
     lsp_sender: Sender<Message>,
+    req_queue: Arc<Mutex<ReqQueue>>,
     diagnostic_manager: DiagnosticManager,
+    pull_diagnostics: Arc<AtomicBool>,
 ) -> debouncer::Sender<Workspace> {
     let (tx, rx) = debouncer::unbounded();
     std::thread::spawn(move || {
+        let refresh_state: Arc<Mutex<RefreshState>> = Arc::default();
         while let Ok(workspace) = rx.recv() {
-            if let Err(why) = publish_diagnostics(&lsp_sender, &diagnostic_manager, &workspace) {
+            if pull_diagnostics.load(Ordering::SeqCst) {
+                refresh_diagnostics(&req_queue, &lsp_sender, &refresh_state);
+            } else if let Err(why) =
+                publish_diagnostics(&lsp_sender, &diagnostic_manager, &workspace)
+            {
                 warn!("Failed to publish diagnostics: {}", why);
             }
         }
@@ -860,21 +1464,72 @@ fn create_debouncer(
     tx
 }
 
+/// Tracks the `workspace/diagnostic/refresh` request that has not been answered yet.
+#[derive(Default)]
+struct RefreshState {
+    is_pending: bool,
+    is_stale: bool,
+}
+
+/// Asks the client to pull the diagnostics again without waiting for the response.
+///
+/// Only one refresh is outstanding at a time, so a client that does not respond does not stall
+/// the diagnostics. Changes in the meantime are refreshed once the client responds.
+fn refresh_diagnostics(
+    req_queue: &Arc<Mutex<ReqQueue>>,
+    lsp_sender: &Sender<Message>,
+    state: &Arc<Mutex<RefreshState>>,
+) {
+    {
+        let mut state = state.lock().unwrap();
+        if state.is_pending {
+            state.is_stale = true;
+            return;
+        }
+
+        state.is_pending = true;
+    }
+
+    let handler_req_queue = Arc::clone(req_queue);
+    let handler_lsp_sender = lsp_sender.clone();
+    let handler_state = Arc::clone(state);
+    let result = send_request_with_handler::<WorkspaceDiagnosticRefresh>(
+        req_queue,
+        lsp_sender,
+        (),
+        move |result| {
+            if let Err(why) = result {
+                warn!("Failed to refresh diagnostics: {}", why);
+            }
+
+            let is_stale = {
+                let mut state = handler_state.lock().unwrap();
+                state.is_pending = false;
+                std::mem::take(&mut state.is_stale)
+            };
+
+            if is_stale {
+                refresh_diagnostics(&handler_req_queue, &handler_lsp_sender, &handler_state);
+            }
+        },
+    );
+
+    if let Err(why) = result {
+        state.lock().unwrap().is_pending = false;
+        warn!("Failed to refresh diagnostics: {}", why);
+    }
+}
+
 fn publish_diagnostics(
     lsp_sender: &Sender<lsp_server::Message>,
     diagnostic_manager: &DiagnosticManager,
     workspace: &Workspace,
 ) -> Result<()> {
-    for document in workspace.documents_by_uri.values() {
-        if matches!(document.data, DocumentData::BuildLog(_)) {
-            continue;
-        }
-
-        let diagnostics = diagnostic_manager.publish(workspace, &document.uri);
+    for (uri, diagnostics) in diagnostic_manager.publish_all(workspace) {
         send_notification::<PublishDiagnostics>(
             lsp_sender,
             PublishDiagnosticsParams {
-                uri: document.uri.as_ref().clone(),
+                uri: uri.as_ref().clone(),
                 version: None,
                 diagnostics,
             },
@@ -884,19 +1539,34 @@ fn publish_diagnostics(
     Ok(())
 }
 
-fn apply_document_edit(old_text: &mut String, changes: Vec<TextDocumentContentChangeEvent>) {
+/// Applies the changes to the text and returns the end of the last ranged edit.
+///
+/// Returns `None` if the last change replaces the whole text.
+fn apply_document_edit(
+    old_text: &mut String,
+    changes: Vec<TextDocumentContentChangeEvent>,
+) -> Option<TextSize> {
+    // The range of each change refers to the text after applying the previous changes
+    let mut line_index = LineIndex::new(old_text);
+    let mut edit_offset = None;
     for change in changes {
-        let line_index = LineIndex::new(old_text);
         match change.range {
             Some(range) => {
-                let range = std::ops::Range::<usize>::from(line_index.offset_lsp_range(range));
-                old_text.replace_range(range, &change.text);
+                let range = line_index.offset_lsp_range(range);
+                let len = TextSize::of(change.text.as_str());
+                edit_offset = Some(range.start() + len);
+                old_text.replace_range(std::ops::Range::<usize>::from(range), &change.text);
+                line_index.apply_edit(old_text, range, len);
             }
             None => {
+                edit_offset = None;
                 *old_text = change.text;
+                line_index = LineIndex::new(old_text);
             }
         };
     }
+
+    edit_offset
 }
 
 struct BuildRequest;
@@ -918,3 +1588,127 @@ impl lsp_types::request::Request for ForwardSearchRequest {
 
     const METHOD: &'static str = "textDocument/forwardSearch";
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(range: Range, text: &str) -> TextDocumentContentChangeEvent {
+        TextDocumentContentChangeEvent {
+            range: Some(range),
+            range_length: None,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_apply_document_edit_crlf() {
+        let mut text = "foo\r\nbar\r\nbaz".to_string();
+        apply_document_edit(
+            &mut text,
+            vec![
+                edit(Range::new(Position::new(1, 0), Position::new(1, 3)), "qux"),
+                edit(Range::new(Position::new(2, 3), Position::new(2, 3)), "\r\n"),
+                edit(Range::new(Position::new(3, 0), Position::new(3, 0)), "end"),
+            ],
+        );
+
+        assert_eq!(text, "foo\r\nqux\r\nbaz\r\nend");
+    }
+
+    #[test]
+    fn test_apply_document_edit_crlf_line_end() {
+        let mut text = "foo\r\nbar".to_string();
+        apply_document_edit(
+            &mut text,
+            vec![edit(
+                Range::new(Position::new(0, 3), Position::new(0, 5)),
+                "!",
+            )],
+        );
+
+        assert_eq!(text, "foo!\r\nbar");
+    }
+
+    #[test]
+    fn test_apply_document_edit_cr() {
+        let mut text = "foo\rbar\rbaz".to_string();
+        apply_document_edit(
+            &mut text,
+            vec![
+                edit(Range::new(Position::new(2, 0), Position::new(2, 3)), "qux"),
+                edit(Range::new(Position::new(0, 3), Position::new(1, 0)), " "),
+            ],
+        );
+
+        assert_eq!(text, "foo bar\rqux");
+    }
+
+    #[test]
+    fn test_apply_document_edit_offset() {
+        let mut text = "foo\nbar\nbaz".to_string();
+        let offset = apply_document_edit(
+            &mut text,
+            vec![
+                edit(Range::new(Position::new(0, 0), Position::new(0, 0)), "\n\n"),
+                edit(Range::new(Position::new(3, 3), Position::new(3, 3)), "qux"),
+            ],
+        );
+
+        assert_eq!(text, "\n\nfoo\nbarqux\nbaz");
+        assert_eq!(offset, Some(TextSize::from(12)));
+    }
+
+    #[test]
+    fn test_apply_document_edit_overlapping() {
+        let mut text = "foo\nbar\nbaz".to_string();
+        let offset = apply_document_edit(
+            &mut text,
+            vec![
+                edit(
+                    Range::new(Position::new(0, 1), Position::new(1, 1)),
+                    "\n\nö",
+                ),
+                edit(Range::new(Position::new(2, 0), Position::new(2, 3)), "x"),
+                edit(Range::new(Position::new(2, 1), Position::new(3, 0)), "ä"),
+            ],
+        );
+
+        assert_eq!(text, "f\n\nxäbaz");
+        assert_eq!(offset, Some(TextSize::from(6)));
+    }
+
+    #[test]
+    fn test_apply_document_edit_after_full() {
+        let mut text = "foo".to_string();
+        apply_document_edit(
+            &mut text,
+            vec![
+                TextDocumentContentChangeEvent {
+                    range: None,
+                    range_length: None,
+                    text: "bar\nbaz".to_string(),
+                },
+                edit(Range::new(Position::new(1, 0), Position::new(1, 3)), "qux"),
+            ],
+        );
+
+        assert_eq!(text, "bar\nqux");
+    }
+
+    #[test]
+    fn test_apply_document_edit_full() {
+        let mut text = "foo".to_string();
+        let offset = apply_document_edit(
+            &mut text,
+            vec![TextDocumentContentChangeEvent {
+                range: None,
+                range_length: None,
+                text: "bar".to_string(),
+            }],
+        );
+
+        assert_eq!(text, "bar");
+        assert_eq!(offset, None);
+    }
+}
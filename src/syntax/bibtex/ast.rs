@@ -1,4 +1,4 @@
-use rowan::{ast::AstNode, NodeOrToken};
+use rowan::{ast::AstNode, NodeOrToken, TextRange};
 
 use super::{
     Lang,
@@ -6,6 +6,20 @@ use super::{
     SyntaxNode, SyntaxToken,
 };
 
+pub fn small_range(node: &dyn AstNode<Language = Lang>) -> TextRange {
+    let full_range = node.syntax().text_range();
+    let start = full_range.start();
+    let mut token = node.syntax().last_token();
+    while let Some(current) = token {
+        if !matches!(current.kind(), WHITESPACE | JUNK) {
+            return TextRange::new(start, current.text_range().end());
+        }
+        token = current.prev_token();
+    }
+
+    TextRange::new(start, start)
+}
+
 macro_rules! ast_node {
     (name: $name:ident, kinds: [$($kind:pat),+], traits: [$($trait: ident),*]) => {
         #[derive(Clone)]
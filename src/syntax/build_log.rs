@@ -55,7 +55,7 @@ pub fn parse(log: &str) -> Parse {
     let bad_boxes = extract_matches(&log, &ranges, &BAD_BOX_REGEX, BuildErrorLevel::Warning);
 
     Parse {
-        errors: vec![tex_errors, warnings, bad_boxes].concat(),
+        errors: [tex_errors, warnings, bad_boxes].concat(),
     }
 }
 
@@ -143,7 +143,7 @@ impl FileRange {
     fn create(log: &str, result: Match) -> Self {
         let mut balance = 1;
         let mut end = result.start() + 1;
-        let chars = (&log[result.start() + 1..]).chars();
+        let chars = log[result.start() + 1..].chars();
         for c in chars {
             if balance <= 0 {
                 break;
@@ -1,9 +1,15 @@
+mod bibliography_style;
+mod citation;
 mod command;
+mod counter;
+mod custom_section;
 mod distro_file;
 mod environment;
 mod explicit_link;
+mod glossary;
 mod graphics_path;
 mod implicit_link;
+mod include_only;
 mod label_name;
 mod label_number;
 mod theorem;
@@ -11,21 +17,29 @@ mod types;
 
 use crate::syntax::latex;
 
-pub use self::types::*;
 use self::{
-    command::{analyze_command, analyze_command_definition},
-    environment::analyze_begin,
+    bibliography_style::analyze_bibliography_style,
+    citation::analyze_citation,
+    command::{analyze_command, analyze_command_definition, analyze_command_reference},
+    counter::analyze_counter_definition,
+    custom_section::analyze_custom_section,
+    environment::{analyze_begin, analyze_environment_definition},
     explicit_link::{analyze_import, analyze_include},
+    glossary::analyze_glossary_entry,
     graphics_path::analyze_graphics_path,
     implicit_link::analyze_implicit_links,
+    include_only::analyze_include_only,
     label_name::analyze_label_name,
-    label_number::analyze_label_number,
+    label_number::{analyze_citation_number, analyze_label_number},
     theorem::analyze_theorem_definition,
 };
+pub use self::{distro_file::resolve_distro_file, types::*};
 
 pub fn analyze(context: &mut LatexAnalyzerContext, root: &latex::SyntaxNode) {
     analyze_implicit_links(context);
     for node in root.descendants() {
+        analyze_custom_section(context, node.clone());
+        analyze_bibliography_style(context, node.clone());
         analyze_command(context, node.clone())
             .or_else(|| analyze_command_definition(context, node.clone()))
             .or_else(|| analyze_begin(context, node.clone()))
@@ -33,8 +47,356 @@ pub fn analyze(context: &mut LatexAnalyzerContext, root: &latex::SyntaxNode) {
             .or_else(|| analyze_import(context, node.clone()))
             .or_else(|| analyze_label_name(context, node.clone()))
             .or_else(|| analyze_label_number(context, node.clone()))
+            .or_else(|| analyze_citation_number(context, node.clone()))
             .or_else(|| analyze_theorem_definition(context, node.clone()))
-            .or_else(|| analyze_graphics_path(context, node));
+            .or_else(|| analyze_environment_definition(context, node.clone()))
+            .or_else(|| analyze_glossary_entry(context, node.clone()))
+            .or_else(|| analyze_counter_definition(context, node.clone()))
+            .or_else(|| analyze_citation(context, node.clone()))
+            .or_else(|| analyze_graphics_path(context, node.clone()))
+            .or_else(|| analyze_include_only(context, node));
     }
+
+    for token in root
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+    {
+        analyze_command_reference(context, token);
+    }
+
     context.extras.has_document_environment = context.extras.environment_names.contains("document");
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use lsp_types::Url;
+    use rowan::{ast::AstNode, TextRange};
+    use rustc_hash::FxHashMap;
+    use smol_str::SmolStr;
+
+    use crate::{CustomSectionCommand, Environment, Options, SectionLevel, SymbolOptions};
+
+    use super::*;
+
+    fn analyze_text(text: &str) -> (latex::SyntaxNode, Extras) {
+        analyze_text_with_base(
+            text,
+            "http://example.com/main.tex",
+            "http://example.com/main.tex",
+        )
+    }
+
+    fn analyze_text_with_base(
+        text: &str,
+        document_uri: &str,
+        base_uri: &str,
+    ) -> (latex::SyntaxNode, Extras) {
+        analyze_text_with_environment(text, document_uri, base_uri, Environment::default())
+    }
+
+    fn analyze_text_with_environment(
+        text: &str,
+        document_uri: &str,
+        base_uri: &str,
+        environment: Environment,
+    ) -> (latex::SyntaxNode, Extras) {
+        let root = latex::SyntaxNode::new_root(latex::parse(text).green);
+        let mut context = LatexAnalyzerContext {
+            environment: &environment,
+            document_uri: Arc::new(Url::parse(document_uri).unwrap()),
+            base_uri: Arc::new(Url::parse(base_uri).unwrap()),
+            import_directory: None,
+            extras: Extras::default(),
+        };
+
+        analyze(&mut context, &root);
+        (root, context.extras)
+    }
+
+    #[test]
+    fn test_citation_index() {
+        let (root, extras) =
+            analyze_text("\\cite{foo,bar} \\cite[p.~2]{foo} \\cites{foo}{baz} \\nocite{*}");
+
+        let mut expected: FxHashMap<SmolStr, Vec<TextRange>> = FxHashMap::default();
+        for key in root
+            .descendants()
+            .filter_map(latex::Citation::cast)
+            .flat_map(|citation| citation.key_lists())
+            .flat_map(|keys| keys.keys())
+        {
+            expected
+                .entry(key.to_string().into())
+                .or_default()
+                .push(latex::small_range(&key));
+        }
+
+        assert_eq!(extras.citation_ranges_by_key, expected);
+        assert_eq!(extras.citation_ranges_by_key["foo"].len(), 3);
+    }
+
+    #[test]
+    fn test_command_index() {
+        let (root, extras) =
+            analyze_text("\\newcommand{\\foo}{\\bar} \\foo \\begin{a}\\foo\\end{a} \\verb|\\foo|");
+
+        let mut expected: FxHashMap<SmolStr, Vec<TextRange>> = FxHashMap::default();
+        for token in root
+            .descendants_with_tokens()
+            .filter_map(|element| element.into_token())
+            .filter(|token| token.kind().is_command_name())
+        {
+            expected
+                .entry(token.text().into())
+                .or_default()
+                .push(token.text_range());
+        }
+
+        assert_eq!(extras.command_ranges_by_name, expected);
+        assert_eq!(extras.command_ranges_by_name["\\foo"].len(), 3);
+    }
+
+    #[test]
+    fn test_label_index() {
+        let (_, extras) =
+            analyze_text("\\label{foo} \\ref{foo,bar} \\crefrange{bar}{foo} \\label{baz}");
+
+        let names: Vec<_> = extras.label_names_by_text["foo"]
+            .iter()
+            .map(|name| (name.range, name.is_definition))
+            .collect();
+
+        assert_eq!(
+            names,
+            [
+                (TextRange::new(7.into(), 10.into()), true),
+                (TextRange::new(17.into(), 20.into()), false),
+                (TextRange::new(42.into(), 45.into()), false),
+            ]
+        );
+
+        assert_eq!(extras.label_names_by_text["bar"].len(), 2);
+        assert_eq!(extras.label_names_by_text["baz"].len(), 1);
+    }
+
+    #[test]
+    fn test_custom_sections() {
+        let options = Options {
+            symbols: SymbolOptions {
+                custom_section_commands: vec![CustomSectionCommand {
+                    name: "mysection".into(),
+                    level: SectionLevel::Section,
+                }],
+            },
+            ..Options::default()
+        };
+
+        let environment = Environment {
+            options: Arc::new(options),
+            ..Environment::default()
+        };
+
+        let (_, extras) = analyze_text_with_environment(
+            "\\mysection{Foo} \\mysection*[Bar]{Baz} \\mysection \\othersection{Qux}",
+            "http://example.com/main.tex",
+            "http://example.com/main.tex",
+            environment,
+        );
+
+        let sections: Vec<_> = extras
+            .custom_sections
+            .iter()
+            .map(|section| (section.title.as_str(), section.level))
+            .collect();
+
+        assert_eq!(sections, [("Foo", 2), ("Baz", 2)]);
+    }
+
+    #[test]
+    fn test_defined_commands() {
+        let (_, extras) = analyze_text(
+            "\\newcommand{\\foo}{} \\def\\bar{} \\providecommand{\\baz}{} \\let\\qux\\relax \\quux",
+        );
+
+        let mut names: Vec<_> = extras
+            .defined_commands
+            .iter()
+            .map(SmolStr::as_str)
+            .collect();
+        names.sort_unstable();
+        assert_eq!(names, ["\\bar", "\\baz", "\\foo", "\\qux"]);
+    }
+
+    #[test]
+    fn test_user_commands() {
+        let text = "\\newcommand{\\foo}{a} \\foo \\renewcommand{\\foo}{b} \\foo";
+        let (_, extras) = analyze_text(text);
+
+        let definitions: Vec<_> = extras
+            .user_commands
+            .iter()
+            .map(|command| &text[command.full_range])
+            .collect();
+        assert_eq!(
+            definitions,
+            ["\\newcommand{\\foo}{a}", "\\renewcommand{\\foo}{b}"]
+        );
+
+        let find = |offset: u32| {
+            extras
+                .find_user_command("\\foo", offset.into())
+                .map(|command| &text[command.full_range])
+        };
+        assert_eq!(find(0), Some("\\newcommand{\\foo}{a}"));
+        assert_eq!(find(21), Some("\\newcommand{\\foo}{a}"));
+        assert_eq!(find(49), Some("\\renewcommand{\\foo}{b}"));
+    }
+
+    #[test]
+    fn test_bibliography_style() {
+        let (_, extras) = analyze_text("\\bibliographystyle{alpha} \\bibliography{foo}");
+        assert_eq!(extras.bibliography_style.as_deref(), Some("alpha"));
+    }
+
+    #[test]
+    fn test_bibliography_stems() {
+        let (_, extras) = analyze_text("\\bibliography{refs1, sub/refs2}");
+
+        let links: Vec<_> = extras
+            .explicit_links
+            .iter()
+            .map(|link| (link.kind, link.stem.as_str(), link.targets[1].as_str()))
+            .collect();
+
+        assert_eq!(
+            links,
+            [
+                (
+                    ExplicitLinkKind::Bibtex,
+                    "refs1",
+                    "http://example.com/refs1.bib"
+                ),
+                (
+                    ExplicitLinkKind::Bibtex,
+                    "sub/refs2",
+                    "http://example.com/sub/refs2.bib"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bibliography_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let environment = Environment {
+            options: Arc::new(Options {
+                bibtex_directories: vec![dir.path().to_path_buf()],
+                ..Options::default()
+            }),
+            ..Environment::default()
+        };
+
+        let (_, extras) = analyze_text_with_environment(
+            "\\bibliography{refs}",
+            "http://example.com/main.tex",
+            "http://example.com/main.tex",
+            environment,
+        );
+
+        let expected = Url::from_file_path(dir.path().join("refs.bib")).unwrap();
+        let targets = &extras.explicit_links[0].targets;
+        assert_eq!(targets[1].as_str(), "http://example.com/refs.bib");
+        assert!(targets.iter().any(|target| **target == expected));
+    }
+
+    #[test]
+    fn test_include_only() {
+        let (_, extras) =
+            analyze_text("\\includeonly{foo} \\include{foo} \\include{bar} \\input{baz}");
+
+        let excluded: Vec<_> = extras
+            .explicit_links
+            .iter()
+            .map(|link| (link.stem.as_str(), link.is_excluded))
+            .collect();
+
+        assert_eq!(extras.include_only, Some(vec![SmolStr::from("foo")]));
+        assert_eq!(excluded, [("foo", false), ("bar", true), ("baz", false)]);
+    }
+
+    #[test]
+    fn test_label_numbers() {
+        let (_, extras) = analyze_text(
+            "\\newlabel{foo}{{A.1}{3}{Title}{section.A.1}{}} \\newlabel{bar}{{}{4}} \\newlabel{qux}{{\\caption@xref {qux}{ on input line 15}}{1}} \\newlabel{baz}{{2}{5}}",
+        );
+
+        let mut numbers: Vec<_> = extras
+            .label_numbers_by_name
+            .iter()
+            .map(|(name, number)| (name.as_str(), number.as_str()))
+            .collect();
+        numbers.sort_unstable();
+
+        assert_eq!(numbers, [("baz", "2"), ("foo", "A.1")]);
+    }
+
+    #[test]
+    fn test_citation_numbers() {
+        let (_, extras) = analyze_text(
+            "\\bibcite{foo}{{1}{2020}{{Foo et~al.}}{{Foo and Bar}}} \\bibcite{bar}{2} \\bibcite{baz}{}",
+        );
+
+        let mut numbers: Vec<_> = extras
+            .citation_numbers_by_key
+            .iter()
+            .map(|(key, number)| (key.as_str(), number.number.as_str()))
+            .collect();
+        numbers.sort_unstable();
+
+        assert_eq!(numbers, [("bar", "2"), ("foo", "1")]);
+    }
+
+    #[test]
+    fn test_nested_imports() {
+        let (_, extras) = analyze_text_with_base(
+            "\\subimport{sections/}{foo} \\subinputfrom{../appendix/}{bar} \\import{chapters/}{baz}",
+            "http://example.com/chapters/intro.tex",
+            "http://example.com/",
+        );
+
+        let targets: Vec<_> = extras
+            .explicit_links
+            .iter()
+            .map(|link| link.targets[1].as_str())
+            .collect();
+
+        assert_eq!(
+            targets,
+            [
+                "http://example.com/chapters/sections/foo.tex",
+                "http://example.com/appendix/bar.tex",
+                "http://example.com/chapters/baz.tex"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_user_environments() {
+        let (_, extras) = analyze_text(
+            "\\newenvironment{foo}[2][x]{}{} \\renewenvironment{bar}{}{} \\newenvironment{baz}[a]{}{}",
+        );
+
+        let environments: Vec<_> = extras
+            .user_environments
+            .iter()
+            .map(|environment| (environment.name.as_str(), environment.argument_count))
+            .collect();
+
+        assert_eq!(
+            environments,
+            [("foo", Some(2)), ("bar", None), ("baz", None)]
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use rowan::ast::AstNode;
+
+use crate::syntax::latex::{self, HasCurly};
+
+use super::LatexAnalyzerContext;
+
+pub fn analyze_bibliography_style(
+    context: &mut LatexAnalyzerContext,
+    node: latex::SyntaxNode,
+) -> Option<()> {
+    let command = latex::GenericCommand::cast(node)?;
+    if command.name()?.text() != "\\bibliographystyle" {
+        return None;
+    }
+
+    let style = command
+        .syntax()
+        .children()
+        .find_map(latex::CurlyGroup::cast)?
+        .content_text()?;
+
+    context.extras.bibliography_style = Some(style.into());
+    Some(())
+}
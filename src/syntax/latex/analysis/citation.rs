@@ -0,0 +1,19 @@
+use rowan::ast::AstNode;
+
+use crate::syntax::latex;
+
+use super::LatexAnalyzerContext;
+
+pub fn analyze_citation(context: &mut LatexAnalyzerContext, node: latex::SyntaxNode) -> Option<()> {
+    let citation = latex::Citation::cast(node)?;
+    for key in citation.key_lists().flat_map(|keys| keys.keys()) {
+        context
+            .extras
+            .citation_ranges_by_key
+            .entry(key.to_string().into())
+            .or_default()
+            .push(latex::small_range(&key));
+    }
+
+    Some(())
+}
@@ -2,14 +2,50 @@ use rowan::ast::AstNode;
 
 use crate::syntax::latex;
 
-use super::LatexAnalyzerContext;
+use super::{LatexAnalyzerContext, UserCommand};
+
+/// Commands that define the command after them but are not parsed as a `CommandDefinition`.
+const DEFINITION_COMMANDS: &[&str] = &[
+    "\\def",
+    "\\gdef",
+    "\\edef",
+    "\\xdef",
+    "\\let",
+    "\\providecommand",
+    "\\providecommand*",
+    "\\NewDocumentCommand",
+    "\\RenewDocumentCommand",
+    "\\ProvideDocumentCommand",
+    "\\DeclareDocumentCommand",
+];
 
 pub fn analyze_command(context: &mut LatexAnalyzerContext, node: latex::SyntaxNode) -> Option<()> {
     let command = latex::GenericCommand::cast(node)?;
-    context
-        .extras
-        .command_names
-        .insert(command.name()?.text().into());
+    let name = command.name()?;
+    context.extras.command_names.insert(name.text().into());
+
+    if DEFINITION_COMMANDS.contains(&name.text()) {
+        // Handles both `\providecommand{\foo}` and `\def\foo`.
+        let defined_name = command
+            .syntax()
+            .descendants_with_tokens()
+            .filter_map(|element| element.into_token())
+            .filter(|token| token.kind().is_command_name())
+            .nth(1)
+            .or_else(|| {
+                command
+                    .syntax()
+                    .next_sibling()
+                    .and_then(latex::GenericCommand::cast)
+                    .and_then(|command| command.name())
+            })?;
+
+        context
+            .extras
+            .defined_commands
+            .insert(defined_name.text().into());
+    }
+
     Some(())
 }
 
@@ -18,9 +54,30 @@ pub fn analyze_command_definition(
     node: latex::SyntaxNode,
 ) -> Option<()> {
     let definition = latex::CommandDefinition::cast(node)?;
+    let name = definition.name()?.command()?;
+    context.extras.command_names.insert(name.text().into());
+    context.extras.defined_commands.insert(name.text().into());
+    context.extras.user_commands.push(UserCommand {
+        name: name.text().into(),
+        range: name.text_range(),
+        full_range: latex::small_range(&definition),
+    });
+    Some(())
+}
+
+pub fn analyze_command_reference(
+    context: &mut LatexAnalyzerContext,
+    token: latex::SyntaxToken,
+) -> Option<()> {
+    if !token.kind().is_command_name() {
+        return None;
+    }
+
     context
         .extras
-        .command_names
-        .insert(definition.name()?.command()?.text().into());
+        .command_ranges_by_name
+        .entry(token.text().into())
+        .or_default()
+        .push(token.text_range());
     Some(())
 }
@@ -0,0 +1,20 @@
+use rowan::ast::AstNode;
+
+use crate::syntax::latex;
+
+use super::{Counter, LatexAnalyzerContext};
+
+pub fn analyze_counter_definition(
+    context: &mut LatexAnalyzerContext,
+    node: latex::SyntaxNode,
+) -> Option<()> {
+    let definition = latex::CounterDefinition::cast(node)?;
+    let name = definition.name()?.key()?;
+    context.extras.counters.push(Counter {
+        name: name.to_string().into(),
+        range: latex::small_range(&name),
+        full_range: latex::small_range(&definition),
+    });
+
+    Some(())
+}
@@ -0,0 +1,37 @@
+use rowan::ast::AstNode;
+
+use crate::syntax::latex::{self, HasCurly};
+
+use super::{CustomSection, LatexAnalyzerContext};
+
+pub fn analyze_custom_section(
+    context: &mut LatexAnalyzerContext,
+    node: latex::SyntaxNode,
+) -> Option<()> {
+    let command = latex::GenericCommand::cast(node)?;
+    let name = command.name()?;
+    let name = name.text()[1..].trim_end_matches('*');
+
+    let level = context
+        .environment
+        .options
+        .symbols
+        .custom_section_commands
+        .iter()
+        .find(|custom| custom.name.trim_start_matches('\\') == name)?
+        .level;
+
+    let title = command
+        .syntax()
+        .children()
+        .find_map(latex::CurlyGroup::cast)?
+        .content_text()?;
+
+    context.extras.custom_sections.push(CustomSection {
+        title,
+        level: level.depth(),
+        range: latex::small_range(&command),
+    });
+
+    Some(())
+}
@@ -2,7 +2,7 @@ use rowan::ast::AstNode;
 
 use crate::syntax::latex;
 
-use super::LatexAnalyzerContext;
+use super::{LatexAnalyzerContext, UserEnvironment};
 
 pub fn analyze_begin(context: &mut LatexAnalyzerContext, node: latex::SyntaxNode) -> Option<()> {
     let begin = latex::Begin::cast(node)?;
@@ -11,3 +11,25 @@ pub fn analyze_begin(context: &mut LatexAnalyzerContext, node: latex::SyntaxNode
     extras.environment_names.insert(name);
     Some(())
 }
+
+pub fn analyze_environment_definition(
+    context: &mut LatexAnalyzerContext,
+    node: latex::SyntaxNode,
+) -> Option<()> {
+    let definition = latex::EnvironmentDefinition::cast(node)?;
+    let name = definition.name()?.key()?;
+    let argument_count = definition
+        .argument_count()
+        .and_then(|group| group.key())
+        .and_then(|key| key.to_string().parse().ok());
+
+    context.extras.user_environments.push(UserEnvironment {
+        name: name.to_string().into(),
+        range: latex::small_range(&name),
+        full_range: latex::small_range(&definition),
+        argument_count,
+        has_optional_argument: definition.default_argument().is_some(),
+    });
+
+    Some(())
+}
@@ -1,8 +1,10 @@
 use std::sync::Arc;
 
+use lsp_types::Url;
 use rowan::ast::AstNode;
+use smol_str::SmolStr;
 
-use crate::syntax::latex;
+use crate::syntax::latex::{self, HasKeyValueBody};
 
 use super::{
     distro_file::resolve_distro_file, ExplicitLink, ExplicitLinkKind, LatexAnalyzerContext,
@@ -15,24 +17,47 @@ pub fn analyze_include(context: &mut LatexAnalyzerContext, node: latex::SyntaxNo
         latex::BIBLATEX_INCLUDE | latex::BIBTEX_INCLUDE => ExplicitLinkKind::Bibtex,
         latex::PACKAGE_INCLUDE => ExplicitLinkKind::Package,
         latex::CLASS_INCLUDE => ExplicitLinkKind::Class,
+        latex::VERBATIM_INCLUDE => ExplicitLinkKind::Verbatim,
+        latex::XR_INCLUDE => ExplicitLinkKind::External,
         _ => return None,
     };
 
-    let extensions = match kind {
+    let extensions: &[&str] = match kind {
         ExplicitLinkKind::Latex => &["tex"],
         ExplicitLinkKind::Bibtex => &["bib"],
         ExplicitLinkKind::Package => &["sty"],
         ExplicitLinkKind::Class => &["cls"],
+        ExplicitLinkKind::Verbatim => &[],
+        ExplicitLinkKind::External => &["tex", "aux"],
     };
 
+    let prefix = if kind == ExplicitLinkKind::External {
+        find_label_prefix(&include)
+    } else {
+        None
+    };
+
+    // `\includeonly` only applies to `\include` but not to `\input`.
+    let include_only = context.extras.include_only.as_ref().filter(|_| {
+        include
+            .command()
+            .map_or(false, |command| command.text() == "\\include")
+    });
+
     for path in include.path_list()?.keys() {
         let stem = path.to_string();
+        let is_excluded =
+            include_only.map_or(false, |names| !names.iter().any(|name| name == &stem));
         let mut targets = vec![Arc::new(context.base_uri.join(&stem).ok()?)];
         for extension in extensions {
             let path = format!("{}.{}", stem, extension);
             targets.push(Arc::new(context.base_uri.join(&path).ok()?));
         }
 
+        if kind == ExplicitLinkKind::Bibtex {
+            resolve_bibtex_directories(context, &stem, &mut targets);
+        }
+
         resolve_distro_file(&context.environment.resolver, &stem, extensions)
             .into_iter()
             .for_each(|target| targets.push(Arc::new(target)));
@@ -42,19 +67,117 @@ pub fn analyze_include(context: &mut LatexAnalyzerContext, node: latex::SyntaxNo
             stem: stem.into(),
             stem_range: latex::small_range(&path),
             targets,
+            base_uri: Arc::clone(&context.base_uri),
+            is_import: false,
+            is_excluded,
+            prefix: prefix.clone(),
         });
     }
 
+    if kind == ExplicitLinkKind::Class {
+        analyze_subfiles_parent(context, &include);
+    }
+
+    Some(())
+}
+
+/// Returns the optional label prefix of `\externaldocument[prefix]{...}`.
+fn find_label_prefix(include: &latex::Include) -> Option<SmolStr> {
+    let prefix = include
+        .syntax()
+        .children()
+        .find_map(latex::BrackGroupKeyValue::cast)?
+        .body()?
+        .pairs()
+        .next()?
+        .key()?;
+
+    Some(prefix.to_string().into())
+}
+
+/// Adds the bibliography from the `bibtexDirectories` option as a fallback if it cannot be found beside the document.
+fn resolve_bibtex_directories(
+    context: &LatexAnalyzerContext,
+    stem: &str,
+    targets: &mut Vec<Arc<Url>>,
+) {
+    let base_directory = context.environment.base_directory(&context.document_uri);
+    for directory in &context.environment.options.bibtex_directories {
+        let directory = base_directory.join(directory);
+        for path in [
+            directory.join(stem),
+            directory.join(format!("{}.bib", stem)),
+        ] {
+            if let Ok(uri) = Url::from_file_path(path) {
+                targets.push(Arc::new(uri));
+            }
+        }
+    }
+}
+
+/// Links a subfile to the main document given by `\documentclass[main.tex]{subfiles}`.
+fn analyze_subfiles_parent(
+    context: &mut LatexAnalyzerContext,
+    include: &latex::Include,
+) -> Option<()> {
+    include
+        .path_list()?
+        .keys()
+        .find(|path| path.to_string() == "subfiles")?;
+
+    let path = include
+        .syntax()
+        .children()
+        .find_map(latex::BrackGroupKeyValue::cast)?
+        .body()?
+        .pairs()
+        .next()?
+        .key()?;
+
+    let stem = path.to_string();
+    let mut targets = vec![Arc::new(context.base_uri.join(&stem).ok()?)];
+    if !stem.ends_with(".tex") {
+        targets.push(Arc::new(
+            context.base_uri.join(&format!("{}.tex", stem)).ok()?,
+        ));
+    }
+
+    context.extras.explicit_links.push(ExplicitLink {
+        kind: ExplicitLinkKind::Latex,
+        stem: stem.into(),
+        stem_range: latex::small_range(&path),
+        targets,
+        base_uri: Arc::clone(&context.base_uri),
+        is_import: false,
+        is_excluded: false,
+        prefix: None,
+    });
+
     Some(())
 }
 
 pub fn analyze_import(context: &mut LatexAnalyzerContext, node: latex::SyntaxNode) -> Option<()> {
     let import = latex::Import::cast(node)?;
 
+    // `\subimport` and friends are relative to the importing file
+    // while `\import` and friends are relative to the root directory.
+    let is_relative = import
+        .command()
+        .map_or(false, |command| command.text().starts_with("\\sub"));
+
+    let base_uri = if is_relative {
+        context
+            .import_directory
+            .as_ref()
+            .unwrap_or(&context.document_uri)
+    } else {
+        &context.base_uri
+    };
+
     let mut targets = Vec::new();
     let directory = match import.directory().and_then(|dir| dir.key()) {
-        Some(dir) => Arc::new(context.base_uri.join(&dir.to_string()).ok()?),
-        None => Arc::clone(&context.base_uri),
+        Some(dir) => Arc::new(base_uri.join(&dir.to_string()).ok()?),
+        None => Arc::clone(base_uri),
     };
 
     let file = import.file()?.key()?;
@@ -66,7 +189,11 @@ pub fn analyze_import(context: &mut LatexAnalyzerContext, node: latex::SyntaxNod
         stem: stem.into(),
         stem_range: latex::small_range(&file),
         targets,
+        base_uri: directory,
         kind: ExplicitLinkKind::Latex,
+        is_import: true,
+        is_excluded: false,
+        prefix: None,
     });
     Some(())
 }
@@ -0,0 +1,77 @@
+use rowan::ast::AstNode;
+
+use crate::syntax::latex::{self, HasCurly, HasKeyValueBody};
+
+use super::{GlossaryEntry, GlossaryEntryKind, LatexAnalyzerContext};
+
+pub fn analyze_glossary_entry(
+    context: &mut LatexAnalyzerContext,
+    node: latex::SyntaxNode,
+) -> Option<()> {
+    analyze_glossary_entry_definition(context, node.clone())
+        .or_else(|| analyze_acronym_definition(context, node))
+}
+
+fn analyze_glossary_entry_definition(
+    context: &mut LatexAnalyzerContext,
+    node: latex::SyntaxNode,
+) -> Option<()> {
+    let entry = latex::GlossaryEntryDefinition::cast(node)?;
+    let name = entry.name()?.key()?;
+    let description = entry
+        .syntax()
+        .children()
+        .find_map(latex::CurlyGroupKeyValue::cast)
+        .and_then(|group| group.body())
+        .and_then(|body| {
+            body.pairs().find(|pair| {
+                pair.key()
+                    .map_or(false, |key| key.to_string() == "description")
+            })
+        })
+        .and_then(|pair| pair.value())
+        .and_then(|value| value_text(&value));
+
+    context.extras.glossary_entries.push(GlossaryEntry {
+        name: name.to_string().into(),
+        range: latex::small_range(&name),
+        full_range: latex::small_range(&entry),
+        description,
+        kind: GlossaryEntryKind::Entry,
+    });
+
+    Some(())
+}
+
+fn analyze_acronym_definition(
+    context: &mut LatexAnalyzerContext,
+    node: latex::SyntaxNode,
+) -> Option<()> {
+    let acronym = latex::AcronymDefinition::cast(node)?;
+    let name = acronym.name()?.key()?;
+    let description = acronym
+        .syntax()
+        .children()
+        .filter_map(latex::CurlyGroup::cast)
+        .nth(1)
+        .and_then(|group| group.content_text());
+
+    context.extras.glossary_entries.push(GlossaryEntry {
+        name: name.to_string().into(),
+        range: latex::small_range(&name),
+        full_range: latex::small_range(&acronym),
+        description,
+        kind: GlossaryEntryKind::Acronym,
+    });
+
+    Some(())
+}
+
+fn value_text(value: &latex::Value) -> Option<String> {
+    let text = match value.syntax().children().find_map(latex::CurlyGroup::cast) {
+        Some(group) => group.content_text()?,
+        None => value.syntax().text().to_string().trim().to_string(),
+    };
+
+    Some(text).filter(|text| !text.is_empty())
+}
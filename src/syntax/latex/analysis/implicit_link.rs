@@ -25,6 +25,10 @@ fn find_by_extension(context: &LatexAnalyzerContext, extension: &str) -> Option<
         if let Some(build_dir) = options.aux_directory.as_ref() {
             find_inside_directory(context, build_dir, &aux_name, &mut targets)?;
         }
+
+        if let Some(output_dir) = options.output_directory.as_ref() {
+            find_inside_directory(context, output_dir, &aux_name, &mut targets)?;
+        }
     }
     Some(targets)
 }
@@ -37,7 +41,7 @@ fn find_inside_directory(
 ) -> Option<()> {
     let path = context
         .environment
-        .current_directory
+        .base_directory(&context.document_uri)
         .join(dir)
         .join(aux_name);
 
@@ -54,7 +58,7 @@ fn find_inside_directory(
 }
 
 fn with_extension(uri: &Url, extension: &str) -> Option<Url> {
-    let file_name = uri.path_segments()?.last()?;
+    let file_name = uri.path_segments()?.next_back()?;
     let file_stem = file_name
         .rfind('.')
         .map(|i| &file_name[..i])
@@ -0,0 +1,18 @@
+use rowan::ast::AstNode;
+
+use crate::syntax::latex;
+
+use super::LatexAnalyzerContext;
+
+pub fn analyze_include_only(
+    context: &mut LatexAnalyzerContext,
+    node: latex::SyntaxNode,
+) -> Option<()> {
+    let include_only = latex::IncludeOnly::cast(node)?;
+    let names = context.extras.include_only.get_or_insert_with(Vec::new);
+    for path in include_only.path_list()?.keys() {
+        names.push(path.to_string().into());
+    }
+
+    Some(())
+}
@@ -1,5 +1,6 @@
 use latex::LabelReferenceRange;
 use rowan::ast::AstNode;
+use smol_str::SmolStr;
 
 use crate::syntax::latex;
 
@@ -20,11 +21,7 @@ fn analyze_label_definition_name(
 ) -> Option<()> {
     let label = latex::LabelDefinition::cast(node)?;
     let name = label.name()?.key()?;
-    context.extras.label_names.push(LabelName {
-        text: name.to_string().into(),
-        range: latex::small_range(&name),
-        is_definition: true,
-    });
+    push_label_name(context, &name, true);
     Some(())
 }
 
@@ -34,11 +31,7 @@ fn analyze_label_reference_name(
 ) -> Option<()> {
     let label = latex::LabelReference::cast(node)?;
     for name in label.name_list()?.keys() {
-        context.extras.label_names.push(LabelName {
-            text: name.to_string().into(),
-            range: latex::small_range(&name),
-            is_definition: false,
-        });
+        push_label_name(context, &name, false);
     }
     Some(())
 }
@@ -49,19 +42,25 @@ fn analyze_label_reference_range_name(
 ) -> Option<()> {
     let label = LabelReferenceRange::cast(node)?;
     if let Some(name1) = label.from().and_then(|name| name.key()) {
-        context.extras.label_names.push(LabelName {
-            text: name1.to_string().into(),
-            range: latex::small_range(&name1),
-            is_definition: false,
-        });
+        push_label_name(context, &name1, false);
     }
 
     if let Some(name2) = label.to().and_then(|name| name.key()) {
-        context.extras.label_names.push(LabelName {
-            text: name2.to_string().into(),
-            range: latex::small_range(&name2),
-            is_definition: false,
-        });
+        push_label_name(context, &name2, false);
     }
     Some(())
 }
+
+fn push_label_name(context: &mut LatexAnalyzerContext, name: &latex::Key, is_definition: bool) {
+    let text: SmolStr = name.to_string().into();
+    context
+        .extras
+        .label_names_by_text
+        .entry(text.clone())
+        .or_default()
+        .push(LabelName {
+            text,
+            range: latex::small_range(name),
+            is_definition,
+        });
+}
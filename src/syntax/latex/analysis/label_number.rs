@@ -1,8 +1,8 @@
-use rowan::ast::AstNode;
+use rowan::{ast::AstNode, TextRange};
 
-use crate::syntax::latex;
+use crate::syntax::latex::{self, HasCurly};
 
-use super::LatexAnalyzerContext;
+use super::{LatexAnalyzerContext, ResolvedCitation};
 
 pub fn analyze_label_number(
     context: &mut LatexAnalyzerContext,
@@ -10,15 +10,59 @@ pub fn analyze_label_number(
 ) -> Option<()> {
     let number = latex::LabelNumber::cast(node)?;
     let name = number.name()?.key()?.to_string();
-    let text = number
-        .text()?
-        .syntax()
-        .descendants_with_tokens()
-        .filter_map(|element| element.into_node())
-        .find(|node| node.kind() == latex::TEXT || node.kind() == latex::MIXED_GROUP)?
-        .text()
-        .to_string();
-
+    let text = find_number(&number.text()?)?;
     context.extras.label_numbers_by_name.insert(name, text);
     Some(())
 }
+
+pub fn analyze_citation_number(
+    context: &mut LatexAnalyzerContext,
+    node: latex::SyntaxNode,
+) -> Option<()> {
+    let number = latex::CitationNumber::cast(node)?;
+    let key = number.key()?.key()?;
+    let group = number.text()?;
+    let text = find_number(&group)?;
+
+    // The parser inserts an empty token after the arguments, which would include the trailing trivia.
+    let start = number.syntax().text_range().start();
+    let full_range = TextRange::new(start, latex::small_range(&group).end());
+    context.extras.citation_numbers_by_key.insert(
+        key.to_string(),
+        ResolvedCitation {
+            number: text,
+            range: latex::small_range(&key),
+            full_range,
+        },
+    );
+
+    Some(())
+}
+
+/// Finds the number inside the second argument of `\newlabel` or `\bibcite`.
+fn find_number(group: &latex::CurlyGroup) -> Option<String> {
+    // The number is the first entry of `{{number}{page}...}`, which is empty for unnumbered labels
+    // and contains commands like `\caption@xref` if the label refers to an undefined counter.
+    // `natbib` uses the same format for `\bibcite` while other styles only write the number.
+    let text = match group.syntax().children().find_map(latex::CurlyGroup::cast) {
+        Some(entry) => {
+            if entry
+                .syntax()
+                .descendants()
+                .any(|node| node.kind() == latex::GENERIC_COMMAND)
+            {
+                return None;
+            }
+
+            entry.content_text()?
+        }
+        None => group
+            .syntax()
+            .descendants()
+            .find(|node| node.kind() == latex::TEXT || node.kind() == latex::MIXED_GROUP)?
+            .text()
+            .to_string(),
+    };
+
+    Some(text).filter(|text| !text.is_empty())
+}
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use lsp_types::Url;
-use rowan::TextRange;
+use rowan::{TextRange, TextSize};
 use rustc_hash::{FxHashMap, FxHashSet};
 use smol_str::SmolStr;
 
@@ -12,6 +12,8 @@ pub struct LatexAnalyzerContext<'a> {
     pub environment: &'a Environment,
     pub document_uri: Arc<Url>,
     pub base_uri: Arc<Url>,
+    /// The directory of the `\import` command that includes the document.
+    pub import_directory: Option<Arc<Url>>,
     pub extras: Extras,
 }
 
@@ -21,11 +23,40 @@ pub struct Extras {
     pub explicit_links: Vec<ExplicitLink>,
     pub has_document_environment: bool,
     pub command_names: FxHashSet<SmolStr>,
+    /// The commands that are defined in the document, for example with `\newcommand` or `\def`.
+    pub defined_commands: FxHashSet<SmolStr>,
     pub environment_names: FxHashSet<String>,
-    pub label_names: Vec<LabelName>,
+    /// The definitions and references of labels grouped by their name.
+    pub label_names_by_text: FxHashMap<SmolStr, Vec<LabelName>>,
     pub label_numbers_by_name: FxHashMap<String, String>,
+    /// The citation labels written to the `.aux` file with `\bibcite`.
+    pub citation_numbers_by_key: FxHashMap<String, ResolvedCitation>,
     pub theorem_environments: Vec<TheoremEnvironment>,
+    pub user_environments: Vec<UserEnvironment>,
+    /// The command definitions of the document in document order.
+    pub user_commands: Vec<UserCommand>,
     pub graphics_paths: FxHashSet<String>,
+    pub glossary_entries: Vec<GlossaryEntry>,
+    /// The counters that are defined with `\newcounter`.
+    pub counters: Vec<Counter>,
+    pub citation_ranges_by_key: FxHashMap<SmolStr, Vec<TextRange>>,
+    pub command_ranges_by_name: FxHashMap<SmolStr, Vec<TextRange>>,
+    pub include_only: Option<Vec<SmolStr>>,
+    pub custom_sections: Vec<CustomSection>,
+    /// The style given by `\bibliographystyle`.
+    pub bibliography_style: Option<SmolStr>,
+}
+
+impl Extras {
+    /// Finds the definition of the command that is in effect at the given offset,
+    /// which is the last definition of the command before the offset.
+    #[must_use]
+    pub fn find_user_command(&self, name: &str, offset: TextSize) -> Option<&UserCommand> {
+        self.user_commands
+            .iter()
+            .rev()
+            .find(|command| command.name == name && command.full_range.start() <= offset)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Default, Hash)]
@@ -41,6 +72,10 @@ pub enum ExplicitLinkKind {
     Class,
     Latex,
     Bibtex,
+    /// A file that is included verbatim like `\lstinputlisting` and is not parsed.
+    Verbatim,
+    /// A separate document whose labels are referenced with `\externaldocument` from `xr`.
+    External,
 }
 
 #[derive(Debug, Clone)]
@@ -48,7 +83,15 @@ pub struct ExplicitLink {
     pub stem: SmolStr,
     pub stem_range: TextRange,
     pub targets: Vec<Arc<Url>>,
+    /// The directory that the stem is resolved against, like the directory of `\import`.
+    pub base_uri: Arc<Url>,
     pub kind: ExplicitLinkKind,
+    /// Whether the link is an `\import` command or one of its variants.
+    pub is_import: bool,
+    /// Whether the link is an `\include` that is skipped because of `\includeonly`.
+    pub is_excluded: bool,
+    /// The prefix of `\externaldocument[prefix]{...}` that is prepended to the labels of the external document.
+    pub prefix: Option<SmolStr>,
 }
 
 impl ExplicitLink {
@@ -56,7 +99,10 @@ impl ExplicitLink {
         match self.kind {
             ExplicitLinkKind::Package => Some(format!("{}.sty", self.stem)),
             ExplicitLinkKind::Class => Some(format!("{}.cls", self.stem)),
-            ExplicitLinkKind::Latex | ExplicitLinkKind::Bibtex => None,
+            ExplicitLinkKind::Latex
+            | ExplicitLinkKind::Bibtex
+            | ExplicitLinkKind::Verbatim
+            | ExplicitLinkKind::External => None,
         }
     }
 }
@@ -67,9 +113,65 @@ pub struct TheoremEnvironment {
     pub description: String,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct UserEnvironment {
+    pub name: SmolStr,
+    pub range: TextRange,
+    pub full_range: TextRange,
+    pub argument_count: Option<usize>,
+    pub has_optional_argument: bool,
+}
+
+/// A command defined with `\newcommand`, `\renewcommand` or a similar command.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct UserCommand {
+    pub name: SmolStr,
+    pub range: TextRange,
+    pub full_range: TextRange,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Default, Hash)]
 pub struct LabelName {
     pub text: SmolStr,
     pub range: TextRange,
     pub is_definition: bool,
 }
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum GlossaryEntryKind {
+    Entry,
+    Acronym,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct GlossaryEntry {
+    pub name: SmolStr,
+    pub range: TextRange,
+    pub full_range: TextRange,
+    pub description: Option<String>,
+    pub kind: GlossaryEntryKind,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Counter {
+    pub name: SmolStr,
+    pub range: TextRange,
+    pub full_range: TextRange,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct ResolvedCitation {
+    /// The number or label that is printed when citing the entry.
+    pub number: String,
+    pub range: TextRange,
+    pub full_range: TextRange,
+}
+
+/// A command that is configured as a sectioning command with `symbols.customSectionCommands`.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct CustomSection {
+    pub title: String,
+    /// The nesting level, starting with 0 for `\part` like `Section::level`.
+    pub level: usize,
+    pub range: TextRange,
+}
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use itertools::{EitherOrBoth, Itertools};
 use rowan::{ast::AstNode, TextRange};
 
@@ -21,6 +23,16 @@ pub fn small_range(node: &dyn AstNode<Language = LatexLanguage>) -> TextRange {
     TextRange::new(start, start)
 }
 
+/// Returns the name of the command that the node stands for if the command can be provided by a package.
+///
+/// Besides generic commands, this includes reference commands like `\crefrange` from `cleveref`.
+pub fn package_command_name(node: &SyntaxNode) -> Option<SyntaxToken> {
+    GenericCommand::cast(node.clone())
+        .and_then(|command| command.name())
+        .or_else(|| LabelReference::cast(node.clone()).and_then(|label| label.command()))
+        .or_else(|| LabelReferenceRange::cast(node.clone()).and_then(|label| label.command()))
+}
+
 macro_rules! cst_node {
     ($name:ident, $($kind:pat),+) => {
         #[derive(Clone)]
@@ -250,8 +262,8 @@ impl PartialEq for Key {
 
 impl Eq for Key {}
 
-impl ToString for Key {
-    fn to_string(&self) -> String {
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut buf = String::new();
         for word in self.words() {
             buf.push_str(word.text());
@@ -259,7 +271,7 @@ impl ToString for Key {
         }
 
         buf.pop().unwrap();
-        buf
+        f.write_str(&buf)
     }
 }
 
@@ -377,6 +389,19 @@ impl Section {
     pub fn name(&self) -> Option<CurlyGroup> {
         self.syntax().children().find_map(CurlyGroup::cast)
     }
+
+    /// Returns the nesting level of the section, starting with 0 for `\part`.
+    pub fn level(&self) -> usize {
+        match self.syntax().kind() {
+            PART => 0,
+            CHAPTER => 1,
+            SECTION => 2,
+            SUBSECTION => 3,
+            SUBSUBSECTION => 4,
+            PARAGRAPH => 5,
+            _ => 6,
+        }
+    }
 }
 
 cst_node!(EnumItem, ENUM_ITEM);
@@ -425,6 +450,12 @@ impl Citation {
     pub fn key_list(&self) -> Option<CurlyGroupWordList> {
         self.syntax().children().find_map(CurlyGroupWordList::cast)
     }
+
+    pub fn key_lists(&self) -> impl Iterator<Item = CurlyGroupWordList> {
+        self.syntax()
+            .children()
+            .filter_map(CurlyGroupWordList::cast)
+    }
 }
 
 cst_node!(
@@ -437,7 +468,8 @@ cst_node!(
     GRAPHICS_INCLUDE,
     SVG_INCLUDE,
     INKSCAPE_INCLUDE,
-    VERBATIM_INCLUDE
+    VERBATIM_INCLUDE,
+    XR_INCLUDE
 );
 
 impl Include {
@@ -528,6 +560,22 @@ impl LabelNumber {
     }
 }
 
+cst_node!(CitationNumber, CITATION_NUMBER);
+
+impl CitationNumber {
+    pub fn command(&self) -> Option<SyntaxToken> {
+        self.syntax().first_token()
+    }
+
+    pub fn key(&self) -> Option<CurlyGroupWord> {
+        self.syntax().children().find_map(CurlyGroupWord::cast)
+    }
+
+    pub fn text(&self) -> Option<CurlyGroup> {
+        self.syntax().children().find_map(CurlyGroup::cast)
+    }
+}
+
 cst_node!(TheoremDefinition, THEOREM_DEFINITION);
 
 impl TheoremDefinition {
@@ -555,11 +603,39 @@ impl CommandDefinition {
         self.syntax().children().find_map(CurlyGroupCommand::cast)
     }
 
+    pub fn argument_count(&self) -> Option<BrackGroupWord> {
+        self.syntax().children().find_map(BrackGroupWord::cast)
+    }
+
+    pub fn default_argument(&self) -> Option<BrackGroup> {
+        self.syntax().children().find_map(BrackGroup::cast)
+    }
+
     pub fn implementation(&self) -> Option<CurlyGroup> {
         self.syntax().children().find_map(CurlyGroup::cast)
     }
 }
 
+cst_node!(EnvironmentDefinition, ENVIRONMENT_DEFINITION);
+
+impl EnvironmentDefinition {
+    pub fn command(&self) -> Option<SyntaxToken> {
+        self.syntax().first_token()
+    }
+
+    pub fn name(&self) -> Option<CurlyGroupWord> {
+        self.syntax().children().find_map(CurlyGroupWord::cast)
+    }
+
+    pub fn argument_count(&self) -> Option<BrackGroupWord> {
+        self.syntax().children().find_map(BrackGroupWord::cast)
+    }
+
+    pub fn default_argument(&self) -> Option<BrackGroup> {
+        self.syntax().children().find_map(BrackGroup::cast)
+    }
+}
+
 cst_node!(AcronymReference, ACRONYM_REFERENCE);
 
 impl AcronymReference {
@@ -639,6 +715,34 @@ impl ColorReference {
     }
 }
 
+cst_node!(CounterDefinition, COUNTER_DEFINITION);
+
+impl CounterDefinition {
+    pub fn command(&self) -> Option<SyntaxToken> {
+        self.syntax().first_token()
+    }
+
+    pub fn name(&self) -> Option<CurlyGroupWord> {
+        self.syntax().children().find_map(CurlyGroupWord::cast)
+    }
+
+    pub fn parent(&self) -> Option<BrackGroupWord> {
+        self.syntax().children().find_map(BrackGroupWord::cast)
+    }
+}
+
+cst_node!(CounterReference, COUNTER_REFERENCE);
+
+impl CounterReference {
+    pub fn command(&self) -> Option<SyntaxToken> {
+        self.syntax().first_token()
+    }
+
+    pub fn name(&self) -> Option<CurlyGroupWord> {
+        self.syntax().children().find_map(CurlyGroupWord::cast)
+    }
+}
+
 cst_node!(GlossaryEntryReference, GLOSSARY_ENTRY_REFERENCE);
 
 impl GlossaryEntryReference {
@@ -675,6 +779,18 @@ impl TikzLibraryImport {
     }
 }
 
+cst_node!(IncludeOnly, INCLUDE_ONLY);
+
+impl IncludeOnly {
+    pub fn command(&self) -> Option<SyntaxToken> {
+        self.syntax().first_token()
+    }
+
+    pub fn path_list(&self) -> Option<CurlyGroupWordList> {
+        self.syntax().children().find_map(CurlyGroupWordList::cast)
+    }
+}
+
 cst_node!(GraphicsPath, GRAPHICS_PATH);
 
 impl GraphicsPath {
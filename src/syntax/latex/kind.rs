@@ -43,11 +43,13 @@ pub enum SyntaxKind {
     SVG_INCLUDE_NAME,
     INKSCAPE_INCLUDE_NAME,
     VERBATIM_INCLUDE_NAME,
+    XR_INCLUDE_NAME,
     IMPORT_NAME,
     LABEL_DEFINITION_NAME,
     LABEL_REFERENCE_NAME,
     LABEL_REFERENCE_RANGE_NAME,
     LABEL_NUMBER_NAME,
+    CITATION_NUMBER_NAME,
     COMMAND_DEFINITION_NAME,
     MATH_OPERATOR_NAME,
     GLOSSARY_ENTRY_DEFINITION_NAME,
@@ -64,6 +66,9 @@ pub enum SyntaxKind {
     BEGIN_BLOCK_COMMENT_NAME,
     END_BLOCK_COMMENT_NAME,
     GRAPHICS_PATH_NAME,
+    INCLUDE_ONLY_NAME,
+    COUNTER_DEFINITION_NAME,
+    COUNTER_REFERENCE_NAME,
 
     PREAMBLE,
     TEXT,
@@ -106,11 +111,13 @@ pub enum SyntaxKind {
     SVG_INCLUDE,
     INKSCAPE_INCLUDE,
     VERBATIM_INCLUDE,
+    XR_INCLUDE,
     IMPORT,
     LABEL_DEFINITION,
     LABEL_REFERENCE,
     LABEL_REFERENCE_RANGE,
     LABEL_NUMBER,
+    CITATION_NUMBER,
     COMMAND_DEFINITION,
     MATH_OPERATOR,
     GLOSSARY_ENTRY_DEFINITION,
@@ -125,6 +132,9 @@ pub enum SyntaxKind {
     TIKZ_LIBRARY_IMPORT,
     ENVIRONMENT_DEFINITION,
     GRAPHICS_PATH,
+    INCLUDE_ONLY,
+    COUNTER_DEFINITION,
+    COUNTER_REFERENCE,
     BLOCK_COMMENT,
     ROOT,
 }
@@ -158,11 +168,13 @@ impl SyntaxKind {
                 | SVG_INCLUDE_NAME
                 | INKSCAPE_INCLUDE_NAME
                 | VERBATIM_INCLUDE_NAME
+                | XR_INCLUDE_NAME
                 | IMPORT_NAME
                 | LABEL_DEFINITION_NAME
                 | LABEL_REFERENCE_NAME
                 | LABEL_REFERENCE_RANGE_NAME
                 | LABEL_NUMBER_NAME
+                | CITATION_NUMBER_NAME
                 | COMMAND_DEFINITION_NAME
                 | MATH_OPERATOR_NAME
                 | GLOSSARY_ENTRY_DEFINITION_NAME
@@ -175,6 +187,9 @@ impl SyntaxKind {
                 | COLOR_DEFINITION_NAME
                 | COLOR_SET_DEFINITION_NAME
                 | TIKZ_LIBRARY_IMPORT_NAME
+                | INCLUDE_ONLY_NAME
+                | COUNTER_DEFINITION_NAME
+                | COUNTER_REFERENCE_NAME
         )
     }
 }
@@ -116,6 +116,10 @@ enum CommandNameToken {
     #[token("\\citet*")]
     #[token("\\citep")]
     #[token("\\citep*")]
+    #[token("\\Citet")]
+    #[token("\\Citet*")]
+    #[token("\\Citep")]
+    #[token("\\Citep*")]
     #[token("\\citeauthor")]
     #[token("\\citeauthor*")]
     #[token("\\Citeauthor")]
@@ -131,16 +135,24 @@ enum CommandNameToken {
     #[token("\\citeyearpar")]
     #[token("\\citealt")]
     #[token("\\citealp")]
+    #[token("\\citealt*")]
+    #[token("\\citealp*")]
+    #[token("\\Citealt")]
+    #[token("\\Citealp")]
+    #[token("\\citenum")]
     #[token("\\citetext")]
     #[token("\\parencite")]
     #[token("\\parencite*")]
     #[token("\\Parencite")]
     #[token("\\footcite")]
+    #[token("\\Footcite")]
     #[token("\\footfullcite")]
     #[token("\\footcitetext")]
+    #[token("\\Footcitetext")]
     #[token("\\textcite")]
     #[token("\\Textcite")]
     #[token("\\smartcite")]
+    #[token("\\Smartcite")]
     #[token("\\supercite")]
     #[token("\\autocite")]
     #[token("\\autocite*")]
@@ -164,6 +176,21 @@ enum CommandNameToken {
     #[token("\\fnotecite")]
     #[token("\\citeA")]
     #[token("\\citeA*")]
+    #[token("\\cites")]
+    #[token("\\Cites")]
+    #[token("\\parencites")]
+    #[token("\\Parencites")]
+    #[token("\\footcites")]
+    #[token("\\Footcites")]
+    #[token("\\footcitetexts")]
+    #[token("\\Footcitetexts")]
+    #[token("\\smartcites")]
+    #[token("\\Smartcites")]
+    #[token("\\textcites")]
+    #[token("\\Textcites")]
+    #[token("\\supercites")]
+    #[token("\\autocites")]
+    #[token("\\Autocites")]
     Citation,
 
     #[token("\\usepackage")]
@@ -196,8 +223,12 @@ enum CommandNameToken {
 
     #[token("\\verbatiminput")]
     #[token("\\VerbatimInput")]
+    #[token("\\lstinputlisting")]
     VerbatimInclude,
 
+    #[token("\\externaldocument")]
+    XrInclude,
+
     #[token("\\import")]
     #[token("\\subimport")]
     #[token("\\inputfrom")]
@@ -226,6 +257,8 @@ enum CommandNameToken {
     #[token("\\lcnamecrefs")]
     #[token("\\labelcref")]
     #[token("\\labelcpageref")]
+    #[token("\\cpageref")]
+    #[token("\\Cpageref")]
     #[token("\\eqref")]
     LabelReference,
 
@@ -233,11 +266,16 @@ enum CommandNameToken {
     #[token("\\crefrange*")]
     #[token("\\Crefrange")]
     #[token("\\Crefrange*")]
+    #[token("\\cpagerefrange")]
+    #[token("\\Cpagerefrange")]
     LabelReferenceRange,
 
     #[token("\\newlabel")]
     LabelNumber,
 
+    #[token("\\bibcite")]
+    CitationNumber,
+
     #[token("\\newcommand")]
     #[token("\\newcommand*")]
     #[token("\\renewcommand")]
@@ -377,9 +415,22 @@ enum CommandNameToken {
     #[token("\\renewenvironment*")]
     EnvironmentDefinition,
 
+    #[token("\\newcounter")]
+    CounterDefinition,
+
+    #[token("\\setcounter")]
+    #[token("\\addtocounter")]
+    #[token("\\stepcounter")]
+    #[token("\\refstepcounter")]
+    #[token("\\value")]
+    CounterReference,
+
     #[token("\\graphicspath")]
     GraphicsPath,
 
+    #[token("\\includeonly")]
+    IncludeOnly,
+
     #[token("\\fi")]
     EndBlockComment,
 
@@ -431,6 +482,10 @@ impl<'a> Lexer<'a> {
         self.tokens.last().map(|(kind, _)| *kind)
     }
 
+    pub fn peek_text(&self) -> Option<&'a str> {
+        self.tokens.last().map(|(_, text)| *text)
+    }
+
     pub fn eat(&mut self) -> Option<(SyntaxKind, &'a str)> {
         self.tokens.pop()
     }
@@ -480,6 +535,16 @@ fn tokenize<'a>(input: &'a str, tokens: &mut Vec<(SyntaxKind, &'a str)>) {
             RootToken::Dollar => {
                 tokens.push((SyntaxKind::DOLLAR, text));
             }
+            RootToken::CommandName if matches!(text, "\\verb" | "\\verb*") => {
+                tokens.push((SyntaxKind::GENERIC_COMMAND_NAME, text));
+                let end = lexer.span().end;
+                lexer = RootToken::lexer(tokenize_inline_verbatim(&lexer.source()[end..], tokens));
+            }
+            RootToken::CommandName if matches!(text, "\\url" | "\\href") => {
+                tokens.push((SyntaxKind::GENERIC_COMMAND_NAME, text));
+                let end = lexer.span().end;
+                lexer = RootToken::lexer(tokenize_url(&lexer.source()[end..], tokens));
+            }
             RootToken::CommandName => {
                 let kind = tokenize_command_name(text);
                 tokens.push((kind, text));
@@ -530,11 +595,13 @@ fn tokenize_command_name(text: &str) -> SyntaxKind {
         CommandNameToken::SvgInclude => SyntaxKind::SVG_INCLUDE_NAME,
         CommandNameToken::InkscapeInclude => SyntaxKind::INKSCAPE_INCLUDE_NAME,
         CommandNameToken::VerbatimInclude => SyntaxKind::VERBATIM_INCLUDE_NAME,
+        CommandNameToken::XrInclude => SyntaxKind::XR_INCLUDE_NAME,
         CommandNameToken::Import => SyntaxKind::IMPORT_NAME,
         CommandNameToken::LabelDefinition => SyntaxKind::LABEL_DEFINITION_NAME,
         CommandNameToken::LabelReference => SyntaxKind::LABEL_REFERENCE_NAME,
         CommandNameToken::LabelReferenceRange => SyntaxKind::LABEL_REFERENCE_RANGE_NAME,
         CommandNameToken::LabelNumber => SyntaxKind::LABEL_NUMBER_NAME,
+        CommandNameToken::CitationNumber => SyntaxKind::CITATION_NUMBER_NAME,
         CommandNameToken::CommandDefinition => SyntaxKind::COMMAND_DEFINITION_NAME,
         CommandNameToken::MathOperator => SyntaxKind::MATH_OPERATOR_NAME,
         CommandNameToken::GlossaryEntryDefinition => SyntaxKind::GLOSSARY_ENTRY_DEFINITION_NAME,
@@ -549,7 +616,10 @@ fn tokenize_command_name(text: &str) -> SyntaxKind {
         CommandNameToken::TikzLibraryImport => SyntaxKind::TIKZ_LIBRARY_IMPORT_NAME,
         CommandNameToken::EnvironmentDefinition => SyntaxKind::ENVIRONMENT_DEFINITION_NAME,
         CommandNameToken::EndBlockComment => SyntaxKind::END_BLOCK_COMMENT_NAME,
+        CommandNameToken::CounterDefinition => SyntaxKind::COUNTER_DEFINITION_NAME,
+        CommandNameToken::CounterReference => SyntaxKind::COUNTER_REFERENCE_NAME,
         CommandNameToken::GraphicsPath => SyntaxKind::GRAPHICS_PATH_NAME,
+        CommandNameToken::IncludeOnly => SyntaxKind::INCLUDE_ONLY_NAME,
         CommandNameToken::Generic => SyntaxKind::GENERIC_COMMAND_NAME,
     }
 }
@@ -601,6 +671,46 @@ fn tokenize_verbatim_environment<'a>(
     &input[end..]
 }
 
+fn tokenize_inline_verbatim<'a>(
+    input: &'a str,
+    tokens: &mut Vec<(SyntaxKind, &'a str)>,
+) -> &'a str {
+    let delimiter = match input.chars().next() {
+        Some(c) if !c.is_whitespace() => c,
+        _ => return input,
+    };
+
+    let start = delimiter.len_utf8();
+    let line_end = input.find(['\r', '\n']).unwrap_or(input.len());
+    let end = input[start..line_end]
+        .find(delimiter)
+        .map_or(line_end, |i| start + i + delimiter.len_utf8());
+
+    tokens.push((SyntaxKind::VERBATIM, &input[..end]));
+    &input[end..]
+}
+
+/// Reads the argument of `\url` and `\href` as a single word
+/// since a URL may contain characters like `%` that are special in LaTeX.
+fn tokenize_url<'a>(input: &'a str, tokens: &mut Vec<(SyntaxKind, &'a str)>) -> &'a str {
+    if !input.starts_with('{') {
+        return input;
+    }
+
+    let end = match input.find(|c: char| c == '}' || c.is_whitespace()) {
+        Some(end) if input[end..].starts_with('}') => end,
+        _ => return input,
+    };
+
+    tokens.push((SyntaxKind::L_CURLY, "{"));
+    if end > 1 {
+        tokens.push((SyntaxKind::WORD, &input[1..end]));
+    }
+
+    tokens.push((SyntaxKind::R_CURLY, "}"));
+    &input[end + 1..]
+}
+
 #[cfg(test)]
 mod tests {
     use insta::assert_debug_snapshot;
@@ -653,6 +763,13 @@ mod tests {
         assert_debug_snapshot!(verify(r#"#"#))
     }
 
+    #[test]
+    fn test_url() {
+        assert_debug_snapshot!(verify(
+            r#"\url{https://example.org/a%20b#c} \href{https://example.org/%7E}{50%}"#
+        ));
+    }
+
     #[test]
     fn test_line_break() {
         assert_debug_snapshot!(verify("hello\nworld"));
@@ -131,11 +131,13 @@ impl<'a> Parser<'a> {
             SVG_INCLUDE_NAME => self.svg_include(),
             INKSCAPE_INCLUDE_NAME => self.inkscape_include(),
             VERBATIM_INCLUDE_NAME => self.verbatim_include(),
+            XR_INCLUDE_NAME => self.xr_include(),
             IMPORT_NAME => self.import(),
             LABEL_DEFINITION_NAME => self.label_definition(),
             LABEL_REFERENCE_NAME => self.label_reference(),
             LABEL_REFERENCE_RANGE_NAME => self.label_reference_range(),
-            LABEL_NUMBER_NAME => self.label_number(),
+            LABEL_NUMBER_NAME => self.label_number(LABEL_NUMBER),
+            CITATION_NUMBER_NAME => self.label_number(CITATION_NUMBER),
             COMMAND_DEFINITION_NAME => self.command_definition(),
             MATH_OPERATOR_NAME => self.math_operator(),
             GLOSSARY_ENTRY_DEFINITION_NAME => self.glossary_entry_definition(),
@@ -152,6 +154,9 @@ impl<'a> Parser<'a> {
             BEGIN_BLOCK_COMMENT_NAME => self.block_comment(),
             END_BLOCK_COMMENT_NAME => self.generic_command(),
             GRAPHICS_PATH_NAME => self.graphics_path(),
+            INCLUDE_ONLY_NAME => self.include_only(),
+            COUNTER_DEFINITION_NAME => self.counter_definition(),
+            COUNTER_REFERENCE_NAME => self.counter_reference(),
             _ => unreachable!(),
         }
     }
@@ -456,6 +461,10 @@ impl<'a> Parser<'a> {
     fn generic_command(&mut self) {
         self.builder.start_node(GENERIC_COMMAND.into());
         self.eat();
+        if self.peek() == Some(VERBATIM) {
+            self.eat();
+        }
+
         while let Some(kind) = self.peek() {
             match kind {
                 LINE_BREAK | WHITESPACE | COMMENT => self.eat(),
@@ -817,21 +826,64 @@ impl<'a> Parser<'a> {
 
     fn citation(&mut self) {
         self.builder.start_node(CITATION.into());
+        let is_multi_cite = self
+            .lexer
+            .peek_text()
+            .map_or(false, |text| text.ends_with("cites"));
+
         self.eat();
         self.trivia();
-        for _ in 0..2 {
-            if self.lexer.peek() == Some(L_BRACK) {
-                self.brack_group();
+        if self
+            .lexer
+            .peek_text()
+            .map_or(false, |text| text.starts_with('<'))
+        {
+            self.angle_note();
+        }
+
+        loop {
+            for _ in 0..2 {
+                if self.lexer.peek() == Some(L_BRACK) {
+                    self.brack_group();
+                }
+            }
+
+            if self.lexer.peek() == Some(L_CURLY) {
+                self.curly_group_word_list();
+            } else {
+                self.builder.token(MISSING.into(), "");
+            }
+
+            if !is_multi_cite || !matches!(self.lexer.peek(), Some(L_BRACK | L_CURLY)) {
+                break;
             }
         }
 
-        if self.lexer.peek() == Some(L_CURLY) {
-            self.curly_group_word_list();
-        } else {
-            self.builder.token(MISSING.into(), "");
+        self.builder.finish_node();
+    }
+
+    /// Parses the note in angle brackets of `apacite` like `\cite<see>[p.~5]{foo}`.
+    fn angle_note(&mut self) {
+        self.builder.start_node(TEXT.into());
+        while let Some(kind) = self.peek() {
+            if !matches!(kind, WORD | COMMA | WHITESPACE | LINE_BREAK | COMMENT) {
+                break;
+            }
+
+            let is_end = kind == WORD
+                && self
+                    .lexer
+                    .peek_text()
+                    .map_or(false, |text| text.ends_with('>'));
+
+            self.eat();
+            if is_end {
+                break;
+            }
         }
 
         self.builder.finish_node();
+        self.trivia();
     }
 
     fn generic_include(&mut self, kind: SyntaxKind, options: bool) {
@@ -952,7 +1004,11 @@ impl<'a> Parser<'a> {
     }
 
     fn verbatim_include(&mut self) {
-        self.generic_include(VERBATIM_INCLUDE, false);
+        self.generic_include(VERBATIM_INCLUDE, true);
+    }
+
+    fn xr_include(&mut self) {
+        self.generic_include(XR_INCLUDE, true);
     }
 
     fn import(&mut self) {
@@ -1011,8 +1067,9 @@ impl<'a> Parser<'a> {
         self.builder.finish_node();
     }
 
-    fn label_number(&mut self) {
-        self.builder.start_node(LABEL_NUMBER.into());
+    /// Parses the `\newlabel` and `\bibcite` commands of `.aux` files.
+    fn label_number(&mut self, kind: SyntaxKind) {
+        self.builder.start_node(kind.into());
         self.eat();
         self.trivia();
         if self.lexer.peek() == Some(L_CURLY) {
@@ -1247,6 +1304,38 @@ impl<'a> Parser<'a> {
         self.builder.finish_node();
     }
 
+    fn counter_definition(&mut self) {
+        self.builder.start_node(COUNTER_DEFINITION.into());
+        self.eat();
+        self.trivia();
+
+        if self.lexer.peek() == Some(L_CURLY) {
+            self.curly_group_word();
+        } else {
+            self.builder.token(MISSING.into(), "");
+        }
+
+        if self.lexer.peek() == Some(L_BRACK) {
+            self.brack_group_word();
+        }
+
+        self.builder.finish_node();
+    }
+
+    fn counter_reference(&mut self) {
+        self.builder.start_node(COUNTER_REFERENCE.into());
+        self.eat();
+        self.trivia();
+
+        if self.lexer.peek() == Some(L_CURLY) {
+            self.curly_group_word();
+        } else {
+            self.builder.token(MISSING.into(), "");
+        }
+
+        self.builder.finish_node();
+    }
+
     fn color_set_definition(&mut self) {
         self.builder.start_node(COLOR_SET_DEFINITION.into());
         self.eat();
@@ -1316,6 +1405,20 @@ impl<'a> Parser<'a> {
         self.builder.finish_node();
     }
 
+    fn include_only(&mut self) {
+        self.builder.start_node(INCLUDE_ONLY.into());
+        self.eat();
+        self.trivia();
+
+        if self.lexer.peek() == Some(L_CURLY) {
+            self.curly_group_word_list();
+        } else {
+            self.builder.token(MISSING.into(), "");
+        }
+
+        self.builder.finish_node();
+    }
+
     fn graphics_path(&mut self) {
         self.builder.start_node(GRAPHICS_PATH.into());
         self.eat();
@@ -1539,6 +1642,16 @@ mod tests {
         assert_debug_snapshot!(setup(r#"\cite[foo][bar]{baz}"#));
     }
 
+    #[test]
+    fn test_citation_multi_cite() {
+        assert_debug_snapshot!(setup(r#"\cites[see][5]{foo}[]{bar} {baz}"#));
+    }
+
+    #[test]
+    fn test_citation_angle_note() {
+        assert_debug_snapshot!(setup(r#"\cite<see, e.g.,>[5]{foo}"#));
+    }
+
     #[test]
     fn test_citation_missing_brace() {
         assert_debug_snapshot!(setup(r#"\cite{foo"#));
@@ -1666,6 +1779,16 @@ mod tests {
         assert_debug_snapshot!(setup(r#"\verbatiminput{foo/bar.txt}"#));
     }
 
+    #[test]
+    fn test_verbatim_include_options() {
+        assert_debug_snapshot!(setup(r#"\lstinputlisting[language=Python]{foo/bar.py}"#));
+    }
+
+    #[test]
+    fn test_xr_include() {
+        assert_debug_snapshot!(setup(r#"\externaldocument[A-]{foo/bar}"#));
+    }
+
     #[test]
     fn test_import_simple() {
         assert_debug_snapshot!(setup(r#"\import{foo}{bar}"#));
@@ -1701,6 +1824,11 @@ mod tests {
         assert_debug_snapshot!(setup(r#"\eqref{foo}"#));
     }
 
+    #[test]
+    fn test_label_reference_page() {
+        assert_debug_snapshot!(setup(r#"\cpageref{foo}"#));
+    }
+
     #[test]
     fn test_label_reference_range_simple() {
         assert_debug_snapshot!(setup(r#"\crefrange{foo}{bar}"#));
@@ -1716,11 +1844,23 @@ mod tests {
         assert_debug_snapshot!(setup(r#"\crefrange{foo{bar}"#));
     }
 
+    #[test]
+    fn test_label_reference_range_page() {
+        assert_debug_snapshot!(setup(r#"\Cpagerefrange{foo}{bar}"#));
+    }
+
     #[test]
     fn test_label_number() {
         assert_debug_snapshot!(setup(r#"\newlabel{foo}{{1.1}}"#));
     }
 
+    #[test]
+    fn test_citation_number() {
+        assert_debug_snapshot!(setup(
+            r#"\bibcite{foo}{{1}{2020}{{Foo et~al.}}{{Foo and Bar}}}"#
+        ));
+    }
+
     #[test]
     fn test_command_definition_simple() {
         assert_debug_snapshot!(setup(r#"\newcommand[1]{\id}{#1}"#));
@@ -1825,6 +1965,16 @@ mod tests {
         assert_debug_snapshot!(setup(r#"\newtheorem{foo}[bar]{Foo}[baz]"#));
     }
 
+    #[test]
+    fn test_counter_definition_with_parent() {
+        assert_debug_snapshot!(setup(r#"\newcounter{foo}[section]"#));
+    }
+
+    #[test]
+    fn test_counter_reference_with_value() {
+        assert_debug_snapshot!(setup(r#"\setcounter{foo}{\value{bar}}"#));
+    }
+
     #[test]
     fn test_color_reference_simple() {
         assert_debug_snapshot!(setup(r#"\color{black}"#));
@@ -1892,6 +2042,16 @@ mod tests {
         assert_debug_snapshot!(setup("\\label{hello\nworld}"));
     }
 
+    #[test]
+    fn test_inline_verbatim_simple() {
+        assert_debug_snapshot!(setup(r#"\verb|{$| \foo"#));
+    }
+
+    #[test]
+    fn test_inline_verbatim_star() {
+        assert_debug_snapshot!(setup(r#"\verb*!x!"#));
+    }
+
     #[test]
     fn test_block_comments() {
         assert_debug_snapshot!(setup(
@@ -1921,6 +2081,11 @@ Baz"#
         assert_debug_snapshot!(setup(r#"\graphicspath{{../figures/}}"#));
     }
 
+    #[test]
+    fn test_include_only() {
+        assert_debug_snapshot!(setup(r#"\includeonly{foo, bar}"#));
+    }
+
     #[test]
     fn test_issue_745() {
         assert_debug_snapshot!(setup(
@@ -0,0 +1,99 @@
+use rustc_hash::FxHashSet;
+
+use crate::{component_db::COMPONENT_DATABASE, Workspace, LANGUAGE_DATA};
+
+/// The names of the environments that are provided by the included packages
+/// or defined somewhere in the workspace.
+#[derive(Debug)]
+pub struct KnownEnvironments<'a> {
+    names: FxHashSet<&'a str>,
+}
+
+impl<'a> KnownEnvironments<'a> {
+    #[must_use]
+    pub fn new(workspace: &'a Workspace) -> Self {
+        let mut names = FxHashSet::default();
+        for component in COMPONENT_DATABASE.linked_components(workspace) {
+            names.extend(component.environments.iter().map(|name| name.as_str()));
+        }
+
+        names.extend(LANGUAGE_DATA.math_environments.iter().map(String::as_str));
+        names.extend(LANGUAGE_DATA.enum_environments.iter().map(String::as_str));
+        for data in workspace
+            .documents_by_uri
+            .values()
+            .filter_map(|document| document.data.as_latex())
+        {
+            names.extend(
+                data.extras
+                    .user_environments
+                    .iter()
+                    .map(|env| env.name.as_str()),
+            );
+            names.extend(
+                data.extras
+                    .theorem_environments
+                    .iter()
+                    .map(|env| env.name.as_str()),
+            );
+        }
+
+        Self { names }
+    }
+
+    /// Suggests a known environment for a name that is most likely misspelled.
+    ///
+    /// Only names within an edit distance of two (one for short names) are suggested.
+    /// Names that only differ from a known environment by case or a trailing `*` are treated as known.
+    #[must_use]
+    pub fn find_similar(&self, name: &str) -> Option<&'a str> {
+        let base = name.trim_end_matches('*');
+        if base.is_empty()
+            || self
+                .names
+                .iter()
+                .any(|known| known.trim_end_matches('*').eq_ignore_ascii_case(base))
+        {
+            return None;
+        }
+
+        let max_distance = if base.chars().count() <= 4 { 1 } else { 2 };
+        self.names
+            .iter()
+            .map(|known| (edit_distance(name, known), *known))
+            .filter(|(distance, _)| *distance <= max_distance)
+            .min()
+            .map(|(_, known)| known)
+    }
+}
+
+/// Computes the Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, x) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, y) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(x != *y);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("enumarate", "enumerate"), 1);
+        assert_eq!(edit_distance("itemzie", "itemize"), 2);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("figure", "figure"), 0);
+    }
+}
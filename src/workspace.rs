@@ -1,5 +1,6 @@
 use std::{
     fs::{self, FileType},
+    io,
     path::{Path, PathBuf},
     sync::{Arc, Mutex},
 };
@@ -12,10 +13,14 @@ use petgraph::{graphmap::DiGraphMap, visit::Dfs};
 use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::{
-    component_db::COMPONENT_DATABASE, syntax::latex::ExplicitLink, Document, DocumentLanguage,
-    Environment,
+    component_db::COMPONENT_DATABASE,
+    syntax::latex::{ExplicitLink, ExplicitLinkKind},
+    Document, DocumentData, DocumentLanguage, Environment,
 };
 
+/// The maximum number of directories that are searched above a document to find its parent.
+const MAX_PARENT_DEPTH: usize = 32;
+
 #[derive(Debug, Clone)]
 pub enum WorkspaceEvent {
     Changed(Workspace, Document),
@@ -29,6 +34,7 @@ pub struct Workspace {
     pub environment: Environment,
     watcher: Option<Arc<Mutex<notify::RecommendedWatcher>>>,
     watched_dirs: Arc<Mutex<FxHashSet<PathBuf>>>,
+    canonical_uris: Arc<Mutex<FxHashMap<Arc<Url>, Arc<Url>>>>,
 }
 
 impl Workspace {
@@ -69,7 +75,18 @@ impl Workspace {
         }
 
         log::debug!("(Re)Loading document: {}", uri);
-        let document = Document::parse(&self.environment, Arc::clone(&uri), text, language);
+        let import_directory = self.find_import_directory(&uri);
+        let mut document = Document::parse(
+            &self.environment,
+            Arc::clone(&uri),
+            text,
+            language,
+            import_directory,
+        );
+
+        if self.environment.options.resolve_symlinks {
+            self.resolve_symlinks(&mut document);
+        }
 
         self.documents_by_uri
             .insert(Arc::clone(&uri), document.clone());
@@ -78,19 +95,35 @@ impl Workspace {
             listener.send(WorkspaceEvent::Changed(self.clone(), document.clone()))?;
         }
 
-        self.expand_parent(&document);
-        self.expand_children(&document);
+        if !self.environment.options.single_file_mode {
+            self.expand_parent(&document);
+            self.expand_children(&document);
+        }
+
         Ok(document)
     }
 
+    /// Reads a document that has been changed outside of the client from disk again.
+    ///
+    /// Build artifacts are loaded even if they are not part of the workspace yet
+    /// while LaTeX and BibTeX documents are only reloaded if they are already known.
+    /// Build artifacts are ignored in single file mode.
     pub fn reload(&mut self, path: PathBuf) -> Result<Option<Document>> {
         let uri = Arc::new(Url::from_file_path(path.clone()).unwrap());
-        if self.is_open(&uri) || !(uri.as_str().ends_with(".log") || uri.as_str().ends_with(".aux"))
-        {
+        self.canonical_uris.lock().unwrap().remove(&uri);
+
+        let is_build_artifact = !self.environment.options.single_file_mode
+            && (uri.as_str().ends_with(".log") || uri.as_str().ends_with(".aux"));
+        let is_known_source = matches!(
+            self.environment.options.language_by_path(&path),
+            Some(DocumentLanguage::Latex | DocumentLanguage::Bibtex)
+        ) && self.documents_by_uri.contains_key(&uri);
+
+        if self.is_open(&uri) || !(is_build_artifact || is_known_source) {
             return Ok(self.documents_by_uri.get(&uri).cloned());
         }
 
-        if let Some(language) = DocumentLanguage::by_path(&path) {
+        if let Some(language) = self.environment.options.language_by_path(&path) {
             let data = fs::read(&path)?;
             let text = Arc::new(String::from_utf8_lossy(&data).into_owned());
             Ok(Some(self.open(uri, text, language)?))
@@ -99,6 +132,81 @@ impl Workspace {
         }
     }
 
+    /// Reads all documents that are not managed by the client from disk.
+    ///
+    /// The text is `None` if the file of the document has been deleted.
+    #[must_use]
+    pub fn read_all(&self) -> Vec<(Arc<Url>, Option<Arc<String>>)> {
+        let mut texts = Vec::new();
+        for uri in self
+            .documents_by_uri
+            .keys()
+            .filter(|uri| uri.scheme() == "file" && !self.is_open(uri))
+        {
+            let path = match uri.to_file_path() {
+                Ok(path) => path,
+                Err(()) => continue,
+            };
+
+            match fs::read(&path) {
+                Ok(data) => {
+                    let text = String::from_utf8_lossy(&data).into_owned();
+                    texts.push((Arc::clone(uri), Some(Arc::new(text))));
+                }
+                Err(why) if why.kind() == io::ErrorKind::NotFound => {
+                    texts.push((Arc::clone(uri), None));
+                }
+                Err(why) => {
+                    log::warn!("Failed to reload {}: {}", uri, why);
+                }
+            };
+        }
+
+        texts
+    }
+
+    /// Replaces the documents that are not managed by the client with the texts of `read_all`,
+    /// removes the documents whose files have been deleted
+    /// and returns the number of reloaded documents.
+    pub fn reload_all(&mut self, texts: Vec<(Arc<Url>, Option<Arc<String>>)>) -> Result<usize> {
+        self.canonical_uris.lock().unwrap().clear();
+        let mut count = 0;
+        for (uri, text) in texts {
+            // The client may have opened the document while it was read from disk.
+            if self.is_open(&uri) {
+                continue;
+            }
+
+            let language = match self.documents_by_uri.get(&uri) {
+                Some(document) => document.data.language(),
+                None => continue,
+            };
+
+            match text {
+                Some(text) => {
+                    self.open(uri, text, language)?;
+                    count += 1;
+                }
+                None => {
+                    self.documents_by_uri.remove(&uri);
+                }
+            };
+        }
+
+        // Open documents are reparsed as well to discover new child documents.
+        for document in self
+            .documents_by_uri
+            .values()
+            .filter(|document| self.is_open(&document.uri))
+            .cloned()
+            .collect::<Vec<_>>()
+        {
+            self.open(document.uri, document.text, document.data.language())?;
+        }
+
+        Ok(count)
+    }
+
     pub fn load(&mut self, path: PathBuf) -> Result<Option<Document>> {
         let uri = Arc::new(Url::from_file_path(path.clone()).unwrap());
 
@@ -106,15 +214,59 @@ impl Workspace {
             return Ok(Some(document));
         }
 
+        if self.environment.options.resolve_symlinks {
+            if let Some(document) = self.find_by_canonical_uri(&uri) {
+                return Ok(Some(document));
+            }
+        }
+
         let data = fs::read(&path)?;
         let text = Arc::new(String::from_utf8_lossy(&data).into_owned());
-        if let Some(language) = DocumentLanguage::by_path(&path) {
+        if let Some(language) = self.environment.options.language_by_path(&path) {
             Ok(Some(self.open(uri, text, language)?))
         } else {
             Ok(None)
         }
     }
 
+    /// Finds a document that refers to the same physical file as the given URI.
+    fn find_by_canonical_uri(&self, uri: &Arc<Url>) -> Option<Document> {
+        let canonical_uri = self.canonicalize(uri);
+        self.documents_by_uri
+            .values()
+            .find(|document| self.canonicalize(&document.uri) == canonical_uri)
+            .cloned()
+    }
+
+    /// Canonicalizes the targets of the explicit links with the `resolveSymlinks` option
+    /// so that a file that is included through a symlink is only loaded once.
+    fn resolve_symlinks(&self, document: &mut Document) {
+        if let DocumentData::Latex(data) = &mut document.data {
+            for link in &mut Arc::make_mut(&mut data.extras).explicit_links {
+                for target in &mut link.targets {
+                    *target = self.canonicalize(target);
+                }
+            }
+        }
+    }
+
+    /// Returns the URI of the physical file that the given URI refers to.
+    ///
+    /// The result is cached until the file is reloaded,
+    /// so that a path is not canonicalized again whenever a document is parsed.
+    fn canonicalize(&self, uri: &Arc<Url>) -> Arc<Url> {
+        let mut canonical_uris = self.canonical_uris.lock().unwrap();
+        let canonical_uri = canonical_uris.entry(Arc::clone(uri)).or_insert_with(|| {
+            uri.to_file_path()
+                .ok()
+                .and_then(|path| fs::canonicalize(path).ok())
+                .and_then(|path| Url::from_file_path(path).ok())
+                .map_or_else(|| Arc::clone(uri), Arc::new)
+        });
+
+        Arc::clone(canonical_uri)
+    }
+
     pub fn close(&mut self, uri: &Url) {
         self.viewport.remove(uri);
     }
@@ -124,7 +276,31 @@ impl Workspace {
     }
 
     pub fn slice(&self, uri: &Url) -> Self {
+        self.slice_impl(uri, true)
+    }
+
+    /// Returns the documents of the compilation unit of the given document.
+    ///
+    /// Documents referenced with `\externaldocument` are only followed if `include_external` is set
+    /// since they are compiled separately.
+    /// Links into another workspace folder are only followed in the direction of the link
+    /// so that unrelated projects of a multi-root workspace are not merged.
+    /// In single file mode, the slice only contains the given document.
+    fn slice_impl(&self, uri: &Url, include_external: bool) -> Self {
+        if self.environment.options.single_file_mode {
+            let mut slice = self.clone();
+            slice.documents_by_uri = FxHashMap::default();
+            if let Some((uri, document)) = self.documents_by_uri.get_key_value(uri) {
+                slice
+                    .documents_by_uri
+                    .insert(Arc::clone(uri), document.clone());
+            }
+
+            return slice;
+        }
+
         let all_uris: Vec<_> = self.documents_by_uri.keys().cloned().collect();
+        let environment = &self.environment;
 
         all_uris
             .iter()
@@ -138,21 +314,40 @@ impl Workspace {
                         .and_then(|document| document.data.as_latex())
                     {
                         let extras = &data.extras;
-                        let mut all_targets =
-                            vec![&extras.implicit_links.aux, &extras.implicit_links.log];
+                        let mut all_targets = vec![
+                            (&extras.implicit_links.aux, false),
+                            (&extras.implicit_links.log, false),
+                        ];
                         for link in &extras.explicit_links {
-                            all_targets.push(&link.targets);
+                            let is_external = link.kind == ExplicitLinkKind::External;
+                            if include_external || !is_external {
+                                // Packages and external documents do not see the documents referencing them.
+                                let is_child = matches!(
+                                    link.kind,
+                                    ExplicitLinkKind::Latex | ExplicitLinkKind::Bibtex
+                                );
+
+                                all_targets.push((&link.targets, is_child));
+                            }
                         }
 
-                        for targets in all_targets {
+                        for (targets, is_child) in all_targets {
                             for target in targets {
                                 if let Some(j) = all_uris.iter().position(|uri| uri == target) {
                                     edges.push((i, j, ()));
 
-                                    if target.as_str().ends_with(".tex")
-                                        || target.as_str().ends_with(".bib")
-                                        || target.as_str().ends_with(".rnw")
-                                    {
+                                    let is_same_folder = environment.find_workspace_folder(uri)
+                                        == environment.find_workspace_folder(target);
+
+                                    // Children with an extension of `extraLatexExtensions` see their parent as well.
+                                    let is_source = matches!(
+                                        environment
+                                            .options
+                                            .language_by_path(Path::new(target.path())),
+                                        Some(DocumentLanguage::Latex | DocumentLanguage::Bibtex)
+                                    );
+
+                                    if is_child && is_same_folder && is_source {
                                         edges.push((j, i, ()));
                                     }
 
@@ -178,9 +373,83 @@ impl Workspace {
             .unwrap_or_default()
     }
 
+    /// Returns `true` if the document is only included with `\include` commands
+    /// that are skipped because of the `\includeonly` command of the root document.
+    #[must_use]
+    pub fn is_excluded(&self, uri: &Url) -> bool {
+        let root = match self.find_parent(uri) {
+            Some(root) => root,
+            None => return false,
+        };
+
+        if root
+            .data
+            .as_latex()
+            .map_or(true, |data| data.extras.include_only.is_none())
+        {
+            return false;
+        }
+
+        // `\include` cannot be nested, so only the links of the root document can be skipped.
+        self.reachable_documents(&root.uri, false).contains(uri)
+            && !self.reachable_documents(&root.uri, true).contains(uri)
+    }
+
+    /// Returns all documents that are skipped because of the `\includeonly` command
+    /// of a root document.
+    #[must_use]
+    pub fn excluded_documents(&self) -> FxHashSet<Arc<Url>> {
+        let mut excluded = FxHashSet::default();
+        for root in self.documents_by_uri.values().filter(|document| {
+            document.data.as_latex().map_or(false, |data| {
+                data.extras.has_document_environment && data.extras.include_only.is_some()
+            })
+        }) {
+            let compiled = self.reachable_documents(&root.uri, true);
+            excluded.extend(
+                self.reachable_documents(&root.uri, false)
+                    .into_iter()
+                    .filter(|uri| !compiled.contains(uri)),
+            );
+        }
+
+        excluded
+    }
+
+    fn reachable_documents(&self, root: &Arc<Url>, skip_excluded: bool) -> FxHashSet<Arc<Url>> {
+        let mut visited = FxHashSet::default();
+        visited.insert(Arc::clone(root));
+        let mut stack = vec![Arc::clone(root)];
+        while let Some(current) = stack.pop() {
+            let data = match self
+                .documents_by_uri
+                .get(&current)
+                .and_then(|document| document.data.as_latex())
+            {
+                Some(data) => data,
+                None => continue,
+            };
+
+            for target in data
+                .extras
+                .explicit_links
+                .iter()
+                .filter(|link| !skip_excluded || !link.is_excluded)
+                .flat_map(|link| &link.targets)
+            {
+                if self.documents_by_uri.contains_key(target) && visited.insert(Arc::clone(target))
+                {
+                    stack.push(Arc::clone(target));
+                }
+            }
+        }
+
+        visited
+    }
+
     #[must_use]
     pub fn find_parent(&self, uri: &Url) -> Option<Document> {
-        self.slice(uri)
+        self.slice_impl(uri, false)
             .documents_by_uri
             .values()
             .find(|document| {
@@ -197,6 +466,22 @@ impl Workspace {
             .cloned()
     }
 
+    /// Returns the directory of the `\import` command that includes the given document.
+    /// Documents that are included by an imported document inherit its directory.
+    fn find_import_directory(&self, uri: &Url) -> Option<Arc<Url>> {
+        self.documents_by_uri.values().find_map(|document| {
+            document
+                .data
+                .as_latex()?
+                .extras
+                .explicit_links
+                .iter()
+                .filter(|link| link.is_import || document.import_directory.is_some())
+                .find(|link| link.targets.iter().any(|target| target.as_ref() == uri))
+                .map(|link| Arc::clone(&link.base_uri))
+        })
+    }
+
     fn expand_parent(&mut self, document: &Document) {
         let all_current_paths = self
             .documents_by_uri
@@ -204,10 +489,30 @@ impl Workspace {
             .filter_map(|doc| doc.uri.to_file_path().ok())
             .collect::<FxHashSet<_>>();
 
+        let options = Arc::clone(&self.environment.options);
+        let environment = self.environment.clone();
+        let workspace_folder = environment.find_workspace_folder(&document.uri);
+        let base_dir = environment.base_directory(&document.uri);
         if document.uri.scheme() == "file" {
             if let Ok(mut path) = document.uri.to_file_path() {
-                while path.pop() && self.find_parent(&document.uri).is_none() {
-                    std::fs::read_dir(&path)
+                let mut visited_dirs = FxHashSet::default();
+                let mut depth = 0;
+                while depth < MAX_PARENT_DEPTH
+                    && path.pop()
+                    && workspace_folder.map_or(true, |folder| path.starts_with(folder))
+                    && self.find_parent(&document.uri).is_none()
+                {
+                    depth += 1;
+                    let dir = fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+                    if !visited_dirs.insert(dir) {
+                        continue;
+                    }
+
+                    if options.exclude_globs.is_excluded(&path, base_dir) {
+                        continue;
+                    }
+
+                    let paths: Vec<_> = fs::read_dir(&path)
                         .into_iter()
                         .flatten()
                         .filter_map(Result::ok)
@@ -215,14 +520,23 @@ impl Workspace {
                         .map(|entry| entry.path())
                         .filter(|path| {
                             matches!(
-                                DocumentLanguage::by_path(path),
+                                options.language_by_path(path),
                                 Some(DocumentLanguage::Latex)
                             )
                         })
+                        .collect();
+
+                    if paths.is_empty() {
+                        continue;
+                    }
+
+                    for path in paths
+                        .into_iter()
                         .filter(|path| !all_current_paths.contains(path))
-                        .for_each(|path| {
-                            let _ = self.load(path);
-                        });
+                        .filter(|path| !options.exclude_globs.is_excluded(path, base_dir))
+                    {
+                        let _ = self.load(path);
+                    }
                 }
             }
         }
@@ -231,22 +545,76 @@ impl Workspace {
     fn expand_children(&mut self, document: &Document) {
         if let Some(data) = document.data.as_latex() {
             let extras = &data.extras;
+
+            // Children that have been loaded before their parent resolved their paths
+            // without the directory of the `\import` command.
+            for link in extras
+                .explicit_links
+                .iter()
+                .filter(|link| link.is_import || document.import_directory.is_some())
+            {
+                for child in link
+                    .targets
+                    .iter()
+                    .filter_map(|target| self.documents_by_uri.get(target))
+                    .filter(|child| child.import_directory.as_ref() != Some(&link.base_uri))
+                    .cloned()
+                    .collect::<Vec<_>>()
+                {
+                    let language = child.data.language();
+                    let _ = self.open(child.uri, child.text, language);
+                }
+            }
+
             let mut all_targets = vec![&extras.implicit_links.aux, &extras.implicit_links.log];
-            for link in &extras.explicit_links {
+            let mut component_targets = Vec::new();
+            // Files that are included verbatim are not loaded since they are not LaTeX.
+            for link in extras
+                .explicit_links
+                .iter()
+                .filter(|link| link.kind != ExplicitLinkKind::Verbatim)
+            {
                 if link
                     .as_component_name()
                     .and_then(|name| COMPONENT_DATABASE.find(&name))
                     .is_none()
                 {
                     all_targets.push(&link.targets);
+                } else {
+                    component_targets.push(&link.targets);
                 }
             }
 
+            let options = Arc::clone(&self.environment.options);
+            let base_dir = self.environment.base_directory(&document.uri).to_path_buf();
             for targets in all_targets {
                 for path in targets
                     .iter()
                     .filter(|uri| uri.scheme() == "file" && uri.fragment().is_none())
                     .filter_map(|uri| uri.to_file_path().ok())
+                    .filter(|path| !options.exclude_globs.is_excluded(path, &base_dir))
+                {
+                    if self.load(path).is_ok() {
+                        break;
+                    }
+                }
+            }
+
+            // Known packages are described by the component database,
+            // so we only load them if they are shadowed by a local file.
+            let resolver = Arc::clone(&self.environment.resolver);
+            for targets in component_targets {
+                for path in targets
+                    .iter()
+                    .filter(|uri| uri.scheme() == "file" && uri.fragment().is_none())
+                    .filter_map(|uri| uri.to_file_path().ok())
+                    .filter(|path| !options.exclude_globs.is_excluded(path, &base_dir))
+                    .filter(|path| {
+                        path.file_name()
+                            .and_then(|name| name.to_str())
+                            .and_then(|name| resolver.files_by_name.get(name))
+                            != Some(path)
+                    })
                 {
                     if self.load(path).is_ok() {
                         break;
@@ -2,12 +2,12 @@ use std::sync::Once;
 
 use anyhow::{bail, Result};
 use crossbeam_channel::{Receiver, Sender};
-use lsp_server::{Connection, Message, Notification, Request, Response};
+use lsp_server::{Connection, Message, Notification, Request, RequestId, Response};
 use lsp_types::{
     notification::{Exit, Initialized},
     request::{Initialize, Shutdown},
     ClientCapabilities, ClientInfo, DidOpenTextDocumentParams, InitializeParams, InitializeResult,
-    InitializedParams, Url,
+    InitializedParams, Url, WorkspaceFolder,
 };
 use tempfile::{tempdir, TempDir};
 use texlab::Server;
@@ -58,12 +58,13 @@ pub struct Client {
     incoming: IncomingHandler,
     directory: TempDir,
     request_id: i32,
+    pending_responses: Vec<Response>,
     _handle: jod_thread::JoinHandle,
 }
 
 impl Client {
     pub fn spawn() -> Result<Self> {
-        INIT_LOGGER.call_once(|| env_logger::init());
+        INIT_LOGGER.call_once(env_logger::init);
 
         let directory = tempdir()?;
         let (client, server) = Connection::memory();
@@ -79,15 +80,25 @@ impl Client {
             incoming,
             directory,
             request_id: 0,
+            pending_responses: Vec::new(),
             _handle,
         })
     }
 
-    #[allow(deprecated)]
     pub fn initialize(
         &mut self,
         client_capabilities: ClientCapabilities,
         client_info: Option<ClientInfo>,
+    ) -> Result<InitializeResult> {
+        self.initialize_with_folders(client_capabilities, client_info, None)
+    }
+
+    #[allow(deprecated)]
+    pub fn initialize_with_folders(
+        &mut self,
+        client_capabilities: ClientCapabilities,
+        client_info: Option<ClientInfo>,
+        workspace_folders: Option<Vec<WorkspaceFolder>>,
     ) -> Result<InitializeResult> {
         let result = self.request::<Initialize>(InitializeParams {
             process_id: None,
@@ -96,7 +107,7 @@ impl Client {
             initialization_options: None,
             capabilities: client_capabilities,
             trace: None,
-            workspace_folders: None,
+            workspace_folders,
             client_info,
             locale: None,
         })?;
@@ -109,13 +120,41 @@ impl Client {
         &mut self,
         params: R::Params,
     ) -> Result<R::Result> {
+        let id = self.send_request::<R>(params)?;
+        self.receive_response::<R>(id)
+    }
+
+    pub fn send_request<R: lsp_types::request::Request>(
+        &mut self,
+        params: R::Params,
+    ) -> Result<RequestId> {
         self.request_id += 1;
 
         self.outgoing
             .send(Request::new(self.request_id.into(), R::METHOD.into(), params).into())?;
 
-        let response = self.incoming.responses.recv()?;
-        assert_eq!(response.id, self.request_id.into());
+        Ok(self.request_id.into())
+    }
+
+    pub fn receive_response<R: lsp_types::request::Request>(
+        &mut self,
+        id: RequestId,
+    ) -> Result<R::Result> {
+        let response = match self
+            .pending_responses
+            .iter()
+            .position(|response| response.id == id)
+        {
+            Some(index) => self.pending_responses.remove(index),
+            None => loop {
+                let response = self.incoming.responses.recv()?;
+                if response.id == id {
+                    break response;
+                }
+
+                self.pending_responses.push(response);
+            },
+        };
 
         let result = match response.result {
             Some(result) => result,
@@ -125,6 +164,34 @@ impl Client {
         Ok(serde_json::from_value(result)?)
     }
 
+    pub fn receive_request<R: lsp_types::request::Request>(
+        &mut self,
+    ) -> Result<(RequestId, R::Params)> {
+        let request = self.incoming.requests.recv()?;
+        let (id, params) = request.extract(R::METHOD)?;
+        Ok((id, params))
+    }
+
+    pub fn respond<R: lsp_types::request::Request>(
+        &mut self,
+        id: RequestId,
+        result: R::Result,
+    ) -> Result<()> {
+        self.outgoing.send(Response::new_ok(id, result).into())?;
+        Ok(())
+    }
+
+    pub fn receive_notification<N: lsp_types::notification::Notification>(
+        &mut self,
+    ) -> Result<N::Params> {
+        loop {
+            let notification = self.incoming.notifications.recv()?;
+            if notification.method == N::METHOD {
+                return Ok(serde_json::from_value(notification.params)?);
+            }
+        }
+    }
+
     pub fn notify<N: lsp_types::notification::Notification>(
         &mut self,
         params: N::Params,
@@ -168,4 +235,16 @@ impl Client {
         Url::from_file_path(self.directory.path().join(name))
             .map_err(|()| anyhow::anyhow!("failed to create uri"))
     }
+
+    pub fn workspace_folder(&self, name: &str) -> Result<WorkspaceFolder> {
+        let path = self.directory.path().join(name);
+        std::fs::create_dir_all(&path)?;
+        let uri =
+            Url::from_directory_path(path).map_err(|()| anyhow::anyhow!("failed to create uri"))?;
+
+        Ok(WorkspaceFolder {
+            uri,
+            name: name.to_string(),
+        })
+    }
 }
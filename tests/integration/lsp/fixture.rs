@@ -15,7 +15,7 @@ enum Line<'a> {
     Empty,
 }
 
-fn parse_line(line: &str) -> Line {
+fn parse_line(line: &str) -> Line<'_> {
     if let Some(name) = line.strip_prefix("%TEX ") {
         Line::File(name, "latex")
     } else if let Some(name) = line.strip_prefix("%BIB ") {
@@ -76,7 +76,7 @@ pub struct Fixture<'a> {
     pub ranges: BTreeMap<u32, FxHashMap<u32, FileRange<'a>>>,
 }
 
-pub fn parse(input: &str) -> Fixture {
+pub fn parse(input: &str) -> Fixture<'_> {
     let mut fixture = Fixture::default();
     let mut file = File::default();
     let mut file_line_index = 0;
@@ -1,13 +1,20 @@
+mod build;
+mod code_action;
 mod completion;
 mod definition;
+mod diagnostic;
+mod did_change;
 mod did_change_configuration;
 mod document_highlight;
 mod document_link;
 mod document_symbol;
 mod folding_range;
 mod formatting;
+mod forward_search;
 mod hover;
 mod inlay_hint;
+mod linked_editing_range;
 mod publish_diagnostics;
 mod references;
 mod rename;
+mod type_definition;
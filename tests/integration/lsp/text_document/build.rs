@@ -0,0 +1,196 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use lsp_types::{
+    notification::{DidChangeConfiguration, LogMessage, Notification},
+    ClientCapabilities, DidChangeConfigurationParams, LogMessageParams, TextDocumentIdentifier,
+};
+use texlab::features::{BuildParams, BuildResult, BuildStatus, CancelBuildRequest};
+
+use crate::lsp::client::Client;
+
+struct BuildRequest;
+
+impl lsp_types::request::Request for BuildRequest {
+    type Params = BuildParams;
+
+    type Result = BuildResult;
+
+    const METHOD: &'static str = "textDocument/build";
+}
+
+#[test]
+#[cfg(unix)]
+fn cancel() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.open(
+        "main.tex",
+        "latex",
+        "\\documentclass{article}\n\\begin{document}\n\\end{document}".to_string(),
+    )?;
+
+    client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams {
+        settings: serde_json::json!({
+            "build": {
+                "executable": "sh",
+                "args": ["-c", "echo started; exec sleep 30"]
+            }
+        }),
+    })?;
+
+    let params = BuildParams {
+        text_document: TextDocumentIdentifier::new(client.uri("main.tex")?),
+    };
+
+    let build_id = client.send_request::<BuildRequest>(params.clone())?;
+    while client.receive_notification::<LogMessage>()?.message != "started" {}
+
+    assert!(client.request::<CancelBuildRequest>(params.clone())?);
+    assert_eq!(
+        client.receive_response::<BuildRequest>(build_id)?.status,
+        BuildStatus::CANCELLED
+    );
+
+    assert!(!client.request::<CancelBuildRequest>(params)?);
+    client.shutdown()?;
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn restart() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.open(
+        "main.tex",
+        "latex",
+        "\\documentclass{article}\n\\begin{document}\n\\end{document}".to_string(),
+    )?;
+
+    client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams {
+        settings: serde_json::json!({
+            "build": {
+                "executable": "sh",
+                "args": ["-c", "echo started; exec sleep 30"]
+            }
+        }),
+    })?;
+
+    let params = BuildParams {
+        text_document: TextDocumentIdentifier::new(client.uri("main.tex")?),
+    };
+
+    let first_id = client.send_request::<BuildRequest>(params.clone())?;
+    while client.receive_notification::<LogMessage>()?.message != "started" {}
+
+    let second_id = client.send_request::<BuildRequest>(params.clone())?;
+    assert_eq!(
+        client.receive_response::<BuildRequest>(first_id)?.status,
+        BuildStatus::CANCELLED
+    );
+
+    while client.receive_notification::<LogMessage>()?.message != "started" {}
+    assert!(client.request::<CancelBuildRequest>(params)?);
+    assert_eq!(
+        client.receive_response::<BuildRequest>(second_id)?.status,
+        BuildStatus::CANCELLED
+    );
+
+    client.shutdown()?;
+    Ok(())
+}
+
+#[test]
+fn cancel_without_build() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.open("main.tex", "latex", String::new())?;
+
+    let cancelled = client.request::<CancelBuildRequest>(BuildParams {
+        text_document: TextDocumentIdentifier::new(client.uri("main.tex")?),
+    })?;
+
+    client.shutdown()?;
+    assert!(!cancelled);
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn on_open_if_missing() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams {
+        settings: serde_json::json!({
+            "build": {
+                "executable": "sh",
+                "args": ["-c", "touch main.pdf"],
+                "onOpenIfMissing": true
+            }
+        }),
+    })?;
+
+    client.open(
+        "main.tex",
+        "latex",
+        "\\documentclass{article}\n\\begin{document}\n\\end{document}".to_string(),
+    )?;
+
+    let pdf_path = client.uri("main.pdf")?.to_file_path().unwrap();
+    let start = Instant::now();
+    while !pdf_path.exists() {
+        assert!(start.elapsed() < Duration::from_secs(10));
+        thread::sleep(Duration::from_millis(50));
+    }
+
+    client.shutdown()?;
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn on_open_if_missing_running() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams {
+        settings: serde_json::json!({
+            "build": {
+                "executable": "sh",
+                "args": ["-c", "echo started; exec sleep 30"],
+                "onOpenIfMissing": true
+            }
+        }),
+    })?;
+
+    client.open(
+        "main.tex",
+        "latex",
+        "\\documentclass{article}\n\\begin{document}\n\\include{foo}\n\\end{document}".to_string(),
+    )?;
+
+    while client.receive_notification::<LogMessage>()?.message != "started" {}
+    client.open("foo.tex", "latex", String::new())?;
+
+    let params = BuildParams {
+        text_document: TextDocumentIdentifier::new(client.uri("main.tex")?),
+    };
+
+    assert!(client.request::<CancelBuildRequest>(params)?);
+    let result = client.shutdown()?;
+    assert!(!result
+        .incoming
+        .notifications
+        .into_iter()
+        .filter_map(|notification| {
+            notification
+                .extract::<LogMessageParams>(LogMessage::METHOD)
+                .ok()
+        })
+        .any(|params| params.message == "started"));
+
+    Ok(())
+}
@@ -0,0 +1,301 @@
+use anyhow::Result;
+use lsp_types::{
+    request::CodeActionRequest, ClientCapabilities, CodeActionContext, CodeActionOrCommand,
+    CodeActionParams, Position, Range, TextDocumentIdentifier, TextEdit, Url,
+};
+
+use crate::lsp::{client::Client, fixture};
+
+fn check(fixture: &str) -> Result<Vec<(String, Url, Vec<TextEdit>)>> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+
+    let fixture = fixture::parse(fixture);
+    for file in fixture.files {
+        client.open(file.name, file.lang, file.text)?;
+    }
+
+    let cursor = fixture.cursor.unwrap();
+    let actual_actions = client
+        .request::<CodeActionRequest>(CodeActionParams {
+            text_document: TextDocumentIdentifier::new(client.uri(cursor.name)?),
+            range: Range::new(cursor.position, cursor.position),
+            context: CodeActionContext::default(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })?
+        .unwrap_or_default();
+
+    client.shutdown()?;
+
+    let mut actions = Vec::new();
+    for action in actual_actions {
+        if let CodeActionOrCommand::CodeAction(action) = action {
+            for (uri, edits) in action
+                .edit
+                .and_then(|edit| edit.changes)
+                .unwrap_or_default()
+            {
+                actions.push((action.title.clone(), uri, edits));
+            }
+        }
+    }
+
+    Ok(actions)
+}
+
+#[test]
+fn missing_package_after_usepackage() -> Result<()> {
+    let actions = check(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \usepackage{amsmath}
+%SRC \begin{document}
+%SRC \qty{1}
+%CUR   ^
+%SRC \end{document}
+"#,
+    )?;
+
+    assert_eq!(actions.len(), 1);
+    let (title, uri, edits) = &actions[0];
+    assert_eq!(title, "Add \\usepackage{physics}");
+    assert!(uri.as_str().ends_with("main.tex"));
+    assert_eq!(
+        edits,
+        &[TextEdit::new(
+            Range::new(Position::new(1, 20), Position::new(1, 20)),
+            "\n\\usepackage{physics}".into()
+        )]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn missing_package_cleveref() -> Result<()> {
+    let actions = check(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \begin{document}
+%SRC \crefrange{foo}{bar}
+%CUR   ^
+%SRC \end{document}
+"#,
+    )?;
+
+    assert_eq!(actions.len(), 1);
+    let (title, _, edits) = &actions[0];
+    assert_eq!(title, "Add \\usepackage{cleveref}");
+    assert_eq!(
+        edits,
+        &[TextEdit::new(
+            Range::new(Position::new(0, 23), Position::new(0, 23)),
+            "\n\\usepackage{cleveref}".into()
+        )]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn missing_package_in_child() -> Result<()> {
+    let actions = check(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \begin{document}
+%SRC \input{child}
+%SRC \end{document}
+
+%TEX child.tex
+%SRC \qty{1}
+%CUR   ^
+"#,
+    )?;
+
+    assert_eq!(actions.len(), 1);
+    let (_, uri, edits) = &actions[0];
+    assert!(uri.as_str().ends_with("main.tex"));
+    assert_eq!(
+        edits,
+        &[TextEdit::new(
+            Range::new(Position::new(0, 23), Position::new(0, 23)),
+            "\n\\usepackage{physics}".into()
+        )]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn missing_package_loaded() -> Result<()> {
+    let actions = check(
+        r#"
+%TEX main.tex
+%SRC \usepackage{booktabs}
+%SRC \toprule
+%CUR   ^
+"#,
+    )?;
+
+    assert!(actions.is_empty());
+    Ok(())
+}
+
+#[test]
+fn missing_package_loaded_transitively() -> Result<()> {
+    let actions = check(
+        r#"
+%TEX main.tex
+%SRC \usepackage{edcntwd0}
+%SRC \MakeLineNo
+%CUR   ^
+"#,
+    )?;
+
+    assert!(actions.is_empty());
+    Ok(())
+}
+
+#[test]
+fn missing_package_ambiguous() -> Result<()> {
+    let actions = check(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \begin{document}
+%SRC \includegraphics{foo}
+%CUR   ^
+%SRC \end{document}
+"#,
+    )?;
+
+    assert!(actions.is_empty());
+    Ok(())
+}
+
+#[test]
+fn missing_package_user_defined() -> Result<()> {
+    let actions = check(
+        r#"
+%TEX main.tex
+%SRC \newcommand{\toprule}{}
+%SRC \toprule
+%CUR   ^
+"#,
+    )?;
+
+    assert!(actions.is_empty());
+    Ok(())
+}
+
+#[test]
+fn misspelled_environment() -> Result<()> {
+    let actions = check(
+        r#"
+%TEX main.tex
+%SRC \begin{enumarate}
+%CUR          ^
+%SRC \end{enumarate}
+"#,
+    )?;
+
+    assert_eq!(actions.len(), 1);
+    let (title, uri, edits) = &actions[0];
+    assert_eq!(title, "Change to \"enumerate\"");
+    assert!(uri.as_str().ends_with("main.tex"));
+    assert_eq!(
+        edits,
+        &[
+            TextEdit::new(
+                Range::new(Position::new(0, 7), Position::new(0, 16)),
+                "enumerate".into()
+            ),
+            TextEdit::new(
+                Range::new(Position::new(1, 5), Position::new(1, 14)),
+                "enumerate".into()
+            ),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn misspelled_environment_known() -> Result<()> {
+    let actions = check(
+        r#"
+%TEX main.tex
+%SRC \begin{itemize}
+%CUR          ^
+%SRC \end{itemize}
+"#,
+    )?;
+
+    assert!(actions.is_empty());
+    Ok(())
+}
+
+#[test]
+fn unescaped_character() -> Result<()> {
+    let actions = check(
+        r#"
+%TEX main.tex
+%SRC \begin{document}
+%SRC Smith & Sons
+%CUR       ^
+%SRC \end{document}
+"#,
+    )?;
+
+    assert_eq!(actions.len(), 1);
+    let (title, uri, edits) = &actions[0];
+    assert_eq!(title, "Escape \"&\"");
+    assert!(uri.as_str().ends_with("main.tex"));
+    assert_eq!(
+        edits,
+        &[TextEdit::new(
+            Range::new(Position::new(1, 6), Position::new(1, 7)),
+            "\\&".into()
+        )]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn unescaped_character_tabular() -> Result<()> {
+    let actions = check(
+        r#"
+%TEX main.tex
+%SRC \begin{tabular}{cc}
+%SRC     a & b
+%CUR       ^
+%SRC \end{tabular}
+"#,
+    )?;
+
+    assert!(actions.is_empty());
+    Ok(())
+}
+
+#[test]
+fn unescaped_character_tikzcd() -> Result<()> {
+    let actions = check(
+        r#"
+%TEX main.tex
+%SRC \begin{document}
+%SRC \begin{tikzcd}
+%SRC     A & B
+%CUR       ^
+%SRC \end{tikzcd}
+%SRC \end{document}
+"#,
+    )?;
+
+    assert!(actions.is_empty());
+    Ok(())
+}
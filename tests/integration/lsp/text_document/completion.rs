@@ -2,8 +2,9 @@ use anyhow::Result;
 use insta::assert_json_snapshot;
 use lsp_types::{
     request::{Completion, ResolveCompletionItem},
-    ClientCapabilities, CompletionItem, CompletionList, CompletionParams, CompletionResponse,
-    CompletionTextEdit, Range,
+    ClientCapabilities, CompletionClientCapabilities, CompletionItem, CompletionItemCapability,
+    CompletionList, CompletionParams, CompletionResponse, CompletionTextEdit, InsertTextFormat,
+    Range, TextDocumentClientCapabilities,
 };
 
 use crate::lsp::{client::Client, fixture};
@@ -248,6 +249,55 @@ fn citation() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn citation_parencite() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \addbibresource{main.bib}
+%SRC \parencite[p.~5]{
+%CUR                  ^
+
+%BIB main.bib
+%SRC @article{foo,}"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn citation_textcite() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \addbibresource{main.bib}
+%SRC \Textcite{f}
+%CUR            ^
+%1.1           ^
+
+%BIB main.bib
+%SRC @article{foo,}"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn citation_in_caption() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \addbibresource{main.bib}
+%SRC \caption{see \cite{}}
+%CUR                    ^
+
+%BIB main.bib
+%SRC @article{foo,}"#
+    )?);
+
+    Ok(())
+}
+
 #[test]
 fn citation_open_brace() -> Result<()> {
     assert_items!(complete(
@@ -281,6 +331,56 @@ fn citation_open_brace_multiple() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn citation_open_brace_tilde() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \addbibresource{main.bib}
+%SRC see~\cite{}
+%CUR           ^
+
+%BIB main.bib
+%SRC @article{foo,}"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn citation_after_comma() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \addbibresource{main.bib}
+%SRC \cite{foo,}
+%CUR           ^
+
+%BIB main.bib
+%SRC @article{foo,}
+%SRC @article{bar,}"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn citation_after_comma_whitespace() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \addbibresource{main.bib}
+%SRC \cite{foo, }
+%CUR            ^
+
+%BIB main.bib
+%SRC @article{foo,}
+%SRC @article{bar,}"#
+    )?);
+
+    Ok(())
+}
+
 #[test]
 fn citation_acronym() -> Result<()> {
     assert_items!(complete(
@@ -297,6 +397,90 @@ fn citation_acronym() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn citation_sorted_by_author() -> Result<()> {
+    let actual_items = complete(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \bibliography{main}
+%SRC \begin{document}
+%SRC \cite{
+%CUR       ^
+%SRC \end{document}
+
+%BIB main.bib
+%SRC @book{a, author = {Donald Knuth}, year = {1984}}
+%SRC @book{b, author = {Donald Knuth}, year = {1968}}
+%SRC @book{c, author = {Niels Abel and Carl Gauss and Leonhard Euler}, year = {1826}}
+%SRC @book{d, editor = {Alan Turing and Alonzo Church}}
+%SRC @book{e, title = {Foo}}"#,
+    )?;
+
+    let actual_items: Vec<_> = actual_items
+        .into_iter()
+        .map(|item| (item.label, item.detail))
+        .collect();
+
+    assert_eq!(
+        actual_items,
+        vec![
+            ("c".to_string(), Some("Abel et al., 1826".to_string())),
+            ("e".to_string(), None),
+            ("b".to_string(), Some("Knuth, 1968".to_string())),
+            ("a".to_string(), Some("Knuth, 1984".to_string())),
+            ("d".to_string(), Some("Turing and Church".to_string())),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn citation_author() -> Result<()> {
+    let actual_items = complete(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \bibliography{main}
+%SRC \begin{document}
+%SRC \cite{Knuth
+%CUR            ^
+%1.1       ^^^^^
+%SRC \end{document}
+
+%BIB main.bib
+%SRC @book{foo, author = {Donald Knuth}, year = {1984}}"#,
+    )?;
+
+    let actual_labels: Vec<_> = actual_items.into_iter().map(|item| item.label).collect();
+    assert_eq!(actual_labels, vec!["foo".to_string()]);
+    Ok(())
+}
+
+#[test]
+fn citation_journal() -> Result<()> {
+    let actual_items = complete(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \bibliography{main}
+%SRC \begin{document}
+%SRC \cite{Nature
+%CUR             ^
+%1.1       ^^^^^^
+%SRC \end{document}
+
+%BIB main.bib
+%SRC @article{foo, author = {Charles Darwin}, title = {Nature}, journal = {Science}}
+%SRC @article{bar, author = {Donald Knuth}, journal = {Nature}}"#,
+    )?;
+
+    let actual_labels: Vec<_> = actual_items.into_iter().map(|item| item.label).collect();
+    assert_eq!(actual_labels, vec!["foo".to_string()]);
+    Ok(())
+}
+
 #[test]
 fn citation_after_brace() -> Result<()> {
     let actual_items = complete(
@@ -441,6 +625,73 @@ fn component_command_simple_package() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn component_command_text_mode() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \righ
+%CUR      ^
+%1.1  ^^^^"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn component_command_math_mode() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \begin{equation}
+%SRC     \righ
+%CUR          ^
+%1.1      ^^^^
+%SRC \end{equation}"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn component_command_text_in_math_mode() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC $\text{\righ}$
+%CUR            ^
+%1.1         ^^^^"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn math_symbol() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC $\alp$
+%CUR      ^
+%1.1   ^^^"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn math_symbol_outside_math_mode() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \alp
+%CUR     ^
+%1.1  ^^^"#
+    )?);
+
+    Ok(())
+}
+
 #[test]
 fn component_command_bibtex() -> Result<()> {
     assert_items!(complete(
@@ -662,6 +913,19 @@ fn field_entry_two_fields_name_closed() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn field_entry_required() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%BIB main.bib
+%SRC @article{foo, author = bar, journ
+%CUR                               ^
+%1.1                             ^^^^^"#
+    )?);
+
+    Ok(())
+}
+
 #[test]
 fn import_package_open_brace() -> Result<()> {
     assert_items!(complete(
@@ -688,6 +952,95 @@ fn import_package_closed_brace() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn package_option_key() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \usepackage[landsc]{geometry}
+%CUR                  ^
+%1.1             ^^^^^^"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn package_option_key_empty() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \usepackage[draft, ]{hyperref}
+%CUR                    ^"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn package_option_value() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \usepackage[paper=a4]{geometry}
+%CUR                     ^
+%1.1                   ^^"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn package_option_value_empty() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \usepackage[driver=]{geometry}
+%CUR                    ^"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn class_option_key() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \documentclass[twoc]{article}
+%CUR                    ^
+%1.1                ^^^^"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn class_option_value() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \documentclass[paper=a]{scrartcl}
+%CUR                       ^
+%1.1                      ^"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn package_option_unknown_package() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \usepackage[foo]{lipsum}
+%CUR               ^
+%1.1              ^^^"#
+    )?);
+
+    Ok(())
+}
+
 #[test]
 fn import_class_open_brace() -> Result<()> {
     assert_items!(complete(
@@ -780,6 +1133,128 @@ fn label() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn label_after_tilde() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \section{Foo}\label{sec:foo}
+%SRC Section~\ref{}
+%CUR              ^"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn label_eqref() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \section{Foo}\label{a:foo}
+%SRC \begin{equation}\label{b:bar}
+%SRC     1 + 1 = 2
+%SRC \end{equation}
+%SRC \eqref{}
+%CUR        ^"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn label_external_document() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \externaldocument{other}
+%SRC \label{sec:bar}
+%SRC \ref{}
+%CUR      ^
+
+%TEX other.tex
+%SRC \section{Foo}\label{sec:foo}"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn counter() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \newcounter{foo}[section]
+%SRC \input{child}
+%SRC \setcounter{fo}{1}
+%CUR              ^
+%1.1             ^^
+
+%TEX child.tex
+%SRC \newcounter{footer}"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn label_external_document_prefix() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \externaldocument[A-]{other}
+%SRC \label{sec:bar}
+%SRC \ref{}
+%CUR      ^
+
+%TEX other.tex
+%SRC \section{Foo}\label{sec:foo}"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn label_in_footnote() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \label{bar}
+%SRC \footnote{\ref{}}
+%CUR                ^"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn label_definition_figure() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \begin{figure}
+%SRC \caption{Foo Bar}
+%SRC \label{}
+%CUR        ^
+%SRC \end{figure}"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn label_definition_section() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \section{Foo Bar}
+%SRC \label{}
+%CUR        ^"#
+    )?);
+
+    Ok(())
+}
+
 #[test]
 fn theorem_begin() -> Result<()> {
     assert_items!(complete(
@@ -833,6 +1308,72 @@ fn tikz_library_closed_brace() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn tikz_library_exclude_listed() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \usetikzlibrary{shapes.misc, shapes.geometric, shapes.}
+%CUR                                                       ^
+%1.1                                                ^^^^^^^"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn tikz_key_draw() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \begin{tikzpicture}
+%SRC \draw[->, thi] (0,0) -- (1,1);
+%CUR              ^
+%1.1           ^^^
+%SRC \end{tikzpicture}"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn tikz_key_environment() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \begin{tikzpicture}[node dis]
+%CUR                             ^
+%1.1                     ^^^^^^^^
+%SRC \end{tikzpicture}"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn tikz_key_value() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \node[fill=re] {};
+%CUR              ^"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn tikz_key_unknown_command() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \foo[thi]
+%CUR         ^"#
+    )?);
+
+    Ok(())
+}
+
 #[test]
 fn test_user_command() -> Result<()> {
     assert_items!(complete(
@@ -851,6 +1392,36 @@ fn test_user_command() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_user_command_missing_package() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \qty{1}
+%SRC \qt
+%CUR    ^
+%1.1  ^^
+"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn test_user_command_defined() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \newcommand{\qty}[1]{#1}
+%SRC \qt
+%CUR    ^
+%1.1  ^^
+"#
+    )?);
+
+    Ok(())
+}
+
 #[test]
 fn test_user_environment() -> Result<()> {
     assert_items!(complete(
@@ -868,3 +1439,114 @@ fn test_user_environment() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_user_environment_definition() -> Result<()> {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \usepackage{preamble}
+%SRC \begin{proofsk}
+%CUR              ^
+%1.1        ^^^^^^^
+
+%TEX preamble.sty
+%SRC \newenvironment{proofsketch}[1]{\begin{proof}[#1]}{\end{proof}}
+"#
+    )?);
+
+    Ok(())
+}
+
+fn complete_snippet(fixture: &str, label: &str) -> Result<(String, Option<InsertTextFormat>)> {
+    let mut client = Client::spawn()?;
+    let capabilities = ClientCapabilities {
+        text_document: Some(TextDocumentClientCapabilities {
+            completion: Some(CompletionClientCapabilities {
+                completion_item: Some(CompletionItemCapability {
+                    snippet_support: Some(true),
+                    ..CompletionItemCapability::default()
+                }),
+                ..CompletionClientCapabilities::default()
+            }),
+            ..TextDocumentClientCapabilities::default()
+        }),
+        ..ClientCapabilities::default()
+    };
+
+    client.initialize(capabilities, None)?;
+    let fixture = fixture::parse(fixture);
+    for file in fixture.files {
+        client.open(file.name, file.lang, file.text)?;
+    }
+
+    let item = client
+        .request::<Completion>(CompletionParams {
+            text_document_position: fixture.cursor.unwrap().into_params(&client)?,
+            partial_result_params: Default::default(),
+            work_done_progress_params: Default::default(),
+            context: None,
+        })?
+        .and_then(|actual| match actual {
+            CompletionResponse::List(list) => {
+                list.items.into_iter().find(|item| item.label == label)
+            }
+            CompletionResponse::Array(_) => unreachable!(),
+        })
+        .unwrap();
+
+    client.shutdown()?;
+    let text = match item.text_edit.unwrap() {
+        CompletionTextEdit::Edit(edit) => edit.new_text,
+        CompletionTextEdit::InsertAndReplace(_) => unreachable!(),
+    };
+
+    Ok((text, item.insert_text_format))
+}
+
+#[test]
+fn environment_snippet_arguments() -> Result<()> {
+    let (text, format) = complete_snippet(
+        r#"
+%TEX main.tex
+%SRC \begin{tabula}
+%CUR          ^"#,
+        "tabular",
+    )?;
+
+    assert_eq!(text, "tabular}{${1:cols}}\n\t$0\n\\end{tabular}");
+    assert_eq!(format, Some(InsertTextFormat::SNIPPET));
+    Ok(())
+}
+
+#[test]
+fn environment_snippet_user_defined() -> Result<()> {
+    let (text, format) = complete_snippet(
+        r#"
+%TEX main.tex
+%SRC \newenvironment{foobar}[2][x]{}{}
+%SRC \begin{fooba
+%CUR          ^"#,
+        "foobar",
+    )?;
+
+    assert_eq!(text, "foobar}{$1}\n\t$0\n\\end{foobar}");
+    assert_eq!(format, Some(InsertTextFormat::SNIPPET));
+    Ok(())
+}
+
+#[test]
+fn environment_snippet_closed() -> Result<()> {
+    let (text, format) = complete_snippet(
+        r#"
+%TEX main.tex
+%SRC \begin{itemi}
+%CUR          ^
+%SRC \end{itemize}"#,
+        "itemize",
+    )?;
+
+    assert_eq!(text, "itemize");
+    assert_eq!(format, None);
+    Ok(())
+}
@@ -2,7 +2,7 @@ use anyhow::Result;
 use assert_unordered::assert_eq_unordered;
 use lsp_types::{
     request::GotoDefinition, ClientCapabilities, GotoDefinitionParams, GotoDefinitionResponse,
-    LocationLink,
+    LocationLink, Position, Range, TextDocumentIdentifier, TextDocumentPositionParams,
 };
 
 use crate::lsp::{client::Client, fixture};
@@ -43,6 +43,51 @@ fn check(fixture: &str) -> Result<()> {
     Ok(())
 }
 
+fn check_local_package(name: &str) -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+
+    let file_name = format!("{}.sty", name);
+    client.store_on_disk(&file_name, "\\ProvidesPackage{foo}")?;
+    client.open(
+        "main.tex",
+        "latex",
+        format!(
+            "\\usepackage{{{}}}\n\\begin{{document}}\\end{{document}}",
+            name
+        ),
+    )?;
+
+    let actual_links = client
+        .request::<GotoDefinition>(GotoDefinitionParams {
+            text_document_position_params: TextDocumentPositionParams::new(
+                TextDocumentIdentifier::new(client.uri("main.tex")?),
+                Position::new(0, 13),
+            ),
+            partial_result_params: Default::default(),
+            work_done_progress_params: Default::default(),
+        })?
+        .map_or(Vec::new(), |actual| match actual {
+            GotoDefinitionResponse::Link(links) => links,
+            GotoDefinitionResponse::Array(_) | GotoDefinitionResponse::Scalar(_) => unreachable!(),
+        });
+
+    let expected_links = vec![LocationLink {
+        origin_selection_range: Some(Range::new(
+            Position::new(0, 12),
+            Position::new(0, 12 + name.len() as u32),
+        )),
+        target_uri: client.uri(&file_name)?,
+        target_range: Range::default(),
+        target_selection_range: Range::default(),
+    }];
+
+    client.shutdown()?;
+
+    assert_eq!(actual_links, expected_links);
+    Ok(())
+}
+
 #[test]
 fn command_definition() -> Result<()> {
     check(
@@ -78,6 +123,64 @@ fn document() -> Result<()> {
     )
 }
 
+#[test]
+fn document_skip_comments() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \input{foo}
+%CUR         ^
+%1.1        ^^^
+
+%TEX foo.tex
+%SRC % !TeX root = main.tex
+%SRC 
+%SRC \section{Foo}
+%1.3 
+%1.2 
+"#,
+    )
+}
+
+#[test]
+fn document_bibtex_percent() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \bibliography{foo}
+%CUR               ^
+%1.1               ^^^
+
+%BIB foo.bib
+%SRC % Foo
+%1.3 
+%1.2 
+%SRC @article{foo, bar = {baz}}
+"#,
+    )
+}
+
+#[test]
+fn package_not_found() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \usepackage{texlab-unknown-package}
+%CUR              ^
+"#,
+    )
+}
+
+#[test]
+fn package_local() -> Result<()> {
+    check_local_package("mylocal")
+}
+
+#[test]
+fn package_local_shadowing_component() -> Result<()> {
+    check_local_package("amsmath")
+}
+
 #[test]
 fn entry() -> Result<()> {
     check(
@@ -99,6 +202,213 @@ fn entry() -> Result<()> {
     )
 }
 
+#[test]
+fn entry_aux_fallback() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \cite{foo}
+%CUR       ^
+%1.1       ^^^
+
+%TEX main.aux
+%SRC \bibcite{foo}{3}
+%1.3          ^^^
+%1.2 ^^^^^^^^^^^^^^^^
+"#,
+    )
+}
+
+#[test]
+fn entry_parencite() -> Result<()> {
+    check(
+        r#"
+%TEX foo.tex
+%SRC \addbibresource{baz.bib}
+%SRC \parencite*{foo}
+%CUR              ^
+%1.1             ^^^
+
+%BIB baz.bib
+%SRC @article{foo, bar = {baz}}
+%1.3          ^^^
+%1.2 ^^^^^^^^^^^^^^^^^^^^^^^^^^
+"#,
+    )
+}
+
+#[test]
+fn entry_prenote() -> Result<()> {
+    check(
+        r#"
+%TEX foo.tex
+%SRC \addbibresource{baz.bib}
+%SRC \cite[p.~5]{foo}
+%CUR              ^
+%1.1             ^^^
+
+%BIB baz.bib
+%SRC @article{foo, bar = {baz}}
+%1.3          ^^^
+%1.2 ^^^^^^^^^^^^^^^^^^^^^^^^^^
+"#,
+    )
+}
+
+#[test]
+fn entry_angle_note() -> Result<()> {
+    check(
+        r#"
+%TEX foo.tex
+%SRC \addbibresource{baz.bib}
+%SRC \cite<see>[5]{foo}
+%CUR                ^
+%1.1               ^^^
+
+%BIB baz.bib
+%SRC @article{foo, bar = {baz}}
+%1.3          ^^^
+%1.2 ^^^^^^^^^^^^^^^^^^^^^^^^^^
+"#,
+    )
+}
+
+#[test]
+fn entry_multi_cite() -> Result<()> {
+    check(
+        r#"
+%TEX foo.tex
+%SRC \addbibresource{baz.bib}
+%SRC \cites{foo}{bar}
+%CUR              ^
+%1.1             ^^^
+
+%BIB baz.bib
+%SRC @article{foo, bar = {baz}}
+%SRC @article{bar, bar = {baz}}
+%1.3          ^^^
+%1.2 ^^^^^^^^^^^^^^^^^^^^^^^^^^
+"#,
+    )
+}
+
+#[test]
+fn entry_bibliography_multiple_files() -> Result<()> {
+    check(
+        r#"
+%TEX foo.tex
+%SRC \bibliography{bar, baz}
+%SRC \cite{foo}
+%CUR       ^
+%1.1       ^^^
+
+%BIB bar.bib
+%SRC @article{qux, bar = {baz}}
+
+%BIB baz.bib
+%SRC @article{foo, bar = {baz}}
+%1.3          ^^^
+%1.2 ^^^^^^^^^^^^^^^^^^^^^^^^^^
+"#,
+    )
+}
+
+#[test]
+fn entry_in_caption() -> Result<()> {
+    check(
+        r#"
+%TEX foo.tex
+%SRC \addbibresource{baz.bib}
+%SRC \caption{see \cite{foo}}
+%CUR                     ^
+%1.1                    ^^^
+
+%BIB baz.bib
+%SRC @article{foo, bar = {baz}}
+%1.3          ^^^
+%1.2 ^^^^^^^^^^^^^^^^^^^^^^^^^^
+"#,
+    )
+}
+
+#[test]
+fn label_in_footnote() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \label{bar}
+%1.3        ^^^
+%1.2        ^^^
+%SRC \footnote{\ref{bar}}
+%CUR                 ^
+%1.1                ^^^
+"#,
+    )
+}
+
+#[test]
+fn label_external_document() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \externaldocument{other}
+%SRC \ref{bar}
+%CUR       ^
+%1.1      ^^^
+
+%TEX other.tex
+%SRC \label{bar}
+%1.3        ^^^
+%1.2        ^^^
+"#,
+    )
+}
+
+#[test]
+fn counter() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \newcounter{foo}[section]
+%1.3             ^^^
+%1.2 ^^^^^^^^^^^^^^^^^^^^^^^^^
+%SRC \addtocounter{foo}{\value{foo}}
+%CUR                           ^
+%1.1                           ^^^
+"#,
+    )
+}
+
+#[test]
+fn glossary_entry() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \newglossaryentry{foo}{name={Foo}, description={A foo}}
+%1.3                   ^^^
+%1.2 ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+%SRC \gls{foo}
+%CUR       ^
+%1.1      ^^^
+"#,
+    )
+}
+
+#[test]
+fn acronym() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \newacronym{fps}{FPS}{Frames per Second}
+%1.3             ^^^
+%1.2 ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+%SRC \acrshort{fps}
+%CUR            ^
+%1.1           ^^^
+"#,
+    )
+}
+
 #[test]
 fn string_simple() -> Result<()> {
     check(
@@ -140,3 +450,151 @@ fn string_field() -> Result<()> {
 "#,
     )
 }
+
+#[test]
+fn label_figure() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \begin{figure}\caption{Foo}\label{fig:foo}\end{figure}
+%1.3                        ^^^
+%1.2 ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+%SRC \ref{fig:foo}
+%CUR       ^
+%1.1      ^^^^^^^
+"#,
+    )
+}
+
+#[test]
+fn label_figure_caption_below() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \begin{figure}\label{fig:foo}\caption{Foo Bar}\end{figure}
+%1.3                                       ^^^^^^^
+%1.2 ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+%SRC \cref{fig:foo}
+%CUR        ^
+%1.1       ^^^^^^^
+"#,
+    )
+}
+
+#[test]
+fn label_table_without_caption() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \begin{table}\label{tab:foo}\end{table}
+%1.3                     ^^^^^^^
+%1.2 ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+%SRC \ref{tab:foo}
+%CUR       ^
+%1.1      ^^^^^^^
+"#,
+    )
+}
+
+#[test]
+fn label_equation() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \begin{equation}\begin{split}x\end{split}\label{eq:foo}\end{equation}
+%1.3                                                 ^^^^^^
+%1.2 ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+%SRC \eqref{eq:foo}
+%CUR         ^
+%1.1        ^^^^^^
+"#,
+    )
+}
+
+#[test]
+fn label_equation_nested() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \begin{equation}\begin{aligned}x\label{eq:foo}\end{aligned}\end{equation}
+%1.3                                        ^^^^^^
+%1.2 ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+%SRC \eqref{eq:foo}
+%CUR         ^
+%1.1        ^^^^^^
+"#,
+    )
+}
+
+#[test]
+fn environment_definition() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \usepackage{preamble}
+%SRC \begin{proofsketch}{Foo}
+%CUR         ^
+%1.1        ^^^^^^^^^^^
+
+%TEX preamble.sty
+%SRC \newenvironment{proofsketch}[1]{\begin{proof}[#1]}{\end{proof}}
+%1.3                 ^^^^^^^^^^^
+%1.2 ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+"#,
+    )
+}
+
+#[test]
+fn command_redefinition() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \newcommand{\foo}{a}
+%SRC \foo
+%SRC \renewcommand{\foo}{b}
+%1.3               ^^^^
+%1.2 ^^^^^^^^^^^^^^^^^^^^^^
+%SRC \foo
+%CUR   ^
+%1.1 ^^^^
+"#,
+    )
+}
+
+#[test]
+fn verbatim_include() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.store_on_disk("code.py", "print('foo')")?;
+    client.open(
+        "main.tex",
+        "latex",
+        "\\lstinputlisting[language=Python]{code.py}".to_string(),
+    )?;
+
+    let actual_links = client
+        .request::<GotoDefinition>(GotoDefinitionParams {
+            text_document_position_params: TextDocumentPositionParams::new(
+                TextDocumentIdentifier::new(client.uri("main.tex")?),
+                Position::new(0, 36),
+            ),
+            partial_result_params: Default::default(),
+            work_done_progress_params: Default::default(),
+        })?
+        .map_or(Vec::new(), |actual| match actual {
+            GotoDefinitionResponse::Link(links) => links,
+            GotoDefinitionResponse::Array(_) | GotoDefinitionResponse::Scalar(_) => unreachable!(),
+        });
+
+    let expected_links = vec![LocationLink {
+        origin_selection_range: Some(Range::new(Position::new(0, 34), Position::new(0, 41))),
+        target_uri: client.uri("code.py")?,
+        target_range: Range::default(),
+        target_selection_range: Range::default(),
+    }];
+
+    client.shutdown()?;
+
+    assert_eq!(actual_links, expected_links);
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use std::{thread, time::Duration};
+
+use anyhow::Result;
+use lsp_types::{
+    notification::{DidChangeConfiguration, Initialized, PublishDiagnostics},
+    ClientCapabilities, DidChangeConfigurationParams, InitializedParams,
+};
+use serde_json::json;
+
+use crate::lsp::client::Client;
+
+struct RawInitialize;
+
+impl lsp_types::request::Request for RawInitialize {
+    type Params = serde_json::Value;
+
+    type Result = serde_json::Value;
+
+    const METHOD: &'static str = "initialize";
+}
+
+struct DocumentDiagnosticRequest;
+
+impl lsp_types::request::Request for DocumentDiagnosticRequest {
+    type Params = serde_json::Value;
+
+    type Result = serde_json::Value;
+
+    const METHOD: &'static str = "textDocument/diagnostic";
+}
+
+struct WorkspaceDiagnosticRequest;
+
+impl lsp_types::request::Request for WorkspaceDiagnosticRequest {
+    type Params = serde_json::Value;
+
+    type Result = serde_json::Value;
+
+    const METHOD: &'static str = "workspace/diagnostic";
+}
+
+struct WorkspaceDiagnosticRefresh;
+
+impl lsp_types::request::Request for WorkspaceDiagnosticRefresh {
+    type Params = ();
+
+    type Result = ();
+
+    const METHOD: &'static str = "workspace/diagnostic/refresh";
+}
+
+#[test]
+fn document() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.open("main.tex", "latex", "}".to_string())?;
+    client.receive_notification::<PublishDiagnostics>()?;
+
+    let uri = client.uri("main.tex")?;
+    let report = client.request::<DocumentDiagnosticRequest>(json!({
+        "textDocument": { "uri": uri },
+    }))?;
+
+    client.shutdown()?;
+
+    assert_eq!(report["kind"], "full");
+    assert_eq!(report["items"].as_array().unwrap().len(), 1);
+    assert_eq!(report["items"][0]["message"], "Unexpected \"}\"");
+    Ok(())
+}
+
+#[test]
+fn workspace() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.open("main.tex", "latex", "}".to_string())?;
+    client.receive_notification::<PublishDiagnostics>()?;
+
+    let uri = client.uri("main.tex")?;
+    let report = client.request::<WorkspaceDiagnosticRequest>(json!({
+        "previousResultIds": [],
+    }))?;
+
+    client.shutdown()?;
+
+    let items = report["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["kind"], "full");
+    assert_eq!(items[0]["uri"], uri.as_str());
+    assert_eq!(items[0]["items"].as_array().unwrap().len(), 1);
+    Ok(())
+}
+
+#[test]
+fn pull_model() -> Result<()> {
+    let mut client = Client::spawn()?;
+    let result = client.request::<RawInitialize>(json!({
+        "capabilities": {
+            "textDocument": { "diagnostic": {} },
+            "workspace": { "diagnostics": { "refreshSupport": true } },
+        },
+    }))?;
+
+    client.notify::<Initialized>(InitializedParams {})?;
+    client.open("main.tex", "latex", "}".to_string())?;
+
+    let (id, _) = client.receive_request::<WorkspaceDiagnosticRefresh>()?;
+    client.respond::<WorkspaceDiagnosticRefresh>(id, ())?;
+
+    let uri = client.uri("main.tex")?;
+    let report = client.request::<DocumentDiagnosticRequest>(json!({
+        "textDocument": { "uri": uri },
+    }))?;
+
+    let published = client
+        .shutdown()?
+        .incoming
+        .notifications
+        .try_iter()
+        .filter(|notification| notification.method == "textDocument/publishDiagnostics")
+        .count();
+
+    let provider = &result["capabilities"]["diagnosticProvider"];
+    assert_eq!(provider["interFileDependencies"], true);
+    assert_eq!(provider["workspaceDiagnostics"], true);
+    assert_eq!(report["items"].as_array().unwrap().len(), 1);
+    assert_eq!(published, 0);
+    Ok(())
+}
+
+#[test]
+fn pull_model_pending_refresh() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.request::<RawInitialize>(json!({
+        "capabilities": {
+            "textDocument": { "diagnostic": {} },
+            "workspace": { "diagnostics": { "refreshSupport": true } },
+        },
+    }))?;
+
+    client.notify::<Initialized>(InitializedParams {})?;
+    client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams {
+        settings: json!({ "diagnostics": { "delay": 10 } }),
+    })?;
+
+    client.open("main.tex", "latex", "}".to_string())?;
+    let (id, _) = client.receive_request::<WorkspaceDiagnosticRefresh>()?;
+
+    // The change is only refreshed after the client has answered the pending refresh.
+    client.open("other.tex", "latex", "}".to_string())?;
+    thread::sleep(Duration::from_millis(200));
+    client.respond::<WorkspaceDiagnosticRefresh>(id, ())?;
+
+    let (id, _) = client.receive_request::<WorkspaceDiagnosticRefresh>()?;
+    client.respond::<WorkspaceDiagnosticRefresh>(id, ())?;
+
+    let pending_requests = client.shutdown()?.incoming.requests.try_iter().count();
+    assert_eq!(pending_requests, 0);
+    Ok(())
+}
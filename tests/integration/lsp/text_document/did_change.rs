@@ -0,0 +1,59 @@
+use anyhow::Result;
+use lsp_types::{
+    notification::DidChangeTextDocument, request::DocumentSymbolRequest, ClientCapabilities,
+    DidChangeTextDocumentParams, DocumentSymbolParams, DocumentSymbolResponse, Position, Range,
+    TextDocumentContentChangeEvent, TextDocumentIdentifier, VersionedTextDocumentIdentifier,
+};
+
+use crate::lsp::client::Client;
+
+fn edit(start: (u32, u32), end: (u32, u32), text: &str) -> TextDocumentContentChangeEvent {
+    TextDocumentContentChangeEvent {
+        range: Some(Range::new(
+            Position::new(start.0, start.1),
+            Position::new(end.0, end.1),
+        )),
+        range_length: None,
+        text: text.to_string(),
+    }
+}
+
+#[test]
+fn line_endings() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.open(
+        "main.tex",
+        "latex",
+        "\\section{Foo}\r\n\\section{Bar}\r\\section{Baz}".to_string(),
+    )?;
+
+    client.notify::<DidChangeTextDocument>(DidChangeTextDocumentParams {
+        text_document: VersionedTextDocumentIdentifier::new(client.uri("main.tex")?, 1),
+        content_changes: vec![
+            edit((1, 9), (1, 12), "Qux"),
+            edit((2, 9), (2, 12), "Quux"),
+            edit((0, 13), (0, 13), "\r\n\\section{Corge}"),
+        ],
+    })?;
+
+    let response = client.request::<DocumentSymbolRequest>(DocumentSymbolParams {
+        text_document: TextDocumentIdentifier::new(client.uri("main.tex")?),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    })?;
+
+    client.shutdown()?;
+
+    let names: Vec<_> = match response.unwrap() {
+        DocumentSymbolResponse::Flat(symbols) => {
+            symbols.into_iter().map(|symbol| symbol.name).collect()
+        }
+        DocumentSymbolResponse::Nested(symbols) => {
+            symbols.into_iter().map(|symbol| symbol.name).collect()
+        }
+    };
+
+    assert_eq!(names, ["Foo", "Corge", "Qux", "Quux"]);
+    Ok(())
+}
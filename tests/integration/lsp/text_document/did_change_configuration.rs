@@ -1,8 +1,11 @@
 use anyhow::Result;
 use insta::assert_snapshot;
 use lsp_types::{
-    notification::{DidChangeConfiguration, Notification, ShowMessage},
-    ClientCapabilities, DidChangeConfigurationParams, ShowMessageParams,
+    notification::{DidChangeConfiguration, Notification, PublishDiagnostics, ShowMessage},
+    request::Completion,
+    ClientCapabilities, CompletionParams, CompletionResponse, DidChangeConfigurationParams,
+    Position, PublishDiagnosticsParams, ShowMessageParams, TextDocumentIdentifier,
+    TextDocumentPositionParams,
 };
 
 use crate::lsp::client::Client;
@@ -37,3 +40,204 @@ fn invalid_configuration() -> Result<()> {
     assert_snapshot!(message);
     Ok(())
 }
+
+#[test]
+fn max_file_size() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+
+    let settings = serde_json::json!({ "maxFileSizeBytes": 4 });
+    client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams {
+        settings: settings.clone(),
+    })?;
+
+    client.open("main.tex", "latex", "\\foo}".to_string())?;
+    client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams { settings })?;
+
+    let result = client.shutdown()?;
+    let notifications: Vec<_> = result.incoming.notifications.into_iter().collect();
+
+    let warnings = notifications
+        .iter()
+        .filter_map(|notification| {
+            notification
+                .clone()
+                .extract::<ShowMessageParams>(ShowMessage::METHOD)
+                .ok()
+        })
+        .filter(|params| params.message.contains("maxFileSizeBytes"))
+        .count();
+
+    let has_diagnostics = notifications
+        .into_iter()
+        .filter_map(|notification| {
+            notification
+                .extract::<PublishDiagnosticsParams>(PublishDiagnostics::METHOD)
+                .ok()
+        })
+        .any(|params| !params.diagnostics.is_empty());
+
+    assert_eq!(warnings, 1);
+    assert!(!has_diagnostics);
+    Ok(())
+}
+
+fn complete_labels(client: &mut Client, name: &str, position: Position) -> Result<Vec<String>> {
+    let items = match client.request::<Completion>(CompletionParams {
+        text_document_position: TextDocumentPositionParams::new(
+            TextDocumentIdentifier::new(client.uri(name)?),
+            position,
+        ),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+        context: None,
+    })? {
+        Some(CompletionResponse::List(list)) => list.items,
+        Some(CompletionResponse::Array(items)) => items,
+        None => Vec::new(),
+    };
+
+    Ok(items.into_iter().map(|item| item.label).collect())
+}
+
+#[test]
+fn lazy_loading() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+
+    client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams {
+        settings: serde_json::json!({ "lazyLoading": true }),
+    })?;
+
+    client.store_on_disk("main.bib", "@article{foo, title = {Foo}}")?;
+    client.open(
+        "main.tex",
+        "latex",
+        "\\bibliography{main}\n\\cite{}".to_string(),
+    )?;
+
+    let labels = complete_labels(&mut client, "main.tex", Position::new(1, 6))?;
+    client.shutdown()?;
+    assert!(labels.iter().any(|label| label == "foo"));
+    Ok(())
+}
+
+#[test]
+fn extra_latex_extensions() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+
+    client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams {
+        settings: serde_json::json!({ "extraLatexExtensions": ["tikz"] }),
+    })?;
+
+    client.store_on_disk("figure.tikz", "\\label{fig:foo}")?;
+    client.open(
+        "main.tex",
+        "latex",
+        "\\input{figure.tikz}\n\\ref{}".to_string(),
+    )?;
+
+    let labels = complete_labels(&mut client, "main.tex", Position::new(1, 5))?;
+    client.shutdown()?;
+    assert!(labels.iter().any(|label| label == "fig:foo"));
+    Ok(())
+}
+
+#[test]
+fn extra_latex_extensions_parent() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+
+    client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams {
+        settings: serde_json::json!({ "extraLatexExtensions": ["tikz"] }),
+    })?;
+
+    client.open(
+        "main.tex",
+        "latex",
+        "\\section{Foo}\\label{sec:foo}\n\\input{figure.tikz}".to_string(),
+    )?;
+
+    client.open("figure.tikz", "latex", "\\ref{}".to_string())?;
+
+    let labels = complete_labels(&mut client, "figure.tikz", Position::new(0, 5))?;
+    client.shutdown()?;
+    assert!(labels.iter().any(|label| label == "sec:foo"));
+    Ok(())
+}
+
+#[test]
+fn exclude_globs_directory_children() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+
+    client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams {
+        settings: serde_json::json!({ "excludeGlobs": ["**/build"] }),
+    })?;
+
+    client.store_on_disk("build/child.tex", "\\section{Foo}\\label{sec:foo}")?;
+    client.open(
+        "main.tex",
+        "latex",
+        "\\ref{}\n\\input{build/child}".to_string(),
+    )?;
+
+    let labels = complete_labels(&mut client, "main.tex", Position::new(0, 5))?;
+    client.shutdown()?;
+    assert!(!labels.iter().any(|label| label == "sec:foo"));
+    Ok(())
+}
+
+#[test]
+fn exclude_globs_directory_parent() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+
+    client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams {
+        settings: serde_json::json!({ "excludeGlobs": ["**/build"] }),
+    })?;
+
+    client.store_on_disk(
+        "build/main.tex",
+        "\\section{Foo}\\label{sec:foo}\n\\input{sub/child}",
+    )?;
+
+    client.open("build/sub/child.tex", "latex", "\\ref{}".to_string())?;
+
+    let labels = complete_labels(&mut client, "build/sub/child.tex", Position::new(0, 5))?;
+    client.shutdown()?;
+    assert!(!labels.iter().any(|label| label == "sec:foo"));
+    Ok(())
+}
+
+#[test]
+fn exclude_globs_workspace_folder_inside_excluded_directory() -> Result<()> {
+    let mut client = Client::spawn()?;
+    let folder = client.workspace_folder("build/project")?;
+    client.initialize_with_folders(ClientCapabilities::default(), None, Some(vec![folder]))?;
+
+    client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams {
+        settings: serde_json::json!({ "excludeGlobs": ["**/build"] }),
+    })?;
+
+    client.store_on_disk(
+        "build/project/main.tex",
+        "\\section{Foo}\\label{sec:foo}\n\\input{sub/child}",
+    )?;
+
+    client.open(
+        "build/project/sub/child.tex",
+        "latex",
+        "\\ref{}".to_string(),
+    )?;
+
+    let labels = complete_labels(
+        &mut client,
+        "build/project/sub/child.tex",
+        Position::new(0, 5),
+    )?;
+    client.shutdown()?;
+    assert!(labels.iter().any(|label| label == "sec:foo"));
+    Ok(())
+}
@@ -1,13 +1,18 @@
 use anyhow::Result;
 use assert_unordered::assert_eq_unordered;
 use lsp_types::{
-    request::DocumentLinkRequest, ClientCapabilities, DocumentLink, DocumentLinkParams,
-    TextDocumentIdentifier,
+    request::{DocumentLinkRequest, DocumentLinkResolve},
+    ClientCapabilities, DocumentLink, DocumentLinkParams, Position, Range, TextDocumentIdentifier,
+    Url,
 };
 
 use crate::lsp::{client::Client, fixture};
 
 fn check(fixture: &str) -> Result<()> {
+    check_with_urls(fixture, &[])
+}
+
+fn check_with_urls(fixture: &str, urls: &[&str]) -> Result<()> {
     let mut client = Client::spawn()?;
     client.initialize(ClientCapabilities::default(), None)?;
 
@@ -16,23 +21,40 @@ fn check(fixture: &str) -> Result<()> {
         client.open(file.name, file.lang, file.text)?;
     }
 
+    let mut urls = urls.iter();
     let mut expected_links = Vec::new();
     for ranges in fixture.ranges.values() {
+        let target = match ranges.get(&2) {
+            Some(range) => client.uri(range.name)?,
+            None => Url::parse(urls.next().unwrap())?,
+        };
+
         expected_links.push(DocumentLink {
             range: ranges[&1].range,
-            target: Some(client.uri(ranges[&2].name)?),
+            target: Some(target),
             tooltip: None,
             data: None,
         });
     }
 
-    let actual_links = client
+    let mut actual_links = Vec::new();
+    for link in client
         .request::<DocumentLinkRequest>(DocumentLinkParams {
             text_document: TextDocumentIdentifier::new(client.uri(fixture.cursor.unwrap().name)?),
             work_done_progress_params: Default::default(),
             partial_result_params: Default::default(),
         })?
-        .unwrap_or_default();
+        .unwrap_or_default()
+    {
+        let link = match link.target {
+            Some(_) => link,
+            None => client.request::<DocumentLinkResolve>(link)?,
+        };
+
+        if link.target.is_some() {
+            actual_links.push(link);
+        }
+    }
 
     client.shutdown()?;
 
@@ -71,3 +93,195 @@ fn document_import() -> Result<()> {
 "#,
     )
 }
+
+#[test]
+fn document_subimport_nested() -> Result<()> {
+    check(
+        r#"
+%TEX foo.tex
+%SRC \import{bar/}{baz}
+
+%TEX bar/baz.tex
+%SRC \subimport{qux/}{quux}
+%1.1                  ^^^^
+%CUR ^
+
+%TEX bar/qux/quux.tex
+%SRC 
+%1.2 
+"#,
+    )
+}
+
+#[test]
+fn document_import_chain() -> Result<()> {
+    check(
+        r#"
+%TEX foo.tex
+%SRC \import{bar/}{sub/baz}
+
+%TEX bar/sub/baz.tex
+%SRC \subimport{qux/}{deep/quux}
+
+%TEX bar/qux/deep/quux.tex
+%SRC \input{corge}
+%1.1        ^^^^^
+%CUR ^
+
+%TEX bar/qux/corge.tex
+%SRC 
+%1.2 
+"#,
+    )
+}
+
+#[test]
+fn document_import_chain_child_first() -> Result<()> {
+    check(
+        r#"
+%TEX bar/qux/corge.tex
+%SRC 
+%1.2 
+
+%TEX bar/qux/deep/quux.tex
+%SRC \input{corge}
+%1.1        ^^^^^
+%CUR ^
+
+%TEX bar/sub/baz.tex
+%SRC \subimport{qux/}{deep/quux}
+
+%TEX foo.tex
+%SRC \import{bar/}{sub/baz}
+"#,
+    )
+}
+
+#[test]
+fn document_not_found() -> Result<()> {
+    check(
+        r#"
+%TEX foo.tex
+%SRC \input{bar.tex}
+%CUR ^
+"#,
+    )
+}
+
+#[test]
+fn url() -> Result<()> {
+    check_with_urls(
+        r#"
+%TEX foo.tex
+%SRC \url{https://www.example.org/foo}
+%1.1      ^^^^^^^^^^^^^^^^^^^^^^^^^^^
+%CUR ^
+"#,
+        &["https://www.example.org/foo"],
+    )
+}
+
+#[test]
+fn url_percent_encoded() -> Result<()> {
+    check_with_urls(
+        r#"
+%TEX foo.tex
+%SRC \url{https://www.example.org/foo%20bar}
+%1.1      ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+%CUR ^
+"#,
+        &["https://www.example.org/foo%20bar"],
+    )
+}
+
+#[test]
+fn href() -> Result<()> {
+    check_with_urls(
+        r#"
+%TEX foo.tex
+%SRC \href{https://www.example.org}{Example}
+%1.1       ^^^^^^^^^^^^^^^^^^^^^^^
+%CUR ^
+"#,
+        &["https://www.example.org"],
+    )
+}
+
+#[test]
+fn url_malformed() -> Result<()> {
+    check(
+        r#"
+%TEX foo.tex
+%SRC \url{www.example.org}
+%CUR ^
+"#,
+    )
+}
+
+#[test]
+fn verbatim_include() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.store_on_disk("code.py", "print('foo')")?;
+    client.open(
+        "main.tex",
+        "latex",
+        "\\verbatiminput{code.py}\n\\lstinputlisting[language=Python]{code.py}".to_string(),
+    )?;
+
+    let mut actual_links = Vec::new();
+    for link in client
+        .request::<DocumentLinkRequest>(DocumentLinkParams {
+            text_document: TextDocumentIdentifier::new(client.uri("main.tex")?),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })?
+        .unwrap_or_default()
+    {
+        actual_links.push(client.request::<DocumentLinkResolve>(link)?);
+    }
+
+    let target = client.uri("code.py")?;
+    let expected_links = vec![
+        DocumentLink {
+            range: Range::new(Position::new(0, 15), Position::new(0, 22)),
+            target: Some(target.clone()),
+            tooltip: None,
+            data: None,
+        },
+        DocumentLink {
+            range: Range::new(Position::new(1, 34), Position::new(1, 41)),
+            target: Some(target),
+            tooltip: None,
+            data: None,
+        },
+    ];
+
+    client.shutdown()?;
+
+    assert_eq_unordered!(actual_links, expected_links);
+    Ok(())
+}
+
+#[test]
+fn include_resolved_lazily() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.open("main.tex", "latex", "\\input{chapter}".to_string())?;
+    client.open("chapter.tex", "latex", String::new())?;
+
+    let links = client
+        .request::<DocumentLinkRequest>(DocumentLinkParams {
+            text_document: TextDocumentIdentifier::new(client.uri("main.tex")?),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })?
+        .unwrap_or_default();
+
+    client.shutdown()?;
+
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].target, None);
+    assert!(links[0].data.is_some());
+    Ok(())
+}
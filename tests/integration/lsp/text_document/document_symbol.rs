@@ -1,7 +1,8 @@
 use anyhow::Result;
 use insta::{assert_json_snapshot, internals::Redaction};
 use lsp_types::{
-    request::DocumentSymbolRequest, DocumentSymbolParams, DocumentSymbolResponse,
+    notification::DidChangeConfiguration, request::DocumentSymbolRequest,
+    DidChangeConfigurationParams, DocumentSymbolParams, DocumentSymbolResponse,
     TextDocumentIdentifier, Url,
 };
 
@@ -13,15 +14,28 @@ struct SymbolResult {
 }
 
 fn find_symbols(fixture: &str, client_capabilities: serde_json::Value) -> Result<SymbolResult> {
+    find_symbols_with_settings(fixture, client_capabilities, None)
+}
+
+fn find_symbols_with_settings(
+    fixture: &str,
+    client_capabilities: serde_json::Value,
+    settings: Option<serde_json::Value>,
+) -> Result<SymbolResult> {
     let mut client = Client::spawn()?;
     client.initialize(serde_json::from_value(client_capabilities)?, None)?;
+    if let Some(settings) = settings {
+        client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams { settings })?;
+    }
 
     let fixture = fixture::parse(fixture);
-    let file = fixture.files.into_iter().next().unwrap();
-    client.open(file.name, file.lang, file.text)?;
+    let main_name = fixture.files[0].name;
+    for file in fixture.files {
+        client.open(file.name, file.lang, file.text)?;
+    }
 
     let response = client.request::<DocumentSymbolRequest>(DocumentSymbolParams {
-        text_document: TextDocumentIdentifier::new(client.uri(file.name)?),
+        text_document: TextDocumentIdentifier::new(client.uri(main_name)?),
         work_done_progress_params: Default::default(),
         partial_result_params: Default::default(),
     })?;
@@ -425,3 +439,181 @@ fn theorem_flat() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn bibtex_nested() -> Result<()> {
+    assert_symbols!(find_symbols(
+        r#"
+%BIB main.bib
+%SRC @preamble{"\newcommand{\noopsort}[1]{}"}
+%SRC @string{kn = "Knuth"}
+%SRC @book{foo, author = kn, title = {Bar}}
+"#,
+        serde_json::json!({
+            "textDocument": {
+                "documentSymbol": {
+                    "hierarchicalDocumentSymbolSupport": true,
+                },
+            },
+        }),
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn bibtex_flat() -> Result<()> {
+    assert_symbols!(find_symbols(
+        r#"
+%BIB main.bib
+%SRC @preamble{"\newcommand{\noopsort}[1]{}"}
+%SRC @string{kn = "Knuth"}
+%SRC @book{foo, author = kn, title = {Bar}}
+"#,
+        serde_json::json!({}),
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn structure_nested() -> Result<()> {
+    assert_symbols!(find_symbols(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \newtheorem{lemma}{Lemma}
+%SRC \begin{document}
+%SRC \section{Foo}\label{sec:foo}
+%SRC \begin{figure}
+%SRC     \caption{Bar\label{fig:bar}}
+%SRC \end{figure}
+%SRC \subsection{Baz}
+%SRC \begin{equation}\label{eq:baz}
+%SRC     Baz
+%SRC \end{equation}
+%SRC \section{Qux}
+%SRC \begin{lemma}[Qux]\label{thm:qux}
+%SRC     Qux
+%SRC \end{lemma}
+%SRC \begin{table}\label{tab:qux}
+%SRC \end{table}
+%SRC \end{document}
+
+%TEX main.aux
+%SRC \relax
+%SRC \newlabel{sec:foo}{{1}{1}}
+%SRC \newlabel{fig:bar}{{1}{1}}
+%SRC \newlabel{eq:baz}{{1}{1}}
+%SRC \newlabel{thm:qux}{{1}{1}}
+"#,
+        serde_json::json!({
+            "textDocument": {
+                "documentSymbol": {
+                    "hierarchicalDocumentSymbolSupport": true,
+                },
+            },
+        }),
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn structure_flat() -> Result<()> {
+    assert_symbols!(find_symbols(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \newtheorem{lemma}{Lemma}
+%SRC \begin{document}
+%SRC \section{Foo}\label{sec:foo}
+%SRC \begin{figure}
+%SRC     \caption{Bar\label{fig:bar}}
+%SRC \end{figure}
+%SRC \subsection{Baz}
+%SRC \begin{equation}\label{eq:baz}
+%SRC     Baz
+%SRC \end{equation}
+%SRC \section{Qux}
+%SRC \begin{lemma}[Qux]\label{thm:qux}
+%SRC     Qux
+%SRC \end{lemma}
+%SRC \begin{table}\label{tab:qux}
+%SRC \end{table}
+%SRC \end{document}
+
+%TEX main.aux
+%SRC \relax
+%SRC \newlabel{sec:foo}{{1}{1}}
+%SRC \newlabel{fig:bar}{{1}{1}}
+%SRC \newlabel{eq:baz}{{1}{1}}
+%SRC \newlabel{thm:qux}{{1}{1}}
+"#,
+        serde_json::json!({}),
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn custom_section_nested() -> Result<()> {
+    assert_symbols!(find_symbols_with_settings(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \begin{document}
+%SRC \mychapter{Foo}
+%SRC \section{Bar}
+%SRC \begin{equation}\label{eq:bar}
+%SRC     Bar
+%SRC \end{equation}
+%SRC \mysection*{Baz}
+%SRC \mychapter[Short]{Qux}
+%SRC \end{document}
+"#,
+        serde_json::json!({
+            "textDocument": {
+                "documentSymbol": {
+                    "hierarchicalDocumentSymbolSupport": true,
+                },
+            },
+        }),
+        Some(serde_json::json!({
+            "symbols": {
+                "customSectionCommands": [
+                    { "name": "mychapter", "level": "chapter" },
+                    { "name": "\\mysection", "level": "section" },
+                ],
+            },
+        })),
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn custom_section_flat() -> Result<()> {
+    assert_symbols!(find_symbols_with_settings(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \begin{document}
+%SRC \mychapter{Foo}
+%SRC \section{Bar}
+%SRC \mysection*{Baz}
+%SRC \end{document}
+"#,
+        serde_json::json!({}),
+        Some(serde_json::json!({
+            "symbols": {
+                "customSectionCommands": [
+                    { "name": "mychapter", "level": "chapter" },
+                    { "name": "mysection", "level": "section" },
+                ],
+            },
+        })),
+    )?);
+
+    Ok(())
+}
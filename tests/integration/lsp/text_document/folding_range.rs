@@ -69,6 +69,49 @@ fn latex() -> Result<()> {
     )
 }
 
+#[test]
+fn latex_section_levels() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \chapter{Foo}
+%SRC \section{Bar}
+%SRC bar
+%SRC \subsection{Baz}
+%SRC baz
+%SRC \section{Qux}
+%SRC qux
+%SRC \chapter{Quux}
+%SRC quux
+%CUR ^
+"#,
+        vec![
+            (0, 0, 6, 3),
+            (1, 0, 4, 3),
+            (3, 0, 4, 3),
+            (5, 0, 6, 3),
+            (7, 0, 8, 4),
+        ],
+    )
+}
+
+#[test]
+fn latex_section_inside_environment() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \section{Foo}
+%SRC foo
+%SRC \begin{appendices}
+%SRC \section{Bar}
+%SRC bar
+%SRC \end{appendices}
+%CUR ^
+"#,
+        vec![(0, 0, 1, 3), (2, 0, 5, 16), (3, 0, 4, 3)],
+    )
+}
+
 #[test]
 fn bibtex() -> Result<()> {
     check(
@@ -1,8 +1,11 @@
 use anyhow::Result;
 use insta::assert_snapshot;
 use lsp_types::{
-    request::Formatting, ClientCapabilities, DocumentFormattingParams, FormattingOptions,
-    TextDocumentIdentifier,
+    notification::{DidChangeConfiguration, Notification, ShowMessage},
+    request::{ApplyWorkspaceEdit, Formatting, OnTypeFormatting},
+    ApplyWorkspaceEditResponse, ClientCapabilities, DidChangeConfigurationParams,
+    DocumentFormattingParams, DocumentOnTypeFormattingParams, FormattingOptions, ShowMessageParams,
+    TextDocumentIdentifier, TextDocumentPositionParams, TextEdit, WorkspaceClientCapabilities,
 };
 use texlab::{LineIndex, LineIndexExt};
 
@@ -30,15 +33,50 @@ fn format(fixture: &str) -> Result<String> {
         .unwrap_or_default();
 
     client.shutdown()?;
+    Ok(apply_edits(old_text, actual_edits))
+}
+
+fn format_on_type(fixture: &str, enabled: bool) -> Result<String> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams {
+        settings: serde_json::json!({ "autoCloseEnvironments": enabled }),
+    })?;
+
+    let fixture = fixture::parse(fixture);
+    let cursor = fixture.cursor.unwrap();
+    let file = fixture.files.into_iter().next().unwrap();
+    let old_text = file.text.clone();
+    client.open(file.name, file.lang, file.text)?;
+
+    let actual_edits = client
+        .request::<OnTypeFormatting>(DocumentOnTypeFormattingParams {
+            text_document_position: TextDocumentPositionParams::new(
+                TextDocumentIdentifier::new(client.uri(cursor.name)?),
+                cursor.position,
+            ),
+            ch: "}".into(),
+            options: FormattingOptions {
+                insert_spaces: true,
+                tab_size: 4,
+                ..Default::default()
+            },
+        })?
+        .unwrap_or_default();
 
+    client.shutdown()?;
+    Ok(apply_edits(old_text, actual_edits))
+}
+
+fn apply_edits(old_text: String, edits: Vec<TextEdit>) -> String {
     let line_index = LineIndex::new(&old_text);
     let mut actual_text = old_text;
-    for edit in actual_edits.into_iter().rev() {
+    for edit in edits.into_iter().rev() {
         let range = line_index.offset_lsp_range(edit.range);
         actual_text.replace_range::<std::ops::Range<usize>>(range.into(), &edit.new_text);
     }
 
-    Ok(actual_text)
+    actual_text
 }
 
 #[test]
@@ -146,3 +184,206 @@ fn bibtex_internal_preamble() -> Result<()> {
 
     Ok(())
 }
+
+fn normalize_keys(fixture: &str) -> Result<String> {
+    let mut client = Client::spawn()?;
+    client.initialize(
+        ClientCapabilities {
+            workspace: Some(WorkspaceClientCapabilities {
+                apply_edit: Some(true),
+                ..WorkspaceClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        },
+        None,
+    )?;
+
+    client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams {
+        settings: serde_json::json!({ "normalizeBibtexKeys": true }),
+    })?;
+
+    let fixture = fixture::parse(fixture);
+    let mut old_texts = Vec::new();
+    for file in fixture.files {
+        old_texts.push((client.uri(file.name)?, file.text.clone()));
+        client.open(file.name, file.lang, file.text)?;
+    }
+
+    let id = client.send_request::<Formatting>(DocumentFormattingParams {
+        text_document: TextDocumentIdentifier::new(client.uri("main.bib")?),
+        work_done_progress_params: Default::default(),
+        options: FormattingOptions {
+            insert_spaces: true,
+            tab_size: 4,
+            ..Default::default()
+        },
+    })?;
+
+    // The response does not wait for the client to apply the edit of the citations.
+    let formatting_edits = client.receive_response::<Formatting>(id)?;
+    let (edit_id, params) = client.receive_request::<ApplyWorkspaceEdit>()?;
+    client.respond::<ApplyWorkspaceEdit>(
+        edit_id,
+        ApplyWorkspaceEditResponse {
+            applied: true,
+            failure_reason: None,
+            failed_change: None,
+        },
+    )?;
+
+    let bibtex_uri = client.uri("main.bib")?;
+    client.shutdown()?;
+
+    let mut changes = params.edit.changes.unwrap_or_default();
+    assert!(!changes.contains_key(&bibtex_uri));
+    changes.insert(bibtex_uri, formatting_edits.unwrap_or_default());
+    let mut actual_text = String::new();
+    for (uri, old_text) in old_texts {
+        let edits = changes.remove(&uri).unwrap_or_default();
+        actual_text.push_str(&apply_edits(old_text, edits));
+        actual_text.push('\n');
+    }
+
+    Ok(actual_text)
+}
+
+#[test]
+fn bibtex_internal_normalize_keys() -> Result<()> {
+    assert_snapshot!(normalize_keys(
+        r#"
+%TEX main.tex
+%SRC \addbibresource{main.bib}
+%SRC \cite{Foo:2020, bar}
+
+%BIB main.bib
+%SRC @article{Foo:2020, author = {M{\"u}ller, J{\"o}rg}, year = 2020}
+%SRC @article{bar, title = {Baz}}"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn bibtex_internal_normalize_keys_without_year() -> Result<()> {
+    assert_snapshot!(normalize_keys(
+        r#"
+%TEX main.tex
+%SRC \addbibresource{main.bib}
+%SRC \cite{foo}
+
+%BIB main.bib
+%SRC @article{foo, author = {Doe, Jane}, year = {unknown}}"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn bibtex_internal_normalize_keys_other_project() -> Result<()> {
+    assert_snapshot!(normalize_keys(
+        r#"
+%TEX main.tex
+%SRC \addbibresource{main.bib}
+%SRC \cite{foo}
+
+%TEX other.tex
+%SRC \cite{foo}
+
+%BIB main.bib
+%SRC @article{foo, author = {Doe, Jane}, year = 2020}"#
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn on_type_close_environment() -> Result<()> {
+    let text = format_on_type(
+        r#"
+%TEX main.tex
+%SRC \begin{document}
+%SRC   \begin{itemize}
+%CUR                  ^
+%SRC \end{document}"#,
+        true,
+    )?;
+
+    assert_eq!(
+        text,
+        "\\begin{document}\n  \\begin{itemize}\n  \\end{itemize}\n\\end{document}\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn on_type_environment_already_closed() -> Result<()> {
+    let text = format_on_type(
+        r#"
+%TEX main.tex
+%SRC \begin{itemize}
+%CUR                ^
+%SRC \end{itemize}"#,
+        true,
+    )?;
+
+    assert_eq!(text, "\\begin{itemize}\n\\end{itemize}\n");
+    Ok(())
+}
+
+#[test]
+fn on_type_disabled() -> Result<()> {
+    let text = format_on_type(
+        r#"
+%TEX main.tex
+%SRC \begin{itemize}
+%CUR                ^"#,
+        false,
+    )?;
+
+    assert_eq!(text, "\\begin{itemize}\n");
+    Ok(())
+}
+
+#[test]
+fn latexindent_not_found() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams {
+        settings: serde_json::json!({
+            "latexindent": { "executable": "texlab-missing-latexindent" }
+        }),
+    })?;
+
+    client.open("main.tex", "latex", "\\section{Foo}".to_string())?;
+    let params = DocumentFormattingParams {
+        text_document: TextDocumentIdentifier::new(client.uri("main.tex")?),
+        work_done_progress_params: Default::default(),
+        options: FormattingOptions {
+            insert_spaces: true,
+            tab_size: 4,
+            ..Default::default()
+        },
+    };
+
+    let edits = client.request::<Formatting>(params.clone())?;
+    assert_eq!(client.request::<Formatting>(params)?, None);
+
+    let result = client.shutdown()?;
+    let messages: Vec<_> = result
+        .incoming
+        .notifications
+        .into_iter()
+        .filter_map(|notification| {
+            notification
+                .extract::<ShowMessageParams>(ShowMessage::METHOD)
+                .ok()
+        })
+        .map(|params| params.message)
+        .filter(|message| message.contains("latexindent"))
+        .collect();
+
+    assert_eq!(edits, None);
+    assert_eq!(messages.len(), 1);
+    assert_snapshot!(messages[0]);
+    Ok(())
+}
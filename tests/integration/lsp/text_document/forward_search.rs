@@ -0,0 +1,110 @@
+use anyhow::Result;
+use lsp_types::{
+    notification::DidChangeConfiguration, ClientCapabilities, DidChangeConfigurationParams,
+    Position, TextDocumentIdentifier, TextDocumentPositionParams,
+};
+use texlab::features::{ForwardSearchResult, ForwardSearchStatus};
+
+use crate::lsp::client::Client;
+
+struct ForwardSearchRequest;
+
+impl lsp_types::request::Request for ForwardSearchRequest {
+    type Params = TextDocumentPositionParams;
+
+    type Result = ForwardSearchResult;
+
+    const METHOD: &'static str = "textDocument/forwardSearch";
+}
+
+fn check(settings: serde_json::Value, pdf_name: Option<&str>) -> Result<ForwardSearchResult> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams { settings })?;
+    if let Some(pdf_name) = pdf_name {
+        client.store_on_disk(pdf_name, "")?;
+    }
+
+    client.open(
+        "main.tex",
+        "latex",
+        "\\documentclass{article}\n\\begin{document}\nFoo\n\\end{document}".to_string(),
+    )?;
+
+    let result = client.request::<ForwardSearchRequest>(TextDocumentPositionParams::new(
+        TextDocumentIdentifier::new(client.uri("main.tex")?),
+        Position::new(2, 1),
+    ))?;
+
+    client.shutdown()?;
+    Ok(result)
+}
+
+#[test]
+fn unconfigured() -> Result<()> {
+    let result = check(serde_json::json!({}), Some("main.pdf"))?;
+    assert_eq!(result.status, ForwardSearchStatus::UNCONFIGURED);
+    assert!(result.message.is_some());
+    assert_eq!(result.pdf_path, None);
+    assert_eq!(result.pid, None);
+    Ok(())
+}
+
+#[test]
+fn missing_pdf() -> Result<()> {
+    let result = check(
+        serde_json::json!({ "forwardSearch": { "executable": "true", "args": [] } }),
+        None,
+    )?;
+
+    assert_eq!(result.status, ForwardSearchStatus::ERROR);
+    assert!(result.message.unwrap().contains("main.tex"));
+    assert_eq!(result.pid, None);
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn success() -> Result<()> {
+    let result = check(
+        serde_json::json!({ "forwardSearch": { "executable": "true", "args": ["%p", "%l"] } }),
+        Some("main.pdf"),
+    )?;
+
+    assert_eq!(result.status, ForwardSearchStatus::SUCCESS);
+    assert_eq!(result.message, None);
+    assert!(result.pdf_path.unwrap().ends_with("main.pdf"));
+    assert_eq!(result.line, Some(3));
+    assert!(result.pid.is_some());
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn previewer_error() -> Result<()> {
+    let result = check(
+        serde_json::json!({ "forwardSearch": { "executable": "false", "args": [] } }),
+        Some("main.pdf"),
+    )?;
+
+    assert_eq!(result.status, ForwardSearchStatus::ERROR);
+    assert!(result.message.is_some());
+    assert!(result.pid.is_some());
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn output_directory() -> Result<()> {
+    let result = check(
+        serde_json::json!({
+            "outputDirectory": "build",
+            "forwardSearch": { "executable": "true", "args": [] }
+        }),
+        Some("build/main.pdf"),
+    )?;
+
+    assert_eq!(result.status, ForwardSearchStatus::SUCCESS);
+    assert!(result.pdf_path.unwrap().ends_with("build/main.pdf"));
+    Ok(())
+}
@@ -1,15 +1,23 @@
 use anyhow::Result;
 use lsp_types::{
-    request::HoverRequest, ClientCapabilities, Hover, HoverContents, HoverParams, MarkupContent,
-    MarkupKind,
+    request::HoverRequest, ClientCapabilities, Hover, HoverClientCapabilities, HoverContents,
+    HoverParams, MarkupContent, MarkupKind, TextDocumentClientCapabilities,
 };
 use texlab::{component_db::COMPONENT_DATABASE, LANGUAGE_DATA};
 
 use crate::lsp::{client::Client, fixture};
 
 fn check(fixture: &str, contents: Option<HoverContents>) -> Result<()> {
+    check_with_capabilities(fixture, ClientCapabilities::default(), contents)
+}
+
+fn check_with_capabilities(
+    fixture: &str,
+    capabilities: ClientCapabilities,
+    contents: Option<HoverContents>,
+) -> Result<()> {
     let mut client = Client::spawn()?;
-    client.initialize(ClientCapabilities::default(), None)?;
+    client.initialize(capabilities, None)?;
 
     let fixture = fixture::parse(fixture);
     for file in fixture.files {
@@ -36,6 +44,19 @@ fn check(fixture: &str, contents: Option<HoverContents>) -> Result<()> {
     Ok(())
 }
 
+fn markdown_capabilities() -> ClientCapabilities {
+    ClientCapabilities {
+        text_document: Some(TextDocumentClientCapabilities {
+            hover: Some(HoverClientCapabilities {
+                dynamic_registration: None,
+                content_format: Some(vec![MarkupKind::Markdown]),
+            }),
+            ..TextDocumentClientCapabilities::default()
+        }),
+        ..ClientCapabilities::default()
+    }
+}
+
 #[test]
 fn empty_latex_document() -> Result<()> {
     check(
@@ -80,6 +101,114 @@ fn citation_inside_cite() -> Result<()> {
     )
 }
 
+#[test]
+fn citation_inside_textcite() -> Result<()> {
+    check(
+        r#"
+%BIB main.bib
+%SRC @article{foo, author = {Foo Bar}, title = {Baz Qux}, year = 1337}
+
+%TEX main.tex
+%SRC \addbibresource{main.bib}
+%SRC \textcite{foo}
+%CUR            ^
+%1.1           ^^^
+"#,
+        Some(HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: "F. Bar: \"Baz Qux\". (1337).".to_string(),
+        })),
+    )
+}
+
+#[test]
+fn citation_crossref_other_file() -> Result<()> {
+    check(
+        r#"
+%BIB main.bib
+%SRC @inproceedings{foo, author = {Foo Bar}, title = {Baz}, crossref = {qux}}
+
+%BIB proceedings.bib
+%SRC @proceedings{qux, title = {Qux}, year = 1337}
+
+%TEX main.tex
+%SRC \addbibresource{main.bib}
+%SRC \addbibresource{proceedings.bib}
+%SRC \cite{foo}
+%CUR        ^
+%1.1       ^^^
+"#,
+        Some(HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: "F. Bar: \"Baz\". *Qux*. 1337.".to_string(),
+        })),
+    )
+}
+
+#[test]
+fn citation_bibliography_style() -> Result<()> {
+    check(
+        r#"
+%BIB main.bib
+%SRC @article{foo, author = {Foo Bar}, title = {Baz Qux}, year = 1337}
+
+%TEX main.tex
+%SRC \bibliographystyle{alpha}
+%SRC \bibliography{main}
+%SRC \cite{foo}
+%CUR        ^
+%1.1       ^^^
+"#,
+        Some(HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: "[Bar37] F. Bar: \"Baz Qux\". (1337).".to_string(),
+        })),
+    )
+}
+
+#[test]
+fn citation_aux_number() -> Result<()> {
+    check(
+        r#"
+%BIB main.bib
+%SRC @article{foo, author = {Foo Bar}, title = {Baz Qux}, year = 1337}
+
+%TEX main.tex
+%SRC \bibliographystyle{plain}
+%SRC \bibliography{main}
+%SRC \cite{foo}
+%CUR        ^
+%1.1       ^^^
+
+%TEX main.aux
+%SRC \bibcite{foo}{3}
+"#,
+        Some(HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: "[3] F. Bar: \"Baz Qux\". (1337).".to_string(),
+        })),
+    )
+}
+
+#[test]
+fn citation_aux_number_without_entry() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \cite{foo}
+%CUR       ^
+%1.1       ^^^
+
+%TEX main.aux
+%SRC \bibcite{foo}{3}
+"#,
+        Some(HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: "[3]".to_string(),
+        })),
+    )
+}
+
 #[test]
 fn citation_inside_entry() -> Result<()> {
     check(
@@ -115,6 +244,24 @@ fn component_known_package() -> Result<()> {
     )
 }
 
+#[test]
+fn component_known_class_markdown() -> Result<()> {
+    check_with_capabilities(
+        r#"
+%TEX main.tex
+%SRC \documentclass{sduthesis}
+%CUR                 ^
+%1.1                ^^^^^^^^^
+"#,
+        markdown_capabilities(),
+        Some(HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: "**Thesis Template of Shandong University**\n\nThesis Template of Shandong University."
+                .into(),
+        })),
+    )
+}
+
 #[test]
 fn component_unknown_class() -> Result<()> {
     check(
@@ -127,6 +274,84 @@ fn component_unknown_class() -> Result<()> {
     )
 }
 
+#[test]
+fn include_preview() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \input{chapter}
+%CUR          ^
+%1.1        ^^^^^^^
+
+%TEX chapter.tex
+%SRC % A comment
+%SRC \section{Introduction}
+%SRC Hello World
+"#,
+        Some(HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::PlainText,
+            value: "\\section{Introduction}\nHello World".to_string(),
+        })),
+    )
+}
+
+#[test]
+fn include_not_found() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \input{chapter}
+%CUR          ^
+"#,
+        None,
+    )
+}
+
+#[test]
+fn tikz_library_known() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \usetikzlibrary{calc, positioning}
+%CUR                          ^
+%1.1                       ^^^^^^^^^^^
+"#,
+        Some(HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::PlainText,
+            value: LANGUAGE_DATA.tikz_library_docs["positioning"].clone(),
+        })),
+    )
+}
+
+#[test]
+fn tikz_library_known_markdown() -> Result<()> {
+    check_with_capabilities(
+        r#"
+%TEX main.tex
+%SRC \usetikzlibrary{calc, positioning}
+%CUR                          ^
+%1.1                       ^^^^^^^^^^^
+"#,
+        markdown_capabilities(),
+        Some(HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: LANGUAGE_DATA.tikz_library_docs["positioning"].clone(),
+        })),
+    )
+}
+
+#[test]
+fn tikz_library_pgf() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \usepgflibrary{fpu}
+%CUR                 ^
+"#,
+        None,
+    )
+}
+
 #[test]
 fn entry_type_known_type() -> Result<()> {
     check(
@@ -178,11 +403,49 @@ fn field_known() -> Result<()> {
 %SRC @article{foo, author = bar}
 %CUR                ^
 %1.1               ^^^^^^
+"#,
+        Some(HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: format!(
+                "{}\n\nRequired field of `@article`",
+                LANGUAGE_DATA.field_documentation("author").unwrap()
+            ),
+        })),
+    )
+}
+
+#[test]
+fn field_optional() -> Result<()> {
+    check(
+        r#"
+%BIB main.bib
+%SRC @misc{foo, howpublished = bar}
+%CUR             ^
+%1.1            ^^^^^^^^^^^^
+"#,
+        Some(HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: format!(
+                "{}\n\nOptional field of `@misc`",
+                LANGUAGE_DATA.field_documentation("howpublished").unwrap()
+            ),
+        })),
+    )
+}
+
+#[test]
+fn field_unknown_entry_type() -> Result<()> {
+    check(
+        r#"
+%BIB main.bib
+%SRC @foo{bar, doi = baz}
+%CUR            ^
+%1.1           ^^^
 "#,
         Some(HoverContents::Markup(MarkupContent {
             kind: MarkupKind::Markdown,
             value: LANGUAGE_DATA
-                .field_documentation("author")
+                .field_documentation("doi")
                 .unwrap()
                 .to_string(),
         })),
@@ -201,6 +464,40 @@ fn field_unknown() -> Result<()> {
     )
 }
 
+#[test]
+fn glossary_entry_description() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \newglossaryentry{foo}{name={Foo}, description={A foo}}
+%SRC \gls{foo}
+%CUR       ^
+%1.1      ^^^
+"#,
+        Some(HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::PlainText,
+            value: "A foo".to_string(),
+        })),
+    )
+}
+
+#[test]
+fn acronym_long_form() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \newacronym[longplural={Frames per Second}]{fps}{FPS}{Frame per Second}
+%SRC \acrshort{fps}
+%CUR            ^
+%1.1           ^^^
+"#,
+        Some(HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::PlainText,
+            value: "Frame per Second".to_string(),
+        })),
+    )
+}
+
 #[test]
 fn section() -> Result<()> {
     check(
@@ -218,6 +515,50 @@ fn section() -> Result<()> {
     )
 }
 
+#[test]
+fn section_aux_number() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \section{Foo}
+%SRC \label{sec:foo}
+%CUR          ^
+%1.1        ^^^^^^^
+
+%TEX main.aux
+%SRC \newlabel{sec:foo}{{2.1}{3}{Foo}{section.2.1}{}}
+"#,
+        Some(HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::PlainText,
+            value: "Section 2.1 (Foo)".to_string(),
+        })),
+    )
+}
+
+#[test]
+fn section_external_document() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \externaldocument{other}
+%SRC \ref{sec:foo}
+%CUR        ^
+%1.1      ^^^^^^^
+
+%TEX other.tex
+%SRC \section{Foo}
+%SRC \label{sec:foo}
+
+%TEX other.aux
+%SRC \newlabel{sec:foo}{{2.1}{3}{Foo}{section.2.1}{}}
+"#,
+        Some(HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::PlainText,
+            value: "Section 2.1 (Foo)".to_string(),
+        })),
+    )
+}
+
 #[test]
 fn string_inside_reference() -> Result<()> {
     check(
@@ -300,3 +641,22 @@ fn label_theorem_child_file_mumber() -> Result<()> {
         })),
     )
 }
+
+#[test]
+fn command_redefinition() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \newcommand{\foo}{a}
+%SRC \foo
+%SRC \renewcommand{\foo}{b}
+%SRC \foo
+%CUR   ^
+%1.1 ^^^^
+"#,
+        Some(HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::PlainText,
+            value: "\\renewcommand{\\foo}{b}".to_string(),
+        })),
+    )
+}
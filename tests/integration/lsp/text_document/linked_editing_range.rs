@@ -0,0 +1,110 @@
+use anyhow::Result;
+use lsp_types::{
+    request::LinkedEditingRange, ClientCapabilities, LinkedEditingRangeParams, LinkedEditingRanges,
+};
+
+use crate::lsp::{client::Client, fixture};
+
+fn check(fixture: &str) -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+
+    let fixture = fixture::parse(fixture);
+    for file in fixture.files {
+        client.open(file.name, file.lang, file.text)?;
+    }
+
+    let mut expected_ranges: Vec<_> = fixture
+        .ranges
+        .values()
+        .flat_map(|ranges| ranges.values())
+        .map(|file_range| file_range.range)
+        .collect();
+
+    expected_ranges.sort_by_key(|range| range.start);
+
+    let actual_ranges = client.request::<LinkedEditingRange>(LinkedEditingRangeParams {
+        text_document_position_params: fixture.cursor.unwrap().into_params(&client)?,
+        work_done_progress_params: Default::default(),
+    })?;
+
+    client.shutdown()?;
+
+    let expected_ranges = Some(expected_ranges)
+        .filter(|ranges| !ranges.is_empty())
+        .map(|ranges| LinkedEditingRanges {
+            ranges,
+            word_pattern: Some(r"[^\s{}\\%]+".to_string()),
+        });
+
+    assert_eq!(actual_ranges, expected_ranges);
+    Ok(())
+}
+
+#[test]
+fn begin() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \begin{foo}
+%CUR         ^
+%1.1        ^^^
+%SRC \end{foo}
+%2.1      ^^^
+"#,
+    )
+}
+
+#[test]
+fn end() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \begin{figure*}
+%1.1        ^^^^^^^
+%SRC \end{figure*}
+%CUR         ^
+%2.1      ^^^^^^^
+"#,
+    )
+}
+
+#[test]
+fn nested() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \begin{foo}
+%SRC \begin{bar}
+%CUR          ^
+%1.1        ^^^
+%SRC \end{bar}
+%2.1      ^^^
+%SRC \end{foo}
+"#,
+    )
+}
+
+#[test]
+fn mismatched() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \begin{foo}
+%CUR         ^
+%SRC \end{bar}
+"#,
+    )
+}
+
+#[test]
+fn outside_of_name() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \begin{foo}
+%CUR   ^
+%SRC \end{foo}
+"#,
+    )
+}
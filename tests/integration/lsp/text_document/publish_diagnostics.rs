@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use anyhow::Result;
 use insta::{assert_json_snapshot, internals::Redaction};
 use lsp_types::{
@@ -5,14 +7,13 @@ use lsp_types::{
         DidChangeConfiguration, DidChangeWatchedFiles, Notification, PublishDiagnostics,
     },
     ClientCapabilities, Diagnostic, DidChangeConfigurationParams, DidChangeWatchedFilesParams,
-    FileChangeType, FileEvent, PublishDiagnosticsParams, Url,
+    FileChangeType, FileEvent, NumberOrString, PublishDiagnosticsParams, Url,
 };
-use rustc_hash::FxHashMap;
 
 use crate::lsp::{client::Client, fixture};
 
 struct DiagnosticResult {
-    all_diagnostics: FxHashMap<Url, Vec<Diagnostic>>,
+    all_diagnostics: BTreeMap<Url, Vec<Diagnostic>>,
     uri_redaction: Redaction,
 }
 
@@ -263,6 +264,20 @@ fn build_log_filter_ignored() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn build_log_after_build_only() -> Result<()> {
+    assert_symbols!(find_diagnostics(
+        BUILD_LOG_FIXTURE,
+        serde_json::json!({
+            "diagnostics": {
+                "buildLogAfterBuildOnly": true
+            }
+        })
+    )?);
+
+    Ok(())
+}
+
 #[test]
 fn build_log_spaces_in_path() -> Result<()> {
     assert_symbols!(find_diagnostics(
@@ -322,3 +337,511 @@ fn build_log_spaces_in_path() -> Result<()> {
     )?);
     Ok(())
 }
+
+#[test]
+fn build_log_included_file() -> Result<()> {
+    assert_symbols!(find_diagnostics(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \begin{document}
+%SRC \input{chapter}
+%SRC \end{document}
+
+%LOG main.log
+%SRC This is pdfTeX, Version 3.141592653-2.6-1.40.22 (TeX Live 2021/W32TeX) (preloaded format=pdflatex 2022.1.23)  16 JUN 2022 11:04
+%SRC entering extended mode
+%SRC **main.tex
+%SRC (./main.tex
+%SRC LaTeX2e <2020-10-01> patch level 4
+%SRC (./main.aux) (./chapter.tex
+%SRC ! Undefined control sequence.
+%SRC l.2 \foo
+%SRC         {}
+%SRC The control sequence at the end of the top line
+%SRC of your error message was never \def'ed.
+%SRC 
+%SRC ) [1] (./main.aux) )
+%SRC Output written on main.pdf (1 page, 9741 bytes)."#,
+        serde_json::json!({})
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn include_only() -> Result<()> {
+    assert_symbols!(find_diagnostics(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \includeonly{a}
+%SRC \begin{document}
+%SRC \include{a}
+%SRC \include{b}
+%SRC \end{document}
+
+%TEX a.tex
+%SRC \foo
+
+%TEX b.tex
+%SRC }
+
+%LOG main.log
+%SRC **main.tex
+%SRC (./main.tex
+%SRC (./a.tex
+%SRC ! Undefined control sequence.
+%SRC l.1 \foo
+%SRC 
+%SRC ) (./b.tex
+%SRC ! Undefined control sequence.
+%SRC l.1 \bar
+%SRC 
+%SRC ) )"#,
+        serde_json::json!({})
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn include_only_nested() -> Result<()> {
+    assert_symbols!(find_diagnostics(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \includeonly{a}
+%SRC \begin{document}
+%SRC \include{a}
+%SRC \include{b}
+%SRC \end{document}
+
+%TEX a.tex
+%SRC \input{c}
+
+%TEX b.tex
+%SRC \input{d}
+
+%TEX c.tex
+%SRC \foo
+
+%TEX d.tex
+%SRC }
+
+%LOG main.log
+%SRC **main.tex
+%SRC (./main.tex
+%SRC (./a.tex (./c.tex
+%SRC ! Undefined control sequence.
+%SRC l.1 \foo
+%SRC 
+%SRC )) (./b.tex (./d.tex
+%SRC ! Undefined control sequence.
+%SRC l.1 \bar
+%SRC 
+%SRC )) )"#,
+        serde_json::json!({})
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn missing_package() -> Result<()> {
+    assert_symbols!(find_diagnostics(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \usepackage{amsmath}
+%SRC \newcommand{\qtyrange}{}
+%SRC \begin{document}
+%SRC \qty{1} \qtyrange \text{foo} \foobar \toprule
+%SRC \end{document}"#,
+        serde_json::json!({ "diagnostics": { "missingPackages": true } })
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn missing_package_cleveref() -> Result<()> {
+    assert_symbols!(find_diagnostics(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \begin{document}
+%SRC \crefrange{foo}{bar} \cpageref{foo} \ref{foo}
+%SRC \end{document}"#,
+        serde_json::json!({ "diagnostics": { "missingPackages": true } })
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn missing_package_cleveref_loaded() -> Result<()> {
+    assert_symbols!(find_diagnostics(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \usepackage{cleveref}
+%SRC \begin{document}
+%SRC \crefrange{foo}{bar} \cpageref{foo} \ref{foo}
+%SRC \end{document}"#,
+        serde_json::json!({ "diagnostics": { "missingPackages": true } })
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn missing_package_disabled() -> Result<()> {
+    assert_symbols!(find_diagnostics(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \begin{document}
+%SRC \qty{1}
+%SRC \end{document}"#,
+        serde_json::json!({})
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn unmatched_environments() -> Result<()> {
+    assert_symbols!(find_diagnostics(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \newenvironment{foo}{\begin{center}}{\end{center}}
+%SRC \begin{document}
+%SRC \begin{itemize}
+%SRC \begin{itemize}
+%SRC \end{itemize}
+%SRC \end{document}
+%SRC \end{center}"#,
+        serde_json::json!({})
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn unmatched_environments_same_name() -> Result<()> {
+    assert_symbols!(find_diagnostics(
+        r#"
+%TEX main.tex
+%SRC \begin{foo}
+%SRC \begin{foo}
+%SRC \begin{bar}
+%SRC \end{baz}
+%SRC \end{foo}"#,
+        serde_json::json!({})
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn unmatched_environments_definitions() -> Result<()> {
+    assert_symbols!(find_diagnostics(
+        r#"
+%TEX main.tex
+%SRC \NewDocumentEnvironment{foo}{}{\begin{center}}{\end{center}}
+%SRC \def\bar{\begin{itemize}}
+%SRC \newcommand{\baz}{\end{itemize}}
+%SRC \textbf{\begin{itemize}\end{enumerate}}
+%SRC \parbox{1cm}{\begin{center}}"#,
+        serde_json::json!({})
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn misspelled_environment() -> Result<()> {
+    assert_symbols!(find_diagnostics(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \newenvironment{proofsketch}{}{}
+%SRC \begin{document}
+%SRC \begin{enumarate}
+%SRC \end{enumarate}
+%SRC \begin{proofsketc}
+%SRC \end{proofsketc}
+%SRC \begin{Itemize}
+%SRC \end{Itemize}
+%SRC \begin{figure*}
+%SRC \end{figure*}
+%SRC \end{document}"#,
+        serde_json::json!({})
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn missing_document_class() -> Result<()> {
+    assert_symbols!(find_diagnostics(
+        r#"
+%TEX main.tex
+%SRC \usepackage{amsmath}
+%SRC \begin{document}
+%SRC \end{document}
+
+%TEX preamble.tex
+%SRC \documentclass{article}
+
+%TEX body.tex
+%SRC \input{preamble}
+%SRC \begin{document}
+%SRC \end{document}
+
+%TEX chapter.tex
+%SRC \section{Foo}"#,
+        serde_json::json!({})
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn bibtex_field_values() -> Result<()> {
+    assert_symbols!(find_diagnostics(
+        r#"
+%BIB main.bib
+%SRC @string{thisyear = 2022}
+%SRC @article{foo,
+%SRC     year = {2o22},
+%SRC     pages = {12--34, 56},
+%SRC     doi = {10.1000/xyz123},
+%SRC }
+%SRC @article{bar,
+%SRC     year = 2022,
+%SRC     pages = "12 to 34",
+%SRC     doi = {https://doi.org/10.1000/xyz123},
+%SRC }
+%SRC @article{baz,
+%SRC     year = thisyear,
+%SRC     pages = {xii--xv},
+%SRC     doi = "10.1000" # "/xyz123",
+%SRC }
+%SRC @article{qux, year = {n.d.}}
+%SRC @article{quux, year = {In Press}}"#,
+        serde_json::json!({})
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn style_checks() -> Result<()> {
+    assert_symbols!(find_diagnostics(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \usepackage{url}
+%SRC \begin{document}
+%SRC Pneumonoultramicroscopicsilicovolcanoconiosisandsomemore
+%SRC \url{https://example.org/a/very/long/path/that/is/allowed/to/break}
+%SRC See Figure ~\ref{fig:foo} and Table~ 1.
+%SRC $\int_0^1 f(x) dx + \int_0^1 g(x)\,dx$
+%SRC \end{document}"#,
+        serde_json::json!({ "diagnostics": { "styleChecks": { "enabled": true } } })
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn style_checks_disabled_check() -> Result<()> {
+    assert_symbols!(find_diagnostics(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \begin{document}
+%SRC See Figure ~\ref{fig:foo}.
+%SRC $\int_0^1 f(x) dx$
+%SRC \end{document}"#,
+        serde_json::json!({
+            "diagnostics": {
+                "styleChecks": { "enabled": true, "nonBreakingSpaces": false }
+            }
+        })
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn show_parser_errors() -> Result<()> {
+    assert_symbols!(find_diagnostics(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \begin{document}
+%SRC Foo] (bar)) baz}
+%SRC \end{document}"#,
+        serde_json::json!({ "diagnostics": { "showParserErrors": true } })
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn unescaped_characters() -> Result<()> {
+    assert_symbols!(find_diagnostics(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \usepackage{url}
+%SRC \newcommand{\pair}[2]{#1 & #2}
+%SRC \begin{document}
+%SRC Smith & Sons sold file_name.tex files with C# and \#1 to 50% of the users.
+%SRC \texttt{foo\_bar} $a_1$ \url{https://example.org/?a=1&b_c=2} % a comment & more
+%SRC \begin{tabular}{cc}
+%SRC     a & b \\
+%SRC \end{tabular}
+%SRC \begin{verbatim}
+%SRC x_y & z
+%SRC \end{verbatim}
+%SRC \begin{tikzcd}
+%SRC     A \arrow[r] & B
+%SRC \end{tikzcd}
+%SRC \begin{IEEEeqnarray}{rCl}
+%SRC     a & = & b
+%SRC \end{IEEEeqnarray}
+%SRC \begin{tikzpicture}
+%SRC     \matrix [matrix of nodes] { a & b \\ };
+%SRC \end{tikzpicture}
+%SRC \penalty10000% no space
+%SRC See \hyperref[sec_a]{the section}.
+%SRC \pgfplotstableread{x_y.txt}\data \textbf{foo_bar}
+%SRC \begin{tikzpicture}
+%SRC     \begin{axis}
+%SRC         \addplot table {data_1.csv};
+%SRC     \end{axis}
+%SRC \end{tikzpicture}
+%SRC \end{document}"#,
+        serde_json::json!({})
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn unescaped_characters_disabled() -> Result<()> {
+    assert_symbols!(find_diagnostics(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \begin{document}
+%SRC Smith & Sons
+%SRC \end{document}"#,
+        serde_json::json!({ "diagnostics": { "unescapedCharacters": false } })
+    )?);
+
+    Ok(())
+}
+
+/// Puts a `chktex` script in front of the `PATH` that reports the same warnings for every document.
+#[cfg(unix)]
+fn install_chktex() {
+    use std::{os::unix::fs::PermissionsExt, sync::Once};
+
+    static INSTALL_CHKTEX: Once = Once::new();
+    INSTALL_CHKTEX.call_once(|| {
+        let directory = tempfile::tempdir().unwrap().into_path();
+        let path = directory.join("chktex");
+        std::fs::write(
+            &path,
+            "#!/bin/sh\nprintf '1:1:1:Warning:1:Command terminated with space.\\n1:1:1:Warning:8:Wrong length of dash may have been used.\\n'\n",
+        )
+        .unwrap();
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let paths = std::env::var_os("PATH").unwrap_or_default();
+        let paths = std::iter::once(directory).chain(std::env::split_paths(&paths));
+        std::env::set_var("PATH", std::env::join_paths(paths).unwrap());
+    });
+}
+
+#[test]
+#[cfg(unix)]
+fn chktex_disabled_codes() -> Result<()> {
+    install_chktex();
+
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams {
+        settings: serde_json::json!({
+            "chktex": { "onOpenAndSave": true, "disabledCodes": ["1"] }
+        }),
+    })?;
+
+    client.open("main.tex", "latex", "\\foo --".to_string())?;
+    let result = client.shutdown()?;
+
+    let codes: Vec<_> = result
+        .incoming
+        .notifications
+        .into_iter()
+        .filter_map(|notification| {
+            notification
+                .extract::<PublishDiagnosticsParams>(PublishDiagnostics::METHOD)
+                .ok()
+        })
+        .last()
+        .unwrap()
+        .diagnostics
+        .into_iter()
+        .map(|diagnostic| diagnostic.code)
+        .collect();
+
+    assert_eq!(codes, [Some(NumberOrString::String("8".into()))]);
+    Ok(())
+}
+
+#[test]
+fn single_file_mode_clears_dropped_documents() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.store_on_disk("child.tex", "}")?;
+    client.open(
+        "main.tex",
+        "latex",
+        "\\documentclass{article}\n\\begin{document}\n\\include{child}\n\\end{document}".into(),
+    )?;
+
+    let child_uri = client.uri("child.tex")?;
+    loop {
+        let params = client.receive_notification::<PublishDiagnostics>()?;
+        if params.uri == child_uri && !params.diagnostics.is_empty() {
+            break;
+        }
+    }
+
+    client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams {
+        settings: serde_json::json!({ "singleFileMode": true }),
+    })?;
+
+    let result = client.shutdown()?;
+    let last_diagnostics = result
+        .incoming
+        .notifications
+        .try_iter()
+        .filter(|notification| notification.method == PublishDiagnostics::METHOD)
+        .map(|notification| {
+            serde_json::from_value::<PublishDiagnosticsParams>(notification.params).unwrap()
+        })
+        .filter(|params| params.uri == child_uri)
+        .last()
+        .map(|params| params.diagnostics);
+
+    assert_eq!(last_diagnostics, Some(Vec::new()));
+    Ok(())
+}
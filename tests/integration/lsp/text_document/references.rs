@@ -1,7 +1,9 @@
 use anyhow::Result;
 use assert_unordered::assert_eq_unordered;
 use lsp_types::{
-    request::References, ClientCapabilities, Location, ReferenceContext, ReferenceParams,
+    notification::DidChangeConfiguration, request::References, ClientCapabilities,
+    DidChangeConfigurationParams, Location, Position, ReferenceContext, ReferenceParams,
+    TextDocumentIdentifier, TextDocumentPositionParams, Url,
 };
 
 use crate::lsp::{client::Client, fixture};
@@ -116,6 +118,27 @@ fn entry_reference_include_decl() -> Result<()> {
     )
 }
 
+#[test]
+fn entry_definition_multi_cite() -> Result<()> {
+    check(
+        r#"
+%BIB foo.bib
+%SRC @article{bar,}
+%CUR            ^
+
+%TEX bar.tex
+%SRC \cites[see]{foo}[p.~5]{bar}
+%1.1                        ^^^
+%SRC \cite[p.~5]{bar}
+%2.1             ^^^
+%SRC \addbibresource{foo.bib}
+"#,
+        ReferenceContext {
+            include_declaration: false,
+        },
+    )
+}
+
 #[test]
 fn label_definition() -> Result<()> {
     check(
@@ -273,3 +296,103 @@ fn string_definition_include_decl() -> Result<()> {
         },
     )
 }
+
+#[test]
+fn command_redefinition() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \newcommand{\foo}{a}
+%SRC \foo
+%SRC \renewcommand{\foo}{b}
+%SRC \foo
+%CUR   ^
+%1.1 ^^^^
+%SRC \foo
+%2.1 ^^^^
+"#,
+        ReferenceContext {
+            include_declaration: false,
+        },
+    )
+}
+
+#[test]
+fn command_redefinition_include_decl() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \newcommand{\foo}{a}
+%1.1             ^^^^
+%SRC \foo
+%CUR   ^
+%2.1 ^^^^
+%SRC \renewcommand{\foo}{b}
+%SRC \foo
+"#,
+        ReferenceContext {
+            include_declaration: true,
+        },
+    )
+}
+
+#[cfg(unix)]
+fn find_symlinked_label_references(resolve_symlinks: bool) -> Result<(Vec<Url>, Url)> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams {
+        settings: serde_json::json!({ "resolveSymlinks": resolve_symlinks }),
+    })?;
+
+    client.store_on_disk("shared/chapter.tex", "\\section{Foo}\\label{sec:foo}")?;
+    let target = client.uri("shared/chapter.tex")?.to_file_path().unwrap();
+    for name in ["foo.tex", "bar.tex"] {
+        let link = client.uri(name)?.to_file_path().unwrap();
+        std::os::unix::fs::symlink(&target, link)?;
+    }
+
+    client.open(
+        "main.tex",
+        "latex",
+        "\\input{foo}\n\\input{bar}\n\\ref{sec:foo}".to_string(),
+    )?;
+
+    let main_uri = client.uri("main.tex")?;
+    let actual_uris = client
+        .request::<References>(ReferenceParams {
+            text_document_position: TextDocumentPositionParams::new(
+                TextDocumentIdentifier::new(main_uri.clone()),
+                Position::new(2, 7),
+            ),
+            context: ReferenceContext {
+                include_declaration: true,
+            },
+            partial_result_params: Default::default(),
+            work_done_progress_params: Default::default(),
+        })?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|location| location.uri)
+        .filter(|uri| *uri != main_uri)
+        .collect();
+
+    let target_uri = Url::from_file_path(std::fs::canonicalize(target)?).unwrap();
+    client.shutdown()?;
+    Ok((actual_uris, target_uri))
+}
+
+#[test]
+#[cfg(unix)]
+fn label_symlinked_child() -> Result<()> {
+    let (actual_uris, _) = find_symlinked_label_references(false)?;
+    assert_eq!(actual_uris.len(), 2);
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn label_symlinked_child_resolve_symlinks() -> Result<()> {
+    let (actual_uris, target_uri) = find_symlinked_label_references(true)?;
+    assert_eq!(actual_uris, vec![target_uri]);
+    Ok(())
+}
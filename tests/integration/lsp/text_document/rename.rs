@@ -1,7 +1,12 @@
 use std::collections::HashMap;
 
 use anyhow::Result;
-use lsp_types::{request::Rename, ClientCapabilities, RenameParams, TextEdit, Url, WorkspaceEdit};
+use lsp_types::{
+    request::{PrepareRenameRequest, Rename},
+    ClientCapabilities, DocumentChangeOperation, DocumentChanges, OneOf, Position,
+    PrepareRenameResponse, Range, RenameFile, RenameParams, ResourceOp, ResourceOperationKind,
+    TextEdit, Url, WorkspaceClientCapabilities, WorkspaceEdit, WorkspaceEditClientCapabilities,
+};
 
 use crate::lsp::{client::Client, fixture};
 
@@ -109,3 +114,289 @@ fn label() -> Result<()> {
         "bar",
     )
 }
+
+fn check_include(
+    fixture: &str,
+    new_name: &str,
+    new_stems: &[&str],
+    renamed_file: Option<(&str, &str)>,
+) -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(
+        ClientCapabilities {
+            workspace: Some(WorkspaceClientCapabilities {
+                workspace_edit: Some(WorkspaceEditClientCapabilities {
+                    document_changes: Some(true),
+                    resource_operations: Some(vec![ResourceOperationKind::Rename]),
+                    ..WorkspaceEditClientCapabilities::default()
+                }),
+                ..WorkspaceClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        },
+        None,
+    )?;
+
+    let fixture = fixture::parse(fixture);
+    for file in fixture.files {
+        client.open(file.name, file.lang, file.text)?;
+    }
+
+    let actual_edit = client
+        .request::<Rename>(RenameParams {
+            text_document_position: fixture.cursor.unwrap().into_params(&client)?,
+            new_name: new_name.to_string(),
+            work_done_progress_params: Default::default(),
+        })?
+        .unwrap_or_default();
+
+    let renamed_file = match renamed_file {
+        Some((old_name, new_name)) => Some((client.uri(old_name)?, client.uri(new_name)?)),
+        None => None,
+    };
+
+    let mut expected_changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+    for (new_stem, ranges) in new_stems.iter().zip(fixture.ranges.values()) {
+        expected_changes
+            .entry(client.uri(ranges[&1].name)?)
+            .or_default()
+            .push(TextEdit::new(ranges[&1].range, new_stem.to_string()));
+    }
+
+    client.shutdown()?;
+
+    let (old_uri, new_uri) = match renamed_file {
+        Some(renamed_file) => renamed_file,
+        None => {
+            assert_eq!(actual_edit, WorkspaceEdit::default());
+            return Ok(());
+        }
+    };
+
+    let mut operations = match actual_edit.document_changes {
+        Some(DocumentChanges::Operations(operations)) => operations,
+        _ => panic!("expected document change operations"),
+    };
+
+    assert_eq!(
+        operations.pop(),
+        Some(DocumentChangeOperation::Op(ResourceOp::Rename(
+            RenameFile {
+                old_uri,
+                new_uri,
+                options: None,
+                annotation_id: None,
+            }
+        )))
+    );
+
+    let mut actual_changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+    for operation in operations {
+        match operation {
+            DocumentChangeOperation::Edit(edit) => {
+                let edits = edit.edits.into_iter().map(|edit| match edit {
+                    OneOf::Left(edit) => edit,
+                    OneOf::Right(edit) => edit.text_edit,
+                });
+
+                actual_changes
+                    .entry(edit.text_document.uri)
+                    .or_default()
+                    .extend(edits);
+            }
+            DocumentChangeOperation::Op(_) => panic!("unexpected resource operation"),
+        }
+    }
+
+    assert_eq!(actual_changes, expected_changes);
+    Ok(())
+}
+
+#[test]
+fn include() -> Result<()> {
+    check_include(
+        r#"
+%TEX main.tex
+%SRC \input{chapter}
+%CUR          ^
+%1.1        ^^^^^^^
+
+%TEX sub/other.tex
+%SRC \include{../chapter.tex}
+%2.1          ^^^^^^^^^^^^^^
+
+%TEX chapter.tex
+%SRC Hello
+"#,
+        "part",
+        &["part", "../part.tex"],
+        Some(("chapter.tex", "part.tex")),
+    )
+}
+
+#[test]
+fn include_import() -> Result<()> {
+    check_include(
+        r#"
+%TEX main.tex
+%SRC \subimport{chapters/}{intro}
+%CUR                       ^
+%1.1                       ^^^^^
+
+%TEX chapters/intro.tex
+%SRC Hello
+"#,
+        "start",
+        &["start"],
+        Some(("chapters/intro.tex", "chapters/start.tex")),
+    )
+}
+
+#[test]
+fn include_subfiles_parent() -> Result<()> {
+    check_include(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \usepackage{subfiles}
+
+%TEX chapters/intro.tex
+%SRC \documentclass[../main]{subfiles}
+%CUR                     ^
+"#,
+        "other",
+        &[],
+        None,
+    )
+}
+
+#[test]
+fn environment() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \begin{foo}
+%CUR         ^
+%1.1        ^^^
+%SRC \end{foo}
+%2.1      ^^^
+"#,
+        "bar",
+    )
+}
+
+fn check_prepare(fixture: &str) -> Result<Option<PrepareRenameResponse>> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+
+    let fixture = fixture::parse(fixture);
+    for file in fixture.files {
+        client.open(file.name, file.lang, file.text)?;
+    }
+
+    let response =
+        client.request::<PrepareRenameRequest>(fixture.cursor.unwrap().into_params(&client)?)?;
+
+    client.shutdown()?;
+    Ok(response)
+}
+
+#[test]
+fn prepare_label() -> Result<()> {
+    let response = check_prepare(
+        r#"
+%TEX main.tex
+%SRC \label{foo}
+%CUR         ^
+"#,
+    )?;
+
+    assert_eq!(
+        response,
+        Some(PrepareRenameResponse::Range(Range::new(
+            Position::new(0, 7),
+            Position::new(0, 10)
+        )))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn prepare_text() -> Result<()> {
+    let response = check_prepare(
+        r#"
+%TEX main.tex
+%SRC Hello World
+%CUR    ^
+"#,
+    )?;
+
+    assert_eq!(response, None);
+    Ok(())
+}
+
+#[test]
+fn prepare_bibtex_command() -> Result<()> {
+    let response = check_prepare(
+        r#"
+%BIB main.bib
+%SRC @article{foo, title = {\LaTeX}}
+%CUR                          ^
+"#,
+    )?;
+
+    assert_eq!(response, None);
+    Ok(())
+}
+
+#[test]
+fn prepare_environment() -> Result<()> {
+    let response = check_prepare(
+        r#"
+%TEX main.tex
+%SRC \begin{foo}
+%CUR         ^
+%SRC \end{foo}
+"#,
+    )?;
+
+    assert_eq!(
+        response,
+        Some(PrepareRenameResponse::Range(Range::new(
+            Position::new(0, 7),
+            Position::new(0, 10)
+        )))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn prepare_environment_mismatched() -> Result<()> {
+    let response = check_prepare(
+        r#"
+%TEX main.tex
+%SRC \begin{foo}
+%CUR         ^
+%SRC \end{bar}
+"#,
+    )?;
+
+    assert_eq!(response, None);
+    Ok(())
+}
+
+#[test]
+fn prepare_environment_unclosed() -> Result<()> {
+    let response = check_prepare(
+        r#"
+%TEX main.tex
+%SRC \begin{foo}
+%CUR         ^
+"#,
+    )?;
+
+    assert_eq!(response, None);
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use anyhow::Result;
+use assert_unordered::assert_eq_unordered;
+use lsp_types::{
+    request::{GotoTypeDefinition, GotoTypeDefinitionParams},
+    ClientCapabilities, GotoDefinitionResponse, LocationLink,
+};
+
+use crate::lsp::{client::Client, fixture};
+
+fn check(fixture: &str) -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+
+    let fixture = fixture::parse(fixture);
+    for file in fixture.files {
+        client.open(file.name, file.lang, file.text)?;
+    }
+
+    let mut expected_links = Vec::new();
+    for ranges in fixture.ranges.values() {
+        expected_links.push(LocationLink {
+            origin_selection_range: Some(ranges[&1].range),
+            target_uri: client.uri(ranges[&2].name)?,
+            target_range: ranges[&2].range,
+            target_selection_range: ranges[&3].range,
+        });
+    }
+
+    let actual_links = client
+        .request::<GotoTypeDefinition>(GotoTypeDefinitionParams {
+            text_document_position_params: fixture.cursor.unwrap().into_params(&client)?,
+            partial_result_params: Default::default(),
+            work_done_progress_params: Default::default(),
+        })?
+        .map_or(Vec::new(), |actual| match actual {
+            GotoDefinitionResponse::Link(links) => links,
+            GotoDefinitionResponse::Array(_) | GotoDefinitionResponse::Scalar(_) => unreachable!(),
+        });
+
+    client.shutdown()?;
+
+    assert_eq_unordered!(actual_links, expected_links);
+    Ok(())
+}
+
+#[test]
+fn theorem_begin() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \newtheorem{lemma}{Lemma}
+%1.3             ^^^^^
+%1.2 ^^^^^^^^^^^^^^^^^^^^^^^^^
+%SRC \begin{lemma}
+%CUR          ^
+%1.1        ^^^^^
+%SRC \end{lemma}
+"#,
+    )
+}
+
+#[test]
+fn theorem_end_other_document() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \include{foo}
+%SRC \newtheorem{lemma}{Lemma}
+%1.3             ^^^^^
+%1.2 ^^^^^^^^^^^^^^^^^^^^^^^^^
+
+%TEX foo.tex
+%SRC \begin{lemma}
+%SRC \end{lemma}
+%CUR        ^
+%1.1      ^^^^^
+"#,
+    )
+}
+
+#[test]
+fn environment_undefined() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \begin{document}
+%CUR         ^
+%SRC \end{document}
+"#,
+    )
+}
@@ -1 +1,8 @@
+mod did_change_watched_files;
+mod did_change_workspace_folders;
+mod execute_command;
+mod expand_macros;
+mod resolve_root;
+mod server_status;
 mod symbol;
+mod will_rename_files;
@@ -0,0 +1,92 @@
+use anyhow::Result;
+use lsp_types::{
+    notification::{DidChangeWatchedFiles, PublishDiagnostics},
+    request::Completion,
+    ClientCapabilities, CompletionParams, CompletionResponse, DidChangeWatchedFilesParams,
+    FileChangeType, FileEvent, Position, TextDocumentIdentifier, TextDocumentPositionParams,
+};
+
+use crate::lsp::client::Client;
+
+fn complete(client: &mut Client, name: &str, position: Position) -> Result<Vec<String>> {
+    let labels = client
+        .request::<Completion>(CompletionParams {
+            text_document_position: TextDocumentPositionParams::new(
+                TextDocumentIdentifier::new(client.uri(name)?),
+                position,
+            ),
+            partial_result_params: Default::default(),
+            work_done_progress_params: Default::default(),
+            context: None,
+        })?
+        .map_or(Vec::new(), |actual| match actual {
+            CompletionResponse::List(list) => list.items,
+            CompletionResponse::Array(items) => items,
+        })
+        .into_iter()
+        .map(|item| item.label)
+        .collect();
+
+    Ok(labels)
+}
+
+#[test]
+fn changed_latex_document() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.store_on_disk("child.tex", "\\label{foo}")?;
+    client.open("main.tex", "latex", "\\include{child}\n\\ref{}".into())?;
+    assert_eq!(
+        complete(&mut client, "main.tex", Position::new(1, 5))?,
+        vec!["foo"]
+    );
+
+    client.store_on_disk("child.tex", "\\label{bar}}")?;
+    let child_uri = client.uri("child.tex")?;
+    client.notify::<DidChangeWatchedFiles>(DidChangeWatchedFilesParams {
+        changes: vec![FileEvent::new(child_uri.clone(), FileChangeType::CHANGED)],
+    })?;
+
+    assert_eq!(
+        complete(&mut client, "main.tex", Position::new(1, 5))?,
+        vec!["bar"]
+    );
+
+    loop {
+        let params = client.receive_notification::<PublishDiagnostics>()?;
+        if params.uri == child_uri && !params.diagnostics.is_empty() {
+            break;
+        }
+    }
+
+    client.shutdown()?;
+    Ok(())
+}
+
+#[test]
+fn changed_bibtex_document() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.store_on_disk("main.bib", "@article{foo,}")?;
+    client.open("main.tex", "latex", "\\bibliography{main}\n\\cite{}".into())?;
+    assert_eq!(
+        complete(&mut client, "main.tex", Position::new(1, 6))?,
+        vec!["foo"]
+    );
+
+    client.store_on_disk("main.bib", "@article{bar,}")?;
+    client.notify::<DidChangeWatchedFiles>(DidChangeWatchedFilesParams {
+        changes: vec![FileEvent::new(
+            client.uri("main.bib")?,
+            FileChangeType::CHANGED,
+        )],
+    })?;
+
+    assert_eq!(
+        complete(&mut client, "main.tex", Position::new(1, 6))?,
+        vec!["bar"]
+    );
+
+    client.shutdown()?;
+    Ok(())
+}
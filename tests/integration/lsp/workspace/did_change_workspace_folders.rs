@@ -0,0 +1,114 @@
+use anyhow::Result;
+use lsp_types::{
+    notification::DidChangeWorkspaceFolders, ClientCapabilities, DidChangeWorkspaceFoldersParams,
+    TextDocumentIdentifier, Url, WorkspaceFoldersChangeEvent,
+};
+use texlab::features::ResolveRootRequest;
+
+use crate::lsp::client::Client;
+
+const PARENT: &str = r#"\documentclass{article}
+\begin{document}
+\input{project/chapter}
+\end{document}"#;
+
+fn resolve_root(client: &mut Client, name: &str) -> Result<Url> {
+    let result =
+        client.request::<ResolveRootRequest>(TextDocumentIdentifier::new(client.uri(name)?))?;
+
+    Ok(result.root_uri)
+}
+
+#[test]
+fn parent_outside_of_folder() -> Result<()> {
+    let mut client = Client::spawn()?;
+    let folder = client.workspace_folder("project")?;
+    client.initialize_with_folders(ClientCapabilities::default(), None, Some(vec![folder]))?;
+    client.store_on_disk("main.tex", PARENT)?;
+    client.open(
+        "project/chapter.tex",
+        "latex",
+        r#"\section{Foo}"#.to_string(),
+    )?;
+
+    let actual_uri = resolve_root(&mut client, "project/chapter.tex")?;
+    let expected_uri = client.uri("project/chapter.tex")?;
+    client.shutdown()?;
+
+    assert_eq!(actual_uri, expected_uri);
+    Ok(())
+}
+
+#[test]
+fn parent_without_folders() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.store_on_disk("main.tex", PARENT)?;
+    client.open(
+        "project/chapter.tex",
+        "latex",
+        r#"\section{Foo}"#.to_string(),
+    )?;
+
+    let actual_uri = resolve_root(&mut client, "project/chapter.tex")?;
+    let expected_uri = client.uri("main.tex")?;
+    client.shutdown()?;
+
+    assert_eq!(actual_uri, expected_uri);
+    Ok(())
+}
+
+#[test]
+fn link_into_other_folder() -> Result<()> {
+    let mut client = Client::spawn()?;
+    let folders = vec![client.workspace_folder("a")?, client.workspace_folder("b")?];
+    client.initialize_with_folders(ClientCapabilities::default(), None, Some(folders))?;
+    client.store_on_disk("b/chapter.tex", r#"\section{Foo}"#)?;
+    client.open(
+        "a/main.tex",
+        "latex",
+        r#"\documentclass{article}
+\begin{document}
+\input{../b/chapter}
+\end{document}"#
+            .to_string(),
+    )?;
+
+    let actual_uri = resolve_root(&mut client, "b/chapter.tex")?;
+    let expected_uri = client.uri("b/chapter.tex")?;
+    client.shutdown()?;
+
+    assert_eq!(actual_uri, expected_uri);
+    Ok(())
+}
+
+#[test]
+fn added_folder() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.store_on_disk("main.tex", PARENT)?;
+    client.open(
+        "project/chapter.tex",
+        "latex",
+        r#"\section{Foo}"#.to_string(),
+    )?;
+    assert_eq!(
+        resolve_root(&mut client, "project/chapter.tex")?,
+        client.uri("main.tex")?
+    );
+
+    let folder = client.workspace_folder("project")?;
+    client.notify::<DidChangeWorkspaceFolders>(DidChangeWorkspaceFoldersParams {
+        event: WorkspaceFoldersChangeEvent {
+            added: vec![folder],
+            removed: Vec::new(),
+        },
+    })?;
+
+    let actual_uri = resolve_root(&mut client, "project/chapter.tex")?;
+    let expected_uri = client.uri("project/chapter.tex")?;
+    client.shutdown()?;
+
+    assert_eq!(actual_uri, expected_uri);
+    Ok(())
+}
@@ -0,0 +1,298 @@
+use anyhow::Result;
+use lsp_types::{
+    notification::{DidChangeConfiguration, PublishDiagnostics},
+    request::ExecuteCommand,
+    ClientCapabilities, DidChangeConfigurationParams, ExecuteCommandParams, Location, Position,
+    Range, TextDocumentIdentifier, WorkspaceEdit,
+};
+
+use crate::lsp::client::Client;
+
+#[test]
+fn reload_workspace() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.store_on_disk("child.tex", "\\section{Foo}")?;
+    client.open(
+        "main.tex",
+        "latex",
+        "\\documentclass{article}\n\\begin{document}\n\\include{child}\n\\end{document}".into(),
+    )?;
+
+    client.store_on_disk("child.tex", "}")?;
+    let count = client.request::<ExecuteCommand>(ExecuteCommandParams {
+        command: "texlab.reloadWorkspace".into(),
+        arguments: Vec::new(),
+        work_done_progress_params: Default::default(),
+    })?;
+
+    assert_eq!(count, Some(serde_json::json!(1)));
+
+    let child_uri = client.uri("child.tex")?;
+    loop {
+        let params = client.receive_notification::<PublishDiagnostics>()?;
+        if params.uri == child_uri && !params.diagnostics.is_empty() {
+            break;
+        }
+    }
+
+    client.shutdown()?;
+    Ok(())
+}
+
+#[test]
+fn reload_workspace_deleted_file() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.store_on_disk("child.tex", "}")?;
+    client.open(
+        "main.tex",
+        "latex",
+        "\\documentclass{article}\n\\begin{document}\n\\include{child}\n\\end{document}".into(),
+    )?;
+
+    let child_uri = client.uri("child.tex")?;
+    loop {
+        let params = client.receive_notification::<PublishDiagnostics>()?;
+        if params.uri == child_uri && !params.diagnostics.is_empty() {
+            break;
+        }
+    }
+
+    std::fs::remove_file(child_uri.to_file_path().unwrap())?;
+    let count = client.request::<ExecuteCommand>(ExecuteCommandParams {
+        command: "texlab.reloadWorkspace".into(),
+        arguments: Vec::new(),
+        work_done_progress_params: Default::default(),
+    })?;
+
+    assert_eq!(count, Some(serde_json::json!(0)));
+
+    loop {
+        let params = client.receive_notification::<PublishDiagnostics>()?;
+        if params.uri == child_uri && params.diagnostics.is_empty() {
+            break;
+        }
+    }
+
+    client.shutdown()?;
+    Ok(())
+}
+
+#[test]
+fn reload_workspace_single_file_mode() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.store_on_disk("child.tex", "\\section{Foo}")?;
+    client.open(
+        "main.tex",
+        "latex",
+        "\\documentclass{article}\n\\begin{document}\n\\include{child}\n\\end{document}".into(),
+    )?;
+
+    client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams {
+        settings: serde_json::json!({ "singleFileMode": true }),
+    })?;
+
+    let count = client.request::<ExecuteCommand>(ExecuteCommandParams {
+        command: "texlab.reloadWorkspace".into(),
+        arguments: Vec::new(),
+        work_done_progress_params: Default::default(),
+    })?;
+
+    assert_eq!(count, Some(serde_json::json!(0)));
+    client.shutdown()?;
+    Ok(())
+}
+
+fn open_root(files: &[(&str, &str)], name: &str) -> Result<(Client, Option<Location>)> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    for (file_name, text) in files {
+        client.open(file_name, "latex", text.to_string())?;
+    }
+
+    let location = client
+        .request::<ExecuteCommand>(ExecuteCommandParams {
+            command: "texlab.openRoot".into(),
+            arguments: vec![serde_json::to_value(TextDocumentIdentifier::new(
+                client.uri(name)?,
+            ))?],
+            work_done_progress_params: Default::default(),
+        })?
+        .map(serde_json::from_value)
+        .transpose()?;
+
+    Ok((client, location))
+}
+
+#[test]
+fn open_root_from_child() -> Result<()> {
+    let (client, location) = open_root(
+        &[
+            (
+                "main.tex",
+                "\\documentclass{article}\n\\begin{document}\n\\include{child}\n\\end{document}",
+            ),
+            ("child.tex", "\\section{Foo}"),
+            (
+                "other.tex",
+                "\\documentclass{article}\n\\begin{document}\n\\end{document}",
+            ),
+        ],
+        "child.tex",
+    )?;
+
+    let position = Position::new(0, 0);
+    assert_eq!(
+        location,
+        Some(Location::new(
+            client.uri("main.tex")?,
+            Range::new(position, position)
+        ))
+    );
+
+    client.shutdown()?;
+    Ok(())
+}
+
+#[test]
+fn open_root_standalone() -> Result<()> {
+    let (client, location) = open_root(
+        &[
+            (
+                "main.tex",
+                "\\documentclass{article}\n\\begin{document}\n\\end{document}",
+            ),
+            ("other.tex", "\\section{Foo}"),
+        ],
+        "other.tex",
+    )?;
+
+    assert_eq!(
+        location.map(|location| location.uri),
+        Some(client.uri("other.tex")?)
+    );
+    client.shutdown()?;
+    Ok(())
+}
+
+#[test]
+fn open_root_external_document() -> Result<()> {
+    let (client, location) = open_root(
+        &[
+            (
+                "main.tex",
+                "\\documentclass{article}\n\\externaldocument{other}\n\\begin{document}\n\\include{child}\n\\end{document}",
+            ),
+            ("child.tex", "\\section{Foo}"),
+            (
+                "other.tex",
+                "\\documentclass{article}\n\\begin{document}\n\\end{document}",
+            ),
+        ],
+        "child.tex",
+    )?;
+
+    assert_eq!(
+        location.map(|location| location.uri),
+        Some(client.uri("main.tex")?)
+    );
+    client.shutdown()?;
+    Ok(())
+}
+
+fn sort_bibliography(text: &str, sort_key: Option<&str>) -> Result<String> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.open("main.bib", "bibtex", text.to_string())?;
+
+    let uri = client.uri("main.bib")?;
+    let mut arguments = vec![serde_json::to_value(TextDocumentIdentifier::new(
+        uri.clone(),
+    ))?];
+    arguments.extend(sort_key.map(serde_json::Value::from));
+
+    let edit: WorkspaceEdit = client
+        .request::<ExecuteCommand>(ExecuteCommandParams {
+            command: "texlab.sortBibliography".into(),
+            arguments,
+            work_done_progress_params: Default::default(),
+        })?
+        .map(serde_json::from_value)
+        .transpose()?
+        .unwrap();
+
+    client.shutdown()?;
+    let mut edits = edit.changes.unwrap().remove(&uri).unwrap();
+    assert_eq!(edits.len(), 1);
+    Ok(edits.remove(0).new_text)
+}
+
+static SORT_BIBLIOGRAPHY_FIXTURE: &str = r#"% Generated by hand
+
+% The newest one
+@article{foo, year = 2022}
+
+@string{jan = "January"}
+@book{Bar, date = {2020-01-01}}
+@misc{baz}
+@preamble{"\\newcommand{\\noop}[1]{}"}
+"#;
+
+#[test]
+fn sort_bibliography_by_key() -> Result<()> {
+    assert_eq!(
+        sort_bibliography(SORT_BIBLIOGRAPHY_FIXTURE, None)?,
+        r#"% Generated by hand
+
+@string{jan = "January"}
+
+@preamble{"\\newcommand{\\noop}[1]{}"}
+
+@book{Bar, date = {2020-01-01}}
+
+@misc{baz}
+
+% The newest one
+@article{foo, year = 2022}
+"#
+    );
+
+    Ok(())
+}
+
+#[test]
+fn sort_bibliography_by_year() -> Result<()> {
+    assert_eq!(
+        sort_bibliography(SORT_BIBLIOGRAPHY_FIXTURE, Some("year"))?,
+        r#"% Generated by hand
+
+@string{jan = "January"}
+
+@preamble{"\\newcommand{\\noop}[1]{}"}
+
+@book{Bar, date = {2020-01-01}}
+
+% The newest one
+@article{foo, year = 2022}
+
+@misc{baz}
+"#
+    );
+
+    Ok(())
+}
+
+#[test]
+fn sort_bibliography_crlf() -> Result<()> {
+    assert_eq!(
+        sort_bibliography(
+            "% Generated by hand\r\n\r\n% Second\r\n@misc{b}\r\n\r\n@misc{a}\r\n",
+            None
+        )?,
+        "% Generated by hand\n\n@misc{a}\n\n% Second\n@misc{b}\n"
+    );
+
+    Ok(())
+}
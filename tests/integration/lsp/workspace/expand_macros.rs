@@ -0,0 +1,138 @@
+use anyhow::Result;
+use lsp_types::{ClientCapabilities, TextDocumentIdentifier};
+use texlab::features::{ExpandMacrosParams, ExpandMacrosRequest, ExpandMacrosResult};
+
+use crate::lsp::{client::Client, fixture};
+
+fn expand(fixture: &str, depth: Option<usize>) -> Result<ExpandMacrosResult> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+
+    let fixture = fixture::parse(fixture);
+    for file in fixture.files {
+        client.open(file.name, file.lang, file.text)?;
+    }
+
+    let selection = &fixture.ranges[&1][&1];
+    let result = client.request::<ExpandMacrosRequest>(ExpandMacrosParams {
+        text_document: TextDocumentIdentifier::new(client.uri(selection.name)?),
+        range: selection.range,
+        depth,
+    })?;
+
+    client.shutdown()?;
+    Ok(result)
+}
+
+#[test]
+fn single_level() -> Result<()> {
+    let result = expand(
+        r#"
+%TEX main.tex
+%SRC \newcommand{\vect}[1]{\mathbf{#1}}
+%SRC \newcommand{\norm}[1]{\left\|#1\right\|}
+%SRC $\norm{\vect{x}} + \vect y$
+%1.1  ^^^^^^^^^^^^^^^^^^^^^^^^^"#,
+        None,
+    )?;
+
+    assert_eq!(
+        result,
+        ExpandMacrosResult {
+            text: "\\left\\|\\vect{x}\\right\\| + \\mathbf{y}".to_string(),
+            truncated: true,
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn nested() -> Result<()> {
+    let result = expand(
+        r#"
+%TEX main.tex
+%SRC \input{macros}
+%SRC $\norm{\vect{x}}$
+%1.1  ^^^^^^^^^^^^^^^
+
+%TEX macros.tex
+%SRC \newcommand{\vect}[1]{\mathbf{#1}}
+%SRC \newcommand{\norm}[1]{\left\|#1\right\|}"#,
+        Some(5),
+    )?;
+
+    assert_eq!(
+        result,
+        ExpandMacrosResult {
+            text: "\\left\\|\\mathbf{x}\\right\\|".to_string(),
+            truncated: false,
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn optional_argument() -> Result<()> {
+    let result = expand(
+        r#"
+%TEX main.tex
+%SRC \newcommand{\seq}[2][n]{#2_1, \dots, #2_#1}
+%SRC \seq{a} and \seq[m]{b}
+%1.1 ^^^^^^^^^^^^^^^^^^^^^^"#,
+        None,
+    )?;
+
+    assert_eq!(
+        result,
+        ExpandMacrosResult {
+            text: "a_1, \\dots, a_n and b_1, \\dots, b_m".to_string(),
+            truncated: false,
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn recursive() -> Result<()> {
+    let result = expand(
+        r#"
+%TEX main.tex
+%SRC \newcommand{\foo}{[\foo]}
+%SRC \foo
+%1.1 ^^^^"#,
+        Some(100),
+    )?;
+
+    assert_eq!(
+        result,
+        ExpandMacrosResult {
+            text: format!("{}\\foo{}", "[".repeat(16), "]".repeat(16)),
+            truncated: true,
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn self_duplicating() -> Result<()> {
+    let body = "\\foo".repeat(1000);
+    let result = expand(
+        &format!(
+            r#"
+%TEX main.tex
+%SRC \newcommand{{\foo}}{{{}}}
+%SRC \foo
+%1.1 ^^^^"#,
+            body
+        ),
+        Some(16),
+    )?;
+
+    assert!(result.truncated);
+    assert!(result.text.len() < 2 << 20);
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use anyhow::Result;
+use lsp_types::{
+    notification::DidChangeConfiguration, ClientCapabilities, DidChangeConfigurationParams,
+    TextDocumentIdentifier,
+};
+use texlab::features::{ResolveRootRequest, ResolveRootResult};
+
+use crate::lsp::{client::Client, fixture};
+
+fn check(fixture: &str, root_name: &str) -> Result<()> {
+    check_with_settings(fixture, root_name, None)
+}
+
+fn check_with_settings(
+    fixture: &str,
+    root_name: &str,
+    settings: Option<serde_json::Value>,
+) -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    if let Some(settings) = settings {
+        client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams { settings })?;
+    }
+
+    let fixture = fixture::parse(fixture);
+    for file in fixture.files {
+        client.open(file.name, file.lang, file.text)?;
+    }
+
+    let actual_result = client.request::<ResolveRootRequest>(TextDocumentIdentifier::new(
+        client.uri(fixture.cursor.unwrap().name)?,
+    ))?;
+
+    let root_uri = client.uri(root_name)?;
+    let directory = root_uri
+        .to_file_path()
+        .ok()
+        .and_then(|path| path.parent().map(ToOwned::to_owned));
+
+    client.shutdown()?;
+
+    let expected_result = ResolveRootResult {
+        root_uri,
+        output_directory: directory.clone(),
+        aux_directory: directory,
+    };
+
+    assert_eq!(actual_result, expected_result);
+    Ok(())
+}
+
+#[test]
+fn parent() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \begin{document}
+%SRC \include{child}
+%SRC \end{document}
+
+%TEX child.tex
+%SRC \section{Foo}
+%CUR ^"#,
+        "main.tex",
+    )
+}
+
+#[test]
+fn standalone() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \section{Foo}
+%CUR ^"#,
+        "main.tex",
+    )
+}
+
+#[test]
+fn subfile() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \usepackage{subfiles}
+%SRC \begin{document}
+%SRC \end{document}
+
+%TEX chapters/foo.tex
+%SRC \documentclass[../main.tex]{subfiles}
+%SRC \begin{document}
+%SRC \end{document}
+%CUR ^"#,
+        "main.tex",
+    )
+}
+
+#[test]
+fn subfile_included() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \usepackage{subfiles}
+%SRC \begin{document}
+%SRC \subfile{chapters/foo}
+%SRC \end{document}
+
+%TEX chapters/foo.tex
+%SRC \documentclass[../main]{subfiles}
+%SRC \begin{document}
+%SRC \end{document}
+%CUR ^"#,
+        "main.tex",
+    )
+}
+
+#[test]
+fn single_file_mode() -> Result<()> {
+    check_with_settings(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \begin{document}
+%SRC \include{child}
+%SRC \end{document}
+
+%TEX child.tex
+%SRC \section{Foo}
+%CUR ^"#,
+        "child.tex",
+        Some(serde_json::json!({ "singleFileMode": true })),
+    )
+}
+
+#[test]
+fn single_file_mode_parent_on_disk() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams {
+        settings: serde_json::json!({ "singleFileMode": true }),
+    })?;
+
+    client.store_on_disk(
+        "main.tex",
+        r#"\documentclass{article}
+\begin{document}
+\include{child}
+\end{document}"#,
+    )?;
+
+    client.open("child.tex", "latex", r#"\section{Foo}"#.to_string())?;
+
+    let actual_result = client
+        .request::<ResolveRootRequest>(TextDocumentIdentifier::new(client.uri("child.tex")?))?;
+
+    let expected_uri = client.uri("child.tex")?;
+    client.shutdown()?;
+
+    assert_eq!(actual_result.root_uri, expected_uri);
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use anyhow::Result;
+use lsp_types::ClientCapabilities;
+use texlab::features::ServerStatusRequest;
+
+use crate::lsp::client::Client;
+
+#[test]
+fn documents() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.open("main.tex", "latex", "\\addbibresource{main.bib}".into())?;
+    client.open("main.bib", "bibtex", "@article{foo,}".into())?;
+
+    let status = client.request::<ServerStatusRequest>(())?;
+    client.shutdown()?;
+
+    assert_eq!(status.documents, 2);
+    assert_eq!(status.open_documents, 2);
+    assert_eq!(status.latex_documents, 1);
+    assert_eq!(status.bibtex_documents, 1);
+    assert_eq!(status.build_logs, 0);
+    assert_eq!(status.text_bytes, 39);
+    Ok(())
+}
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use lsp_types::{
+    notification::DidChangeConfiguration, request::WillRenameFiles, ClientCapabilities,
+    DidChangeConfigurationParams, FileRename, RenameFilesParams, TextEdit, Url, WorkspaceEdit,
+};
+
+use crate::lsp::{client::Client, fixture};
+
+fn check(fixture: &str, renames: &[(&str, &str)], new_stems: &[&str]) -> Result<()> {
+    check_with_settings(fixture, renames, new_stems, |_| Ok(None))
+}
+
+fn check_with_settings(
+    fixture: &str,
+    renames: &[(&str, &str)],
+    new_stems: &[&str],
+    settings: impl FnOnce(&Client) -> Result<Option<serde_json::Value>>,
+) -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    if let Some(settings) = settings(&client)? {
+        client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams { settings })?;
+    }
+
+    let fixture = fixture::parse(fixture);
+    for file in fixture.files {
+        client.open(file.name, file.lang, file.text)?;
+    }
+
+    let mut expected_changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+    for (new_stem, ranges) in new_stems.iter().zip(fixture.ranges.values()) {
+        expected_changes
+            .entry(client.uri(ranges[&1].name)?)
+            .or_default()
+            .push(TextEdit::new(ranges[&1].range, new_stem.to_string()));
+    }
+
+    let mut files = Vec::new();
+    for (old_name, new_name) in renames {
+        files.push(FileRename {
+            old_uri: client.uri(old_name)?.to_string(),
+            new_uri: client.uri(new_name)?.to_string(),
+        });
+    }
+
+    let actual_edit = client.request::<WillRenameFiles>(RenameFilesParams { files })?;
+    client.shutdown()?;
+
+    let mut actual_changes = actual_edit
+        .and_then(|edit| edit.changes)
+        .unwrap_or_default();
+
+    for edits in actual_changes.values_mut() {
+        edits.sort_by_key(|edit| edit.range.start);
+    }
+
+    assert_eq!(
+        WorkspaceEdit::new(actual_changes),
+        WorkspaceEdit::new(expected_changes)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn file() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \input{chapters/intro}
+%1.1        ^^^^^^^^^^^^^^
+%SRC \bibliography{refs}
+%2.1               ^^^^
+%SRC \input{other}
+
+%TEX chapters/intro.tex
+%SRC Hello
+
+%BIB refs.bib
+%SRC @article{foo, title = {Foo}}
+"#,
+        &[
+            ("chapters/intro.tex", "chapters/start.tex"),
+            ("refs.bib", "literature.bib"),
+        ],
+        &["chapters/start", "literature"],
+    )
+}
+
+#[test]
+fn import() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \import{chapters/}{intro}
+%1.1                    ^^^^^
+%SRC \subimport{appendix/}{proof.tex}
+%2.1                       ^^^^^^^^^
+
+%TEX chapters/intro.tex
+%SRC Hello
+
+%TEX appendix/proof.tex
+%SRC Hello
+"#,
+        &[
+            ("chapters/intro.tex", "chapters/start.tex"),
+            ("appendix/proof.tex", "appendix/lemma.tex"),
+        ],
+        &["start", "lemma.tex"],
+    )
+}
+
+#[test]
+fn bibtex_directory() -> Result<()> {
+    check_with_settings(
+        r#"
+%TEX main.tex
+%SRC \bibliography{refs}
+%1.1               ^^^^
+
+%BIB bib/refs.bib
+%SRC @article{foo, title = {Foo}}
+"#,
+        &[("bib/refs.bib", "bib/literature.bib")],
+        &["literature"],
+        |client| {
+            let directory = client.uri("bib/refs.bib")?.join(".")?;
+            Ok(Some(serde_json::json!({
+                "bibtexDirectories": [directory.to_file_path().unwrap()]
+            })))
+        },
+    )
+}
+
+#[test]
+fn explicit_extension() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \include{chapter.tex}
+%1.1          ^^^^^^^^^^^
+
+%TEX chapter.tex
+%SRC Hello
+"#,
+        &[("chapter.tex", "part.tex")],
+        &["part.tex"],
+    )
+}
+
+#[test]
+fn directory() -> Result<()> {
+    check(
+        r#"
+%TEX main.tex
+%SRC \input{chapters/intro}
+%1.1        ^^^^^^^^^^^^^^
+%SRC \input{chapters/outro.tex}
+%2.1        ^^^^^^^^^^^^^^^^^^
+
+%TEX chapters/intro.tex
+%SRC Hello
+
+%TEX chapters/outro.tex
+%SRC World
+"#,
+        &[("chapters", "parts")],
+        &["parts/intro", "parts/outro.tex"],
+    )
+}
+
+#[test]
+fn unrelated() -> Result<()> {
+    let mut client = Client::spawn()?;
+    client.initialize(ClientCapabilities::default(), None)?;
+    client.open("main.tex", "latex", "\\input{chapter}".into())?;
+
+    let actual_edit = client.request::<WillRenameFiles>(RenameFilesParams {
+        files: vec![FileRename {
+            old_uri: client.uri("other.tex")?.to_string(),
+            new_uri: client.uri("part.tex")?.to_string(),
+        }],
+    })?;
+
+    client.shutdown()?;
+
+    assert_eq!(actual_edit, None);
+    Ok(())
+}